@@ -309,6 +309,21 @@ impl<E: Pairing> PoKOfSignature23G1Proof<E> {
         )
     }
 
+    /// Number of messages that were not revealed in this proof. This is derived from the number of
+    /// responses of the Schnorr protocol which has a response for each hidden message followed by
+    /// the responses for `A_bar` and `B_bar`. The revealed messages (and hence the total number of signed messages) are
+    /// not part of the proof.
+    pub fn hidden_message_count(&self) -> Result<usize, BBSPlusError> {
+        let total_responses = if let Some(resp) = self.sc_resp.as_ref() {
+            resp.0.len()
+        } else if let Some(resp) = self.sc_partial_resp.as_ref() {
+            resp.total_responses
+        } else {
+            return Err(BBSPlusError::NeedEitherPartialOrCompleteSchnorrResponse);
+        };
+        Ok(total_responses.saturating_sub(2))
+    }
+
     /// Get the response from post-challenge phase of the Schnorr protocol for the given message index
     /// `msg_idx`. Used when comparing message equality
     pub fn get_resp_for_message(
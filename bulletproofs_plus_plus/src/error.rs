@@ -11,4 +11,8 @@ pub enum BulletproofsPlusPlusError {
     ValueIncompatibleWithBase(String),
     IncorrectBounds(String),
     IncorrectNumberOfCommitments(usize, usize),
+    /// Hashing to the curve failed when creating the generators
+    HashToCurveFailed(String),
+    /// The number of generators required doesn't fit in a `u32`
+    TooManyGenerators(String),
 }
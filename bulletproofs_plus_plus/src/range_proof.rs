@@ -1105,6 +1105,7 @@ mod tests {
     use super::*;
     use crate::setup::SetupParams;
     use ark_bls12_381::Fr;
+    use ark_ec::CurveGroup;
     use ark_std::{
         rand::{prelude::StdRng, SeedableRng},
         UniformRand,
@@ -1185,6 +1186,113 @@ mod tests {
         );
     }
 
+    #[test]
+    fn extend_setup_params_for_larger_range() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let base = 4;
+        let label = b"test";
+
+        let mut setup_params = SetupParams::<ark_bls12_381::G1Affine>::new_for_perfect_range_proof::<
+            Blake2b512,
+        >(label, base, 16, 1);
+
+        // Commitment created before extending the params
+        let v = 1u64 << 40;
+        let gamma = Fr::rand(&mut rng);
+        let V = vec![setup_params.compute_pedersen_commitment(v, &gamma)];
+
+        let num_bits = 64;
+        let g_count =
+            SetupParams::<ark_bls12_381::G1Affine>::get_no_of_G(base, num_bits, 1).unwrap();
+        assert!(g_count as usize > setup_params.G_vec.len());
+        setup_params.extend_generators::<Blake2b512>(
+            label,
+            g_count - setup_params.G_vec.len() as u32,
+            0,
+        );
+        assert_eq!(setup_params.G_vec.len(), g_count as usize);
+
+        // Extending is same as creating the params with more generators
+        assert_eq!(
+            setup_params,
+            SetupParams::new_for_perfect_range_proof::<Blake2b512>(label, base, num_bits, 1)
+        );
+        // Existing commitment remains valid
+        assert_eq!(V[0], setup_params.compute_pedersen_commitment(v, &gamma));
+
+        let prover =
+            Prover::new_with_given_base(base, num_bits, V.clone(), vec![v], vec![gamma]).unwrap();
+        let mut transcript = new_merlin_transcript(b"BPP/tests");
+        transcript.append(b"setup", &setup_params);
+        let prf = prover
+            .prove(&mut rng, setup_params.clone(), &mut transcript)
+            .unwrap();
+
+        let mut transcript = new_merlin_transcript(b"BPP/tests");
+        transcript.append(b"setup", &setup_params);
+        prf.verify(num_bits, &V, &setup_params, &mut transcript)
+            .unwrap();
+    }
+
+    #[test]
+    fn range_proof_for_vector_pedersen_commitment() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let base = 16;
+        let num_bits = 64;
+        let setup_params = SetupParams::<ark_bls12_381::G1Affine>::new_for_perfect_range_proof::<
+            Blake2b512,
+        >(b"test", base, num_bits, 1);
+
+        let v = 1u64 << 40;
+        let gammas = (0..3).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let C = setup_params
+            .compute_vector_pedersen_commitment(v, &gammas)
+            .unwrap();
+
+        // A single blinding factor gives the usual commitment
+        assert_eq!(
+            setup_params
+                .compute_vector_pedersen_commitment(v, &gammas[..1])
+                .unwrap(),
+            setup_params.compute_pedersen_commitment(v, &gammas[0])
+        );
+
+        // Removing the other blinding factors gives a commitment for which a range proof can be created
+        let V = vec![(C.into_group()
+            - setup_params.H_vec[1] * gammas[1]
+            - setup_params.H_vec[2] * gammas[2])
+            .into_affine()];
+        assert_eq!(
+            V[0],
+            setup_params.compute_pedersen_commitment(v, &gammas[0])
+        );
+
+        let prover =
+            Prover::new_with_given_base(base, num_bits, V.clone(), vec![v], vec![gammas[0]])
+                .unwrap();
+        let mut transcript = new_merlin_transcript(b"BPP/tests");
+        transcript.append(b"setup", &setup_params);
+        let prf = prover
+            .prove(&mut rng, setup_params.clone(), &mut transcript)
+            .unwrap();
+        let mut transcript = new_merlin_transcript(b"BPP/tests");
+        transcript.append(b"setup", &setup_params);
+        prf.verify(num_bits, &V, &setup_params, &mut transcript)
+            .unwrap();
+
+        // As many blinding factors as `H_vec` but not more
+        let gammas = (0..setup_params.H_vec.len() + 1)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        assert!(setup_params
+            .compute_vector_pedersen_commitment(v, &gammas[..setup_params.H_vec.len()])
+            .is_ok());
+        assert!(matches!(
+            setup_params.compute_vector_pedersen_commitment(v, &gammas),
+            Err(BulletproofsPlusPlusError::UnexpectedLengthOfVectors(_))
+        ));
+    }
+
     #[test]
     fn rangeproof_bls12381() {
         check_for_perfect_range::<ark_bls12_381::G1Affine>()
@@ -1205,6 +1313,90 @@ mod tests {
         check_for_perfect_range::<ark_secp256k1::Affine>()
     }
 
+    #[test]
+    fn checked_pedersen_commitment() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let setup_params = SetupParams::<ark_bls12_381::G1Affine>::new_for_perfect_range_proof::<
+            Blake2b512,
+        >(b"test", 16, 16, 1);
+        let gamma = Fr::rand(&mut rng);
+
+        for (base, num_bits, max) in [(2, 4, 16), (16, 8, 256), (8, 16, 32768), (16, 16, 65536)] {
+            // Largest value that can be proven in range
+            assert_eq!(
+                setup_params
+                    .compute_pedersen_commitment_checked(max - 1, &gamma, base, num_bits)
+                    .unwrap(),
+                setup_params.compute_pedersen_commitment(max - 1, &gamma)
+            );
+            assert!(matches!(
+                setup_params.compute_pedersen_commitment_checked(max, &gamma, base, num_bits),
+                Err(BulletproofsPlusPlusError::IncorrectBounds(_))
+            ));
+        }
+
+        // All values fit when the range covers u64
+        assert!(setup_params
+            .compute_pedersen_commitment_checked(u64::MAX, &gamma, 16, 64)
+            .is_ok());
+
+        for base in [0, 1, 6] {
+            assert!(matches!(
+                setup_params.compute_pedersen_commitment_checked(1, &gamma, base, 8),
+                Err(BulletproofsPlusPlusError::ExpectedPowerOfTwo(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn value_to_digits() {
+        let setup_params = SetupParams::<ark_bls12_381::G1Affine>::new_for_perfect_range_proof::<
+            Blake2b512,
+        >(b"test", 16, 16, 1);
+        let digits = |v: u64, base: u16, num_bits: u16| {
+            setup_params
+                .value_to_digits(v, base, num_bits)
+                .unwrap()
+                .into_iter()
+                .map(|d| d.into_bigint().as_ref()[0])
+                .collect::<Vec<_>>()
+        };
+
+        // Least significant digit first
+        assert_eq!(digits(9, 4, 4), vec![1, 2]);
+        assert_eq!(digits(13, 4, 4), vec![1, 3]);
+        assert_eq!(digits(0xabcd, 16, 16), vec![0xd, 0xc, 0xb, 0xa]);
+        assert_eq!(digits(5, 2, 4), vec![1, 0, 1, 0]);
+        assert_eq!(digits(300, 256, 16), vec![44, 1]);
+
+        for (base, num_bits, max) in [(2, 4, 16), (16, 8, 256), (8, 16, 32768), (16, 16, 65536)] {
+            let num_digits = (num_bits / util::base_bits(base)) as usize;
+            assert_eq!(digits(0, base, num_bits), vec![0; num_digits]);
+            // Largest value that can be proven in range has all digits equal to `base - 1`
+            assert_eq!(
+                digits(max - 1, base, num_bits),
+                vec![base as u64 - 1; num_digits]
+            );
+            assert!(matches!(
+                setup_params.value_to_digits(max, base, num_bits),
+                Err(BulletproofsPlusPlusError::IncorrectBounds(_))
+            ));
+        }
+
+        assert_eq!(digits(u64::MAX, 16, 64), vec![15; 16]);
+
+        assert!(matches!(
+            setup_params.value_to_digits(1, 16, 3),
+            Err(BulletproofsPlusPlusError::ValueIncompatibleWithBase(_))
+        ));
+        for base in [0, 1, 6] {
+            assert!(matches!(
+                setup_params.value_to_digits(1, base, 8),
+                Err(BulletproofsPlusPlusError::ExpectedPowerOfTwo(_))
+            ));
+        }
+    }
+
     #[test]
     fn poly() {
         let q = Fr::from(2);
@@ -1,13 +1,34 @@
-use crate::util::base_bits;
-use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use crate::{error::BulletproofsPlusPlusError, util::base_bits};
+use ark_ec::{hashing::HashToCurve, AffineRepr, CurveGroup, VariableBaseMSM};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{cfg_into_iter, rand::RngCore, vec::Vec, UniformRand};
+use ark_std::{cfg_into_iter, format, rand::RngCore, string::ToString, vec::Vec, UniformRand};
 use digest::Digest;
 use dock_crypto_utils::{concat_slices, hashing_utils::affine_group_elem_from_try_and_incr};
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
+#[cfg(feature = "std")]
+use std::{
+    any::{Any, TypeId},
+    sync::{Arc, Mutex, OnceLock},
+};
+
+/// Maximum number of params kept by `SetupParams::new_cached`. Once full, the oldest entry is evicted.
+#[cfg(feature = "std")]
+pub const MAX_CACHED_SETUP_PARAMS: usize = 16;
+
+/// Cache key is the group and digest types along with the arguments to `SetupParams::new`
+#[cfg(feature = "std")]
+type CacheKey = (TypeId, TypeId, Vec<u8>, u32, u32);
+
+#[cfg(feature = "std")]
+type CacheEntry = (CacheKey, Arc<dyn Any + Send + Sync>);
+
+/// Entries in insertion order, the oldest first
+#[cfg(feature = "std")]
+static SETUP_PARAMS_CACHE: OnceLock<Mutex<Vec<CacheEntry>>> = OnceLock::new();
+
 #[derive(Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct SetupParams<G: AffineRepr> {
     pub G: G,
@@ -18,53 +39,150 @@ pub struct SetupParams<G: AffineRepr> {
 impl<G: AffineRepr> SetupParams<G> {
     pub fn new<D: Digest>(label: &[u8], g_count: u32, h_count: u32) -> Self {
         let g = affine_group_elem_from_try_and_incr::<G, D>(&concat_slices![label, b" : G"]);
-        let g_vec = cfg_into_iter!((0..g_count))
-            .map(|i| {
-                affine_group_elem_from_try_and_incr::<G, D>(&concat_slices![
-                    label,
-                    b" : g_",
-                    i.to_le_bytes()
-                ])
-            })
-            .collect::<Vec<G>>();
-        let h_vec = cfg_into_iter!((0..h_count))
-            .map(|i| {
-                affine_group_elem_from_try_and_incr::<G, D>(&concat_slices![
-                    label,
-                    b" : h_",
-                    i.to_le_bytes()
-                ])
-            })
-            .collect::<Vec<G>>();
         Self {
             G: g,
-            G_vec: g_vec,
-            H_vec: h_vec,
+            G_vec: Self::generators::<D>(label, b" : g_", 0, g_count),
+            H_vec: Self::generators::<D>(label, b" : h_", 0, h_count),
+        }
+    }
+
+    /// Same as `Self::new` but remembers the created params so that later calls with the same group, digest,
+    /// `label` and counts return a copy of them rather than deriving the generators again. The cache is
+    /// shared across threads and holds at most `MAX_CACHED_SETUP_PARAMS` params, evicting the oldest.
+    #[cfg(feature = "std")]
+    pub fn new_cached<D: Digest + 'static>(label: &[u8], g_count: u32, h_count: u32) -> Self {
+        let key = (
+            TypeId::of::<G>(),
+            TypeId::of::<D>(),
+            label.to_vec(),
+            g_count,
+            h_count,
+        );
+        let cache = SETUP_PARAMS_CACHE.get_or_init(|| Mutex::new(Vec::new()));
+        let cached = cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v.clone());
+        if let Some(params) = cached.as_ref().and_then(|p| p.downcast_ref::<Self>()) {
+            return params.clone();
+        }
+        // Generators are derived without holding the lock so that other callers aren't blocked
+        let params = Self::new::<D>(label, g_count, h_count);
+        let mut entries = cache.lock().unwrap_or_else(|e| e.into_inner());
+        if !entries.iter().any(|(k, _)| *k == key) {
+            if entries.len() >= MAX_CACHED_SETUP_PARAMS {
+                entries.remove(0);
+            }
+            entries.push((key, Arc::new(params.clone())));
         }
+        params
+    }
+
+    /// Same as `Self::new` but creates the generators using the hash-to-curve method `H`, like one of the
+    /// suites of RFC 9380, rather than try-and-increment so that they can be recreated by other implementations
+    /// of the same method. `label` is the domain separation tag and the messages hashed are `" : G"` for `G`
+    /// and `" : g_"` or `" : h_"` followed by the generator's index as 4 bytes in little endian for `G_vec`
+    /// and `H_vec`.
+    pub fn new_with_hasher<H: HashToCurve<G::Group> + Sync>(
+        label: &[u8],
+        g_count: u32,
+        h_count: u32,
+    ) -> Result<Self, BulletproofsPlusPlusError> {
+        let hasher = H::new(label)
+            .map_err(|e| BulletproofsPlusPlusError::HashToCurveFailed(e.to_string()))?;
+        let g = hasher
+            .hash(b" : G")
+            .map_err(|e| BulletproofsPlusPlusError::HashToCurveFailed(e.to_string()))?;
+        Ok(Self {
+            G: g,
+            G_vec: Self::generators_with_hasher(&hasher, b" : g_", g_count)?,
+            H_vec: Self::generators_with_hasher(&hasher, b" : h_", h_count)?,
+        })
+    }
+
+    /// Append `additional_g` generators to `G_vec` and `additional_h` generators to `H_vec`. The new
+    /// generators are derived the same way as in `Self::new` with indices continuing from the current
+    /// sizes so `label` must be the one these params were created with. The result is the same as creating
+    /// the params with the larger counts, thus `G` and `H_vec[0]`, and hence existing Pedersen commitments,
+    /// remain valid while larger range proofs can be created.
+    pub fn extend_generators<D: Digest>(
+        &mut self,
+        label: &[u8],
+        additional_g: u32,
+        additional_h: u32,
+    ) {
+        let g_start = self.G_vec.len() as u32;
+        let h_start = self.H_vec.len() as u32;
+        self.G_vec.append(&mut Self::generators::<D>(
+            label,
+            b" : g_",
+            g_start,
+            g_start + additional_g,
+        ));
+        self.H_vec.append(&mut Self::generators::<D>(
+            label,
+            b" : h_",
+            h_start,
+            h_start + additional_h,
+        ));
     }
 
-    /// Create setup params for perfect range, i.e a range of form `[0, base^l)`
+    /// Create setup params for perfect range, i.e a range of form `[0, base^l)`. Panics if the number of
+    /// generators needed doesn't fit in a `u32`, use `Self::try_new_for_perfect_range_proof` to get an error instead.
     pub fn new_for_perfect_range_proof<D: Digest>(
         label: &[u8],
         base: u16,
         num_value_bits: u16,
         num_proofs: u32,
     ) -> Self {
-        Self::new::<D>(
-            label,
-            Self::get_no_of_G(base, num_value_bits, num_proofs),
-            8,
-        )
+        Self::try_new_for_perfect_range_proof::<D>(label, base, num_value_bits, num_proofs)
+            .expect("invalid base or too many generators")
     }
 
-    /// Create setup params for an arbitrary range, i.e a range of form `[a, b)`
+    /// Create setup params for an arbitrary range, i.e a range of form `[a, b)`. Panics if the number of
+    /// generators needed doesn't fit in a `u32`, use `Self::try_new_for_arbitrary_range_proof` to get an error instead.
     pub fn new_for_arbitrary_range_proof<D: Digest>(
         label: &[u8],
         base: u16,
         num_value_bits: u16,
         num_proofs: u32,
     ) -> Self {
-        Self::new_for_perfect_range_proof::<D>(label, base, num_value_bits, num_proofs * 2)
+        Self::try_new_for_arbitrary_range_proof::<D>(label, base, num_value_bits, num_proofs)
+            .expect("invalid base or too many generators")
+    }
+
+    /// Same as `Self::new_for_perfect_range_proof` but returns an error if the number of generators needed
+    /// doesn't fit in a `u32`
+    pub fn try_new_for_perfect_range_proof<D: Digest>(
+        label: &[u8],
+        base: u16,
+        num_value_bits: u16,
+        num_proofs: u32,
+    ) -> Result<Self, BulletproofsPlusPlusError> {
+        Ok(Self::new::<D>(
+            label,
+            Self::get_no_of_G(base, num_value_bits, num_proofs)?,
+            8,
+        ))
+    }
+
+    /// Same as `Self::new_for_arbitrary_range_proof` but returns an error if the number of generators needed
+    /// doesn't fit in a `u32`
+    pub fn try_new_for_arbitrary_range_proof<D: Digest>(
+        label: &[u8],
+        base: u16,
+        num_value_bits: u16,
+        num_proofs: u32,
+    ) -> Result<Self, BulletproofsPlusPlusError> {
+        let num_proofs = num_proofs.checked_mul(2).ok_or_else(|| {
+            BulletproofsPlusPlusError::TooManyGenerators(format!(
+                "number of proofs={} is too large",
+                num_proofs
+            ))
+        })?;
+        Self::try_new_for_perfect_range_proof::<D>(label, base, num_value_bits, num_proofs)
     }
 
     /// Create Pedersen commitment as `C = v*G + gamma*H_vec[0]`
@@ -72,6 +190,92 @@ impl<G: AffineRepr> SetupParams<G> {
         ((self.G * G::ScalarField::from(v)) + self.H_vec[0] * gamma).into_affine()
     }
 
+    /// Create Pedersen commitment with several blinding factors as `C = v*G + gammas[0]*H_vec[0] + gammas[1]*H_vec[1] + ...`.
+    /// Errors if there are more blinding factors than `H_vec`. Range proofs only support the blinding
+    /// factor for `H_vec[0]` so to prove that `v` is in range, the commitment without the other blinding
+    /// factors, i.e. `C - gammas[1]*H_vec[1] - gammas[2]*H_vec[2] - ...`, needs to be used.
+    pub fn compute_vector_pedersen_commitment(
+        &self,
+        v: u64,
+        gammas: &[G::ScalarField],
+    ) -> Result<G, BulletproofsPlusPlusError> {
+        if gammas.len() > self.H_vec.len() {
+            return Err(BulletproofsPlusPlusError::UnexpectedLengthOfVectors(
+                format!(
+                    "number of blinding factors={} should not be more than the size of H_vec={}",
+                    gammas.len(),
+                    self.H_vec.len()
+                ),
+            ));
+        }
+        Ok((self.G * G::ScalarField::from(v)
+            + G::Group::msm_unchecked(&self.H_vec[..gammas.len()], gammas))
+        .into_affine())
+    }
+
+    /// Same as `Self::compute_pedersen_commitment` but first checks that `v` can be proven to be in range by a
+    /// range proof with the given `base` and `num_bits`, i.e. `v < base^(num_bits / bits in base)`. Committing
+    /// to a value outside that range produces a commitment for which a range proof can never be created.
+    pub fn compute_pedersen_commitment_checked(
+        &self,
+        v: u64,
+        gamma: &G::ScalarField,
+        base: u16,
+        num_bits: u16,
+    ) -> Result<G, BulletproofsPlusPlusError> {
+        Self::check_value_in_range(v, base, num_bits)?;
+        Ok(self.compute_pedersen_commitment(v, gamma))
+    }
+
+    /// Decompose `v` into its `num_value_bits / bits in base` digits in base `base`, least significant digit
+    /// first. This is the digit vector the range proof commits to with `G_vec` so it can be passed as `n` to
+    /// `Self::compute_commitment`. Errors if `v` can't be proven to be in range by a range proof with the
+    /// given `base` and `num_value_bits`, as in `Self::compute_pedersen_commitment_checked`.
+    pub fn value_to_digits(
+        &self,
+        v: u64,
+        base: u16,
+        num_value_bits: u16,
+    ) -> Result<Vec<G::ScalarField>, BulletproofsPlusPlusError> {
+        Self::check_base(base)?;
+        let num_base_bits = base_bits(base);
+        if num_value_bits < num_base_bits {
+            return Err(BulletproofsPlusPlusError::ValueIncompatibleWithBase(format!(
+                "number of bits in value={} which should not be less than number of bits in base={}",
+                num_value_bits, base
+            )));
+        }
+        let num_digits = Self::check_value_in_range(v, base, num_value_bits)?;
+        let mask = (1u64 << num_base_bits) - 1;
+        let mut v = v;
+        let mut digits = Vec::with_capacity(num_digits as usize);
+        for _ in 0..num_digits {
+            digits.push(G::ScalarField::from(v & mask));
+            v >>= num_base_bits;
+        }
+        Ok(digits)
+    }
+
+    /// Check that `v < base^(num_bits / bits in base)` and return the number of digits `num_bits / bits in base`
+    fn check_value_in_range(
+        v: u64,
+        base: u16,
+        num_bits: u16,
+    ) -> Result<u32, BulletproofsPlusPlusError> {
+        Self::check_base(base)?;
+        let num_digits = (num_bits / base_bits(base)) as u32;
+        // If the upper bound doesn't fit in u128, any u64 is in range
+        if let Some(max) = (base as u128).checked_pow(num_digits) {
+            if v as u128 >= max {
+                return Err(BulletproofsPlusPlusError::IncorrectBounds(format!(
+                    "value={} should be < max={}",
+                    v, max
+                )));
+            }
+        }
+        Ok(num_digits)
+    }
+
     /// Returns `v*g + <g_vec, n> + <h_vec, l>`
     pub fn compute_commitment(
         &self,
@@ -110,8 +314,219 @@ impl<G: AffineRepr> SetupParams<G> {
         (self.G, self.H_vec[0])
     }
 
-    /// Get number of generators `G_i` required for creating proofs
-    pub fn get_no_of_G(base: u16, num_value_bits: u16, num_proofs: u32) -> u32 {
-        core::cmp::max(num_value_bits as u32 / base_bits(base) as u32, base as u32) * num_proofs
+    fn check_base(base: u16) -> Result<(), BulletproofsPlusPlusError> {
+        if base < 2 || !base.is_power_of_two() {
+            return Err(BulletproofsPlusPlusError::ExpectedPowerOfTwo(format!(
+                "base={} but should be a power of 2 greater than 1",
+                base
+            )));
+        }
+        Ok(())
+    }
+
+    /// Generators with indices in `[start, end)` created by hashing `label`, `prefix` and the index
+    fn generators<D: Digest>(label: &[u8], prefix: &[u8], start: u32, end: u32) -> Vec<G> {
+        cfg_into_iter!((start..end))
+            .map(|i| {
+                affine_group_elem_from_try_and_incr::<G, D>(&concat_slices![
+                    label,
+                    prefix,
+                    i.to_le_bytes()
+                ])
+            })
+            .collect::<Vec<G>>()
+    }
+
+    fn generators_with_hasher<H: HashToCurve<G::Group> + Sync>(
+        hasher: &H,
+        prefix: &[u8],
+        count: u32,
+    ) -> Result<Vec<G>, BulletproofsPlusPlusError> {
+        cfg_into_iter!((0..count))
+            .map(|i| {
+                hasher
+                    .hash(&concat_slices![prefix, i.to_le_bytes()])
+                    .map_err(|e| BulletproofsPlusPlusError::HashToCurveFailed(e.to_string()))
+            })
+            .collect::<Result<Vec<G>, _>>()
+    }
+
+    /// Get number of generators `G_i` required for creating proofs. Errors if the number doesn't fit in a `u32`
+    pub fn get_no_of_G(
+        base: u16,
+        num_value_bits: u16,
+        num_proofs: u32,
+    ) -> Result<u32, BulletproofsPlusPlusError> {
+        Self::check_base(base)?;
+        core::cmp::max(num_value_bits as u32 / base_bits(base) as u32, base as u32)
+            .checked_mul(num_proofs)
+            .ok_or_else(|| {
+                BulletproofsPlusPlusError::TooManyGenerators(format!(
+                    "base={}, number of value bits={} and number of proofs={} need more than {} generators",
+                    base,
+                    num_value_bits,
+                    num_proofs,
+                    u32::MAX
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::{g1::Config as G1Config, G1Affine, G1Projective};
+    use ark_ec::hashing::{curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurveBasedHasher};
+    use ark_ff::field_hashers::DefaultFieldHasher;
+    use blake2::Blake2b512;
+    use sha2::Sha256;
+
+    type Hasher =
+        MapToCurveBasedHasher<G1Projective, DefaultFieldHasher<Sha256, 128>, WBMap<G1Config>>;
+
+    #[test]
+    fn generators_from_hash_to_curve() {
+        let params_1 = SetupParams::<G1Affine>::new_with_hasher::<Hasher>(b"test", 10, 8).unwrap();
+        let params_2 = SetupParams::<G1Affine>::new_with_hasher::<Hasher>(b"test", 10, 8).unwrap();
+        assert_eq!(params_1, params_2);
+        assert_eq!(params_1.G_vec.len(), 10);
+        assert_eq!(params_1.H_vec.len(), 8);
+
+        // Generators are distinct
+        let mut gens = params_1.G_vec.clone();
+        gens.extend_from_slice(&params_1.H_vec);
+        gens.push(params_1.G);
+        for i in 0..gens.len() {
+            assert!(!gens[i].is_zero());
+            for j in i + 1..gens.len() {
+                assert_ne!(gens[i], gens[j]);
+            }
+        }
+
+        // A different label gives different generators
+        let params_3 =
+            SetupParams::<G1Affine>::new_with_hasher::<Hasher>(b"test-1", 10, 8).unwrap();
+        assert_ne!(params_1.G, params_3.G);
+        assert_ne!(params_1.G_vec, params_3.G_vec);
+        assert_ne!(params_1.H_vec, params_3.H_vec);
+
+        // and so does the default try-and-increment
+        let params_4 = SetupParams::<G1Affine>::new::<Blake2b512>(b"test", 10, 8);
+        assert_ne!(params_1.G, params_4.G);
+        assert_ne!(params_1.G_vec, params_4.G_vec);
+    }
+
+    /// The params cached for the given arguments, if any
+    #[cfg(feature = "std")]
+    fn cached<D: Digest + 'static>(
+        label: &[u8],
+        g_count: u32,
+        h_count: u32,
+    ) -> Option<SetupParams<G1Affine>> {
+        let key = (
+            TypeId::of::<G1Affine>(),
+            TypeId::of::<D>(),
+            label.to_vec(),
+            g_count,
+            h_count,
+        );
+        SETUP_PARAMS_CACHE
+            .get()?
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(k, _)| *k == key)
+            .and_then(|(_, v)| v.downcast_ref::<SetupParams<G1Affine>>().cloned())
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn cached_params() {
+        // The cache is shared by all tests in this binary so this test only uses labels of its own and only
+        // checks for the presence or absence of its own params
+        let label = b"test-cached";
+        let params_1 = SetupParams::<G1Affine>::new_cached::<Blake2b512>(label, 512, 8);
+        assert_eq!(
+            params_1,
+            SetupParams::<G1Affine>::new::<Blake2b512>(label, 512, 8)
+        );
+        assert_eq!(cached::<Blake2b512>(label, 512, 8), Some(params_1.clone()));
+        let params_2 = SetupParams::<G1Affine>::new_cached::<Blake2b512>(label, 512, 8);
+        assert_eq!(params_1, params_2);
+
+        // Different counts or digest aren't served from the cache
+        let params_3 = SetupParams::<G1Affine>::new_cached::<Blake2b512>(label, 10, 8);
+        assert_eq!(params_3.G_vec.len(), 10);
+        assert_eq!(params_3.G_vec, params_1.G_vec[..10]);
+        assert_eq!(cached::<Blake2b512>(label, 10, 8), Some(params_3));
+        let params_4 = SetupParams::<G1Affine>::new_cached::<Sha256>(label, 512, 8);
+        assert_eq!(
+            params_4,
+            SetupParams::<G1Affine>::new::<Sha256>(label, 512, 8)
+        );
+        assert_ne!(params_4.G, params_1.G);
+        assert_eq!(cached::<Sha256>(label, 512, 8), Some(params_4));
+
+        // Adding as many params as the cache holds evicts the older ones
+        let label = b"test-cached-eviction";
+        for i in 0..MAX_CACHED_SETUP_PARAMS as u32 {
+            SetupParams::<G1Affine>::new_cached::<Blake2b512>(label, i, 1);
+        }
+        assert_eq!(cached::<Blake2b512>(b"test-cached", 512, 8), None);
+        let last = MAX_CACHED_SETUP_PARAMS as u32 - 1;
+        assert_eq!(
+            cached::<Blake2b512>(label, last, 1),
+            Some(SetupParams::<G1Affine>::new::<Blake2b512>(label, last, 1))
+        );
+    }
+
+    #[test]
+    fn no_of_generators_overflow() {
+        type Params = SetupParams<G1Affine>;
+
+        assert_eq!(Params::get_no_of_G(2, 64, 1).unwrap(), 64);
+        assert_eq!(Params::get_no_of_G(16, 64, 3).unwrap(), 48);
+        // `base` generators are needed when it exceeds the number of digits
+        assert_eq!(Params::get_no_of_G(256, 64, 2).unwrap(), 512);
+
+        // Largest number of proofs for which the count fits in a u32
+        assert_eq!(
+            Params::get_no_of_G(2, 64, u32::MAX / 64).unwrap(),
+            (u32::MAX / 64) * 64
+        );
+        assert!(matches!(
+            Params::get_no_of_G(2, 64, u32::MAX / 64 + 1),
+            Err(BulletproofsPlusPlusError::TooManyGenerators(_))
+        ));
+        assert!(matches!(
+            Params::get_no_of_G(32768, 64, u32::MAX / 32768 + 1),
+            Err(BulletproofsPlusPlusError::TooManyGenerators(_))
+        ));
+        assert!(matches!(
+            Params::get_no_of_G(2, 64, u32::MAX),
+            Err(BulletproofsPlusPlusError::TooManyGenerators(_))
+        ));
+
+        // Doubling the number of proofs for arbitrary ranges also overflows
+        assert!(matches!(
+            Params::try_new_for_arbitrary_range_proof::<Blake2b512>(
+                b"test",
+                2,
+                64,
+                u32::MAX / 2 + 1
+            ),
+            Err(BulletproofsPlusPlusError::TooManyGenerators(_))
+        ));
+        assert!(matches!(
+            Params::try_new_for_perfect_range_proof::<Blake2b512>(b"test", 2, 64, u32::MAX),
+            Err(BulletproofsPlusPlusError::TooManyGenerators(_))
+        ));
+
+        for base in [0, 1, 6] {
+            assert!(matches!(
+                Params::get_no_of_G(base, 64, 1),
+                Err(BulletproofsPlusPlusError::ExpectedPowerOfTwo(_))
+            ));
+        }
     }
 }
@@ -71,6 +71,12 @@ impl<E: Pairing> SignaturePoK<E> {
         self.k.challenge_contribution(&mut writer)
     }
 
+    /// Number of messages that were not revealed in this proof, i.e. the messages committed in `k`.
+    /// The revealed messages (and hence the total number of signed messages) are not part of the proof.
+    pub fn hidden_message_count(&self) -> usize {
+        self.k.committed_message_indices.len()
+    }
+
     /// Get the response from post-challenge phase of the Schnorr protocol for the given
     /// message index `msg_idx`. Used when comparing message equality.
     pub fn response_for_message<I>(
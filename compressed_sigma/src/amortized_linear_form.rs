@@ -55,7 +55,7 @@ where
             (0..max_size).map(|_| G::ScalarField::rand(rng)).collect()
         };
         let rho = G::ScalarField::rand(rng);
-        let t = linear_form.eval(&r);
+        let t = linear_form.try_eval(&r)?;
         // h * rho is done separately to avoid copying g
         let A = G::Group::msm_unchecked(g, &r).add(&h.mul_bigint(rho.into_bigint()));
         Ok(Self {
@@ -132,7 +132,7 @@ where
 
         // Check \sum_{i}(y_i * c^i) + t == L(z_tilde)
         let c_y = inner_product(&challenge_powers, y);
-        if !(c_y + t - linear_form.eval(&self.z_tilde)).is_zero() {
+        if !(c_y + t - linear_form.try_eval(&self.z_tilde)?).is_zero() {
             return Err(CompSigmaError::InvalidResponse);
         }
         Ok(())
@@ -145,7 +145,7 @@ where
         k: &G,
         linear_form: &L,
         new_challenge: &G::ScalarField,
-    ) -> compressed_linear_form::Response<G> {
+    ) -> Result<compressed_linear_form::Response<G>, CompSigmaError> {
         let (g_hat, L_tilde) =
             compressed_linear_form::prepare_generators_and_linear_form_for_compression(
                 g,
@@ -458,7 +458,9 @@ mod tests {
             .unwrap();
 
         let c_1 = Fr::rand(&mut rng);
-        let comp_resp = response.compress::<Blake2b512, _>(&g, &h, &k, &linear_form, &c_1);
+        let comp_resp = response
+            .compress::<Blake2b512, _>(&g, &h, &k, &linear_form, &c_1)
+            .unwrap();
         Response::is_valid_compressed::<Blake2b512, _>(
             &g,
             &h,
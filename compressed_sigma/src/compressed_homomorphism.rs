@@ -45,7 +45,7 @@ where
         blindings: Option<Vec<G::ScalarField>>,
     ) -> Result<Self, CompSigmaError> {
         if !g.len().is_power_of_two() {
-            return Err(CompSigmaError::UncompressedNotPowerOf2);
+            return Err(CompSigmaError::NonPowerOfTwoSize(g.len()));
         }
         let r = if let Some(blindings) = blindings {
             if blindings.len() != g.len() {
@@ -73,16 +73,16 @@ where
         challenge: &G::ScalarField,
     ) -> Result<Response<G>, CompSigmaError> {
         if !g.len().is_power_of_two() {
-            return Err(CompSigmaError::UncompressedNotPowerOf2);
+            return Err(CompSigmaError::NonPowerOfTwoSize(g.len()));
         }
         if g.len() != x.len() {
-            return Err(CompSigmaError::UncompressedNotPowerOf2);
+            return Err(CompSigmaError::VectorLenMismatch);
         }
         if !f.size().is_power_of_two() {
-            return Err(CompSigmaError::UncompressedNotPowerOf2);
+            return Err(CompSigmaError::NonPowerOfTwoSize(f.size()));
         }
         if f.size() != x.len() {
-            return Err(CompSigmaError::UncompressedNotPowerOf2);
+            return Err(CompSigmaError::VectorLenMismatch);
         }
 
         // z = [c_0 * r_0 + x_0, c_0 * r_1 + x_1, ..., c_0 * r_n + x_n]
@@ -239,7 +239,7 @@ where
         }
 
         if (g.len() != 2) || (f.size() != 2) {
-            return Err(CompSigmaError::UncompressedNotPowerOf2);
+            return Err(CompSigmaError::WrongRecursionLevel);
         }
 
         if G::Group::msm_unchecked(&g, &[self.z_prime_0, self.z_prime_1]) != Q {
@@ -356,7 +356,7 @@ where
         f: &F,
     ) -> Result<(), CompSigmaError> {
         if !g.len().is_power_of_two() {
-            return Err(CompSigmaError::UncompressedNotPowerOf2);
+            return Err(CompSigmaError::NonPowerOfTwoSize(g.len()));
         }
         if self.A.len() != self.B.len() {
             return Err(CompSigmaError::VectorLenMismatch);
@@ -371,7 +371,7 @@ where
             return Err(CompSigmaError::WrongRecursionLevel);
         }
         if !f.size().is_power_of_two() {
-            return Err(CompSigmaError::UncompressedNotPowerOf2);
+            return Err(CompSigmaError::NonPowerOfTwoSize(f.size()));
         }
         Ok(())
     }
@@ -509,4 +509,23 @@ mod tests {
         check_compression(63);
         check_compression(64);
     }
+
+    #[test]
+    fn non_power_of_two_sizes() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        for size in [5, 6] {
+            let homomorphism = TestHom {
+                constants: (0..size)
+                    .map(|_| <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine())
+                    .collect::<Vec<_>>(),
+            };
+            let g = (0..size)
+                .map(|_| <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine())
+                .collect::<Vec<_>>();
+            assert!(matches!(
+                RandomCommitment::new(&mut rng, &g, &homomorphism, None),
+                Err(CompSigmaError::NonPowerOfTwoSize(s)) if s == size
+            ));
+        }
+    }
 }
@@ -12,9 +12,10 @@ use ark_std::{
     UniformRand,
 };
 use digest::Digest;
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use crate::{error::CompSigmaError, transforms::LinearForm};
-use dock_crypto_utils::hashing_utils::field_elem_from_try_and_incr;
+use dock_crypto_utils::{hashing_utils::field_elem_from_try_and_incr, transcript::Transcript};
 
 use crate::utils::{elements_to_element_products, get_g_multiples_for_verifying_compression};
 use dock_crypto_utils::msm::WindowTable;
@@ -22,11 +23,17 @@ use dock_crypto_utils::msm::WindowTable;
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
-#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+/// Commitment to the prover's randomness. `r` and `rho` are secret and are zeroized on drop. `G` can be
+/// any group, like G2 of a pairing when the vector is committed in G2.
+#[derive(
+    Clone, Debug, PartialEq, Eq, Zeroize, ZeroizeOnDrop, CanonicalSerialize, CanonicalDeserialize,
+)]
 pub struct RandomCommitment<G: AffineRepr> {
     pub r: Vec<G::ScalarField>,
     pub rho: G::ScalarField,
+    #[zeroize(skip)]
     pub A_hat: G,
+    #[zeroize(skip)]
     pub t: G::ScalarField,
 }
 
@@ -50,18 +57,35 @@ where
         blindings: Option<Vec<G::ScalarField>>,
     ) -> Result<Self, CompSigmaError> {
         if !(g.len() + 1).is_power_of_two() {
-            return Err(CompSigmaError::UncompressedNotPowerOf2);
+            return Err(CompSigmaError::NonPowerOfTwoSize(g.len() + 1));
         }
         let r = if let Some(blindings) = blindings {
-            if blindings.len() != g.len() {
-                return Err(CompSigmaError::VectorLenMismatch);
-            }
             blindings
         } else {
             (0..g.len()).map(|_| G::ScalarField::rand(rng)).collect()
         };
         let rho = G::ScalarField::rand(rng);
-        let t = linear_form.eval(&r);
+        Self::new_deterministic(g, h, linear_form, r, rho)
+    }
+
+    /// Same as `Self::new` but takes both the blindings `r` and `rho` rather than sampling them, for when
+    /// proof generation must be reproducible. The caller is responsible for deriving them such that they are
+    /// never reused across proofs, like from a PRF keyed with a secret seed.
+    pub fn new_deterministic<L: LinearForm<G::ScalarField>>(
+        g: &[G],
+        h: &G,
+        linear_form: &L,
+        blindings: Vec<G::ScalarField>,
+        rho: G::ScalarField,
+    ) -> Result<Self, CompSigmaError> {
+        if !(g.len() + 1).is_power_of_two() {
+            return Err(CompSigmaError::NonPowerOfTwoSize(g.len() + 1));
+        }
+        if blindings.len() != g.len() {
+            return Err(CompSigmaError::VectorLenMismatch);
+        }
+        let r = blindings;
+        let t = linear_form.try_eval(&r)?;
         // h * rho is done separately to avoid copying g
         let A_hat = G::Group::msm_unchecked(g, &r).add(&h.mul_bigint(rho.into_bigint()));
         Ok(Self {
@@ -83,15 +107,101 @@ where
         c_0: &G::ScalarField,
         c_1: &G::ScalarField,
     ) -> Result<Response<G>, CompSigmaError> {
+        let (z_hat, g_hat, L_tilde) =
+            self.prepare_for_compression(g, h, linear_form, x, gamma, c_0, c_1)?;
+        Self::compressed_response::<D, L>(z_hat, g_hat, k, L_tilde)
+    }
+
+    /// Generate the challenges `c_0` and `c_1` from `transcript` after adding `A_hat` and `t` to it. The
+    /// verifier derives the same challenges using `challenges_from_transcript`.
+    pub fn challenges_from_transcript(
+        &self,
+        transcript: &mut impl Transcript,
+    ) -> (G::ScalarField, G::ScalarField) {
+        challenges_from_transcript(&self.A_hat, &self.t, transcript)
+    }
+
+    /// Same as `Self::response` but the challenge of each round of the compression is generated from
+    /// `transcript` after adding that round's `A` and `B` to it rather than by hashing the `A`s and `B`s
+    /// of the rounds so far. Use when this protocol is part of a larger one sharing the transcript.
+    pub fn response_with_transcript<L: LinearForm<G::ScalarField>>(
+        &self,
+        g: &[G],
+        h: &G,
+        k: &G,
+        linear_form: &L,
+        x: &[G::ScalarField],
+        gamma: &G::ScalarField,
+        c_0: &G::ScalarField,
+        c_1: &G::ScalarField,
+        transcript: &mut impl Transcript,
+    ) -> Result<Response<G>, CompSigmaError> {
+        let (z_hat, g_hat, L_tilde) =
+            self.prepare_for_compression(g, h, linear_form, x, gamma, c_0, c_1)?;
+        Self::compressed_response_with_transcript(z_hat, g_hat, k, L_tilde, transcript)
+    }
+
+    /// Same as `Self::response` but consumes the commitment so that the blindings are zeroized once
+    /// the response is created.
+    pub fn response_consuming<D: Digest, L: LinearForm<G::ScalarField>>(
+        self,
+        g: &[G],
+        h: &G,
+        k: &G,
+        linear_form: &L,
+        x: &[G::ScalarField],
+        gamma: &G::ScalarField,
+        c_0: &G::ScalarField,
+        c_1: &G::ScalarField,
+    ) -> Result<Response<G>, CompSigmaError> {
+        self.response::<D, L>(g, h, k, linear_form, x, gamma, c_0, c_1)
+    }
+
+    /// Run the compressed (non-zero) proof of knowledge of the response vector as described in the
+    /// Protocol 4 in the paper. The relation in this proof is Q = g_hat * z_hat + k * L_tilde(z_hat)
+    /// and knowledge of z_hat needs to be proven but the proof is not zero-knowledge
+    pub fn compressed_response<D: Digest, L: LinearForm<G::ScalarField>>(
+        z_hat: Vec<G::ScalarField>,
+        g_hat: Vec<G>,
+        k: &G,
+        L_tilde: L,
+    ) -> Result<Response<G>, CompSigmaError> {
+        let mut bytes = vec![];
+        Self::_compressed_response(z_hat, g_hat, k, L_tilde, |A, B| {
+            hash_round_challenge::<_, D, _>(&mut bytes, A, B)
+        })
+    }
+
+    /// Same as `Self::compressed_response` but the challenge of each round is generated from `transcript`
+    pub fn compressed_response_with_transcript<L: LinearForm<G::ScalarField>>(
+        z_hat: Vec<G::ScalarField>,
+        g_hat: Vec<G>,
+        k: &G,
+        L_tilde: L,
+        transcript: &mut impl Transcript,
+    ) -> Result<Response<G>, CompSigmaError> {
+        Self::_compressed_response(z_hat, g_hat, k, L_tilde, |A, B| {
+            transcript_round_challenge(transcript, A, B)
+        })
+    }
+
+    fn prepare_for_compression<L: LinearForm<G::ScalarField>>(
+        &self,
+        g: &[G],
+        h: &G,
+        linear_form: &L,
+        x: &[G::ScalarField],
+        gamma: &G::ScalarField,
+        c_0: &G::ScalarField,
+        c_1: &G::ScalarField,
+    ) -> Result<(Vec<G::ScalarField>, Vec<G>, L), CompSigmaError> {
         if !(g.len() + 1).is_power_of_two() {
-            return Err(CompSigmaError::UncompressedNotPowerOf2);
+            return Err(CompSigmaError::NonPowerOfTwoSize(g.len() + 1));
         }
         if g.len() != x.len() {
             return Err(CompSigmaError::VectorLenMismatch);
         }
-        if !linear_form.size().is_power_of_two() {
-            return Err(CompSigmaError::UncompressedNotPowerOf2);
-        }
+        linear_form.validate_splittable()?;
         if (linear_form.size() - 1) != x.len() {
             return Err(CompSigmaError::VectorLenMismatch);
         }
@@ -109,21 +219,17 @@ where
 
         let (g_hat, L_tilde) =
             prepare_generators_and_linear_form_for_compression::<G, L>(g, h, linear_form, c_1);
-
-        Ok(Self::compressed_response::<D, L>(z_hat, g_hat, k, L_tilde))
+        Ok((z_hat, g_hat, L_tilde))
     }
 
-    /// Run the compressed (non-zero) proof of knowledge of the response vector as described in the
-    /// Protocol 4 in the paper. The relation in this proof is Q = g_hat * z_hat + k * L_tilde(z_hat)
-    /// and knowledge of z_hat needs to be proven but the proof is not zero-knowledge
-    pub fn compressed_response<D: Digest, L: LinearForm<G::ScalarField>>(
+    /// `round_challenge` returns the challenge for the round given that round's `A` and `B`
+    fn _compressed_response<L: LinearForm<G::ScalarField>>(
         mut z_hat: Vec<G::ScalarField>,
         mut g_hat: Vec<G>,
         k: &G,
         mut L_tilde: L,
-    ) -> Response<G> {
-        let mut bytes = vec![];
-
+        mut round_challenge: impl FnMut(&G::Group, &G::Group) -> G::ScalarField,
+    ) -> Result<Response<G>, CompSigmaError> {
         let mut As = vec![];
         let mut Bs = vec![];
 
@@ -143,15 +249,13 @@ where
 
             // A = g_hat_r * z_hat_l + k * L_tilde_r(z_hat_l)
             let A = G::Group::msm_unchecked(&g_hat_r, &z_hat)
-                + k_table.multiply(&L_tilde_r.eval(&z_hat));
+                + k_table.multiply(&L_tilde_r.try_eval(&z_hat)?);
 
             // B = g_hat_l * z_hat_r + k * L_tilde_l(z_hat_r)
             let B = G::Group::msm_unchecked(&g_hat, &z_hat_r)
-                + k_table.multiply(&L_tilde_l.eval(&z_hat_r));
+                + k_table.multiply(&L_tilde_l.try_eval(&z_hat_r)?);
 
-            A.serialize_compressed(&mut bytes).unwrap();
-            B.serialize_compressed(&mut bytes).unwrap();
-            let c = field_elem_from_try_and_incr::<G::ScalarField, D>(&bytes);
+            let c = round_challenge(&A, &B);
             let c_repr = c.into_bigint();
 
             // Set `g_hat` as g' in the paper
@@ -172,12 +276,12 @@ where
             Bs.push(B);
         }
 
-        Response {
+        Ok(Response {
             z_prime_0: z_hat[0],
             z_prime_1: z_hat[1],
             A: G::Group::normalize_batch(&As),
             B: G::Group::normalize_batch(&Bs),
-        }
+        })
     }
 }
 
@@ -185,6 +289,55 @@ impl<G> Response<G>
 where
     G: AffineRepr,
 {
+    /// Check that the sizes of `A` and `B` are valid for a proof created with `g_len` generators `g`, i.e.
+    /// `A` and `B` have the same size and `g_len + 1 == 2^(A.len() + 1)`. Meant to be called right after
+    /// deserializing a response, before doing any cryptographic work. The `is_valid*` functions do this as well.
+    pub fn validate_shape(&self, g_len: usize) -> Result<(), CompSigmaError> {
+        if !(g_len + 1).is_power_of_two() {
+            return Err(CompSigmaError::NonPowerOfTwoSize(g_len + 1));
+        }
+        if self.A.len() != self.B.len() {
+            return Err(CompSigmaError::VectorLenMismatch);
+        }
+        // Comparing the exponents rather than computing `2^(A.len() + 1)` as that overflows for a large `A`
+        if (g_len + 1).trailing_zeros() as usize != self.A.len() + 1 {
+            return Err(CompSigmaError::WrongRecursionLevel);
+        }
+        Ok(())
+    }
+
+    /// Check that the response doesn't have more than `max_rounds` rounds of compression, i.e. neither `A` nor
+    /// `B` has more than `max_rounds` elements. Bounds the work done by a verifier for a response from an
+    /// untrusted party, thus meant to be called before any cryptographic work.
+    pub fn validate_rounds(&self, max_rounds: usize) -> Result<(), CompSigmaError> {
+        let rounds = core::cmp::max(self.A.len(), self.B.len());
+        if rounds > max_rounds {
+            return Err(CompSigmaError::TooManyRounds(rounds, max_rounds));
+        }
+        Ok(())
+    }
+
+    /// Same as `Self::is_valid` but first checks that the response doesn't have more than `max_rounds`
+    /// rounds using `Self::validate_rounds`
+    #[allow(clippy::too_many_arguments)]
+    pub fn is_valid_with_max_rounds<D: Digest, L: LinearForm<G::ScalarField>>(
+        &self,
+        g: &[G],
+        h: &G,
+        k: &G,
+        P: &G,
+        y: &G::ScalarField,
+        linear_form: &L,
+        A_hat: &G,
+        t: &G::ScalarField,
+        c_0: &G::ScalarField,
+        c_1: &G::ScalarField,
+        max_rounds: usize,
+    ) -> Result<(), CompSigmaError> {
+        self.validate_rounds(max_rounds)?;
+        self.is_valid::<D, L>(g, h, k, P, y, linear_form, A_hat, t, c_0, c_1)
+    }
+
     /// Validate the proof of knowledge in the recursive manner where the size of the various
     /// vectors is reduced to half in each iteration. This execution is similar to the prover's.
     /// A naive and thus slower implementation than `is_valid`
@@ -209,6 +362,29 @@ where
         self.recursively_validate_compressed::<D, L>(Q, g_hat, L_tilde, k)
     }
 
+    /// Same as `Self::is_valid_recursive` but for a response created with `RandomCommitment::response_with_transcript`
+    pub fn is_valid_recursive_with_transcript<L: LinearForm<G::ScalarField>>(
+        &self,
+        g: &[G],
+        h: &G,
+        k: &G,
+        P: &G,
+        y: &G::ScalarField,
+        linear_form: &L,
+        A_hat: &G,
+        t: &G::ScalarField,
+        c_0: &G::ScalarField,
+        c_1: &G::ScalarField,
+        transcript: &mut impl Transcript,
+    ) -> Result<(), CompSigmaError> {
+        self.check_sizes(g, linear_form)?;
+
+        let (g_hat, L_tilde) =
+            prepare_generators_and_linear_form_for_compression::<G, L>(g, h, linear_form, c_1);
+        let Q = calculate_Q(k, P, y, A_hat, t, c_0, c_1);
+        self.recursively_validate_compressed_with_transcript(Q, g_hat, L_tilde, k, transcript)
+    }
+
     /// Validate the proof of knowledge in the non-recursive manner. This will delay scalar multiplications
     /// till the end similar to whats described in the Bulletproofs paper, thus is faster than the recursive
     /// version above. The key idea is that the verifier knows both `A` and `B` at the start and thus he knows
@@ -235,18 +411,112 @@ where
         self.validate_compressed::<D, L>(Q, g_hat, L_tilde, k)
     }
 
+    /// Same as `Self::is_valid` but takes `Q` rather than computing it from `P`, `y`, `A_hat` and `t`. Useful
+    /// when the caller already has `Q`, like when checking several responses against the same `Q`. `Q`
+    /// can be computed using `calculate_Q`.
+    pub fn is_valid_with_Q<D: Digest, L: LinearForm<G::ScalarField>>(
+        &self,
+        g: &[G],
+        h: &G,
+        k: &G,
+        linear_form: &L,
+        Q: G::Group,
+        c_1: &G::ScalarField,
+    ) -> Result<(), CompSigmaError> {
+        self.check_sizes(g, linear_form)?;
+
+        let (g_hat, L_tilde) =
+            prepare_generators_and_linear_form_for_compression::<G, L>(g, h, linear_form, c_1);
+        self.validate_compressed::<D, L>(Q, g_hat, L_tilde, k)
+    }
+
+    /// Same as `Self::is_valid` but for a response created with `RandomCommitment::response_with_transcript`
+    pub fn is_valid_with_transcript<L: LinearForm<G::ScalarField>>(
+        &self,
+        g: &[G],
+        h: &G,
+        k: &G,
+        P: &G,
+        y: &G::ScalarField,
+        linear_form: &L,
+        A_hat: &G,
+        t: &G::ScalarField,
+        c_0: &G::ScalarField,
+        c_1: &G::ScalarField,
+        transcript: &mut impl Transcript,
+    ) -> Result<(), CompSigmaError> {
+        self.check_sizes(g, linear_form)?;
+
+        let (g_hat, L_tilde) =
+            prepare_generators_and_linear_form_for_compression::<G, L>(g, h, linear_form, c_1);
+        let Q = calculate_Q(k, P, y, A_hat, t, c_0, c_1);
+        self.validate_compressed_with_transcript(Q, g_hat, L_tilde, k, transcript)
+    }
+
     pub fn recursively_validate_compressed<D: Digest, L: LinearForm<G::ScalarField>>(
+        &self,
+        Q: G::Group,
+        g_hat: Vec<G>,
+        L_tilde: L,
+        k: &G,
+    ) -> Result<(), CompSigmaError> {
+        let mut bytes = vec![];
+        self._recursively_validate_compressed(Q, g_hat, L_tilde, k, |A, B| {
+            hash_round_challenge::<_, D, _>(&mut bytes, A, B)
+        })
+    }
+
+    /// Same as `Self::recursively_validate_compressed` but the challenge of each round is generated from `transcript`
+    pub fn recursively_validate_compressed_with_transcript<L: LinearForm<G::ScalarField>>(
+        &self,
+        Q: G::Group,
+        g_hat: Vec<G>,
+        L_tilde: L,
+        k: &G,
+        transcript: &mut impl Transcript,
+    ) -> Result<(), CompSigmaError> {
+        self._recursively_validate_compressed(Q, g_hat, L_tilde, k, |A, B| {
+            transcript_round_challenge(transcript, A, B)
+        })
+    }
+
+    pub fn validate_compressed<D: Digest, L: LinearForm<G::ScalarField>>(
+        &self,
+        Q: G::Group,
+        g_hat: Vec<G>,
+        L_tilde: L,
+        k: &G,
+    ) -> Result<(), CompSigmaError> {
+        let mut bytes = vec![];
+        self._validate_compressed(Q, g_hat, L_tilde, k, |A, B| {
+            hash_round_challenge::<_, D, _>(&mut bytes, A, B)
+        })
+    }
+
+    /// Same as `Self::validate_compressed` but the challenge of each round is generated from `transcript`
+    pub fn validate_compressed_with_transcript<L: LinearForm<G::ScalarField>>(
+        &self,
+        Q: G::Group,
+        g_hat: Vec<G>,
+        L_tilde: L,
+        k: &G,
+        transcript: &mut impl Transcript,
+    ) -> Result<(), CompSigmaError> {
+        self._validate_compressed(Q, g_hat, L_tilde, k, |A, B| {
+            transcript_round_challenge(transcript, A, B)
+        })
+    }
+
+    fn _recursively_validate_compressed<L: LinearForm<G::ScalarField>>(
         &self,
         mut Q: G::Group,
         mut g_hat: Vec<G>,
         mut L_tilde: L,
         k: &G,
+        mut round_challenge: impl FnMut(&G, &G) -> G::ScalarField,
     ) -> Result<(), CompSigmaError> {
-        let mut bytes = vec![];
         for (A, B) in self.A.iter().zip(self.B.iter()) {
-            A.serialize_compressed(&mut bytes).unwrap();
-            B.serialize_compressed(&mut bytes).unwrap();
-            let c = field_elem_from_try_and_incr::<G::ScalarField, D>(&bytes);
+            let c = round_challenge(A, B);
             let c_repr = c.into_bigint();
 
             let m = g_hat.len();
@@ -263,14 +533,14 @@ where
         }
 
         if (g_hat.len() != 2) || (L_tilde.size() != 2) {
-            return Err(CompSigmaError::UncompressedNotPowerOf2);
+            return Err(CompSigmaError::WrongRecursionLevel);
         }
 
         // Check if g_hat * [z'_0, z'_1] + k * L_tilde([z'_0, z'_1]) == Q
         g_hat.push(*k);
 
         let mut scalars = vec![self.z_prime_0, self.z_prime_1];
-        let l_z = L_tilde.eval(&[self.z_prime_0, self.z_prime_1]);
+        let l_z = L_tilde.try_eval(&[self.z_prime_0, self.z_prime_1])?;
         scalars.push(l_z);
 
         if G::Group::msm_unchecked(&g_hat, &scalars) == Q {
@@ -280,22 +550,20 @@ where
         }
     }
 
-    pub fn validate_compressed<D: Digest, L: LinearForm<G::ScalarField>>(
+    fn _validate_compressed<L: LinearForm<G::ScalarField>>(
         &self,
         mut Q: G::Group,
         mut g_hat: Vec<G>,
         mut L_tilde: L,
         k: &G,
+        mut round_challenge: impl FnMut(&G, &G) -> G::ScalarField,
     ) -> Result<(), CompSigmaError> {
         // Create challenges for each round and store in `challenges`
         let mut challenges = vec![];
         // Holds squares of challenge of each round
         let mut challenge_squares = vec![];
-        let mut bytes = vec![];
         for (A, B) in self.A.iter().zip(self.B.iter()) {
-            A.serialize_compressed(&mut bytes).unwrap();
-            B.serialize_compressed(&mut bytes).unwrap();
-            let c = field_elem_from_try_and_incr::<G::ScalarField, D>(&bytes);
+            let c = round_challenge(A, B);
 
             let (L_tilde_l, L_tilde_r) = L_tilde.split_in_half();
             L_tilde = L_tilde_l.scale(&c).add(&L_tilde_r);
@@ -342,7 +610,7 @@ where
             + G::Group::msm_bigint(&self.B, &B_multiples)
             + Q;
 
-        let l_z = L_tilde.eval(&[self.z_prime_0, self.z_prime_1]);
+        let l_z = L_tilde.try_eval(&[self.z_prime_0, self.z_prime_1])?;
 
         g_hat.push(*k);
         g_hat_multiples.push(l_z);
@@ -360,18 +628,8 @@ where
         g: &[G],
         linear_form: &L,
     ) -> Result<(), CompSigmaError> {
-        if !(g.len() + 1).is_power_of_two() {
-            return Err(CompSigmaError::UncompressedNotPowerOf2);
-        }
-        if self.A.len() != self.B.len() {
-            return Err(CompSigmaError::VectorLenMismatch);
-        }
-        if (g.len() + 1) != (1 << (self.A.len() + 1)) {
-            return Err(CompSigmaError::WrongRecursionLevel);
-        }
-        if !linear_form.size().is_power_of_two() {
-            return Err(CompSigmaError::UncompressedNotPowerOf2);
-        }
+        self.validate_shape(g.len())?;
+        linear_form.validate_splittable()?;
         Ok(())
     }
 }
@@ -395,8 +653,47 @@ pub fn prepare_generators_and_linear_form_for_compression<
     (g_hat, L_tilde)
 }
 
+/// Challenge for a round of compression created by hashing the `A`s and `B`s of all rounds so far.
+/// `bytes` holds the serialized `A`s and `B`s of the previous rounds.
+fn hash_round_challenge<F: PrimeField, D: Digest, T: CanonicalSerialize>(
+    bytes: &mut Vec<u8>,
+    A: &T,
+    B: &T,
+) -> F {
+    A.serialize_compressed(&mut *bytes).unwrap();
+    B.serialize_compressed(&mut *bytes).unwrap();
+    field_elem_from_try_and_incr::<F, D>(bytes)
+}
+
+/// Challenge for a round of compression created from the transcript after adding the round's `A` and `B`
+fn transcript_round_challenge<F: PrimeField, T: CanonicalSerialize>(
+    transcript: &mut impl Transcript,
+    A: &T,
+    B: &T,
+) -> F {
+    transcript.append(b"A", A);
+    transcript.append(b"B", B);
+    transcript.challenge_scalar(b"c")
+}
+
+/// Challenges `c_0` and `c_1` generated from the transcript after adding the prover's commitment
+/// `A_hat` and `t` to it. Used by the verifier to derive the same challenges as the prover's
+/// `RandomCommitment::challenges_from_transcript`.
+pub fn challenges_from_transcript<G: AffineRepr>(
+    A_hat: &G,
+    t: &G::ScalarField,
+    transcript: &mut impl Transcript,
+) -> (G::ScalarField, G::ScalarField) {
+    transcript.append(b"A_hat", A_hat);
+    transcript.append(b"t", t);
+    let c_0 = transcript.challenge_scalar(b"c_0");
+    let c_1 = transcript.challenge_scalar(b"c_1");
+    (c_0, c_1)
+}
+
 /// Q = P*c_0 + k * (c_1*(c_0*y + t)) + A_hat
-fn calculate_Q<G: AffineRepr>(
+/// This is the commitment whose opening the compressed response proves
+pub fn calculate_Q<G: AffineRepr>(
     k: &G,
     P: &G,
     y: &G::ScalarField,
@@ -408,9 +705,37 @@ fn calculate_Q<G: AffineRepr>(
     P.mul(c_0) + k.mul(*c_1 * (*c_0 * y + t)) + A
 }
 
+/// `P = g * x + h * gamma`, the commitment to the vector `x` with randomness `gamma` whose opening is
+/// proven. Meant to be used by both the prover and the verifier to create `P`. Errors if `g` and `x`
+/// have different sizes.
+pub fn commit<G: AffineRepr>(
+    g: &[G],
+    h: &G,
+    x: &[G::ScalarField],
+    gamma: &G::ScalarField,
+) -> Result<G, CompSigmaError> {
+    if g.len() != x.len() {
+        return Err(CompSigmaError::VectorLenMismatch);
+    }
+    Ok((G::Group::msm_unchecked(g, x) + h.mul(gamma)).into_affine())
+}
+
+/// `y = L(x)`, the evaluation of the linear form at the committed vector `x`. Meant to be used along
+/// with `commit` to create `y`. Errors if `x` is larger than the linear form.
+pub fn eval_linear_form<F: Field, L: LinearForm<F>>(
+    linear_form: &L,
+    x: &[F],
+) -> Result<F, CompSigmaError> {
+    if x.len() > linear_form.size() {
+        return Err(CompSigmaError::VectorLenMismatch);
+    }
+    linear_form.try_eval(x)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::transforms::{ScaledLinearForm, SumLinearForm};
     use ark_bls12_381::Bls12_381;
     use ark_ec::pairing::Pairing;
     use ark_ff::Zero;
@@ -419,9 +744,12 @@ mod tests {
         UniformRand,
     };
     use blake2::Blake2b512;
+    use dock_crypto_utils::transcript::new_merlin_transcript;
     use std::time::Instant;
 
     type Fr = <Bls12_381 as Pairing>::ScalarField;
+    type G1 = <Bls12_381 as Pairing>::G1Affine;
+    type G2 = <Bls12_381 as Pairing>::G2Affine;
 
     struct TestLinearForm {
         pub constants: Vec<Fr>,
@@ -429,27 +757,61 @@ mod tests {
 
     impl_simple_linear_form!(TestLinearForm, Fr);
 
+    /// Linear form of `size` random constants padded with a zero, the committed vector `x`, its
+    /// blinding `gamma`, the commitment key `g`, `h`, `k`, the commitment `P` and the evaluation `y`
+    struct Fixture<G: AffineRepr> {
+        linear_form: TestLinearForm,
+        x: Vec<Fr>,
+        gamma: Fr,
+        g: Vec<G>,
+        h: G,
+        k: G,
+        P: G,
+        y: Fr,
+    }
+
+    fn fixture<G: AffineRepr<ScalarField = Fr>>(rng: &mut StdRng, size: usize) -> Fixture<G> {
+        let mut linear_form = TestLinearForm {
+            constants: (0..size).map(|_| Fr::rand(rng)).collect::<Vec<_>>(),
+        };
+        linear_form.constants.push(Fr::zero());
+
+        let x = (0..size).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        let gamma = Fr::rand(rng);
+        let g = (0..size)
+            .map(|_| G::Group::rand(rng).into_affine())
+            .collect::<Vec<_>>();
+        let h = G::Group::rand(rng).into_affine();
+        let k = G::Group::rand(rng).into_affine();
+
+        let P = (G::Group::msm_unchecked(&g, &x) + h.mul_bigint(gamma.into_bigint())).into_affine();
+        let y = linear_form.eval(&x);
+        Fixture {
+            linear_form,
+            x,
+            gamma,
+            g,
+            h,
+            k,
+            P,
+            y,
+        }
+    }
+
     #[test]
     fn compression() {
-        fn check_compression(size: u32) {
+        fn check_compression<G: AffineRepr<ScalarField = Fr>>(size: usize) {
             let mut rng = StdRng::seed_from_u64(0u64);
-            let mut linear_form = TestLinearForm {
-                constants: (0..size).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>(),
-            };
-            linear_form.constants.push(Fr::zero());
-
-            let x = (0..size).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
-            let gamma = Fr::rand(&mut rng);
-            let g = (0..size)
-                .map(|_| <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine())
-                .collect::<Vec<_>>();
-            let h = <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine();
-            let k = <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine();
-
-            let P = (<Bls12_381 as Pairing>::G1::msm_unchecked(&g, &x)
-                + h.mul_bigint(gamma.into_bigint()))
-            .into_affine();
-            let y = linear_form.eval(&x);
+            let Fixture {
+                linear_form,
+                x,
+                gamma,
+                g,
+                h,
+                k,
+                P,
+                y,
+            } = fixture::<G>(&mut rng, size);
 
             let rand_comm = RandomCommitment::new(&mut rng, &g, &h, &linear_form, None).unwrap();
 
@@ -503,10 +865,926 @@ mod tests {
             );
         }
 
-        check_compression(3);
-        check_compression(7);
-        check_compression(15);
-        check_compression(31);
-        check_compression(63);
+        check_compression::<G1>(3);
+        check_compression::<G2>(3);
+        check_compression::<G1>(7);
+        check_compression::<G2>(7);
+        check_compression::<G1>(15);
+        check_compression::<G2>(15);
+        check_compression::<G1>(31);
+        check_compression::<G2>(31);
+        check_compression::<G1>(63);
+        check_compression::<G2>(63);
+    }
+
+    #[test]
+    fn compression_with_transcript() {
+        fn check<G: AffineRepr<ScalarField = Fr>>(size: usize) {
+            let mut rng = StdRng::seed_from_u64(0u64);
+            let Fixture {
+                linear_form,
+                x,
+                gamma,
+                g,
+                h,
+                k,
+                P,
+                y,
+            } = fixture::<G>(&mut rng, size);
+
+            let rand_comm = RandomCommitment::new(&mut rng, &g, &h, &linear_form, None).unwrap();
+
+            let c_0 = Fr::rand(&mut rng);
+            let c_1 = Fr::rand(&mut rng);
+
+            // The transcript is shared with other protocols so it already has data in it
+            let mut prover_transcript = new_merlin_transcript(b"test");
+            prover_transcript.append(b"P", &P);
+            let response = rand_comm
+                .response_with_transcript(
+                    &g,
+                    &h,
+                    &k,
+                    &linear_form,
+                    &x,
+                    &gamma,
+                    &c_0,
+                    &c_1,
+                    &mut prover_transcript,
+                )
+                .unwrap();
+
+            let verifier_transcript = || {
+                let mut t = new_merlin_transcript(b"test");
+                t.append(b"P", &P);
+                t
+            };
+            response
+                .is_valid_recursive_with_transcript(
+                    &g,
+                    &h,
+                    &k,
+                    &P,
+                    &y,
+                    &linear_form,
+                    &rand_comm.A_hat,
+                    &rand_comm.t,
+                    &c_0,
+                    &c_1,
+                    &mut verifier_transcript(),
+                )
+                .unwrap();
+            response
+                .is_valid_with_transcript(
+                    &g,
+                    &h,
+                    &k,
+                    &P,
+                    &y,
+                    &linear_form,
+                    &rand_comm.A_hat,
+                    &rand_comm.t,
+                    &c_0,
+                    &c_1,
+                    &mut verifier_transcript(),
+                )
+                .unwrap();
+
+            // A transcript with different data gives different challenges
+            let mut other_transcript = new_merlin_transcript(b"test");
+            other_transcript.append(b"P", &h);
+            assert!(response
+                .is_valid_with_transcript(
+                    &g,
+                    &h,
+                    &k,
+                    &P,
+                    &y,
+                    &linear_form,
+                    &rand_comm.A_hat,
+                    &rand_comm.t,
+                    &c_0,
+                    &c_1,
+                    &mut other_transcript,
+                )
+                .is_err());
+
+            // The response isn't valid for the hash based challenges
+            assert!(response
+                .is_valid::<Blake2b512, _>(
+                    &g,
+                    &h,
+                    &k,
+                    &P,
+                    &y,
+                    &linear_form,
+                    &rand_comm.A_hat,
+                    &rand_comm.t,
+                    &c_0,
+                    &c_1,
+                )
+                .is_err());
+        }
+
+        check::<G1>(3);
+        check::<G2>(3);
+        check::<G1>(15);
+        check::<G2>(15);
+    }
+
+    #[test]
+    fn validation_with_given_Q() {
+        fn check<G: AffineRepr<ScalarField = Fr>>(size: usize) {
+            let mut rng = StdRng::seed_from_u64(0u64);
+            let Fixture {
+                linear_form,
+                x,
+                gamma,
+                g,
+                h,
+                k,
+                P,
+                y,
+            } = fixture::<G>(&mut rng, size);
+
+            let rand_comm = RandomCommitment::new(&mut rng, &g, &h, &linear_form, None).unwrap();
+
+            let c_0 = Fr::rand(&mut rng);
+            let c_1 = Fr::rand(&mut rng);
+
+            let response = rand_comm
+                .response::<Blake2b512, _>(&g, &h, &k, &linear_form, &x, &gamma, &c_0, &c_1)
+                .unwrap();
+
+            response
+                .is_valid::<Blake2b512, _>(
+                    &g,
+                    &h,
+                    &k,
+                    &P,
+                    &y,
+                    &linear_form,
+                    &rand_comm.A_hat,
+                    &rand_comm.t,
+                    &c_0,
+                    &c_1,
+                )
+                .unwrap();
+
+            let Q = calculate_Q(&k, &P, &y, &rand_comm.A_hat, &rand_comm.t, &c_0, &c_1);
+            response
+                .is_valid_with_Q::<Blake2b512, _>(&g, &h, &k, &linear_form, Q, &c_1)
+                .unwrap();
+
+            // Incorrect Q or challenge fails
+            assert!(response
+                .is_valid_with_Q::<Blake2b512, _>(&g, &h, &k, &linear_form, Q + P, &c_1)
+                .is_err());
+            assert!(response
+                .is_valid_with_Q::<Blake2b512, _>(&g, &h, &k, &linear_form, Q, &c_0)
+                .is_err());
+        }
+
+        check::<G1>(3);
+        check::<G2>(3);
+        check::<G1>(7);
+        check::<G2>(7);
+    }
+
+    #[test]
+    fn challenges_from_transcript_for_prover_and_verifier() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let Fixture {
+            linear_form,
+            x,
+            gamma,
+            g,
+            h,
+            k,
+            P,
+            y,
+        } = fixture::<G1>(&mut rng, 7);
+
+        let rand_comm = RandomCommitment::new(&mut rng, &g, &h, &linear_form, None).unwrap();
+
+        let mut prover_transcript = new_merlin_transcript(b"test");
+        prover_transcript.append(b"P", &P);
+        let (c_0, c_1) = rand_comm.challenges_from_transcript(&mut prover_transcript);
+        assert_ne!(c_0, c_1);
+        let response = rand_comm
+            .response_with_transcript(
+                &g,
+                &h,
+                &k,
+                &linear_form,
+                &x,
+                &gamma,
+                &c_0,
+                &c_1,
+                &mut prover_transcript,
+            )
+            .unwrap();
+
+        // Verifier only has `A_hat` and `t` and derives the same challenges
+        let mut verifier_transcript = new_merlin_transcript(b"test");
+        verifier_transcript.append(b"P", &P);
+        let (v_c_0, v_c_1) =
+            challenges_from_transcript(&rand_comm.A_hat, &rand_comm.t, &mut verifier_transcript);
+        assert_eq!(c_0, v_c_0);
+        assert_eq!(c_1, v_c_1);
+        response
+            .is_valid_with_transcript(
+                &g,
+                &h,
+                &k,
+                &P,
+                &y,
+                &linear_form,
+                &rand_comm.A_hat,
+                &rand_comm.t,
+                &v_c_0,
+                &v_c_1,
+                &mut verifier_transcript,
+            )
+            .unwrap();
+
+        // Challenges depend on the commitment
+        let mut other_transcript = new_merlin_transcript(b"test");
+        other_transcript.append(b"P", &P);
+        let (o_c_0, o_c_1) =
+            challenges_from_transcript(&rand_comm.A_hat, &gamma, &mut other_transcript);
+        assert_ne!(c_0, o_c_0);
+        assert_ne!(c_1, o_c_1);
+    }
+
+    #[test]
+    fn random_commitment_zeroize() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let Fixture {
+            linear_form,
+            x,
+            gamma,
+            g,
+            h,
+            k,
+            ..
+        } = fixture::<G1>(&mut rng, 7);
+        let c_0 = Fr::rand(&mut rng);
+        let c_1 = Fr::rand(&mut rng);
+
+        let rand_comm = RandomCommitment::new(&mut rng, &g, &h, &linear_form, None).unwrap();
+        let A_hat = rand_comm.A_hat;
+        let t = rand_comm.t;
+
+        // Only the blindings are zeroized
+        let mut zeroized = rand_comm.clone();
+        zeroized.zeroize();
+        // Zeroizing a `Vec` wipes its elements and then clears it
+        assert!(zeroized.r.is_empty());
+        assert!(zeroized.rho.is_zero());
+        assert_eq!(zeroized.A_hat, A_hat);
+        assert_eq!(zeroized.t, t);
+
+        let response = rand_comm
+            .response::<Blake2b512, _>(&g, &h, &k, &linear_form, &x, &gamma, &c_0, &c_1)
+            .unwrap();
+        let response_from_consumed = rand_comm
+            .response_consuming::<Blake2b512, _>(&g, &h, &k, &linear_form, &x, &gamma, &c_0, &c_1)
+            .unwrap();
+        assert_eq!(response, response_from_consumed);
+    }
+
+    #[test]
+    fn deterministic_random_commitment() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let size = 7;
+        let Fixture {
+            linear_form, g, h, ..
+        } = fixture::<G1>(&mut rng, size);
+
+        let r = (0..size).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let rho = Fr::rand(&mut rng);
+
+        let comm_1 =
+            RandomCommitment::new_deterministic(&g, &h, &linear_form, r.clone(), rho).unwrap();
+        let comm_2 =
+            RandomCommitment::new_deterministic(&g, &h, &linear_form, r.clone(), rho).unwrap();
+        assert_eq!(comm_1.A_hat, comm_2.A_hat);
+        assert_eq!(comm_1.t, comm_2.t);
+
+        // A different `rho` changes only `A_hat`
+        let comm_3 = RandomCommitment::new_deterministic(
+            &g,
+            &h,
+            &linear_form,
+            r.clone(),
+            rho + Fr::from(1u64),
+        )
+        .unwrap();
+        assert_ne!(comm_1.A_hat, comm_3.A_hat);
+        assert_eq!(comm_1.t, comm_3.t);
+
+        assert!(matches!(
+            RandomCommitment::new_deterministic(&g, &h, &linear_form, r[1..].to_vec(), rho),
+            Err(CompSigmaError::VectorLenMismatch)
+        ));
+    }
+
+    #[test]
+    fn composed_linear_form() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let size = 7;
+
+        let mut a = (0..size).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        a.push(Fr::zero());
+        // Smaller than `a` so it gets padded
+        let b = (0..size - 2)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let c = Fr::rand(&mut rng);
+
+        // a_0*x_0 + a_1*x_1 + ... + c*(b_0*x_0 + b_1*x_1 + ...)
+        let composed = SumLinearForm::new(
+            TestLinearForm {
+                constants: a.clone(),
+            },
+            ScaledLinearForm::new(
+                TestLinearForm {
+                    constants: b.clone(),
+                },
+                c,
+            ),
+        );
+        let flattened = TestLinearForm {
+            constants: a
+                .iter()
+                .enumerate()
+                .map(|(i, a_i)| *a_i + b.get(i).map(|b_i| c * b_i).unwrap_or(Fr::zero()))
+                .collect(),
+        };
+        assert_eq!(composed.size(), flattened.size());
+
+        let x = (0..size).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        assert_eq!(composed.eval(&x), flattened.eval(&x));
+
+        // Folding the halves, as done during compression, gives the same result
+        let s = Fr::rand(&mut rng);
+        let (l, r) = composed.split_in_half();
+        let (fl, fr) = flattened.split_in_half();
+        assert_eq!(l.size(), fl.size());
+        assert_eq!(r.size(), fr.size());
+        assert_eq!(l.eval(&x[..4]), fl.eval(&x[..4]));
+        assert_eq!(r.eval(&x[4..]), fr.eval(&x[4..]));
+        assert_eq!(
+            l.scale(&s).add(&r).eval(&x[4..]),
+            fl.scale(&s).add(&fr).eval(&x[4..])
+        );
+
+        // Proof using the composed form
+        let gamma = Fr::rand(&mut rng);
+        let g = (0..size)
+            .map(|_| <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let h = <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine();
+        let k = <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine();
+
+        let P = (<Bls12_381 as Pairing>::G1::msm_unchecked(&g, &x)
+            + h.mul_bigint(gamma.into_bigint()))
+        .into_affine();
+        let y = composed.eval(&x);
+
+        let rand_comm = RandomCommitment::new(&mut rng, &g, &h, &composed, None).unwrap();
+        let c_0 = Fr::rand(&mut rng);
+        let c_1 = Fr::rand(&mut rng);
+        let response = rand_comm
+            .response::<Blake2b512, _>(&g, &h, &k, &composed, &x, &gamma, &c_0, &c_1)
+            .unwrap();
+        response
+            .is_valid::<Blake2b512, _>(
+                &g,
+                &h,
+                &k,
+                &P,
+                &y,
+                &composed,
+                &rand_comm.A_hat,
+                &rand_comm.t,
+                &c_0,
+                &c_1,
+            )
+            .unwrap();
+        // The flattened form is equivalent so the proof is valid for it as well
+        response
+            .is_valid::<Blake2b512, _>(
+                &g,
+                &h,
+                &k,
+                &P,
+                &y,
+                &flattened,
+                &rand_comm.A_hat,
+                &rand_comm.t,
+                &c_0,
+                &c_1,
+            )
+            .unwrap();
+    }
+
+    /// Fails to evaluate inputs longer than itself rather than ignoring the extra elements
+    struct StrictLinearForm(TestLinearForm);
+
+    impl LinearForm<Fr> for StrictLinearForm {
+        fn eval(&self, x: &[Fr]) -> Fr {
+            self.try_eval(x).unwrap()
+        }
+
+        fn try_eval(&self, x: &[Fr]) -> Result<Fr, CompSigmaError> {
+            if x.len() > self.0.size() {
+                return Err(CompSigmaError::VectorLenMismatch);
+            }
+            Ok(self.0.eval(x))
+        }
+
+        fn scale(&self, scalar: &Fr) -> Self {
+            Self(self.0.scale(scalar))
+        }
+
+        fn add(&self, other: &Self) -> Self {
+            Self(self.0.add(&other.0))
+        }
+
+        fn split_in_half(&self) -> (Self, Self) {
+            let (l, r) = self.0.split_in_half();
+            (Self(l), Self(r))
+        }
+
+        fn size(&self) -> usize {
+            self.0.size()
+        }
+
+        fn pad(&self, new_size: u32) -> Self {
+            Self(self.0.pad(new_size))
+        }
+    }
+
+    #[test]
+    fn fallible_linear_form() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let strict_form = |size: usize, rng: &mut StdRng| {
+            let mut constants = (0..size).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+            constants.push(Fr::zero());
+            StrictLinearForm(TestLinearForm { constants })
+        };
+        let Fixture {
+            linear_form,
+            x,
+            gamma,
+            g,
+            h,
+            k,
+            P,
+            y,
+        } = fixture::<G1>(&mut rng, 7);
+        let linear_form = StrictLinearForm(linear_form);
+
+        let rand_comm = RandomCommitment::new(&mut rng, &g, &h, &linear_form, None).unwrap();
+        let c_0 = Fr::rand(&mut rng);
+        let c_1 = Fr::rand(&mut rng);
+        let response = rand_comm
+            .response::<Blake2b512, _>(&g, &h, &k, &linear_form, &x, &gamma, &c_0, &c_1)
+            .unwrap();
+        response
+            .is_valid::<Blake2b512, _>(
+                &g,
+                &h,
+                &k,
+                &P,
+                &y,
+                &linear_form,
+                &rand_comm.A_hat,
+                &rand_comm.t,
+                &c_0,
+                &c_1,
+            )
+            .unwrap();
+
+        // A form smaller than the input returns an error instead of panicking or ignoring extra inputs
+        let small_form = strict_form(3, &mut rng);
+        assert!(matches!(
+            RandomCommitment::new(&mut rng, &g, &h, &small_form, None),
+            Err(CompSigmaError::VectorLenMismatch)
+        ));
+        assert!(matches!(
+            response.is_valid::<Blake2b512, _>(
+                &g,
+                &h,
+                &k,
+                &P,
+                &y,
+                &small_form,
+                &rand_comm.A_hat,
+                &rand_comm.t,
+                &c_0,
+                &c_1,
+            ),
+            Err(CompSigmaError::VectorLenMismatch)
+        ));
+    }
+
+    /// Splits a form of size 4 unevenly, so it's only caught once the compression reaches that size
+    struct UnevenSplitLinearForm(TestLinearForm);
+
+    impl LinearForm<Fr> for UnevenSplitLinearForm {
+        fn eval(&self, x: &[Fr]) -> Fr {
+            self.0.eval(x)
+        }
+
+        fn scale(&self, scalar: &Fr) -> Self {
+            Self(self.0.scale(scalar))
+        }
+
+        fn add(&self, other: &Self) -> Self {
+            Self(self.0.add(&other.0))
+        }
+
+        fn split_in_half(&self) -> (Self, Self) {
+            let mid = if self.0.size() == 4 {
+                3
+            } else {
+                self.0.size() / 2
+            };
+            let (l, r) = self.0.constants.split_at(mid);
+            (
+                Self(TestLinearForm {
+                    constants: l.to_vec(),
+                }),
+                Self(TestLinearForm {
+                    constants: r.to_vec(),
+                }),
+            )
+        }
+
+        fn size(&self) -> usize {
+            self.0.size()
+        }
+
+        fn pad(&self, new_size: u32) -> Self {
+            Self(self.0.pad(new_size))
+        }
+    }
+
+    #[test]
+    fn misbehaving_split_in_half() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let Fixture {
+            linear_form,
+            x,
+            gamma,
+            g,
+            h,
+            k,
+            P,
+            y,
+        } = fixture::<G1>(&mut rng, 7);
+        let bad_form = UnevenSplitLinearForm(TestLinearForm {
+            constants: linear_form.constants.clone(),
+        });
+        assert!(linear_form.validate_splittable().is_ok());
+        assert!(matches!(
+            bad_form.validate_splittable(),
+            Err(CompSigmaError::InvalidLinearFormSplit(4))
+        ));
+
+        let rand_comm = RandomCommitment::new(&mut rng, &g, &h, &linear_form, None).unwrap();
+        let c_0 = Fr::rand(&mut rng);
+        let c_1 = Fr::rand(&mut rng);
+
+        // Both the prover and the verifier reject the form before starting the compression
+        assert!(matches!(
+            rand_comm.response::<Blake2b512, _>(&g, &h, &k, &bad_form, &x, &gamma, &c_0, &c_1),
+            Err(CompSigmaError::InvalidLinearFormSplit(4))
+        ));
+
+        let response = rand_comm
+            .response::<Blake2b512, _>(&g, &h, &k, &linear_form, &x, &gamma, &c_0, &c_1)
+            .unwrap();
+        assert!(matches!(
+            response.is_valid::<Blake2b512, _>(
+                &g,
+                &h,
+                &k,
+                &P,
+                &y,
+                &bad_form,
+                &rand_comm.A_hat,
+                &rand_comm.t,
+                &c_0,
+                &c_1,
+            ),
+            Err(CompSigmaError::InvalidLinearFormSplit(4))
+        ));
+        response
+            .is_valid::<Blake2b512, _>(
+                &g,
+                &h,
+                &k,
+                &P,
+                &y,
+                &linear_form,
+                &rand_comm.A_hat,
+                &rand_comm.t,
+                &c_0,
+                &c_1,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn non_power_of_two_sizes() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let h = <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine();
+        let k = <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine();
+        let gamma = Fr::rand(&mut rng);
+        let c_0 = Fr::rand(&mut rng);
+        let c_1 = Fr::rand(&mut rng);
+
+        // Generators and the linear form are padded with 1 element so sizes 4 and 5 become 5 and 6
+        for (size, expected) in [(4, 5), (5, 6)] {
+            let linear_form = TestLinearForm {
+                constants: (0..expected).map(|_| Fr::rand(&mut rng)).collect(),
+            };
+            let g = (0..size)
+                .map(|_| <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine())
+                .collect::<Vec<_>>();
+            assert!(matches!(
+                RandomCommitment::new(&mut rng, &g, &h, &linear_form, None),
+                Err(CompSigmaError::NonPowerOfTwoSize(s)) if s == expected
+            ));
+        }
+
+        // Correctly sized generators but a linear form of size 5 or 6
+        let size = 3;
+        let x = (0..size).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let g = (0..size)
+            .map(|_| <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let mut linear_form = TestLinearForm {
+            constants: (0..size).map(|_| Fr::rand(&mut rng)).collect(),
+        };
+        linear_form.constants.push(Fr::zero());
+        let rand_comm = RandomCommitment::new(&mut rng, &g, &h, &linear_form, None).unwrap();
+        for form_size in [5, 6] {
+            let bad_form = TestLinearForm {
+                constants: (0..form_size).map(|_| Fr::rand(&mut rng)).collect(),
+            };
+            assert!(matches!(
+                rand_comm.response::<Blake2b512, _>(&g, &h, &k, &bad_form, &x, &gamma, &c_0, &c_1),
+                Err(CompSigmaError::NonPowerOfTwoSize(s)) if s == form_size
+            ));
+        }
+    }
+
+    #[test]
+    fn response_shape() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let Fixture {
+            linear_form,
+            x,
+            gamma,
+            g,
+            h,
+            k,
+            P,
+            y,
+        } = fixture::<G1>(&mut rng, 7);
+        let c_0 = Fr::rand(&mut rng);
+        let c_1 = Fr::rand(&mut rng);
+
+        let rand_comm = RandomCommitment::new(&mut rng, &g, &h, &linear_form, None).unwrap();
+        let response = rand_comm
+            .response::<Blake2b512, _>(&g, &h, &k, &linear_form, &x, &gamma, &c_0, &c_1)
+            .unwrap();
+
+        let mut bytes = vec![];
+        response.serialize_compressed(&mut bytes).unwrap();
+        let response = Response::<G1>::deserialize_compressed(&bytes[..]).unwrap();
+        response.validate_shape(g.len()).unwrap();
+
+        let is_valid = |r: &Response<G1>| {
+            r.is_valid::<Blake2b512, _>(
+                &g,
+                &h,
+                &k,
+                &P,
+                &y,
+                &linear_form,
+                &rand_comm.A_hat,
+                &rand_comm.t,
+                &c_0,
+                &c_1,
+            )
+        };
+        is_valid(&response).unwrap();
+
+        // Mismatched `A` and `B`
+        let mut bad = response.clone();
+        bad.B.pop();
+        assert!(matches!(
+            bad.validate_shape(g.len()),
+            Err(CompSigmaError::VectorLenMismatch)
+        ));
+        assert!(matches!(
+            is_valid(&bad),
+            Err(CompSigmaError::VectorLenMismatch)
+        ));
+        let mut bad = response.clone();
+        bad.A.push(bad.A[0]);
+        assert!(matches!(
+            bad.validate_shape(g.len()),
+            Err(CompSigmaError::VectorLenMismatch)
+        ));
+
+        // `A` and `B` of equal size but wrong for the number of generators
+        let mut bad = response.clone();
+        bad.A.push(bad.A[0]);
+        bad.B.push(bad.B[0]);
+        assert!(matches!(
+            bad.validate_shape(g.len()),
+            Err(CompSigmaError::WrongRecursionLevel)
+        ));
+        assert!(matches!(
+            is_valid(&bad),
+            Err(CompSigmaError::WrongRecursionLevel)
+        ));
+        let mut bad = response.clone();
+        bad.A.pop();
+        bad.B.pop();
+        assert!(matches!(
+            bad.validate_shape(g.len()),
+            Err(CompSigmaError::WrongRecursionLevel)
+        ));
+        let mut bad = response.clone();
+        bad.A.clear();
+        bad.B.clear();
+        assert!(matches!(
+            bad.validate_shape(g.len()),
+            Err(CompSigmaError::WrongRecursionLevel)
+        ));
+
+        // Sizes that would overflow `2^(A.len() + 1)` don't panic
+        let mut bad = response.clone();
+        bad.A = vec![response.A[0]; 70];
+        bad.B = vec![response.B[0]; 70];
+        assert!(matches!(
+            bad.validate_shape(g.len()),
+            Err(CompSigmaError::WrongRecursionLevel)
+        ));
+
+        // Response is valid only for the number of generators it was created with
+        assert!(matches!(
+            response.validate_shape(15),
+            Err(CompSigmaError::WrongRecursionLevel)
+        ));
+        assert!(matches!(
+            response.validate_shape(3),
+            Err(CompSigmaError::WrongRecursionLevel)
+        ));
+        assert!(matches!(
+            response.validate_shape(6),
+            Err(CompSigmaError::NonPowerOfTwoSize(7))
+        ));
+    }
+
+    #[test]
+    fn commitment_and_evaluation_helpers() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let size = 7;
+        let Fixture {
+            linear_form,
+            x,
+            gamma,
+            g,
+            h,
+            k,
+            P,
+            y,
+        } = fixture::<G1>(&mut rng, size);
+        assert_eq!(P, commit(&g, &h, &x, &gamma).unwrap());
+        assert_eq!(y, eval_linear_form(&linear_form, &x).unwrap());
+
+        let c_0 = Fr::rand(&mut rng);
+        let c_1 = Fr::rand(&mut rng);
+        let rand_comm = RandomCommitment::new(&mut rng, &g, &h, &linear_form, None).unwrap();
+        let response = rand_comm
+            .response::<Blake2b512, _>(&g, &h, &k, &linear_form, &x, &gamma, &c_0, &c_1)
+            .unwrap();
+        response
+            .is_valid::<Blake2b512, _>(
+                &g,
+                &h,
+                &k,
+                &P,
+                &y,
+                &linear_form,
+                &rand_comm.A_hat,
+                &rand_comm.t,
+                &c_0,
+                &c_1,
+            )
+            .unwrap();
+
+        // Commitment to a different vector or with different randomness isn't accepted
+        let other_gamma = Fr::rand(&mut rng);
+        let other_P = commit(&g, &h, &x, &other_gamma).unwrap();
+        assert!(response
+            .is_valid::<Blake2b512, _>(
+                &g,
+                &h,
+                &k,
+                &other_P,
+                &y,
+                &linear_form,
+                &rand_comm.A_hat,
+                &rand_comm.t,
+                &c_0,
+                &c_1,
+            )
+            .is_err());
+
+        assert!(matches!(
+            commit(&g[..size - 1], &h, &x, &gamma),
+            Err(CompSigmaError::VectorLenMismatch)
+        ));
+        let mut long_x = x.clone();
+        long_x.extend_from_slice(&[Fr::rand(&mut rng), Fr::rand(&mut rng)]);
+        assert!(matches!(
+            eval_linear_form(&linear_form, &long_x),
+            Err(CompSigmaError::VectorLenMismatch)
+        ));
+    }
+
+    #[test]
+    fn max_rounds() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let Fixture {
+            linear_form,
+            x,
+            gamma,
+            g,
+            h,
+            k,
+            P,
+            y,
+        } = fixture::<G1>(&mut rng, 7);
+        let c_0 = Fr::rand(&mut rng);
+        let c_1 = Fr::rand(&mut rng);
+
+        let rand_comm = RandomCommitment::new(&mut rng, &g, &h, &linear_form, None).unwrap();
+        let response = rand_comm
+            .response::<Blake2b512, _>(&g, &h, &k, &linear_form, &x, &gamma, &c_0, &c_1)
+            .unwrap();
+        // 8 = 2^3 generators including the padding thus 2 rounds
+        assert_eq!(response.A.len(), 2);
+
+        let is_valid = |r: &Response<G1>, max_rounds: usize| {
+            r.is_valid_with_max_rounds::<Blake2b512, _>(
+                &g,
+                &h,
+                &k,
+                &P,
+                &y,
+                &linear_form,
+                &rand_comm.A_hat,
+                &rand_comm.t,
+                &c_0,
+                &c_1,
+                max_rounds,
+            )
+        };
+        is_valid(&response, 2).unwrap();
+        is_valid(&response, 64).unwrap();
+        assert!(matches!(
+            is_valid(&response, 1),
+            Err(CompSigmaError::TooManyRounds(2, 1))
+        ));
+
+        // Over-long `A` is rejected before checking its size against the generators
+        let mut bad = response.clone();
+        bad.A = vec![response.A[0]; 1000];
+        assert!(matches!(
+            bad.validate_rounds(64),
+            Err(CompSigmaError::TooManyRounds(1000, 64))
+        ));
+        assert!(matches!(
+            is_valid(&bad, 64),
+            Err(CompSigmaError::TooManyRounds(1000, 64))
+        ));
+        // and so is an over-long `B`
+        let mut bad = response.clone();
+        bad.B = vec![response.B[0]; 1000];
+        assert!(matches!(
+            is_valid(&bad, 64),
+            Err(CompSigmaError::TooManyRounds(1000, 64))
+        ));
     }
 }
@@ -6,10 +6,17 @@ pub enum CompSigmaError {
     InvalidResponse,
     VectorTooShort,
     VectorLenMismatch,
-    UncompressedNotPowerOf2,
+    /// The number of generators or the size of the linear form or homomorphism (including any padding)
+    /// must be a power of 2 but was the contained size
+    NonPowerOfTwoSize(usize),
+    /// Splitting a linear form of the contained size in half did not give 2 forms of half its size
+    InvalidLinearFormSplit(usize),
     Serialization(SerializationError),
     WrongRecursionLevel,
     FaultyParameterSize,
+    /// The response has more rounds than the verifier allows. The values are the number of rounds in the
+    /// response and the maximum allowed.
+    TooManyRounds(usize, usize),
 }
 
 impl From<SerializationError> for CompSigmaError {
@@ -1,11 +1,19 @@
 use crate::error::CompSigmaError;
 use ark_ff::Field;
+use ark_std::vec::Vec;
+use dock_crypto_utils::ff::{add_vecs, inner_product, scale};
 
 /// For a linear form, i.e. for a form `L` and a vector `x` of size `n`, `L(x) = a_0*x_0 + a_1*x_1 + ... + a_n*x_n`
 /// for constants `a_0`, `a_1`, etc
 pub trait LinearForm<F: Field>: Sized {
     fn eval(&self, x: &[F]) -> F;
 
+    /// Same as `Self::eval` but returns an error rather than panicking on an invalid input like one of
+    /// wrong length. The default implementation never fails.
+    fn try_eval(&self, x: &[F]) -> Result<F, CompSigmaError> {
+        Ok(self.eval(x))
+    }
+
     fn scale(&self, scalar: &F) -> Self;
 
     fn add(&self, other: &Self) -> Self;
@@ -15,6 +23,245 @@ pub trait LinearForm<F: Field>: Sized {
     fn size(&self) -> usize;
 
     fn pad(&self, new_size: u32) -> Self;
+
+    /// Check that the form can be compressed, i.e. its size is a power of 2 and repeatedly splitting it in
+    /// half and folding the halves, as done in each round of the compression, reduces it to size 2. An
+    /// implementation whose `split_in_half` mishandles the boundary would otherwise only make the final
+    /// relation fail without indicating why.
+    fn validate_splittable(&self) -> Result<(), CompSigmaError> {
+        let size = self.size();
+        if !size.is_power_of_two() {
+            return Err(CompSigmaError::NonPowerOfTwoSize(size));
+        }
+        if size < 2 {
+            return Err(CompSigmaError::VectorTooShort);
+        }
+        if size == 2 {
+            return Ok(());
+        }
+        let (l, r) = self.split_in_half();
+        if l.size() != size / 2 || r.size() != size / 2 {
+            return Err(CompSigmaError::InvalidLinearFormSplit(size));
+        }
+        l.add(&r).validate_splittable()
+    }
+}
+
+/// The inner product with a public vector `a`, i.e. `L(x) = <a, x> = a_0*x_0 + a_1*x_1 + ... + a_{n-1}*x_{n-1}`.
+/// This is the most common linear form so it's provided rather than having each user write their own.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InnerProductLinearForm<F: Field> {
+    coefficients: Vec<F>,
+}
+
+impl<F: Field> InnerProductLinearForm<F> {
+    pub fn new(coefficients: Vec<F>) -> Self {
+        Self { coefficients }
+    }
+
+    pub fn coefficients(&self) -> &[F] {
+        &self.coefficients
+    }
+}
+
+/// Sum of 2 linear forms over the same vector, i.e. `L(x) = L_1(x) + L_2(x)`. The smaller form is padded
+/// so that both have the same size and are split at the same position.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SumLinearForm<L1, L2> {
+    first: L1,
+    second: L2,
+}
+
+/// A linear form multiplied by a scalar, i.e. `L(x) = c * L_1(x)`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScaledLinearForm<F: Field, L> {
+    form: L,
+    scalar: F,
+}
+
+impl<L1, L2> SumLinearForm<L1, L2> {
+    pub fn new<F: Field>(first: L1, second: L2) -> Self
+    where
+        L1: LinearForm<F>,
+        L2: LinearForm<F>,
+    {
+        let size = first.size().max(second.size()) as u32;
+        Self {
+            first: first.pad(size),
+            second: second.pad(size),
+        }
+    }
+
+    pub fn first(&self) -> &L1 {
+        &self.first
+    }
+
+    pub fn second(&self) -> &L2 {
+        &self.second
+    }
+}
+
+impl<F: Field, L: LinearForm<F>> ScaledLinearForm<F, L> {
+    pub fn new(form: L, scalar: F) -> Self {
+        Self { form, scalar }
+    }
+
+    pub fn form(&self) -> &L {
+        &self.form
+    }
+
+    pub fn scalar(&self) -> &F {
+        &self.scalar
+    }
+}
+
+impl<F: Field> LinearForm<F> for InnerProductLinearForm<F> {
+    /// Elements of `x` beyond the size of the form are ignored
+    fn eval(&self, x: &[F]) -> F {
+        inner_product(&self.coefficients, x)
+    }
+
+    fn try_eval(&self, x: &[F]) -> Result<F, CompSigmaError> {
+        if x.len() > self.coefficients.len() {
+            return Err(CompSigmaError::VectorLenMismatch);
+        }
+        Ok(self.eval(x))
+    }
+
+    fn scale(&self, scalar: &F) -> Self {
+        Self {
+            coefficients: scale(&self.coefficients, scalar),
+        }
+    }
+
+    /// If the forms are of different sizes, the smaller one is considered to be padded with zeros
+    fn add(&self, other: &Self) -> Self {
+        Self {
+            coefficients: add_vecs(&self.coefficients, &other.coefficients),
+        }
+    }
+
+    /// For an odd size, the right half is padded with a zero so that both halves are of the same size
+    fn split_in_half(&self) -> (Self, Self) {
+        let mid = self.coefficients.len().div_ceil(2);
+        let (l, r) = self.coefficients.split_at(mid);
+        (Self::new(l.to_vec()), Self::new(r.to_vec()).pad(mid as u32))
+    }
+
+    fn size(&self) -> usize {
+        self.coefficients.len()
+    }
+
+    fn pad(&self, new_size: u32) -> Self {
+        let mut coefficients = self.coefficients.clone();
+        if coefficients.len() < new_size as usize {
+            coefficients.resize(new_size as usize, F::zero());
+        }
+        Self { coefficients }
+    }
+}
+
+impl<F: Field, L1: LinearForm<F>, L2: LinearForm<F>> LinearForm<F> for SumLinearForm<L1, L2> {
+    fn eval(&self, x: &[F]) -> F {
+        self.first.eval(x) + self.second.eval(x)
+    }
+
+    fn try_eval(&self, x: &[F]) -> Result<F, CompSigmaError> {
+        Ok(self.first.try_eval(x)? + self.second.try_eval(x)?)
+    }
+
+    fn scale(&self, scalar: &F) -> Self {
+        Self {
+            first: self.first.scale(scalar),
+            second: self.second.scale(scalar),
+        }
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        Self {
+            first: self.first.add(&other.first),
+            second: self.second.add(&other.second),
+        }
+    }
+
+    fn split_in_half(&self) -> (Self, Self) {
+        let (first_l, first_r) = self.first.split_in_half();
+        let (second_l, second_r) = self.second.split_in_half();
+        (
+            Self {
+                first: first_l,
+                second: second_l,
+            },
+            Self {
+                first: first_r,
+                second: second_r,
+            },
+        )
+    }
+
+    fn size(&self) -> usize {
+        self.first.size()
+    }
+
+    fn pad(&self, new_size: u32) -> Self {
+        Self {
+            first: self.first.pad(new_size),
+            second: self.second.pad(new_size),
+        }
+    }
+}
+
+impl<F: Field, L: LinearForm<F>> LinearForm<F> for ScaledLinearForm<F, L> {
+    fn eval(&self, x: &[F]) -> F {
+        self.form.eval(x) * self.scalar
+    }
+
+    fn try_eval(&self, x: &[F]) -> Result<F, CompSigmaError> {
+        Ok(self.form.try_eval(x)? * self.scalar)
+    }
+
+    fn scale(&self, scalar: &F) -> Self {
+        Self {
+            form: self.form.scale(scalar),
+            scalar: self.scalar,
+        }
+    }
+
+    /// The scalars of both forms are applied to the underlying forms before adding them
+    fn add(&self, other: &Self) -> Self {
+        Self {
+            form: self
+                .form
+                .scale(&self.scalar)
+                .add(&other.form.scale(&other.scalar)),
+            scalar: F::one(),
+        }
+    }
+
+    fn split_in_half(&self) -> (Self, Self) {
+        let (l, r) = self.form.split_in_half();
+        (
+            Self {
+                form: l,
+                scalar: self.scalar,
+            },
+            Self {
+                form: r,
+                scalar: self.scalar,
+            },
+        )
+    }
+
+    fn size(&self) -> usize {
+        self.form.size()
+    }
+
+    fn pad(&self, new_size: u32) -> Self {
+        Self {
+            form: self.form.pad(new_size),
+            scalar: self.scalar,
+        }
+    }
 }
 
 /// For a group homomorphism, i.e. for a function `f` and vectors `x` and `y`, `f(x+y) = f(x)*f(y)`
@@ -33,3 +280,131 @@ pub trait Homomorphism<F: Field>: Sized {
 
     fn pad(&self, new_size: u32) -> Self;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compressed_linear_form::{RandomCommitment, Response};
+    use ark_bls12_381::{Fr, G1Affine};
+    use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+    use ark_ff::{PrimeField, Zero};
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        UniformRand,
+    };
+    use blake2::Blake2b512;
+
+    fn naive_inner_product(a: &[Fr], x: &[Fr]) -> Fr {
+        let mut sum = Fr::zero();
+        for i in 0..a.len().min(x.len()) {
+            sum += a[i] * x[i];
+        }
+        sum
+    }
+
+    #[test]
+    fn inner_product_linear_form() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        for size in [1, 2, 3, 7, 8, 15, 16, 33] {
+            let a = (0..size).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+            let x = (0..size).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+            let form = InnerProductLinearForm::new(a.clone());
+            assert_eq!(form.size(), size);
+            assert_eq!(form.eval(&x), naive_inner_product(&a, &x));
+            assert_eq!(form.try_eval(&x).unwrap(), naive_inner_product(&a, &x));
+            // Missing elements of `x` are treated as zero
+            assert_eq!(
+                form.eval(&x[..size / 2]),
+                naive_inner_product(&a, &x[..size / 2])
+            );
+            let mut longer_x = x.clone();
+            longer_x.push(Fr::rand(&mut rng));
+            assert!(matches!(
+                form.try_eval(&longer_x),
+                Err(CompSigmaError::VectorLenMismatch)
+            ));
+
+            let c = Fr::rand(&mut rng);
+            assert_eq!(form.scale(&c).eval(&x), c * naive_inner_product(&a, &x));
+
+            let b = (0..size + 2)
+                .map(|_| Fr::rand(&mut rng))
+                .collect::<Vec<_>>();
+            let other = InnerProductLinearForm::new(b.clone());
+            let mut longer_x = x.clone();
+            longer_x.extend([Fr::rand(&mut rng), Fr::rand(&mut rng)]);
+            assert_eq!(form.add(&other).size(), size + 2);
+            assert_eq!(
+                form.add(&other).eval(&longer_x),
+                naive_inner_product(&a, &longer_x) + naive_inner_product(&b, &longer_x)
+            );
+
+            let padded = form.pad(size as u32 + 3);
+            assert_eq!(padded.size(), size + 3);
+            assert_eq!(padded.eval(&x), form.eval(&x));
+            assert_eq!(form.pad(1), form);
+
+            // Evaluating the halves over the halves of `x` gives the evaluation over `x`
+            let (l, r) = form.split_in_half();
+            assert_eq!(l.size(), r.size());
+            let mid = l.size();
+            assert_eq!(
+                l.eval(&x[..mid]) + r.eval(&x[mid..]),
+                naive_inner_product(&a, &x)
+            );
+        }
+
+        let form = InnerProductLinearForm::new(vec![Fr::rand(&mut rng); 16]);
+        form.validate_splittable().unwrap();
+        let form = InnerProductLinearForm::new(vec![Fr::rand(&mut rng); 12]);
+        assert!(matches!(
+            form.validate_splittable(),
+            Err(CompSigmaError::NonPowerOfTwoSize(12))
+        ));
+    }
+
+    #[test]
+    fn compression_with_inner_product_linear_form() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        for size in [3, 7, 15, 31] {
+            let mut coefficients = (0..size).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+            coefficients.push(Fr::zero());
+            let linear_form = InnerProductLinearForm::new(coefficients);
+
+            let x = (0..size).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+            let gamma = Fr::rand(&mut rng);
+            let g = (0..size)
+                .map(|_| <G1Affine as AffineRepr>::Group::rand(&mut rng).into_affine())
+                .collect::<Vec<_>>();
+            let h = <G1Affine as AffineRepr>::Group::rand(&mut rng).into_affine();
+            let k = <G1Affine as AffineRepr>::Group::rand(&mut rng).into_affine();
+
+            let P = (<G1Affine as AffineRepr>::Group::msm_unchecked(&g, &x)
+                + h.mul_bigint(gamma.into_bigint()))
+            .into_affine();
+            let y = linear_form.eval(&x);
+            assert_eq!(y, naive_inner_product(linear_form.coefficients(), &x));
+
+            let rand_comm = RandomCommitment::new(&mut rng, &g, &h, &linear_form, None).unwrap();
+            let c_0 = Fr::rand(&mut rng);
+            let c_1 = Fr::rand(&mut rng);
+            let response: Response<G1Affine> = rand_comm
+                .response::<Blake2b512, _>(&g, &h, &k, &linear_form, &x, &gamma, &c_0, &c_1)
+                .unwrap();
+            response
+                .is_valid::<Blake2b512, _>(
+                    &g,
+                    &h,
+                    &k,
+                    &P,
+                    &y,
+                    &linear_form,
+                    &rand_comm.A_hat,
+                    &rand_comm.t,
+                    &c_0,
+                    &c_1,
+                )
+                .unwrap();
+        }
+    }
+}
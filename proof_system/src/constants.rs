@@ -2,6 +2,9 @@ pub const COMPOSITE_PROOF_LABEL: &'static [u8; 15] = b"composite-proof";
 pub const COMPOSITE_PROOF_CHALLENGE_LABEL: &'static [u8; 25] = b"composite-proof-challenge";
 pub const NONCE_LABEL: &'static [u8; 5] = b"nonce";
 pub const CONTEXT_LABEL: &'static [u8; 7] = b"context";
+pub const NONCE_DERIVATION_LABEL: &[u8; 16] = b"nonce-derivation";
+pub const DOMAIN_SEPARATOR_LABEL: &[u8; 16] = b"domain-separator";
+pub const EXTRA_TRANSCRIPT_DATA_LABEL: &[u8; 10] = b"extra-data";
 pub const BBS_PLUS_LABEL: &'static [u8; 4] = b"BBS+";
 pub const BBS_23_LABEL: &'static [u8; 5] = b"BBS23";
 pub const PS_LABEL: &'static [u8; 2] = b"PS";
@@ -11,6 +14,8 @@ pub const VB_ACCUM_NON_MEM_LABEL: &'static [u8; 29] = b"VB-accumulator-non-membe
 pub const KB_UNI_ACCUM_MEM_LABEL: &'static [u8; 35] = b"KB-universal-accumulator-membership";
 pub const KB_UNI_ACCUM_NON_MEM_LABEL: &'static [u8; 39] =
     b"KB-universal-accumulator-non-membership";
+pub const KB_UNI_ACCUM_MEM_AND_NON_MEM_LABEL: &[u8; 54] =
+    b"KB-universal-accumulator-membership-and-non-membership";
 pub const VB_ACCUM_CDH_MEM_LABEL: &'static [u8; 29] = b"VB-accumulator-CDH-membership";
 pub const VB_ACCUM_CDH_NON_MEM_LABEL: &'static [u8; 33] = b"VB-accumulator-CDH-non-membership";
 
@@ -20,6 +25,7 @@ pub const KB_UNI_ACCUM_CDH_NON_MEM_LABEL: &'static [u8; 43] =
     b"KB-universal-accumulator-CDH-non-membership";
 pub const KB_POS_ACCUM_MEM_LABEL: &'static [u8; 34] = b"KB-positive-accumulator-membership";
 pub const KB_POS_ACCUM_CDH_MEM_LABEL: &'static [u8; 38] = b"KB-positive-accumulator-CDH-membership";
+pub const ACCUMULATOR_ID_LABEL: &[u8; 14] = b"accumulator-id";
 
 pub const BBDT16_KVAC_LABEL: &'static [u8; 14] = b"BDDT-2016-KVAC";
 pub const VE_TZ_21_LABEL: &'static [u8; 8] = b"VE-TZ-21";
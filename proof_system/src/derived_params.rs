@@ -2,6 +2,7 @@
 //! creating these parameters.
 
 use crate::{
+    error::ProofSystemError,
     statement::bound_check_smc::{SmcParamsAndCommitmentKey, SmcParamsWithPairingAndCommitmentKey},
     sub_protocols::saver::SaverProtocol,
 };
@@ -59,6 +60,7 @@ pub struct DerivedParamsTracker<'a, Ref: PartialEq, DP, E> {
 }
 
 /// Maps statement identifiers to derived params
+#[derive(Clone)]
 pub struct StatementDerivedParams<DP> {
     derived_params: BTreeMap<usize, DP>,
     derived_params_for_statement: BTreeMap<usize, usize>,
@@ -95,6 +97,19 @@ where
         }
     }
 
+    /// Same as `Self::on_new_statement_idx` but uses the given already derived param rather than creating
+    /// it if need be.
+    pub fn on_new_statement_idx_with_derived(&mut self, orig: &'a Ref, derived: DP, s_idx: usize) {
+        if let Some(k) = self.find(orig) {
+            self.derived_params_for_statement.insert(s_idx, k);
+        } else {
+            self.derived_params.insert(self.origs_ref.len(), derived);
+            self.derived_params_for_statement
+                .insert(s_idx, self.origs_ref.len());
+            self.origs_ref.push(orig);
+        }
+    }
+
     /// Finished tracking derived params, return map of statement to derived params
     pub fn finish(self) -> StatementDerivedParams<DP> {
         StatementDerivedParams {
@@ -109,6 +124,13 @@ impl<DP> StatementDerivedParams<DP> {
         self.derived_params
             .get(self.derived_params_for_statement.get(&s_idx)?)
     }
+
+    /// Same as `Self::get` but fails with `ProofSystemError::MissingDerivedParams` if nothing was derived for
+    /// the statement, like when the params were derived from a different `ProofSpec`
+    pub fn try_get(&self, s_idx: usize) -> Result<&DP, ProofSystemError> {
+        self.get(s_idx)
+            .ok_or(ProofSystemError::MissingDerivedParams(s_idx))
+    }
 }
 
 /// To derive commitment key from `LegoVerifyingKey`
@@ -16,6 +16,9 @@ pub enum ProofSystemError {
     UnequalWitnessAndStatementCount(usize, usize),
     WitnessIncompatibleWithStatement(usize, String, String),
     ProofIncompatibleWithStatement(usize, String, String),
+    /// Same as `ProofIncompatibleWithStatement` but carries only the statement index. Returned instead of it
+    /// when the `lite-errors` feature is enabled to avoid formatting the statement and proof.
+    ProofIncompatibleWithStatementLite(usize),
     ProofIncompatibleWithBBSPlusProtocol,
     ProofIncompatibleWithSchnorrProtocol,
     ProofIncompatibleWithAccumulatorMembershipProtocol,
@@ -76,7 +79,9 @@ pub enum ProofSystemError {
     /// Witness is being used a zero knowledge proof (bound check, accumulator, etc) while also being
     /// revealed. This shouldn't be the case, ever.
     WitnessAlreadyBeingRevealed(usize, usize),
-    SnarckpackSrsNotProvided,
+    /// Aggregation of SNARK proofs is requested but the Snarkpack SRS of the prover or verifier, as
+    /// needed, isn't given
+    SnarkpackSrsNotProvided,
     NotASaverStatementProof,
     RandomizedPairingCheckFailed,
     SameStatementIdsFoundInMultipleAggregations(Vec<usize>),
@@ -117,6 +122,122 @@ pub enum ProofSystemError {
     VerifiableEncryptionFailed(u32, VerifiableEncryptionError),
     NotALegoGroth16StatementProof,
     NotAVeTZ21StatementProof,
+    /// Statement index given for aggregation does not exist in the `ProofSpec`
+    InvalidAggregateStatementIndex(usize),
+    /// Statement index given for Groth16 aggregation is not a SAVER statement
+    AggregateStatementNotSaver(usize),
+    /// Statement index given for LegoGroth16 aggregation is not a LegoGroth16 statement
+    AggregateStatementNotLegoGroth16(usize),
+    /// The statement proof at the index is not meant for aggregation though its statement is being aggregated
+    StatementProofNotForAggregation(usize),
+    /// The Snarkpack SRS is for a different number of proofs than the ones being aggregated. The
+    /// values are the number of proofs being aggregated and the number supported by the SRS.
+    IncompatibleSnarkpackSrs(usize, usize),
+    /// The statement at the given index of the list isn't an accumulator membership statement
+    NotAnAccumulatorStatement(usize),
+    /// The statement at the given index of the list isn't an accumulator non-membership statement
+    NotAnAccumulatorNonMembershipStatement(usize),
+    /// The statement at the given index isn't a BBS+ signature statement
+    NotABBSPlusStatement(usize),
+    /// The statement at the index can't be skipped during verification as its witness is proven equal to
+    /// the witness of a statement being verified or its proof is aggregated with others
+    CannotSkipLinkedStatement(usize),
+    /// Verification was cancelled using the flag given to `Proof::verify_cancellable`
+    Cancelled,
+    /// The serialized proof has a version that isn't supported. The values are the version found and the
+    /// version supported.
+    UnsupportedProofVersion(u16, u16),
+    /// The bytes given to `Proof::from_bytes_safe` aren't a valid serialized proof
+    MalformedProofBytes,
+    /// A group element in the statement or its proof at the index is not on the curve or not in the
+    /// prime order subgroup
+    InvalidGroupElement(usize),
+    /// The proof has more statement proofs than the proof spec has statements. The values are the number
+    /// of statement proofs found and the number of statements.
+    ExtraStatementProofs(usize, usize),
+    /// The accumulator witness for the statement at the index doesn't verify against the statement's
+    /// accumulator value. Only returned when `ProverConfig::validate_witnesses` is set.
+    InvalidWitnessForStatement(usize),
+    /// The proof spec has no statements. Verifying such a proof is only allowed when
+    /// `VerifierConfig::allow_empty_proof` is set.
+    NoStatements,
+    /// Different blindings were given in `ProverConfig::witness_blindings` for witnesses that are proven
+    /// equal. The value is the reference of one of these witnesses.
+    ConflictingWitnessBlindings((usize, usize)),
+    /// No nonce was given for verification though `VerifierConfig::require_nonce` is set
+    NonceRequired,
+    /// The size of the proof of the statement at the index can't be estimated before generating the proof
+    ProofSizeEstimationNotSupported(usize),
+    /// The statement at the first index refers to the setup param at the second index but that setup param
+    /// either doesn't exist or is not of the type the statement expects
+    DanglingSetupParamRef(usize, usize),
+    /// The proof specs being merged have different contexts or different Snarkpack SRS
+    IncompatibleProofSpecsForMerge,
+    /// The public set of the set membership statement at the index is empty
+    EmptyPublicSet(usize),
+    /// The witness of the set membership statement at the index is not present in the statement's public set
+    WitnessNotInPublicSet(usize),
+    /// The proof of the set membership statement at the index failed verification
+    PublicSetMembershipProofFailed(usize),
+    /// The `VerificationCheckpoint` wasn't created for the proof being verified or for the given proof spec
+    InvalidVerificationCheckpoint,
+    /// The witness of the discrete log statement at the index isn't in a witness equality with the member of
+    /// an accumulator membership statement
+    DLNotEqualToAccumulatorMember(usize),
+    /// The proof that a SAVER ciphertext is a re-randomization of another ciphertext failed verification
+    SaverCiphertextLinkProofFailed,
+    /// The robust TZ21 proof has a different number of ciphertexts of hidden parties, from which the subset of
+    /// `rdkgith_decls::SUBSET_SIZE` ciphertexts is chosen during compression, than expected for the parameters
+    /// in `rdkgith_decls`, as when the prover used different parameters. The values are the number found and
+    /// the number expected.
+    TZ21SubsetSizeMismatch(usize, usize),
+    /// The proof of the statement at the index is a revealed BBS+ signature, which isn't bound to the nonce,
+    /// but `VerifierConfig::require_nonce` is set
+    ReplayableProofWithNonceRequired(usize),
+    /// The prepared parameters given to `ProofSpec::prepare_with_parameters` were derived from a proof spec
+    /// with different statements or setup params
+    PreparedParametersMismatch,
+    /// No parameters were derived for the statement at the index
+    MissingDerivedParams(usize),
+    /// The sub-protocol of the statement at the index requested a challenge while being initialized but the
+    /// challenge is only generated once all statements are initialized
+    ChallengeRequestedDuringInit(usize),
+}
+
+impl ProofSystemError {
+    /// Older misspelled name of `Self::SnarkpackSrsNotProvided`, kept only so that code constructing the error
+    /// with the old name still compiles. It can't be used as a pattern as this type doesn't derive `PartialEq`
+    /// and `Eq`, so `match` arms for the old variant must be changed to `Self::SnarkpackSrsNotProvided`.
+    #[deprecated(note = "Use `ProofSystemError::SnarkpackSrsNotProvided`, also in `match` arms")]
+    #[allow(non_upper_case_globals)]
+    pub const SnarckpackSrsNotProvided: Self = Self::SnarkpackSrsNotProvided;
+
+    /// Index of the statement whose proof failed verification, when the error is about a single statement's
+    /// proof. Returns `None` for errors not tied to a single statement, like a failed batched pairing check.
+    pub fn failed_statement_index(&self) -> Option<usize> {
+        match self {
+            Self::ProofIncompatibleWithStatement(i, _, _)
+            | Self::ProofIncompatibleWithStatementLite(i)
+            | Self::WitnessResponseNotEqual(i, _)
+            | Self::ResponseForWitnessNotFoundForStatement(i)
+            | Self::InvalidGroupElement(i)
+            | Self::PublicSetMembershipProofFailed(i) => Some(*i),
+            Self::SchnorrProofContributionFailed(i, _)
+            | Self::BBSPlusProofContributionFailed(i, _)
+            | Self::BBSProofContributionFailed(i, _)
+            | Self::VBAccumProofContributionFailed(i, _)
+            | Self::SaverProofContributionFailed(i, _)
+            | Self::LegoSnarkProofContributionFailed(i, _)
+            | Self::PSProofContributionFailed(i, _)
+            | Self::BulletproofsPlusPlusProofContributionFailed(i, _)
+            | Self::SmcRangeProofContributionFailed(i, _)
+            | Self::DetachedVBAccumProofContributionFailed(i, _)
+            | Self::KBAccumProofContributionFailed(i, _)
+            | Self::BBDT16KVACProofContributionFailed(i, _)
+            | Self::VerifiableEncryptionFailed(i, _) => Some(*i as usize),
+            _ => None,
+        }
+    }
 }
 
 impl From<SchnorrError> for ProofSystemError {
@@ -99,7 +99,10 @@
 //! - proof of knowledge of a BBS or BBS+ or PS signature and signed messages
 //! - proof of knowledge of multiple BBS or BBS+ or PS signature and equality of certain messages
 //! - proof of knowledge of multiple BBS or BBS+ or PS signature and inequality of certain messages with public values
+//!   or with other messages
+//! - proof of knowledge of a BBS or BBS+ or PS signature and that a message is one of the values of a small public set
 //! - proof of knowledge of accumulator membership and non-membership
+//! - proof of knowledge of accumulator membership of the discrete log of a public key
 //! - proof of knowledge of Pedersen commitment opening.
 //! - proof of knowledge of BBS or BBS+ or PS signature(s) and that certain message(s) satisfy given bounds (range proof)
 //! - verifiable encryption of messages in a BBS or BBS+ or PS signature using zk-SNARK based protocol SAVER or
@@ -116,6 +119,10 @@
 //!   messages are equal among them without revealing them.
 //! - test `pok_of_bbs_plus_sig_and_inequality_with_public_value` proves knowledge of a BBS+ signature and also that
 //!   certain message is not equal to a public value without revealing the message
+//! - test `pok_of_bbs_plus_sigs_and_inequality_of_hidden_messages` proves knowledge of 2 BBS+ signatures and also that
+//!   a message from one is not equal to a message from the other without revealing either message
+//! - test `pok_of_bbs_plus_sig_and_public_set_membership` proves knowledge of a BBS+ signature and also that
+//!   certain message is one of the values of a public set without revealing the message or which value it is
 //! - test `pok_of_bbs_plus_sig_and_accumulator` proves knowledge of a BBS+ signature and also that certain messages
 //!   are present and absent in the 2 accumulators respectively.
 //! - test `pok_of_knowledge_in_pedersen_commitment_and_bbs_plus_sig` proves knowledge of a BBS+ signature and opening
@@ -178,6 +185,7 @@ pub mod prover;
 pub mod statement;
 pub mod statement_proof;
 pub mod sub_protocols;
+mod transcript;
 pub mod verifier;
 pub mod witness;
 
@@ -185,7 +193,7 @@ pub mod prelude {
     pub use crate::{
         error::ProofSystemError, meta_statement::*, proof::*, proof_spec::*, prover::*,
         setup_params::*, statement::*, statement_proof::*,
-        sub_protocols::bound_check_legogroth16::generate_snark_srs_bound_check, verifier::*,
-        witness::*,
+        sub_protocols::bound_check_legogroth16::generate_snark_srs_bound_check,
+        transcript::CompositeTranscriptBuilder, verifier::*, witness::*,
     };
 }
@@ -1,7 +1,11 @@
-use crate::prelude::StatementProof;
+use crate::{error::ProofSystemError, prelude::StatementProof};
 use ark_ec::pairing::Pairing;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{collections::BTreeSet, vec::Vec};
+use ark_std::{
+    collections::BTreeSet,
+    io::{Read, Write},
+    vec::Vec,
+};
 use legogroth16::aggregation;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -32,3 +36,175 @@ impl<E: Pairing> PartialEq for Proof<E> {
         // TODO: Add remaining
     }
 }
+
+/// Version of the serialized form of `Proof` written by `Proof::serialize_versioned`. Needs to be bumped
+/// whenever a change makes proofs serialized by the previous version deserialize differently, like when
+/// the order of `StatementProof` variants changes.
+pub const PROOF_SERIALIZATION_VERSION: u16 = 1;
+
+impl<E: Pairing> Proof<E> {
+    /// Serialize the proof in compressed form preceded by `PROOF_SERIALIZATION_VERSION` so that a verifier
+    /// running a different version of this crate can reject it with `ProofSystemError::UnsupportedProofVersion`
+    /// rather than failing in an obscure way or, worse, misinterpreting it.
+    pub fn serialize_versioned<W: Write>(&self, writer: W) -> Result<(), ProofSystemError> {
+        self.serialize_with_version(PROOF_SERIALIZATION_VERSION, writer)
+    }
+
+    /// Deserialize a proof serialized with `Self::serialize_versioned`. Fails if the proof was serialized
+    /// with a version other than `PROOF_SERIALIZATION_VERSION`.
+    pub fn deserialize_versioned<R: Read>(reader: R) -> Result<Self, ProofSystemError> {
+        Self::deserialize_with_version(PROOF_SERIALIZATION_VERSION, reader)
+    }
+
+    /// Deserialize a proof serialized in compressed form, like with `CanonicalSerialize::serialize_compressed`,
+    /// from bytes that might have come from an untrusted party. Unlike `CanonicalDeserialize`, which fails
+    /// differently for each kind of statement proof, any failure, including trailing bytes or a panic in the
+    /// deserialization of some statement proof, results in `ProofSystemError::MalformedProofBytes`.
+    /// Panics are only caught when the `std` feature is enabled.
+    pub fn from_bytes_safe(bytes: &[u8]) -> Result<Self, ProofSystemError> {
+        let deserialize = || {
+            let mut reader = bytes;
+            let proof = Self::deserialize_compressed(&mut reader).ok()?;
+            reader.is_empty().then_some(proof)
+        };
+        #[cfg(feature = "std")]
+        let proof = std::panic::catch_unwind(std::panic::AssertUnwindSafe(deserialize))
+            .ok()
+            .flatten();
+        #[cfg(not(feature = "std"))]
+        let proof = deserialize();
+        proof.ok_or(ProofSystemError::MalformedProofBytes)
+    }
+
+    /// Indices of the statements whose proofs are aggregated in this proof. Returns the groups of
+    /// aggregated Groth16 proofs followed by the groups of aggregated LegoGroth16 proofs, each group
+    /// being the statements covered by one aggregate proof. Useful for checking that the statements
+    /// expected to be aggregated, as given in the `ProofSpec`, actually are.
+    pub fn aggregated_statement_indices(&self) -> (Vec<BTreeSet<usize>>, Vec<BTreeSet<usize>>) {
+        let indices = |aggregated: &Option<Vec<AggregatedGroth16<E>>>| {
+            aggregated
+                .as_ref()
+                .map(|a| a.iter().map(|a| a.statements.clone()).collect())
+                .unwrap_or_default()
+        };
+        (
+            indices(&self.aggregated_groth16),
+            indices(&self.aggregated_legogroth16),
+        )
+    }
+
+    fn serialize_with_version<W: Write>(
+        &self,
+        version: u16,
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        version.serialize_compressed(&mut writer)?;
+        self.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+
+    fn deserialize_with_version<R: Read>(
+        supported_version: u16,
+        mut reader: R,
+    ) -> Result<Self, ProofSystemError> {
+        let version = u16::deserialize_compressed(&mut reader)?;
+        if version != supported_version {
+            return Err(ProofSystemError::UnsupportedProofVersion(
+                version,
+                supported_version,
+            ));
+        }
+        Ok(Self::deserialize_compressed(&mut reader)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::Bls12_381;
+    use ark_std::{
+        rand::{rngs::StdRng, RngCore, SeedableRng},
+        vec,
+    };
+
+    #[test]
+    fn from_bytes_safe_never_panics() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let proof = Proof::<Bls12_381> {
+            statement_proofs: vec![],
+            aggregated_groth16: None,
+            aggregated_legogroth16: None,
+        };
+        let mut bytes = vec![];
+        proof.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(Proof::from_bytes_safe(&bytes).unwrap(), proof);
+
+        // Trailing bytes
+        bytes.push(0);
+        assert!(matches!(
+            Proof::<Bls12_381>::from_bytes_safe(&bytes),
+            Err(ProofSystemError::MalformedProofBytes)
+        ));
+
+        // Random byte strings
+        for len in 0..256 {
+            let mut bytes = vec![0u8; len];
+            rng.fill_bytes(&mut bytes);
+            assert!(Proof::<Bls12_381>::from_bytes_safe(&bytes).is_err());
+        }
+
+        // A single statement proof of each kind followed by random bytes so that the deserialization
+        // of every kind of statement proof is exercised
+        for variant in 0..=u8::MAX {
+            for len in [0, 1, 31, 48, 96, 200, 1000] {
+                let mut bytes = vec![];
+                1u64.serialize_compressed(&mut bytes).unwrap();
+                bytes.push(variant);
+                let mut rest = vec![0u8; len];
+                rng.fill_bytes(&mut rest);
+                bytes.extend_from_slice(&rest);
+                if let Err(e) = Proof::<Bls12_381>::from_bytes_safe(&bytes) {
+                    assert!(matches!(e, ProofSystemError::MalformedProofBytes));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn versioned_serialization() {
+        let proof = Proof::<Bls12_381> {
+            statement_proofs: vec![],
+            aggregated_groth16: None,
+            aggregated_legogroth16: None,
+        };
+
+        let mut bytes = vec![];
+        proof.serialize_versioned(&mut bytes).unwrap();
+        assert_eq!(
+            bytes.len(),
+            PROOF_SERIALIZATION_VERSION.compressed_size() + proof.compressed_size()
+        );
+        assert_eq!(Proof::deserialize_versioned(&bytes[..]).unwrap(), proof);
+
+        // A proof serialized by a newer version
+        let mut bytes = vec![];
+        proof
+            .serialize_with_version(PROOF_SERIALIZATION_VERSION + 1, &mut bytes)
+            .unwrap();
+        assert!(matches!(
+            Proof::<Bls12_381>::deserialize_versioned(&bytes[..]),
+            Err(ProofSystemError::UnsupportedProofVersion(found, supported))
+                if found == PROOF_SERIALIZATION_VERSION + 1 && supported == PROOF_SERIALIZATION_VERSION
+        ));
+
+        // A newer verifier reading a proof serialized with the current version
+        let mut bytes = vec![];
+        proof.serialize_versioned(&mut bytes).unwrap();
+        assert!(matches!(
+            Proof::<Bls12_381>::deserialize_with_version(PROOF_SERIALIZATION_VERSION + 1, &bytes[..]),
+            Err(ProofSystemError::UnsupportedProofVersion(found, supported))
+                if found == PROOF_SERIALIZATION_VERSION && supported == PROOF_SERIALIZATION_VERSION + 1
+        ));
+    }
+}
@@ -1,19 +1,26 @@
 use crate::{
+    constants::{CONTEXT_LABEL, NONCE_DERIVATION_LABEL},
     derived_params::{DerivedParamsTracker, StatementDerivedParams},
     error::ProofSystemError,
-    meta_statement::{MetaStatement, MetaStatements},
+    meta_statement::{EqualWitnesses, MetaStatement, MetaStatements},
+    proof::Proof,
     setup_params::SetupParams,
     statement::{
         bound_check_smc::{SmcParamsAndCommitmentKey, SmcParamsWithPairingAndCommitmentKey},
         Statement, Statements,
     },
 };
-use ark_ec::pairing::Pairing;
+use ark_ec::{
+    pairing::{Pairing, PairingOutput},
+    AffineRepr,
+};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 use ark_std::{
     collections::{BTreeMap, BTreeSet},
     io::{Read, Write},
+    vec,
     vec::Vec,
+    Zero,
 };
 use bbs_plus::setup::{
     PreparedPublicKeyG2 as PreparedBBSPlusPk,
@@ -25,7 +32,11 @@ use coconut_crypto::setup::{
     PreparedPublicKey as PreparedPSPk, PreparedSignatureParams as PreparedPSSigParams,
     PublicKey as PSPk, SignatureParams as PSSigParams,
 };
-use dock_crypto_utils::commitment::PedersenCommitmentKey;
+use digest::Digest;
+use dock_crypto_utils::{
+    commitment::PedersenCommitmentKey, concat_slices, signature::MultiMessageSignatureParams,
+    transcript::Transcript,
+};
 use legogroth16::{
     aggregation::srs::{ProverSRS, VerifierSRS},
     PreparedVerifyingKey as LegoPreparedVerifyingKey, VerifyingKey as LegoVerifyingKey,
@@ -37,6 +48,7 @@ use saver::prelude::{
 };
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use sha3::Sha3_512;
 use smc_range_proof::prelude::MemberCommitmentKey;
 use vb_accumulator::{
     kb_positive_accumulator::setup::{
@@ -59,10 +71,50 @@ pub enum SnarkpackSRS<E: Pairing> {
     VerifierSrs(VerifierSRS<E>),
 }
 
+/// Commitment keys derived from the public params of the statements of a `ProofSpec`. Returned by
+/// `ProofSpec::derive_commitment_keys`
+pub type DerivedCommitmentKeys<E> = (
+    StatementDerivedParams<Vec<<E as Pairing>::G1Affine>>,
+    StatementDerivedParams<Vec<<E as Pairing>::G1Affine>>,
+    StatementDerivedParams<(Vec<<E as Pairing>::G1Affine>, Vec<<E as Pairing>::G1Affine>)>,
+    StatementDerivedParams<Vec<<E as Pairing>::G1Affine>>,
+    StatementDerivedParams<[<E as Pairing>::G1Affine; 2]>,
+    StatementDerivedParams<[<E as Pairing>::G1Affine; 2]>,
+    StatementDerivedParams<[<E as Pairing>::G1Affine; 2]>,
+);
+
+/// Prepared parameters for pairings derived from the public params of the statements of a `ProofSpec`.
+/// Returned by `ProofSpec::derive_prepared_parameters`
+pub type PreparedParameters<E> = (
+    StatementDerivedParams<LegoPreparedVerifyingKey<E>>,
+    StatementDerivedParams<PreparedEncryptionGens<E>>,
+    StatementDerivedParams<PreparedEncryptionKey<E>>,
+    StatementDerivedParams<SaverPreparedVerifyingKey<E>>,
+    StatementDerivedParams<PreparedBBSPlusSigParams<E>>,
+    StatementDerivedParams<PreparedBBSPlusPk<E>>,
+    StatementDerivedParams<PreparedAccumParams<E>>,
+    StatementDerivedParams<PreparedAccumPk<E>>,
+    StatementDerivedParams<KBPreparedAccumParams<E>>,
+    StatementDerivedParams<KBPreparedAccumPk<E>>,
+    StatementDerivedParams<PreparedPSSigParams<E>>,
+    StatementDerivedParams<PreparedPSPk<E>>,
+    StatementDerivedParams<PreparedBBSSigParams23<E>>,
+    StatementDerivedParams<SmcParamsWithPairingAndCommitmentKey<E>>,
+);
+
+/// `PreparedParameters` along with a digest of the statements and setup params of the `ProofSpec` they were
+/// derived from so that `ProofSpec::prepare_with_parameters` can check that they are used with the same
+/// statements. Returned by `ProofSpec::derive_prepared_parameters_for_reuse`
+#[derive(Clone)]
+pub struct DerivedPreparedParameters<E: Pairing> {
+    pub(crate) params: PreparedParameters<E>,
+    spec_digest: Vec<u8>,
+}
+
 /// Describes the relations that need to proven. This is created independently by the prover and verifier and must
 /// be agreed upon and be same before creating a `Proof`. Represented as collection of `Statement`s and `MetaStatement`s.
 /// Also contains other instructions like which proofs to aggregate.
-#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(bound = ""))]
 pub struct ProofSpec<E: Pairing> {
@@ -74,6 +126,11 @@ pub struct ProofSpec<E: Pairing> {
     /// the proof or the verifier's identity or some verifier-specific identity of the holder
     /// or all of the above combined.
     pub context: Option<Vec<u8>>,
+    /// Additional contexts, each hashed into the proof with a label containing its index, for binding a
+    /// proof to several audiences at once like when it's presented to multiple verifiers. The prover and
+    /// verifiers must use the same contexts in the same order.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub contexts: Option<Vec<Vec<u8>>>,
     /// Statement indices for which Groth16 proof should be aggregated. Each BTreeSet represents one
     /// group of statements whose proof will be aggregated into 1 aggregate proof. The number of aggregate
     /// proofs is the length of the vector
@@ -85,6 +142,23 @@ pub struct ProofSpec<E: Pairing> {
     pub snark_aggregation_srs: Option<SnarkpackSRS<E>>,
 }
 
+/// A `ProofSpec` along with the commitment keys, prepared pairing parameters and disjoint witness equalities
+/// derived from it. Created using `ProofSpec::prepare`.
+pub struct PreparedProofSpec<E: Pairing> {
+    pub(crate) spec: ProofSpec<E>,
+    pub(crate) commitment_keys: DerivedCommitmentKeys<E>,
+    pub(crate) prepared_params: PreparedParameters<E>,
+    pub(crate) disjoint_equalities: Vec<EqualWitnesses>,
+}
+
+impl<E: Pairing> PreparedProofSpec<E> {
+    /// The validated `ProofSpec` everything else was derived from. It can't be modified as the derived data
+    /// would then be stale.
+    pub fn spec(&self) -> &ProofSpec<E> {
+        &self.spec
+    }
+}
+
 impl<E: Pairing> ProofSpec<E> {
     /// Create a new `ProofSpec`
     pub fn new(
@@ -98,12 +172,26 @@ impl<E: Pairing> ProofSpec<E> {
             meta_statements,
             setup_params,
             context,
+            contexts: None,
             aggregate_groth16: None,
             aggregate_legogroth16: None,
             snark_aggregation_srs: None,
         }
     }
 
+    /// Same as `Self::new` but binds the proof to each of the given `contexts` as well.
+    pub fn new_with_contexts(
+        statements: Statements<E>,
+        meta_statements: MetaStatements,
+        setup_params: Vec<SetupParams<E>>,
+        context: Option<Vec<u8>>,
+        contexts: Vec<Vec<u8>>,
+    ) -> Self {
+        let mut spec = Self::new(statements, meta_statements, setup_params, context);
+        spec.contexts = Some(contexts);
+        spec
+    }
+
     /// Same as `Self::new` but specifies which proofs should be aggregated.
     pub fn new_with_aggregation(
         statements: Statements<E>,
@@ -119,12 +207,245 @@ impl<E: Pairing> ProofSpec<E> {
             meta_statements,
             setup_params,
             context,
+            contexts: None,
             aggregate_groth16,
             aggregate_legogroth16,
             snark_aggregation_srs,
         }
     }
 
+    /// Add the context and the additional contexts to the transcript of the proof's challenge. The `i`-th
+    /// additional context is added with label `CONTEXT_LABEL || i as 4 byte little endian`.
+    pub(crate) fn context_contribution<T: Transcript>(&self, transcript: &mut T) {
+        if let Some(ctx) = &self.context {
+            transcript.append_message(CONTEXT_LABEL, ctx);
+        }
+        if let Some(contexts) = &self.contexts {
+            for (i, ctx) in contexts.iter().enumerate() {
+                transcript.append_message_without_static_label(
+                    &concat_slices!(CONTEXT_LABEL, (i as u32).to_le_bytes()),
+                    ctx,
+                );
+            }
+        }
+    }
+
+    /// Derive a nonce from the context, the additional contexts and `extra`, an application supplied salt like
+    /// a session id or a timestamp. The derivation is deterministic so the same salt always gives the same
+    /// nonce. This does not make the nonce fresh, the salt must be unique for each proof request and the
+    /// verifier must still reject a nonce it has seen before to prevent replays.
+    pub fn derive_nonce<D: Digest>(&self, extra: &[u8]) -> Vec<u8> {
+        let mut hasher = D::new();
+        hasher.update(NONCE_DERIVATION_LABEL);
+        match &self.context {
+            Some(ctx) => {
+                hasher.update([1]);
+                hasher.update((ctx.len() as u64).to_le_bytes());
+                hasher.update(ctx);
+            }
+            None => hasher.update([0]),
+        }
+        let contexts = self.contexts.as_deref().unwrap_or_default();
+        hasher.update((contexts.len() as u64).to_le_bytes());
+        for ctx in contexts {
+            hasher.update((ctx.len() as u64).to_le_bytes());
+            hasher.update(ctx);
+        }
+        hasher.update((extra.len() as u64).to_le_bytes());
+        hasher.update(extra);
+        hasher.finalize().to_vec()
+    }
+
+    /// Estimate the size in bytes of the compressed serialization of the proof created for this proof spec without
+    /// creating it. The estimate is exact for proofs created with the default `ProverConfig`, including when some
+    /// responses are skipped because of witness equalities.
+    /// Proofs of SAVER, LegoGroth16 bound check, R1CS, set-membership check based bound check (with or without
+    /// keyed verification), TZ21 verifiable encryption and detached accumulator statements aren't supported and
+    /// for these `ProofSystemError::ProofSizeEstimationNotSupported` is returned.
+    pub fn estimate_proof_size(&self) -> Result<usize, ProofSystemError> {
+        let g1 = E::G1Affine::zero().compressed_size();
+        let g2 = E::G2Affine::zero().compressed_size();
+        let gt = PairingOutput::<E>::zero().compressed_size();
+        let fr = E::ScalarField::zero().compressed_size();
+        // Vectors are prefixed by their length as u64 and options by a byte
+        let len_prefix = 8;
+        let opt_prefix = 1;
+        // Randomized witness, Schnorr commitment and responses of a VB membership proof. The response for
+        // the (non)member is never part of these proofs as it comes from the proof of another statement
+        let vb_mem = 7 * g1 + gt + opt_prefix + 4 * fr;
+        let vb_non_mem = 11 * g1 + gt + opt_prefix + 7 * fr;
+        // Randomized signature, and the commitment and response of the partial proof for the CDH based and keyed
+        // verification membership proofs
+        let cdh_mem = 3 * g1 + fr + 2 * opt_prefix;
+        // Partial proof of knowledge of the BB signature in a KB positive accumulator membership proof
+        let bb_sig_partial = 9 * g1 + gt + 2 * opt_prefix + 7 * fr;
+        // Schnorr responses for `count` witnesses of which `skipped` come from the proof of another statement.
+        // A partial response is a map from witness index to response followed by the total number of responses
+        let responses = |count: usize, skipped: usize| {
+            if skipped == 0 {
+                len_prefix + count * fr
+            } else {
+                2 * len_prefix + (count - skipped) * (len_prefix + fr)
+            }
+        };
+        let hidden_messages = |supported: usize, revealed: usize| {
+            supported.checked_sub(revealed).ok_or(
+                ProofSystemError::BBSPlusProtocolInvalidMessageCount(revealed, supported),
+            )
+        };
+
+        // Responses are skipped the same way as the prover does
+        let disjoint_equalities = self.meta_statements.disjoint_witness_equalities();
+        let mut resp_generated = BTreeSet::new();
+        let mut skipped = |s_idx: usize, witness_count: usize| {
+            Proof::<E>::get_responses_to_skip(
+                s_idx,
+                witness_count,
+                &disjoint_equalities,
+                &mut resp_generated,
+            )
+            .len()
+        };
+
+        // Statement proofs are serialized as a vector with each item prefixed by its variant index
+        let mut size = len_prefix;
+        for (s_idx, statement) in self.statements.0.iter().enumerate() {
+            size += 1;
+            size += match statement {
+                Statement::PoKBBSSignatureG1Prover(s) => {
+                    let total = s
+                        .get_params(&self.setup_params, s_idx)?
+                        .supported_message_count();
+                    let hidden = hidden_messages(total, s.revealed_messages.len())?;
+                    5 * g1 + 2 * fr + 2 * opt_prefix + responses(hidden + 2, skipped(s_idx, total))
+                }
+                Statement::PoKBBSSignatureG1Verifier(s) => {
+                    let total = s
+                        .get_params(&self.setup_params, s_idx)?
+                        .supported_message_count();
+                    let hidden = hidden_messages(total, s.revealed_messages.len())?;
+                    5 * g1 + 2 * fr + 2 * opt_prefix + responses(hidden + 2, skipped(s_idx, total))
+                }
+                Statement::PoKBBSSignature23G1Prover(s) => {
+                    let total = s
+                        .get_params(&self.setup_params, s_idx)?
+                        .supported_message_count();
+                    let hidden = hidden_messages(total, s.revealed_messages.len())?;
+                    5 * g1 + 2 * fr + 2 * opt_prefix + responses(hidden + 1, skipped(s_idx, total))
+                }
+                Statement::PoKBBSSignature23G1Verifier(s) => {
+                    let total = s
+                        .get_params(&self.setup_params, s_idx)?
+                        .supported_message_count();
+                    let hidden = hidden_messages(total, s.revealed_messages.len())?;
+                    5 * g1 + 2 * fr + 2 * opt_prefix + responses(hidden + 1, skipped(s_idx, total))
+                }
+                Statement::PoKBBSSignature23IETFG1Prover(s) => {
+                    let total = s
+                        .get_params(&self.setup_params, s_idx)?
+                        .supported_message_count();
+                    let hidden = hidden_messages(total, s.revealed_messages.len())?;
+                    3 * g1 + 2 * opt_prefix + responses(hidden + 2, skipped(s_idx, total))
+                }
+                Statement::PoKBBSSignature23IETFG1Verifier(s) => {
+                    let total = s
+                        .get_params(&self.setup_params, s_idx)?
+                        .supported_message_count();
+                    let hidden = hidden_messages(total, s.revealed_messages.len())?;
+                    3 * g1 + 2 * opt_prefix + responses(hidden + 2, skipped(s_idx, total))
+                }
+                Statement::PedersenCommitment(s) => {
+                    let count = s.get_commitment_key(&self.setup_params, s_idx)?.len();
+                    g1 + responses(count, skipped(s_idx, count))
+                }
+                Statement::PedersenCommitmentG2(s) => {
+                    let count = s.get_commitment_key_g2(&self.setup_params, s_idx)?.len();
+                    g2 + responses(count, skipped(s_idx, count))
+                }
+                Statement::DLEqualToAccumulatorMember(_) => g1 + responses(1, skipped(s_idx, 1)),
+                Statement::VBAccumulatorMembership(_)
+                | Statement::KBUniversalAccumulatorMembership(_)
+                | Statement::KBUniversalAccumulatorNonMembership(_) => vb_mem,
+                Statement::VBAccumulatorNonMembership(_) => vb_non_mem,
+                Statement::KBUniversalAccumulatorMembershipAndNonMembership(_) => 2 * vb_mem,
+                Statement::VBAccumulatorMembershipCDHProver(_)
+                | Statement::VBAccumulatorMembershipCDHVerifier(_)
+                | Statement::KBUniversalAccumulatorMembershipCDHProver(_)
+                | Statement::KBUniversalAccumulatorMembershipCDHVerifier(_)
+                | Statement::KBUniversalAccumulatorNonMembershipCDHProver(_)
+                | Statement::KBUniversalAccumulatorNonMembershipCDHVerifier(_)
+                | Statement::VBAccumulatorMembershipKV(_)
+                | Statement::VBAccumulatorMembershipKVFullVerifier(_)
+                | Statement::KBUniversalAccumulatorMembershipKV(_)
+                | Statement::KBUniversalAccumulatorMembershipKVFullVerifier(_)
+                | Statement::KBUniversalAccumulatorNonMembershipKV(_)
+                | Statement::KBUniversalAccumulatorNonMembershipKVFullVerifier(_) => cdh_mem,
+                Statement::VBAccumulatorNonMembershipCDHProver(_)
+                | Statement::VBAccumulatorNonMembershipCDHVerifier(_) => {
+                    // Only 1 of the 3 responses of the first Schnorr proof is included followed by a proof of
+                    // knowledge of discrete log
+                    4 * g1 + responses(3, 2) + g1 + fr
+                }
+                Statement::KBPositiveAccumulatorMembership(_) => bb_sig_partial + vb_mem,
+                Statement::KBPositiveAccumulatorMembershipCDH(_) => bb_sig_partial + cdh_mem,
+                Statement::PoKPSSignature(s) => {
+                    let total = s
+                        .get_params(&self.setup_params, s_idx)?
+                        .supported_message_count();
+                    let hidden = hidden_messages(total, s.revealed_messages.len())?;
+                    // The proof always has responses for all hidden messages but these still count as generated
+                    skipped(s_idx, total);
+                    // Randomized signature, commitment and value of `K`, the range of committed message indices
+                    // as 2 u64 and responses for the hidden messages and the randomness
+                    2 * g1 + 2 * g2 + 2 * 8 + len_prefix + (hidden + 1) * fr
+                }
+                Statement::PoKBBDT16MAC(s) => {
+                    let total = s
+                        .get_params::<E>(&self.setup_params, s_idx)?
+                        .supported_message_count();
+                    let hidden = hidden_messages(total, s.revealed_messages.len())?;
+                    5 * g1 + 2 * fr + 2 * opt_prefix + responses(hidden + 2, skipped(s_idx, total))
+                }
+                Statement::PoKBBDT16MACFullVerifier(s) => {
+                    let total = s
+                        .get_params::<E>(&self.setup_params, s_idx)?
+                        .supported_message_count();
+                    let hidden = hidden_messages(total, s.revealed_messages.len())?;
+                    5 * g1 + 2 * fr + 2 * opt_prefix + responses(hidden + 2, skipped(s_idx, total))
+                }
+                // The inequality proof followed by the commitments to the witnesses and proofs of knowledge of their
+                // openings which skip the response for the witness
+                Statement::PublicInequality(_) => 4 * g1 + 5 * fr + g1 + g1 + responses(2, 1),
+                Statement::HiddenInequality(_) => 4 * g1 + 5 * fr + 2 * (2 * g1 + responses(2, 1)),
+                Statement::PublicSetMembership(s) => {
+                    // A commitment, challenge and response for each member of the set
+                    let n = s.set.len();
+                    g1 + len_prefix + n * g1 + 2 * (len_prefix + n * fr) + g1 + responses(2, 1)
+                }
+                Statement::BoundCheckBpp(s) => {
+                    let params = s.get_setup_params(&self.setup_params, s_idx)?;
+                    // The weighted norm linear argument halves its vectors in each round till both are of size 1
+                    let rounds =
+                        params.G_vec.len().max(params.H_vec.len()).trailing_zeros() as usize;
+                    // Commitments to the 2 values, the base as u16, the commitments of the 3 rounds, the
+                    // argument's 2 vectors of group elements and 2 vectors of a single field element
+                    let bpp_proof = len_prefix
+                        + 2 * g1
+                        + 2
+                        + 4 * g1
+                        + 2 * (len_prefix + rounds * g1)
+                        + 2 * (len_prefix + fr);
+                    // Each of the 2 Schnorr proofs skips the response for the message
+                    bpp_proof + 2 * (g1 + responses(2, 1))
+                }
+                _ => return Err(ProofSystemError::ProofSizeEstimationNotSupported(s_idx)),
+            };
+        }
+        // Options for aggregated Groth16 and LegoGroth16 proofs which are `None` as the statements that can
+        // be aggregated aren't supported
+        Ok(size + 2 * opt_prefix)
+    }
+
     pub fn add_statement(&mut self, statement: Statement<E>) -> usize {
         self.statements.add(statement)
     }
@@ -133,6 +454,94 @@ impl<E: Pairing> ProofSpec<E> {
         self.meta_statements.add(meta_statement)
     }
 
+    /// Combine 2 proof specs, like ones created independently for different credentials, into a single
+    /// proof spec. The statements of `other` are placed after the statements of `self` so the statement
+    /// indices in `other`'s meta statements and aggregation instructions are shifted accordingly. Setup
+    /// params of `other` that are already present in `self` are reused, the rest are appended and the
+    /// statements of `other` are updated to refer to the new indices. The contexts and the Snarkpack
+    /// SRS, when given in both, must be the same.
+    pub fn merge(mut self, other: Self) -> Result<Self, ProofSystemError> {
+        fn merge_optional<T: PartialEq>(
+            a: Option<T>,
+            b: Option<T>,
+        ) -> Result<Option<T>, ProofSystemError> {
+            match (a, b) {
+                (Some(a), Some(b)) if a != b => {
+                    Err(ProofSystemError::IncompatibleProofSpecsForMerge)
+                }
+                (a, b) => Ok(a.or(b)),
+            }
+        }
+
+        fn merge_aggregations(
+            a: Option<Vec<BTreeSet<usize>>>,
+            b: Option<Vec<BTreeSet<usize>>>,
+            offset: usize,
+        ) -> Option<Vec<BTreeSet<usize>>> {
+            let b = b.map(|b| {
+                b.into_iter()
+                    .map(|s_ids| s_ids.into_iter().map(|s_id| s_id + offset).collect())
+                    .collect::<Vec<_>>()
+            });
+            match (a, b) {
+                (Some(mut a), Some(b)) => {
+                    a.extend(b);
+                    Some(a)
+                }
+                (a, b) => a.or(b),
+            }
+        }
+
+        self.context = merge_optional(self.context, other.context)?;
+        self.contexts = merge_optional(self.contexts, other.contexts)?;
+        self.snark_aggregation_srs =
+            merge_optional(self.snark_aggregation_srs, other.snark_aggregation_srs)?;
+
+        let st_offset = self.statements.len();
+
+        // New index of each of other's setup params
+        let mut new_param_indices = Vec::with_capacity(other.setup_params.len());
+        for sp in other.setup_params {
+            match self.setup_params.iter().position(|p| *p == sp) {
+                Some(i) => new_param_indices.push(i),
+                None => {
+                    new_param_indices.push(self.setup_params.len());
+                    self.setup_params.push(sp);
+                }
+            }
+        }
+
+        for (i, mut st) in other.statements.0.into_iter().enumerate() {
+            for idx in st.setup_param_refs_mut().into_iter().flatten() {
+                *idx = *new_param_indices
+                    .get(*idx)
+                    .ok_or(ProofSystemError::DanglingSetupParamRef(st_offset + i, *idx))?;
+            }
+            self.statements.add(st);
+        }
+
+        for mt in other.meta_statements.0 {
+            match mt {
+                MetaStatement::WitnessEquality(w) => {
+                    self.meta_statements.add_witness_equality(EqualWitnesses(
+                        w.0.into_iter()
+                            .map(|(s_id, w_id)| (s_id + st_offset, w_id))
+                            .collect(),
+                    ));
+                }
+            }
+        }
+
+        self.aggregate_groth16 =
+            merge_aggregations(self.aggregate_groth16, other.aggregate_groth16, st_offset);
+        self.aggregate_legogroth16 = merge_aggregations(
+            self.aggregate_legogroth16,
+            other.aggregate_legogroth16,
+            st_offset,
+        );
+        Ok(self)
+    }
+
     /// Sanity check to ensure the proof spec is valid. This should never error as these are used
     /// by same entity creating them.
     pub fn validate(&self) -> Result<(), ProofSystemError> {
@@ -142,7 +551,7 @@ impl<E: Pairing> ProofSpec<E> {
         if (self.aggregate_groth16.is_some() || self.aggregate_legogroth16.is_some())
             && self.snark_aggregation_srs.is_none()
         {
-            return Err(ProofSystemError::SnarckpackSrsNotProvided);
+            return Err(ProofSystemError::SnarkpackSrsNotProvided);
         }
 
         // Check that the same statement id does not occur in self.aggregate_groth16 and self.aggregate_legogroth16
@@ -219,26 +628,283 @@ impl<E: Pairing> ProofSpec<E> {
                 }
             }
         }
+
+        // The discrete log must be shown equal to the member of an accumulator
+        let disjoint_equalities = self.meta_statements.disjoint_witness_equalities();
+        for (i, st) in self.statements.0.iter().enumerate() {
+            if let Statement::DLEqualToAccumulatorMember(_) = st {
+                let is_linked = disjoint_equalities
+                    .iter()
+                    .find(|eq| eq.has_wit_ref(&(i, 0)))
+                    .map(|eq| {
+                        eq.0.iter().any(|(j, k)| {
+                            *k == 0
+                                && self
+                                    .statements
+                                    .0
+                                    .get(*j)
+                                    .map(|s| s.is_accumulator_membership_statement())
+                                    .unwrap_or(false)
+                        })
+                    })
+                    .unwrap_or(false);
+                if !is_linked {
+                    return Err(ProofSystemError::DLNotEqualToAccumulatorMember(i));
+                }
+            }
+        }
+        self.validate_setup_param_refs()
+    }
+
+    /// Check that every reference to `setup_params` made by the statements points to an existing setup
+    /// param of the type that the statement expects. Without this check, a bad reference only surfaces
+    /// when the setup param is fetched during proof generation or verification.
+    pub fn validate_setup_param_refs(&self) -> Result<(), ProofSystemError> {
+        macro_rules! check_refs {
+            ($s: ident, $i: ident, $($ref: ident => $getter: ident),+) => {{
+                $(
+                    if let Some(r) = $s.$ref {
+                        if $s.$getter(&self.setup_params, $i).is_err() {
+                            return Err(ProofSystemError::DanglingSetupParamRef($i, r));
+                        }
+                    }
+                )+
+            }};
+        }
+
+        for (i, st) in self.statements.0.iter().enumerate() {
+            match st {
+                Statement::PoKBBSSignatureG1Prover(s) => {
+                    check_refs!(s, i, signature_params_ref => get_params)
+                }
+                Statement::PoKBBSSignatureG1Verifier(s) => {
+                    check_refs!(s, i, signature_params_ref => get_params, public_key_ref => get_public_key)
+                }
+                Statement::PoKBBSSignature23G1Prover(s) => {
+                    check_refs!(s, i, signature_params_ref => get_params)
+                }
+                Statement::PoKBBSSignature23G1Verifier(s) => {
+                    check_refs!(s, i, signature_params_ref => get_params, public_key_ref => get_public_key)
+                }
+                Statement::PoKBBSSignature23IETFG1Prover(s) => {
+                    check_refs!(s, i, signature_params_ref => get_params)
+                }
+                Statement::PoKBBSSignature23IETFG1Verifier(s) => {
+                    check_refs!(s, i, signature_params_ref => get_params, public_key_ref => get_public_key)
+                }
+                Statement::PoKPSSignature(s) => {
+                    check_refs!(s, i, signature_params_ref => get_params, public_key_ref => get_public_key)
+                }
+                Statement::PoKBBDT16MAC(s) => check_refs!(s, i, mac_params_ref => get_params),
+                Statement::PoKBBDT16MACFullVerifier(s) => {
+                    check_refs!(s, i, mac_params_ref => get_params)
+                }
+                Statement::PedersenCommitment(s) => {
+                    check_refs!(s, i, key_ref => get_commitment_key)
+                }
+                Statement::PedersenCommitmentG2(s) => {
+                    check_refs!(s, i, key_ref => get_commitment_key_g2)
+                }
+                Statement::VBAccumulatorMembership(s) => {
+                    check_refs!(s, i, params_ref => get_params, public_key_ref => get_public_key, proving_key_ref => get_proving_key)
+                }
+                Statement::VBAccumulatorNonMembership(s) => {
+                    check_refs!(s, i, params_ref => get_params, public_key_ref => get_public_key, proving_key_ref => get_proving_key)
+                }
+                Statement::KBUniversalAccumulatorMembership(s) => {
+                    check_refs!(s, i, params_ref => get_params, public_key_ref => get_public_key, proving_key_ref => get_proving_key)
+                }
+                Statement::KBUniversalAccumulatorNonMembership(s) => {
+                    check_refs!(s, i, params_ref => get_params, public_key_ref => get_public_key, proving_key_ref => get_proving_key)
+                }
+                Statement::KBUniversalAccumulatorMembershipAndNonMembership(s) => {
+                    check_refs!(s, i, params_ref => get_params, public_key_ref => get_public_key, proving_key_ref => get_proving_key)
+                }
+                Statement::KBPositiveAccumulatorMembership(s) => {
+                    check_refs!(s, i, params_ref => get_params, public_key_ref => get_public_key, proving_key_ref => get_proving_key)
+                }
+                Statement::KBPositiveAccumulatorMembershipCDH(s) => {
+                    check_refs!(s, i, params_ref => get_params, public_key_ref => get_public_key, proving_key_ref => get_proving_key)
+                }
+                Statement::DetachedAccumulatorMembershipProver(s) => {
+                    check_refs!(s, i, params_ref => get_params, public_key_ref => get_public_key, proving_key_ref => get_proving_key)
+                }
+                Statement::DetachedAccumulatorMembershipVerifier(s) => {
+                    check_refs!(s, i, params_ref => get_params, public_key_ref => get_public_key, proving_key_ref => get_proving_key)
+                }
+                Statement::DetachedAccumulatorNonMembershipProver(s) => {
+                    check_refs!(s, i, params_ref => get_params, public_key_ref => get_public_key, proving_key_ref => get_proving_key)
+                }
+                Statement::DetachedAccumulatorNonMembershipVerifier(s) => {
+                    check_refs!(s, i, params_ref => get_params, public_key_ref => get_public_key, proving_key_ref => get_proving_key)
+                }
+                Statement::VBAccumulatorMembershipCDHVerifier(s) => {
+                    check_refs!(s, i, params_ref => get_params, public_key_ref => get_public_key)
+                }
+                Statement::VBAccumulatorNonMembershipCDHProver(s) => {
+                    check_refs!(s, i, params_ref => get_params)
+                }
+                Statement::VBAccumulatorNonMembershipCDHVerifier(s) => {
+                    check_refs!(s, i, params_ref => get_params, public_key_ref => get_public_key)
+                }
+                Statement::KBUniversalAccumulatorMembershipCDHVerifier(s) => {
+                    check_refs!(s, i, params_ref => get_params, public_key_ref => get_public_key)
+                }
+                Statement::KBUniversalAccumulatorNonMembershipCDHVerifier(s) => {
+                    check_refs!(s, i, params_ref => get_params, public_key_ref => get_public_key)
+                }
+                Statement::SaverProver(s) => {
+                    check_refs!(s, i, encryption_gens_ref => get_encryption_gens, chunked_commitment_gens_ref => get_chunked_commitment_gens, encryption_key_ref => get_encryption_key, snark_proving_key_ref => get_snark_proving_key)
+                }
+                Statement::SaverVerifier(s) => {
+                    check_refs!(s, i, encryption_gens_ref => get_encryption_gens, chunked_commitment_gens_ref => get_chunked_commitment_gens, encryption_key_ref => get_encryption_key, snark_verifying_key_ref => get_snark_verifying_key)
+                }
+                Statement::BoundCheckLegoGroth16Prover(s) => {
+                    check_refs!(s, i, snark_proving_key_ref => get_proving_key)
+                }
+                Statement::BoundCheckLegoGroth16Verifier(s) => {
+                    check_refs!(s, i, snark_verifying_key_ref => get_verifying_key)
+                }
+                Statement::R1CSCircomProver(s) => {
+                    check_refs!(s, i, r1cs_ref => get_r1cs, wasm_bytes_ref => get_wasm_bytes, snark_proving_key_ref => get_proving_key)
+                }
+                Statement::R1CSCircomVerifier(s) => {
+                    check_refs!(s, i, public_inputs_ref => get_public_inputs, snark_verifying_key_ref => get_verifying_key)
+                }
+                Statement::BoundCheckBpp(s) => check_refs!(s, i, params_ref => get_setup_params),
+                Statement::BoundCheckSmc(s) => {
+                    check_refs!(s, i, params_and_comm_key_ref => get_params_and_comm_key)
+                }
+                Statement::BoundCheckSmcWithKVProver(s) => {
+                    check_refs!(s, i, params_ref => get_params_and_comm_key)
+                }
+                Statement::BoundCheckSmcWithKVVerifier(s) => {
+                    check_refs!(s, i, params_ref => get_params_and_comm_key_and_sk)
+                }
+                Statement::PublicInequality(s) => check_refs!(s, i, comm_key_ref => get_comm_key),
+                Statement::HiddenInequality(s) => check_refs!(s, i, comm_key_ref => get_comm_key),
+                Statement::PublicSetMembership(s) => {
+                    check_refs!(s, i, comm_key_ref => get_comm_key)
+                }
+                Statement::VeTZ21(s) | Statement::VeTZ21Robust(s) => {
+                    check_refs!(s, i, enc_params_ref => get_enc_params, comm_key_ref => get_comm_key)
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that the statements requested for aggregation are of the correct type, i.e. SAVER statements
+    /// for Groth16 aggregation and bound check or R1CS statements for LegoGroth16 aggregation, and that
+    /// the Snarkpack SRS supports the number of proofs in each aggregation.
+    pub fn validate_snark_aggregation(&self) -> Result<(), ProofSystemError> {
+        let srs_size = match &self.snark_aggregation_srs {
+            Some(SnarkpackSRS::ProverSrs(srs)) => srs.n as usize,
+            Some(SnarkpackSRS::VerifierSrs(srs)) => srs.n as usize,
+            None => {
+                if self.aggregate_groth16.is_some() || self.aggregate_legogroth16.is_some() {
+                    return Err(ProofSystemError::SnarkpackSrsNotProvided);
+                }
+                return Ok(());
+            }
+        };
+
+        let check_srs_size = |s_ids: &BTreeSet<usize>| {
+            if s_ids.len() != srs_size {
+                return Err(ProofSystemError::IncompatibleSnarkpackSrs(
+                    s_ids.len(),
+                    srs_size,
+                ));
+            }
+            Ok(())
+        };
+
+        if let Some(g16) = &self.aggregate_groth16 {
+            for s_ids in g16 {
+                for s_idx in s_ids {
+                    match self.statements.0.get(*s_idx) {
+                        Some(Statement::SaverProver(_)) | Some(Statement::SaverVerifier(_)) => (),
+                        Some(_) => {
+                            return Err(ProofSystemError::AggregateStatementNotSaver(*s_idx))
+                        }
+                        None => {
+                            return Err(ProofSystemError::InvalidAggregateStatementIndex(*s_idx))
+                        }
+                    }
+                }
+                check_srs_size(s_ids)?;
+            }
+        }
+        if let Some(lg16) = &self.aggregate_legogroth16 {
+            for s_ids in lg16 {
+                for s_idx in s_ids {
+                    match self.statements.0.get(*s_idx) {
+                        Some(Statement::BoundCheckLegoGroth16Prover(_))
+                        | Some(Statement::BoundCheckLegoGroth16Verifier(_))
+                        | Some(Statement::R1CSCircomProver(_))
+                        | Some(Statement::R1CSCircomVerifier(_)) => (),
+                        Some(_) => {
+                            return Err(ProofSystemError::AggregateStatementNotLegoGroth16(*s_idx))
+                        }
+                        None => {
+                            return Err(ProofSystemError::InvalidAggregateStatementIndex(*s_idx))
+                        }
+                    }
+                }
+                check_srs_size(s_ids)?;
+            }
+        }
         Ok(())
     }
 
+    /// Validate the proof spec and derive everything the verifier needs from it which doesn't depend on the
+    /// proof or the nonce. Useful when several proofs are verified against the same spec, like proofs
+    /// with different nonces, as the returned `PreparedProofSpec` can be passed to `Proof::verify_using_prepared`
+    /// for each of them.
+    pub fn prepare(self) -> Result<PreparedProofSpec<E>, ProofSystemError> {
+        self.validate()?;
+        let prepared_params = self.derive_prepared_parameters()?;
+        self.prepare_given_parameters(prepared_params)
+    }
+
+    /// Same as `Self::prepare` but uses the given prepared pairing parameters rather than deriving them. Useful
+    /// when the verifier derived them once, like at startup, using `Self::derive_prepared_parameters_for_reuse`
+    /// on a spec with the same statements and setup params. Parameters derived from a spec with different
+    /// statements or setup params are rejected with `ProofSystemError::PreparedParametersMismatch`.
+    pub fn prepare_with_parameters(
+        self,
+        prepared_params: DerivedPreparedParameters<E>,
+    ) -> Result<PreparedProofSpec<E>, ProofSystemError> {
+        self.validate()?;
+        if prepared_params.spec_digest != self.digest_for_prepared_parameters()? {
+            return Err(ProofSystemError::PreparedParametersMismatch);
+        }
+        self.prepare_given_parameters(prepared_params.params)
+    }
+
+    fn prepare_given_parameters(
+        self,
+        prepared_params: PreparedParameters<E>,
+    ) -> Result<PreparedProofSpec<E>, ProofSystemError> {
+        let commitment_keys = self.derive_commitment_keys()?;
+        let disjoint_equalities = if self.meta_statements.is_empty() {
+            vec![]
+        } else {
+            self.meta_statements.disjoint_witness_equalities()
+        };
+        Ok(PreparedProofSpec {
+            spec: self,
+            commitment_keys,
+            prepared_params,
+            disjoint_equalities,
+        })
+    }
+
     /// Derive commitment keys for Schnorr protocol from public params. This is done to avoid
     /// creating them if the same public params are used in multiple statements and is effectively a
     /// pre-processing step done for optimization.
-    pub fn derive_commitment_keys(
-        &self,
-    ) -> Result<
-        (
-            StatementDerivedParams<Vec<E::G1Affine>>,
-            StatementDerivedParams<Vec<E::G1Affine>>,
-            StatementDerivedParams<(Vec<E::G1Affine>, Vec<E::G1Affine>)>,
-            StatementDerivedParams<Vec<E::G1Affine>>,
-            StatementDerivedParams<[E::G1Affine; 2]>,
-            StatementDerivedParams<[E::G1Affine; 2]>,
-            StatementDerivedParams<[E::G1Affine; 2]>,
-        ),
-        ProofSystemError,
-    > {
+    pub fn derive_commitment_keys(&self) -> Result<DerivedCommitmentKeys<E>, ProofSystemError> {
         let mut derived_bound_check_lego_comm =
             DerivedParamsTracker::<LegoVerifyingKey<E>, Vec<E::G1Affine>, E>::new();
         let mut derived_ek_comm =
@@ -353,6 +1019,14 @@ impl<E: Pairing> ProofSpec<E> {
                     let ck = s.get_comm_key(&self.setup_params, s_idx)?;
                     derived_ineq_comm.on_new_statement_idx(ck, s_idx);
                 }
+                Statement::HiddenInequality(s) => {
+                    let ck = s.get_comm_key(&self.setup_params, s_idx)?;
+                    derived_ineq_comm.on_new_statement_idx(ck, s_idx);
+                }
+                Statement::PublicSetMembership(s) => {
+                    let ck = s.get_comm_key(&self.setup_params, s_idx)?;
+                    derived_ineq_comm.on_new_statement_idx(ck, s_idx);
+                }
                 _ => (),
             }
         }
@@ -367,29 +1041,28 @@ impl<E: Pairing> ProofSpec<E> {
         ))
     }
 
+    /// Same as `Self::derive_prepared_parameters` but also records which statements and setup params the
+    /// parameters were derived from so they can be passed to `Self::prepare_with_parameters`.
+    pub fn derive_prepared_parameters_for_reuse(
+        &self,
+    ) -> Result<DerivedPreparedParameters<E>, ProofSystemError> {
+        Ok(DerivedPreparedParameters {
+            params: self.derive_prepared_parameters()?,
+            spec_digest: self.digest_for_prepared_parameters()?,
+        })
+    }
+
+    /// Hash of the statements and setup params, which are all the prepared parameters depend on
+    fn digest_for_prepared_parameters(&self) -> Result<Vec<u8>, ProofSystemError> {
+        let mut bytes = Vec::new();
+        self.statements.serialize_compressed(&mut bytes)?;
+        self.setup_params.serialize_compressed(&mut bytes)?;
+        Ok(Sha3_512::digest(&bytes).to_vec())
+    }
+
     /// Derive prepared keys for performing pairings. This is done to avoid preparing the same
     /// parameters again and is effectively a pre-processing step done for optimization.
-    pub fn derive_prepared_parameters(
-        &self,
-    ) -> Result<
-        (
-            StatementDerivedParams<LegoPreparedVerifyingKey<E>>,
-            StatementDerivedParams<PreparedEncryptionGens<E>>,
-            StatementDerivedParams<PreparedEncryptionKey<E>>,
-            StatementDerivedParams<SaverPreparedVerifyingKey<E>>,
-            StatementDerivedParams<PreparedBBSPlusSigParams<E>>,
-            StatementDerivedParams<PreparedBBSPlusPk<E>>,
-            StatementDerivedParams<PreparedAccumParams<E>>,
-            StatementDerivedParams<PreparedAccumPk<E>>,
-            StatementDerivedParams<KBPreparedAccumParams<E>>,
-            StatementDerivedParams<KBPreparedAccumPk<E>>,
-            StatementDerivedParams<PreparedPSSigParams<E>>,
-            StatementDerivedParams<PreparedPSPk<E>>,
-            StatementDerivedParams<PreparedBBSSigParams23<E>>,
-            StatementDerivedParams<SmcParamsWithPairingAndCommitmentKey<E>>,
-        ),
-        ProofSystemError,
-    > {
+    pub fn derive_prepared_parameters(&self) -> Result<PreparedParameters<E>, ProofSystemError> {
         let mut derived_lego_vk =
             DerivedParamsTracker::<LegoVerifyingKey<E>, LegoPreparedVerifyingKey<E>, E>::new();
         let mut derived_enc_gens =
@@ -465,6 +1138,9 @@ impl<E: Pairing> ProofSpec<E> {
                 Statement::KBUniversalAccumulatorNonMembership(s) => {
                     set_derived_for_accum!(s, s_idx, derived_accum_p, derived_accum_pk);
                 }
+                Statement::KBUniversalAccumulatorMembershipAndNonMembership(s) => {
+                    set_derived_for_accum!(s, s_idx, derived_accum_p, derived_accum_pk);
+                }
                 Statement::VBAccumulatorMembershipCDHVerifier(s) => {
                     set_derived_for_accum!(s, s_idx, derived_accum_p, derived_accum_pk);
                 }
@@ -495,7 +1171,14 @@ impl<E: Pairing> ProofSpec<E> {
                 }
                 Statement::BoundCheckLegoGroth16Verifier(s) => {
                     let verifying_key = s.get_verifying_key(&self.setup_params, s_idx)?;
-                    derived_lego_vk.on_new_statement_idx(verifying_key, s_idx);
+                    match s.prepared_verifying_key() {
+                        Some(pvk) => derived_lego_vk.on_new_statement_idx_with_derived(
+                            verifying_key,
+                            pvk.clone(),
+                            s_idx,
+                        ),
+                        None => derived_lego_vk.on_new_statement_idx(verifying_key, s_idx),
+                    }
                 }
                 Statement::R1CSCircomVerifier(s) => {
                     let verifying_key = s.get_verifying_key(&self.setup_params, s_idx)?;
@@ -541,6 +1224,7 @@ impl<E: Pairing> Default for ProofSpec<E> {
             meta_statements: MetaStatements::new(),
             setup_params: Vec::new(),
             context: None,
+            contexts: None,
             aggregate_groth16: None,
             aggregate_legogroth16: None,
             snark_aggregation_srs: None,
@@ -552,6 +1236,113 @@ mod serialization {
     use super::*;
     use ark_serialize::{Compress, Valid, Validate};
 
+    // The byte marking whether `context` is present also has a bit marking whether `contexts` is, which is
+    // then serialized last. So a spec without `contexts` serializes the same as before `contexts` was added.
+
+    impl<E: Pairing> Valid for ProofSpec<E> {
+        fn check(&self) -> Result<(), SerializationError> {
+            self.statements.check()?;
+            self.setup_params.check()?;
+            self.snark_aggregation_srs.check()
+        }
+    }
+
+    impl<E: Pairing> CanonicalSerialize for ProofSpec<E> {
+        fn serialize_with_mode<W: Write>(
+            &self,
+            mut writer: W,
+            compress: Compress,
+        ) -> Result<(), SerializationError> {
+            self.statements.serialize_with_mode(&mut writer, compress)?;
+            self.meta_statements
+                .serialize_with_mode(&mut writer, compress)?;
+            self.setup_params
+                .serialize_with_mode(&mut writer, compress)?;
+            let flags = (self.context.is_some() as u8) | ((self.contexts.is_some() as u8) << 1);
+            flags.serialize_with_mode(&mut writer, compress)?;
+            if let Some(context) = &self.context {
+                context.serialize_with_mode(&mut writer, compress)?;
+            }
+            self.aggregate_groth16
+                .serialize_with_mode(&mut writer, compress)?;
+            self.aggregate_legogroth16
+                .serialize_with_mode(&mut writer, compress)?;
+            self.snark_aggregation_srs
+                .serialize_with_mode(&mut writer, compress)?;
+            if let Some(contexts) = &self.contexts {
+                contexts.serialize_with_mode(&mut writer, compress)?;
+            }
+            Ok(())
+        }
+
+        fn serialized_size(&self, compress: Compress) -> usize {
+            self.statements.serialized_size(compress)
+                + self.meta_statements.serialized_size(compress)
+                + self.setup_params.serialized_size(compress)
+                + self.context.serialized_size(compress)
+                + self.aggregate_groth16.serialized_size(compress)
+                + self.aggregate_legogroth16.serialized_size(compress)
+                + self.snark_aggregation_srs.serialized_size(compress)
+                + self
+                    .contexts
+                    .as_ref()
+                    .map_or(0, |c| c.serialized_size(compress))
+        }
+    }
+
+    impl<E: Pairing> CanonicalDeserialize for ProofSpec<E> {
+        fn deserialize_with_mode<R: Read>(
+            mut reader: R,
+            compress: Compress,
+            validate: Validate,
+        ) -> Result<Self, SerializationError> {
+            let statements =
+                CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+            let meta_statements =
+                CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+            let setup_params =
+                CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+            let flags = u8::deserialize_with_mode(&mut reader, compress, validate)?;
+            if flags > 3 {
+                return Err(SerializationError::InvalidData);
+            }
+            let context = if flags & 1 == 1 {
+                Some(CanonicalDeserialize::deserialize_with_mode(
+                    &mut reader,
+                    compress,
+                    validate,
+                )?)
+            } else {
+                None
+            };
+            let aggregate_groth16 =
+                CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+            let aggregate_legogroth16 =
+                CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+            let snark_aggregation_srs =
+                CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+            let contexts = if flags & 2 == 2 {
+                Some(CanonicalDeserialize::deserialize_with_mode(
+                    &mut reader,
+                    compress,
+                    validate,
+                )?)
+            } else {
+                None
+            };
+            Ok(Self {
+                statements,
+                meta_statements,
+                setup_params,
+                context,
+                contexts,
+                aggregate_groth16,
+                aggregate_legogroth16,
+                snark_aggregation_srs,
+            })
+        }
+    }
+
     impl<E: Pairing> Valid for SnarkpackSRS<E> {
         fn check(&self) -> Result<(), SerializationError> {
             match self {
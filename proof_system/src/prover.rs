@@ -2,12 +2,11 @@
 
 use crate::{
     constants::{
-        BBDT16_KVAC_LABEL, BBS_23_LABEL, BBS_PLUS_LABEL, COMPOSITE_PROOF_CHALLENGE_LABEL,
-        COMPOSITE_PROOF_LABEL, CONTEXT_LABEL, KB_POS_ACCUM_CDH_MEM_LABEL, KB_POS_ACCUM_MEM_LABEL,
-        KB_UNI_ACCUM_CDH_MEM_LABEL, KB_UNI_ACCUM_CDH_NON_MEM_LABEL, KB_UNI_ACCUM_MEM_LABEL,
-        KB_UNI_ACCUM_NON_MEM_LABEL, NONCE_LABEL, PS_LABEL, VB_ACCUM_CDH_MEM_LABEL,
-        VB_ACCUM_CDH_NON_MEM_LABEL, VB_ACCUM_MEM_LABEL, VB_ACCUM_NON_MEM_LABEL, VE_TZ_21_LABEL,
-        VE_TZ_21_ROBUST_LABEL,
+        BBDT16_KVAC_LABEL, BBS_23_LABEL, BBS_PLUS_LABEL, KB_POS_ACCUM_CDH_MEM_LABEL,
+        KB_POS_ACCUM_MEM_LABEL, KB_UNI_ACCUM_CDH_MEM_LABEL, KB_UNI_ACCUM_CDH_NON_MEM_LABEL,
+        KB_UNI_ACCUM_MEM_AND_NON_MEM_LABEL, KB_UNI_ACCUM_MEM_LABEL, KB_UNI_ACCUM_NON_MEM_LABEL,
+        PS_LABEL, VB_ACCUM_CDH_MEM_LABEL, VB_ACCUM_CDH_NON_MEM_LABEL, VB_ACCUM_MEM_LABEL,
+        VB_ACCUM_NON_MEM_LABEL, VE_TZ_21_LABEL, VE_TZ_21_ROBUST_LABEL,
     },
     error::ProofSystemError,
     meta_statement::{EqualWitnesses, WitnessRef},
@@ -30,6 +29,7 @@ use crate::{
                 VBAccumulatorMembershipKVSubProtocol,
             },
             KBPositiveAccumulatorMembershipSubProtocol,
+            KBUniversalAccumulatorMembershipAndNonMembershipSubProtocol,
             KBUniversalAccumulatorMembershipSubProtocol,
             KBUniversalAccumulatorNonMembershipSubProtocol, VBAccumulatorMembershipSubProtocol,
             VBAccumulatorNonMembershipSubProtocol,
@@ -37,26 +37,31 @@ use crate::{
         bbdt16_kvac::PoKOfMACSubProtocol,
         bbs_23::PoKBBSSigG1SubProtocol,
         bbs_23_ietf::PoKBBSSigIETFG1SubProtocol,
-        bbs_plus::PoKBBSSigG1SubProtocol as PoKBBSPlusSigG1SubProtocol,
+        bbs_plus::{
+            reveals_all_messages, PoKBBSSigG1SubProtocol as PoKBBSPlusSigG1SubProtocol,
+            RevealedBBSSigG1SubProtocol,
+        },
         bound_check_bpp::BoundCheckBppProtocol,
         bound_check_legogroth16::BoundCheckLegoGrothProtocol,
         bound_check_smc::BoundCheckSmcProtocol,
         bound_check_smc_with_kv::BoundCheckSmcWithKVProtocol,
-        inequality::InequalityProtocol,
+        inequality::{HiddenInequalityProtocol, InequalityProtocol},
         ps_signature::PSSignaturePoK,
+        public_set_membership::PublicSetMembershipProtocol,
         r1cs_legogorth16::R1CSLegogroth16Protocol,
         saver::SaverProtocol,
         schnorr::SchnorrProtocol,
         verifiable_encryption_tz_21::VeTZ21Protocol,
         SubProtocol,
     },
+    transcript::{CompositeTranscriptBuilder, DeferredTranscript, ProofTranscript},
     witness::{Witness, Witnesses},
 };
 use ark_ec::pairing::Pairing;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{
     collections::{BTreeMap, BTreeSet},
-    format,
+    format, mem,
     rand::RngCore,
     vec,
     vec::Vec,
@@ -64,14 +69,20 @@ use ark_std::{
 };
 use digest::Digest;
 use dock_crypto_utils::{
-    aliases::FullDigest,
-    expect_equality,
-    hashing_utils::field_elem_from_try_and_incr,
+    aliases::FullDigest, expect_equality, hashing_utils::field_elem_from_try_and_incr,
     signature::MultiMessageSignatureParams,
-    transcript::{MerlinTranscript, Transcript},
 };
 use legogroth16::aggregation::srs::PreparedProverSRS;
 use saver::encryption::Ciphertext;
+use vb_accumulator::{
+    kb_positive_accumulator::KBPositiveAccumulator,
+    prelude::{Accumulator, PositiveAccumulator, UniversalAccumulator},
+};
+
+#[cfg(feature = "parallel")]
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 /// The SAVER randomness, ciphertext and proof to reuse when creating the composite proof. This is more
 /// efficient than generating a new ciphertext and proof.
@@ -91,6 +102,40 @@ pub struct OldLegoGroth16Proof<E: Pairing>(pub E::ScalarField, pub legogroth16::
 pub struct ProverConfig<E: Pairing> {
     pub reuse_saver_proofs: Option<BTreeMap<usize, OldSaverProof<E>>>,
     pub reuse_legogroth16_proofs: Option<BTreeMap<usize, OldLegoGroth16Proof<E>>>,
+    /// Application specific domain separator for the proof's transcript so that proofs created for one
+    /// application don't verify in another. The verifier must use the same label.
+    pub transcript_label: Option<Vec<u8>>,
+    /// If set, check that each accumulator (non)membership witness verifies against the statement's
+    /// accumulator value before creating the proof and fail with `ProofSystemError::InvalidWitnessForStatement`
+    /// if it doesn't. Without this, a wrong witness results in a proof that fails verification. Only
+    /// statements that have the accumulator public key are checked so the keyed-verification statements and
+    /// the CDH ones other than for KB positive accumulator aren't.
+    pub validate_witnesses: bool,
+    /// Extra data to add to the proof's transcript. The verifier must use a builder with the same data.
+    pub transcript_builder: CompositeTranscriptBuilder,
+    /// If set, the sub-protocols of the statements are initialized, i.e. their commitments to randomness
+    /// are created, in parallel with each statement using its own RNG seeded from the given one. Only this
+    /// commitment phase is parallel, the responses are created one statement after another as some
+    /// sub-protocols, like Bulletproofs++, keep using the transcript after the challenge is generated.
+    /// Has no effect without the `parallel` feature.
+    pub init_in_parallel: bool,
+    /// Blindings to use for specific witnesses, keyed by the witness reference, rather than random ones.
+    /// If the witness is part of a witness equality, the blinding is used for all witnesses of that
+    /// equality. Meant for linking a witness of this proof to a Schnorr protocol run outside this crate
+    /// with the same challenge as the response of the witness will be `blinding + challenge * witness`.
+    ///
+    /// The blinding is as sensitive as the witness and must not be reused in another proof. Since the
+    /// challenges of 2 proofs differ, anyone seeing both the proofs can compute the witness as
+    /// `(response_1 - response_2) / (challenge_1 - challenge_2)`. To make proofs created at different
+    /// times linkable, reveal a pseudonym of the witness, like a Pedersen commitment to it with a fixed
+    /// base, instead.
+    pub witness_blindings: Option<BTreeMap<WitnessRef, E::ScalarField>>,
+    /// If set, a BBS+ statement that reveals all the signed messages is proved by sending the signature
+    /// itself rather than a proof of knowledge of it, which is smaller and faster to create and verify.
+    /// The signature is the same in every such proof so unlike the proof of knowledge, it's not bound
+    /// to the nonce and makes the presentations linkable and replayable. The verifier must also set
+    /// `VerifierConfig::reveal_all_fast_path`.
+    pub reveal_all_fast_path: bool,
 }
 
 impl<E: Pairing> Default for ProverConfig<E> {
@@ -98,6 +143,12 @@ impl<E: Pairing> Default for ProverConfig<E> {
         Self {
             reuse_saver_proofs: None,
             reuse_legogroth16_proofs: None,
+            transcript_label: None,
+            validate_witnesses: false,
+            transcript_builder: CompositeTranscriptBuilder::default(),
+            init_in_parallel: false,
+            witness_blindings: None,
+            reveal_all_fast_path: false,
         }
     }
 }
@@ -118,6 +169,16 @@ impl<E: Pairing> ProverConfig<E> {
     }
 }
 
+/// A statement's sub-protocol after initialization along with its contribution to the transcript
+struct InitializedSubProtocol<'a, E: Pairing> {
+    sub_protocol: SubProtocol<'a, E>,
+    /// Randomness of the commitment in SAVER and LegoGroth16 proofs
+    commitment_randomness: Option<E::ScalarField>,
+    transcript: DeferredTranscript,
+    /// Blindings of witness equalities that weren't used by the statement
+    unused_blindings: BTreeMap<WitnessRef, E::ScalarField>,
+}
+
 macro_rules! err_incompat_witness {
     ($s_idx:ident, $s: ident, $witness: ident) => {
         return Err(ProofSystemError::WitnessIncompatibleWithStatement(
@@ -141,8 +202,37 @@ impl<E: Pairing> Proof<E> {
         proof_spec: ProofSpec<E>,
         witnesses: Witnesses<E>,
         nonce: Option<Vec<u8>>,
-        mut config: ProverConfig<E>,
+        config: ProverConfig<E>,
     ) -> Result<(Self, BTreeMap<usize, E::ScalarField>), ProofSystemError> {
+        let (proof, comm_rand, _) = Self::_new::<R, D>(rng, proof_spec, witnesses, nonce, config)?;
+        Ok((proof, comm_rand))
+    }
+
+    /// Same as `Self::new` but also returns the bytes fed to the transcript before the challenge was
+    /// generated. Only meant for debugging a proof that fails to verify by comparing these bytes with the
+    /// ones returned by `Proof::debug_challenge_bytes` for the verifier.
+    #[cfg(feature = "debug-transcript")]
+    #[allow(clippy::type_complexity)]
+    pub fn new_with_debug_challenge_bytes<R: RngCore, D: FullDigest + Digest>(
+        rng: &mut R,
+        proof_spec: ProofSpec<E>,
+        witnesses: Witnesses<E>,
+        nonce: Option<Vec<u8>>,
+        config: ProverConfig<E>,
+    ) -> Result<(Self, BTreeMap<usize, E::ScalarField>, Vec<u8>), ProofSystemError> {
+        let (proof, comm_rand, transcript) =
+            Self::_new::<R, D>(rng, proof_spec, witnesses, nonce, config)?;
+        Ok((proof, comm_rand, transcript.challenge_input()))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn _new<R: RngCore, D: FullDigest + Digest>(
+        rng: &mut R,
+        proof_spec: ProofSpec<E>,
+        witnesses: Witnesses<E>,
+        nonce: Option<Vec<u8>>,
+        mut config: ProverConfig<E>,
+    ) -> Result<(Self, BTreeMap<usize, E::ScalarField>, ProofTranscript), ProofSystemError> {
         proof_spec.validate()?;
 
         // There should be a witness for each statement
@@ -170,6 +260,25 @@ impl<E: Pairing> Proof<E> {
             }
         }
 
+        // Use the blindings given by the prover, overriding any random ones
+        if let Some(witness_blindings) = config.witness_blindings.take() {
+            let mut fixed = BTreeMap::<WitnessRef, E::ScalarField>::new();
+            for (wr, blinding) in witness_blindings {
+                let wit_refs = match disjoint_equalities.iter().find(|eq| eq.has_wit_ref(&wr)) {
+                    Some(eq) => eq.0.iter().cloned().collect::<Vec<_>>(),
+                    None => vec![wr],
+                };
+                for w in wit_refs {
+                    if let Some(b) = fixed.insert(w, blinding) {
+                        if b != blinding {
+                            return Err(ProofSystemError::ConflictingWitnessBlindings(wr));
+                        }
+                    }
+                    blindings.insert(w, blinding);
+                }
+            }
+        }
+
         // Prepare commitment keys for running Schnorr protocols of all statements.
         let (
             bound_check_lego_comm,
@@ -181,131 +290,193 @@ impl<E: Pairing> Proof<E> {
             ineq_comm,
         ) = proof_spec.derive_commitment_keys()?;
 
-        let mut sub_protocols = Vec::<SubProtocol<E>>::with_capacity(proof_spec.statements.0.len());
-
         // Randomness used by SAVER and LegoGroth16 proofs. This is tracked and returned so subsequent proofs for
         // the same public params and witness can reuse this randomness
         let mut commitment_randomness = BTreeMap::<usize, E::ScalarField>::new();
 
-        let mut transcript = MerlinTranscript::new(COMPOSITE_PROOF_LABEL);
-        if let Some(n) = nonce.as_ref() {
-            transcript.append_message(NONCE_LABEL, n);
-        }
-        if let Some(ctx) = &proof_spec.context {
-            transcript.append_message(CONTEXT_LABEL, ctx);
-        }
+        let mut transcript = config.transcript_builder.start(
+            config.transcript_label.as_ref(),
+            nonce.as_ref(),
+            &proof_spec,
+        );
 
-        macro_rules! accum_protocol_init {
-            ($s: ident, $s_idx: ident, $w: ident, $protocol: ident, $protocol_variant: ident, $label: ident) => {{
-                let blinding = blindings.remove(&($s_idx, 0));
-                let params = $s.get_params(&proof_spec.setup_params, $s_idx)?;
-                let pk = $s.get_public_key(&proof_spec.setup_params, $s_idx)?;
-                let prk = $s.get_proving_key(&proof_spec.setup_params, $s_idx)?;
-                let mut sp = $protocol::new($s_idx, params, pk, prk, $s.accumulator_value);
-                sp.init(rng, blinding, $w)?;
-                transcript.set_label($label);
-                sp.challenge_contribution(&mut transcript)?;
-                sub_protocols.push(SubProtocol::$protocol_variant(sp));
-            }};
+        // Give each statement the blindings of its witnesses. Blindings for statements that don't exist are
+        // left in `blindings` and result in an error below.
+        let mut statement_blindings = (0..proof_spec.statements.len())
+            .map(|_| BTreeMap::new())
+            .collect::<Vec<_>>();
+        for (wr, b) in mem::take(&mut blindings) {
+            match statement_blindings.get_mut(wr.0) {
+                Some(sb) => sb.insert(wr, b),
+                None => blindings.insert(wr, b),
+            };
         }
+        let inputs = witnesses
+            .0
+            .into_iter()
+            .zip(statement_blindings)
+            .enumerate()
+            .map(|(s_idx, (w, b))| {
+                (
+                    s_idx,
+                    w,
+                    b,
+                    config.get_saver_proof(&s_idx),
+                    config.get_legogroth16_proof(&s_idx),
+                )
+            })
+            .collect::<Vec<_>>();
 
-        macro_rules! sig_protocol_init {
-            ($s: ident, $s_idx: ident, $w: ident, $protocol: ident, $func_name: ident, $protocol_variant: ident, $label: ident) => {{
-                // Prepare blindings for this signature proof
-                let blindings_map = build_blindings_map::<E>(
-                    &mut blindings,
-                    $s_idx,
-                    $w.unrevealed_messages.keys().cloned(),
-                );
-                let sig_params = $s.get_params(&proof_spec.setup_params, $s_idx)?;
-                let mut sp = $protocol::$func_name($s_idx, &$s.revealed_messages, sig_params);
-                sp.init(rng, blindings_map, $w)?;
-                transcript.set_label($label);
-                sp.challenge_contribution(&mut transcript)?;
-                sub_protocols.push(SubProtocol::$protocol_variant(sp));
-            }};
-        }
+        // Initialize the sub-protocol of the statement at index `s_idx`. `blindings` contains the blindings of
+        // this statement's witnesses that are part of a witness equality. The sub-protocol's contribution to
+        // the transcript is returned rather than added to `transcript` so that the statements can be
+        // initialized independently of each other.
+        let init_sub_protocol = |s_idx: usize,
+                                 witness: Witness<E>,
+                                 mut rng: &mut dyn RngCore,
+                                 mut blindings: BTreeMap<WitnessRef, E::ScalarField>,
+                                 old_saver_proof: Option<OldSaverProof<E>>,
+                                 old_legogroth16_proof: Option<OldLegoGroth16Proof<E>>|
+         -> Result<InitializedSubProtocol<E>, ProofSystemError> {
+            let rng = &mut rng;
+            let statement = &proof_spec.statements.0[s_idx];
+            let mut transcript = DeferredTranscript::default();
+            let mut commitment_randomness = None;
 
-        macro_rules! ped_comm_protocol_init {
-            ($s: ident, $s_idx: ident, $w: ident, $cm_key_func: ident, $protocol_variant: ident) => {{
-                let blindings_map = build_blindings_map::<E>(&mut blindings, $s_idx, 0..$w.len());
-                let comm_key = $s.$cm_key_func(&proof_spec.setup_params, $s_idx)?;
-                let mut sp = SchnorrProtocol::new($s_idx, comm_key, $s.commitment);
-                sp.init(rng, blindings_map, $w)?;
-                sp.challenge_contribution(&mut transcript)?;
-                sub_protocols.push(SubProtocol::$protocol_variant(sp));
-            }};
-        }
+            macro_rules! accum_protocol_init {
+                ($s: ident, $s_idx: ident, $w: ident, $protocol: ident, $protocol_variant: ident, $label: ident) => {{
+                    let blinding = blindings.remove(&($s_idx, 0));
+                    let params = $s.get_params(&proof_spec.setup_params, $s_idx)?;
+                    let pk = $s.get_public_key(&proof_spec.setup_params, $s_idx)?;
+                    let prk = $s.get_proving_key(&proof_spec.setup_params, $s_idx)?;
+                    let mut sp = $protocol::new($s_idx, params, pk, prk, $s.accumulator_value);
+                    sp.init(rng, blinding, $w)?;
+                    transcript.set_label($label);
+                    $s.accumulator_id_contribution(&mut transcript);
+                    sp.challenge_contribution(&mut transcript)?;
+                    SubProtocol::$protocol_variant(sp)
+                }};
+            }
 
-        macro_rules! accum_kv_protocol_init {
-            ($s: ident, $s_idx: ident, $w: ident, $protocol: ident, $protocol_variant: ident, $label: ident) => {{
-                let blinding = blindings.remove(&($s_idx, 0));
-                let mut sp = $protocol::new($s_idx, $s.accumulator_value);
-                sp.init(rng, blinding, $w)?;
-                transcript.set_label($label);
-                sp.challenge_contribution(&mut transcript)?;
-                sub_protocols.push(SubProtocol::$protocol_variant(sp));
-            }};
-        }
+            // When enabled in the config, fail early if the witness doesn't verify against the statement.
+            // `$is_valid` is only evaluated when the check is enabled.
+            macro_rules! validate_witness {
+                ($s_idx: ident, $is_valid: expr) => {
+                    if config.validate_witnesses && !$is_valid {
+                        return Err(ProofSystemError::InvalidWitnessForStatement($s_idx));
+                    }
+                };
+            }
 
-        macro_rules! ve_tz_21_init {
-            ($rng: ident, $s_idx: ident, $s: ident, $w: ident, $init_name: ident, $label: ident) => {{
-                let witness_count = $w.len();
-                let comm_key = $s.get_comm_key(&proof_spec.setup_params, $s_idx)?;
-                // +1 since commitment includes randomness as well to make it perfectly hiding
-                if comm_key.len() < (witness_count + 1) {
-                    return Err(ProofSystemError::IncompatiblePedCommSetupParamAtIndex(
+            macro_rules! sig_protocol_init {
+                ($s: ident, $s_idx: ident, $w: ident, $protocol: ident, $func_name: ident, $protocol_variant: ident, $label: ident) => {{
+                    // Prepare blindings for this signature proof
+                    let blindings_map = build_blindings_map::<E>(
+                        &mut blindings,
                         $s_idx,
-                    ));
-                }
-                // Get blindings for all the witnesses
-                let mut b = Vec::with_capacity(witness_count);
-                for i in 0..witness_count {
-                    if let Some(blinding) = blindings.remove(&($s_idx, i)) {
-                        b.push(blinding);
-                    } else {
-                        return Err(ProofSystemError::MissingBlindingForStatementAtIndex(
-                            $s_idx, i,
+                        $w.unrevealed_messages.keys().cloned(),
+                    );
+                    let sig_params = $s.get_params(&proof_spec.setup_params, $s_idx)?;
+                    let mut sp = $protocol::$func_name($s_idx, &$s.revealed_messages, sig_params);
+                    sp.init(rng, blindings_map, $w)?;
+                    transcript.set_label($label);
+                    sp.challenge_contribution(&mut transcript)?;
+                    SubProtocol::$protocol_variant(sp)
+                }};
+            }
+
+            macro_rules! ped_comm_protocol_init {
+                ($s: ident, $s_idx: ident, $w: ident, $cm_key_func: ident, $protocol_variant: ident) => {{
+                    let blindings_map =
+                        build_blindings_map::<E>(&mut blindings, $s_idx, 0..$w.len());
+                    let comm_key = $s.$cm_key_func(&proof_spec.setup_params, $s_idx)?;
+                    let mut sp = SchnorrProtocol::new($s_idx, comm_key, $s.commitment);
+                    sp.init(rng, blindings_map, $w)?;
+                    sp.challenge_contribution(&mut transcript)?;
+                    SubProtocol::$protocol_variant(sp)
+                }};
+            }
+
+            macro_rules! accum_kv_protocol_init {
+                ($s: ident, $s_idx: ident, $w: ident, $protocol: ident, $protocol_variant: ident, $label: ident) => {{
+                    let blinding = blindings.remove(&($s_idx, 0));
+                    let mut sp = $protocol::new($s_idx, $s.accumulator_value);
+                    sp.init(rng, blinding, $w)?;
+                    transcript.set_label($label);
+                    sp.challenge_contribution(&mut transcript)?;
+                    SubProtocol::$protocol_variant(sp)
+                }};
+            }
+
+            macro_rules! ve_tz_21_init {
+                ($rng: ident, $s_idx: ident, $s: ident, $w: ident, $init_name: ident, $label: ident) => {{
+                    let witness_count = $w.len();
+                    let comm_key = $s.get_comm_key(&proof_spec.setup_params, $s_idx)?;
+                    // +1 since commitment includes randomness as well to make it perfectly hiding
+                    if comm_key.len() < (witness_count + 1) {
+                        return Err(ProofSystemError::IncompatiblePedCommSetupParamAtIndex(
+                            $s_idx,
                         ));
                     }
-                }
-                let enc_params = $s.get_enc_params(&proof_spec.setup_params, $s_idx)?;
-                let mut sp = VeTZ21Protocol::new($s_idx, comm_key, enc_params);
-                sp.$init_name::<R>($rng, $w, b)?;
-                transcript.set_label($label);
-                sp.challenge_contribution(&mut transcript)?;
-                sub_protocols.push(SubProtocol::VeTZ21(sp));
-            }};
-        }
+                    // Get blindings for all the witnesses
+                    let mut b = Vec::with_capacity(witness_count);
+                    for i in 0..witness_count {
+                        if let Some(blinding) = blindings.remove(&($s_idx, i)) {
+                            b.push(blinding);
+                        } else {
+                            return Err(ProofSystemError::MissingBlindingForStatementAtIndex(
+                                $s_idx, i,
+                            ));
+                        }
+                    }
+                    let enc_params = $s.get_enc_params(&proof_spec.setup_params, $s_idx)?;
+                    let mut sp = VeTZ21Protocol::new($s_idx, comm_key, enc_params);
+                    sp.$init_name($rng, $w, b)?;
+                    transcript.set_label($label);
+                    sp.challenge_contribution(&mut transcript)?;
+                    SubProtocol::VeTZ21(sp)
+                }};
+            }
 
-        /// Build a map of blindings for witnesses of given the statement index. The key is the witness
-        /// index and value is the blinding. Also removes that blinding from the global blindings map
-        /// containing blinding for each witness reference.
-        fn build_blindings_map<E: Pairing>(
-            blindings: &mut BTreeMap<WitnessRef, E::ScalarField>,
-            s_idx: usize,
-            wit_idx: impl Iterator<Item = usize>,
-        ) -> BTreeMap<usize, E::ScalarField> {
-            let mut blindings_map = BTreeMap::new();
-            for k in wit_idx {
-                match blindings.remove(&(s_idx, k)) {
-                    Some(b) => blindings_map.insert(k, b),
-                    None => None,
-                };
+            /// Build a map of blindings for witnesses of given the statement index. The key is the witness
+            /// index and value is the blinding. Also removes that blinding from the statement's blindings map
+            /// containing blinding for each witness reference.
+            fn build_blindings_map<E: Pairing>(
+                blindings: &mut BTreeMap<WitnessRef, E::ScalarField>,
+                s_idx: usize,
+                wit_idx: impl Iterator<Item = usize>,
+            ) -> BTreeMap<usize, E::ScalarField> {
+                let mut blindings_map = BTreeMap::new();
+                for k in wit_idx {
+                    match blindings.remove(&(s_idx, k)) {
+                        Some(b) => blindings_map.insert(k, b),
+                        None => None,
+                    };
+                }
+                blindings_map
             }
-            blindings_map
-        }
 
-        // Initialize sub-protocols for each statement
-        for (s_idx, (statement, witness)) in proof_spec
-            .statements
-            .0
-            .iter()
-            .zip(witnesses.0.into_iter())
-            .enumerate()
-        {
-            match statement {
+            let sub_protocol = match statement {
                 Statement::PoKBBSSignatureG1Prover(s) => match witness {
+                    Witness::PoKBBSSignatureG1(w)
+                        if config.reveal_all_fast_path
+                            && reveals_all_messages::<E>(
+                                &s.revealed_messages,
+                                s.get_params(&proof_spec.setup_params, s_idx)?
+                                    .supported_message_count(),
+                            ) =>
+                    {
+                        let sig_params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let mut sp = RevealedBBSSigG1SubProtocol::new(
+                            s_idx,
+                            &s.revealed_messages,
+                            sig_params,
+                        );
+                        sp.init(w.signature.clone())?;
+                        transcript.set_label(BBS_PLUS_LABEL);
+                        sp.challenge_contribution(&mut transcript)?;
+                        SubProtocol::RevealedBBSSignatureG1(sp)
+                    }
                     Witness::PoKBBSSignatureG1(w) => {
                         sig_protocol_init!(
                             s,
@@ -315,7 +486,7 @@ impl<E: Pairing> Proof<E> {
                             new_for_prover,
                             PoKBBSSignatureG1,
                             BBS_PLUS_LABEL
-                        );
+                        )
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
@@ -329,7 +500,7 @@ impl<E: Pairing> Proof<E> {
                             new_for_prover,
                             PoKBBSSignature23G1,
                             BBS_23_LABEL
-                        );
+                        )
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
@@ -343,12 +514,22 @@ impl<E: Pairing> Proof<E> {
                             new_for_prover,
                             PoKBBSSignature23IETFG1,
                             BBS_23_LABEL
-                        );
+                        )
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
                 Statement::VBAccumulatorMembership(s) => match witness {
                     Witness::VBAccumulatorMembership(w) => {
+                        validate_witness!(
+                            s_idx,
+                            PositiveAccumulator::verify_membership_given_accumulated(
+                                &s.accumulator_value,
+                                &w.element,
+                                &w.witness,
+                                s.get_public_key(&proof_spec.setup_params, s_idx)?,
+                                s.get_params(&proof_spec.setup_params, s_idx)?,
+                            )
+                        );
                         accum_protocol_init!(
                             s,
                             s_idx,
@@ -362,6 +543,16 @@ impl<E: Pairing> Proof<E> {
                 },
                 Statement::VBAccumulatorNonMembership(s) => match witness {
                     Witness::VBAccumulatorNonMembership(w) => {
+                        validate_witness!(
+                            s_idx,
+                            UniversalAccumulator::verify_non_membership_given_accumulated(
+                                &s.accumulator_value,
+                                &w.element,
+                                &w.witness,
+                                s.get_public_key(&proof_spec.setup_params, s_idx)?,
+                                s.get_params(&proof_spec.setup_params, s_idx)?,
+                            )
+                        );
                         accum_protocol_init!(
                             s,
                             s_idx,
@@ -375,6 +566,16 @@ impl<E: Pairing> Proof<E> {
                 },
                 Statement::KBUniversalAccumulatorMembership(s) => match witness {
                     Witness::KBUniAccumulatorMembership(w) => {
+                        validate_witness!(
+                            s_idx,
+                            PositiveAccumulator::verify_membership_given_accumulated(
+                                &s.accumulator_value,
+                                &w.element,
+                                &w.witness.0,
+                                s.get_public_key(&proof_spec.setup_params, s_idx)?,
+                                s.get_params(&proof_spec.setup_params, s_idx)?,
+                            )
+                        );
                         accum_protocol_init!(
                             s,
                             s_idx,
@@ -388,6 +589,16 @@ impl<E: Pairing> Proof<E> {
                 },
                 Statement::KBUniversalAccumulatorNonMembership(s) => match witness {
                     Witness::KBUniAccumulatorNonMembership(w) => {
+                        validate_witness!(
+                            s_idx,
+                            PositiveAccumulator::verify_membership_given_accumulated(
+                                &s.accumulator_value,
+                                &w.element,
+                                &w.witness.0,
+                                s.get_public_key(&proof_spec.setup_params, s_idx)?,
+                                s.get_params(&proof_spec.setup_params, s_idx)?,
+                            )
+                        );
                         accum_protocol_init!(
                             s,
                             s_idx,
@@ -399,6 +610,49 @@ impl<E: Pairing> Proof<E> {
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
+                Statement::KBUniversalAccumulatorMembershipAndNonMembership(s) => match witness {
+                    Witness::KBUniAccumulatorMembershipAndNonMembership(w) => {
+                        if config.validate_witnesses {
+                            let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                            let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                            // Non-membership in KB accumulator is membership in the accumulator of non-members
+                            if !PositiveAccumulator::verify_membership_given_accumulated(
+                                &s.mem_accumulator_value,
+                                &w.membership.element,
+                                &w.membership.witness.0,
+                                pk,
+                                params,
+                            ) || !PositiveAccumulator::verify_membership_given_accumulated(
+                                &s.non_mem_accumulator_value,
+                                &w.non_membership.element,
+                                &w.non_membership.witness.0,
+                                pk,
+                                params,
+                            ) {
+                                return Err(ProofSystemError::InvalidWitnessForStatement(s_idx));
+                            }
+                        }
+                        let mem_blinding = blindings.remove(&(s_idx, 0));
+                        let non_mem_blinding = blindings.remove(&(s_idx, 1));
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                        let mut sp =
+                            KBUniversalAccumulatorMembershipAndNonMembershipSubProtocol::new(
+                                s_idx,
+                                params,
+                                pk,
+                                prk,
+                                s.mem_accumulator_value,
+                                s.non_mem_accumulator_value,
+                            );
+                        sp.init(rng, mem_blinding, non_mem_blinding, w)?;
+                        transcript.set_label(KB_UNI_ACCUM_MEM_AND_NON_MEM_LABEL);
+                        sp.challenge_contribution(&mut transcript)?;
+                        SubProtocol::KBUniversalAccumulatorMembershipAndNonMembership(sp)
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
                 Statement::VBAccumulatorMembershipCDHProver(s) => match witness {
                     Witness::VBAccumulatorMembership(w) => {
                         let blinding = blindings.remove(&(s_idx, 0));
@@ -409,7 +663,7 @@ impl<E: Pairing> Proof<E> {
                         sp.init(rng, blinding, w)?;
                         transcript.set_label(VB_ACCUM_CDH_MEM_LABEL);
                         sp.challenge_contribution(&mut transcript)?;
-                        sub_protocols.push(SubProtocol::VBAccumulatorMembershipCDH(sp));
+                        SubProtocol::VBAccumulatorMembershipCDH(sp)
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
@@ -426,7 +680,7 @@ impl<E: Pairing> Proof<E> {
                         sp.init(rng, blinding, w)?;
                         transcript.set_label(VB_ACCUM_CDH_NON_MEM_LABEL);
                         sp.challenge_contribution(&mut transcript)?;
-                        sub_protocols.push(SubProtocol::VBAccumulatorNonMembershipCDH(sp));
+                        SubProtocol::VBAccumulatorNonMembershipCDH(sp)
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
@@ -440,7 +694,7 @@ impl<E: Pairing> Proof<E> {
                         sp.init(rng, blinding, w)?;
                         transcript.set_label(KB_UNI_ACCUM_CDH_MEM_LABEL);
                         sp.challenge_contribution(&mut transcript)?;
-                        sub_protocols.push(SubProtocol::KBUniversalAccumulatorMembershipCDH(sp));
+                        SubProtocol::KBUniversalAccumulatorMembershipCDH(sp)
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
@@ -455,12 +709,23 @@ impl<E: Pairing> Proof<E> {
                         sp.init(rng, blinding, w)?;
                         transcript.set_label(KB_UNI_ACCUM_CDH_NON_MEM_LABEL);
                         sp.challenge_contribution(&mut transcript)?;
-                        sub_protocols.push(SubProtocol::KBUniversalAccumulatorNonMembershipCDH(sp));
+                        SubProtocol::KBUniversalAccumulatorNonMembershipCDH(sp)
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
                 Statement::KBPositiveAccumulatorMembership(s) => match witness {
                     Witness::KBPosAccumulatorMembership(w) => {
+                        validate_witness!(
+                            s_idx,
+                            KBPositiveAccumulator::from_accumulated(s.accumulator_value)
+                                .verify_membership(
+                                    &w.element,
+                                    &w.witness,
+                                    s.get_public_key(&proof_spec.setup_params, s_idx)?,
+                                    s.get_params(&proof_spec.setup_params, s_idx)?,
+                                )
+                                .is_ok()
+                        );
                         accum_protocol_init!(
                             s,
                             s_idx,
@@ -474,6 +739,17 @@ impl<E: Pairing> Proof<E> {
                 },
                 Statement::KBPositiveAccumulatorMembershipCDH(s) => match witness {
                     Witness::KBPosAccumulatorMembership(w) => {
+                        validate_witness!(
+                            s_idx,
+                            KBPositiveAccumulator::from_accumulated(s.accumulator_value)
+                                .verify_membership(
+                                    &w.element,
+                                    &w.witness,
+                                    s.get_public_key(&proof_spec.setup_params, s_idx)?,
+                                    s.get_params(&proof_spec.setup_params, s_idx)?,
+                                )
+                                .is_ok()
+                        );
                         accum_protocol_init!(
                             s,
                             s_idx,
@@ -487,7 +763,7 @@ impl<E: Pairing> Proof<E> {
                 },
                 Statement::PedersenCommitment(s) => match witness {
                     Witness::PedersenCommitment(w) => {
-                        ped_comm_protocol_init!(s, s_idx, w, get_commitment_key, PoKDiscreteLogs);
+                        ped_comm_protocol_init!(s, s_idx, w, get_commitment_key, PoKDiscreteLogs)
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
@@ -499,7 +775,7 @@ impl<E: Pairing> Proof<E> {
                             w,
                             get_commitment_key_g2,
                             PoKDiscreteLogsG2
-                        );
+                        )
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
@@ -523,7 +799,7 @@ impl<E: Pairing> Proof<E> {
                             pk,
                         );
 
-                        match config.get_saver_proof(&s_idx) {
+                        match old_saver_proof {
                             // Found a proof to reuse.
                             Some(OldSaverProof(v, ct, proof)) => {
                                 sp.init_with_ciphertext_and_proof(
@@ -535,8 +811,7 @@ impl<E: Pairing> Proof<E> {
                                 sp.init(rng, ck_comm_ct, &cc_keys.0, &cc_keys.1, w, blinding)?;
                             }
                         }
-                        commitment_randomness.insert(
-                            s_idx,
+                        commitment_randomness = Some(
                             *sp.sp_ciphertext
                                 .as_ref()
                                 .unwrap()
@@ -548,7 +823,7 @@ impl<E: Pairing> Proof<E> {
                         );
 
                         sp.challenge_contribution(&mut transcript)?;
-                        sub_protocols.push(SubProtocol::Saver(sp));
+                        SubProtocol::Saver(sp)
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
@@ -565,7 +840,7 @@ impl<E: Pairing> Proof<E> {
                             proving_key,
                         );
 
-                        match config.get_legogroth16_proof(&s_idx) {
+                        match old_legogroth16_proof {
                             // Found a proof to reuse.
                             Some(OldLegoGroth16Proof(v, proof)) => sp
                                 .init_with_old_randomness_and_proof(
@@ -574,8 +849,7 @@ impl<E: Pairing> Proof<E> {
                             None => sp.init(rng, comm_key, w, blinding)?,
                         }
 
-                        commitment_randomness.insert(
-                            s_idx,
+                        commitment_randomness = Some(
                             *sp.sp
                                 .as_ref()
                                 .unwrap()
@@ -587,7 +861,7 @@ impl<E: Pairing> Proof<E> {
                         );
 
                         sp.challenge_contribution(&mut transcript)?;
-                        sub_protocols.push(SubProtocol::BoundCheckLegoGroth16(sp));
+                        SubProtocol::BoundCheckLegoGroth16(sp)
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
@@ -604,7 +878,7 @@ impl<E: Pairing> Proof<E> {
                         let comm_key = r1cs_comm_keys.get(s_idx).unwrap();
                         let mut sp = R1CSLegogroth16Protocol::new_for_prover(s_idx, proving_key);
 
-                        match config.get_legogroth16_proof(&s_idx) {
+                        match old_legogroth16_proof {
                             Some(OldLegoGroth16Proof(v, proof)) => sp
                                 .init_with_old_randomness_and_proof(
                                     rng,
@@ -622,8 +896,7 @@ impl<E: Pairing> Proof<E> {
                             }
                         }
 
-                        commitment_randomness.insert(
-                            s_idx,
+                        commitment_randomness = Some(
                             *sp.sp
                                 .as_ref()
                                 .unwrap()
@@ -635,7 +908,7 @@ impl<E: Pairing> Proof<E> {
                         );
 
                         sp.challenge_contribution(&mut transcript)?;
-                        sub_protocols.push(SubProtocol::R1CSLegogroth16Protocol(sp));
+                        SubProtocol::R1CSLegogroth16Protocol(sp)
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
@@ -650,10 +923,10 @@ impl<E: Pairing> Proof<E> {
                         let params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
                         let mut sp = PSSignaturePoK::new(s_idx, &s.revealed_messages, params, pk);
-                        sp.init::<R>(rng, blindings_map, w)?;
+                        sp.init(rng, blindings_map, w)?;
                         transcript.set_label(PS_LABEL);
                         sp.challenge_contribution(&mut transcript)?;
-                        sub_protocols.push(SubProtocol::PSSignaturePoK(sp));
+                        SubProtocol::PSSignaturePoK(sp)
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
@@ -667,7 +940,7 @@ impl<E: Pairing> Proof<E> {
                             BoundCheckBppProtocol::new(s_idx, s.min, s.max, bpp_setup_params);
                         sp.init(rng, comm_key.as_slice(), w, blinding)?;
                         sp.challenge_contribution(&mut transcript)?;
-                        sub_protocols.push(SubProtocol::BoundCheckBpp(sp));
+                        SubProtocol::BoundCheckBpp(sp)
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
@@ -681,7 +954,7 @@ impl<E: Pairing> Proof<E> {
                             BoundCheckSmcProtocol::new(s_idx, s.min, s.max, params_comm_key);
                         sp.init(rng, comm_key_as_slice, w, blinding)?;
                         sp.challenge_contribution(&mut transcript)?;
-                        sub_protocols.push(SubProtocol::BoundCheckSmc(sp));
+                        SubProtocol::BoundCheckSmc(sp)
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
@@ -699,7 +972,7 @@ impl<E: Pairing> Proof<E> {
                         );
                         sp.init(rng, comm_key_as_slice, w, blinding)?;
                         sp.challenge_contribution(&mut transcript)?;
-                        sub_protocols.push(SubProtocol::BoundCheckSmcWithKV(sp));
+                        SubProtocol::BoundCheckSmcWithKV(sp)
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
@@ -711,7 +984,51 @@ impl<E: Pairing> Proof<E> {
                             InequalityProtocol::new(s_idx, s.inequal_to.clone(), &comm_key);
                         sp.init(rng, ineq_comm.get(s_idx).unwrap().as_slice(), w, blinding)?;
                         sp.challenge_contribution(&mut transcript)?;
-                        sub_protocols.push(SubProtocol::Inequality(sp));
+                        SubProtocol::Inequality(sp)
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
+                Statement::HiddenInequality(s) => match witness {
+                    Witness::HiddenInequality(w) => {
+                        let blinding1 = blindings.remove(&(s_idx, 0));
+                        let blinding2 = blindings.remove(&(s_idx, 1));
+                        let comm_key = s.get_comm_key(&proof_spec.setup_params, s_idx)?;
+                        let mut sp = HiddenInequalityProtocol::new(s_idx, comm_key);
+                        sp.init(
+                            rng,
+                            ineq_comm.get(s_idx).unwrap().as_slice(),
+                            w.value1,
+                            w.value2,
+                            blinding1,
+                            blinding2,
+                        )?;
+                        sp.challenge_contribution(&mut transcript)?;
+                        SubProtocol::HiddenInequality(sp)
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
+                Statement::DLEqualToAccumulatorMember(s) => match witness {
+                    Witness::DLEqualToAccumulatorMember(w) => {
+                        let blindings_map = build_blindings_map::<E>(&mut blindings, s_idx, 0..1);
+                        let mut sp = SchnorrProtocol::new(
+                            s_idx,
+                            ark_std::slice::from_ref(&s.base),
+                            s.public_key,
+                        );
+                        sp.init(rng, blindings_map, vec![w])?;
+                        sp.challenge_contribution(&mut transcript)?;
+                        SubProtocol::PoKDiscreteLogs(sp)
+                    }
+                    _ => err_incompat_witness!(s_idx, s, witness),
+                },
+                Statement::PublicSetMembership(s) => match witness {
+                    Witness::PublicSetMembership(w) => {
+                        let blinding = blindings.remove(&(s_idx, 0));
+                        let comm_key = s.get_comm_key(&proof_spec.setup_params, s_idx)?;
+                        let mut sp = PublicSetMembershipProtocol::new(s_idx, &s.set, comm_key);
+                        sp.init(rng, ineq_comm.get(s_idx).unwrap().as_slice(), w, blinding)?;
+                        sp.challenge_contribution(&mut transcript)?;
+                        SubProtocol::PublicSetMembership(sp)
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
@@ -726,7 +1043,7 @@ impl<E: Pairing> Proof<E> {
                         // sp.init(rng, s.accumulator_value, blinding, w)?;
                         // transcript.set_label(VB_ACCUM_MEM_LABEL);
                         // sp.challenge_contribution(&mut transcript)?;
-                        // sub_protocols.push(SubProtocol::DetachedAccumulatorMembership(sp));
+                        // SubProtocol::DetachedAccumulatorMembership(sp)
                         todo!()
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
@@ -743,7 +1060,7 @@ impl<E: Pairing> Proof<E> {
                         // sp.init(rng, s.accumulator_value, blinding, w)?;
                         // transcript.set_label(VB_ACCUM_NON_MEM_LABEL);
                         // sp.challenge_contribution(&mut transcript)?;
-                        // sub_protocols.push(SubProtocol::DetachedAccumulatorNonMembership(sp));
+                        // SubProtocol::DetachedAccumulatorNonMembership(sp)
                         todo!()
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
@@ -758,7 +1075,7 @@ impl<E: Pairing> Proof<E> {
                             new,
                             PoKOfBBDT16MAC,
                             BBDT16_KVAC_LABEL
-                        );
+                        )
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
@@ -771,7 +1088,7 @@ impl<E: Pairing> Proof<E> {
                             VBAccumulatorMembershipKVSubProtocol,
                             VBAccumulatorMembershipKV,
                             VB_ACCUM_MEM_LABEL
-                        );
+                        )
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
@@ -784,7 +1101,7 @@ impl<E: Pairing> Proof<E> {
                             KBUniversalAccumulatorMembershipKVSubProtocol,
                             KBUniversalAccumulatorMembershipKV,
                             KB_UNI_ACCUM_MEM_LABEL
-                        );
+                        )
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
@@ -797,24 +1114,84 @@ impl<E: Pairing> Proof<E> {
                             KBUniversalAccumulatorNonMembershipKVSubProtocol,
                             KBUniversalAccumulatorNonMembershipKV,
                             KB_UNI_ACCUM_NON_MEM_LABEL
-                        );
+                        )
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
                 Statement::VeTZ21(s) => match witness {
                     Witness::VeTZ21(w) => {
-                        ve_tz_21_init!(rng, s_idx, s, w, init, VE_TZ_21_LABEL);
+                        ve_tz_21_init!(rng, s_idx, s, w, init, VE_TZ_21_LABEL)
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
                 Statement::VeTZ21Robust(s) => match witness {
                     Witness::VeTZ21Robust(w) => {
-                        ve_tz_21_init!(rng, s_idx, s, w, init_robust, VE_TZ_21_ROBUST_LABEL);
+                        ve_tz_21_init!(rng, s_idx, s, w, init_robust, VE_TZ_21_ROBUST_LABEL)
                     }
                     _ => err_incompat_witness!(s_idx, s, witness),
                 },
                 _ => return Err(ProofSystemError::InvalidStatement),
+            };
+            if transcript.challenge_requested() {
+                return Err(ProofSystemError::ChallengeRequestedDuringInit(s_idx));
+            }
+            Ok(InitializedSubProtocol {
+                sub_protocol,
+                commitment_randomness,
+                transcript,
+                unused_blindings: blindings,
+            })
+        };
+
+        #[cfg(feature = "parallel")]
+        let initialized = if config.init_in_parallel {
+            // `rng` can't be shared across threads so each statement gets its own RNG seeded from `rng`
+            let seeds = (0..inputs.len())
+                .map(|_| {
+                    let mut seed = <StdRng as SeedableRng>::Seed::default();
+                    rng.fill_bytes(&mut seed);
+                    seed
+                })
+                .collect::<Vec<_>>();
+            inputs
+                .into_par_iter()
+                .zip(seeds)
+                .map(|((s_idx, w, b, old_saver, old_lego), seed)| {
+                    init_sub_protocol(
+                        s_idx,
+                        w,
+                        &mut StdRng::from_seed(seed),
+                        b,
+                        old_saver,
+                        old_lego,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        } else {
+            inputs
+                .into_iter()
+                .map(|(s_idx, w, b, old_saver, old_lego)| {
+                    init_sub_protocol(s_idx, w, rng, b, old_saver, old_lego)
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        };
+        #[cfg(not(feature = "parallel"))]
+        let initialized = inputs
+            .into_iter()
+            .map(|(s_idx, w, b, old_saver, old_lego)| {
+                init_sub_protocol(s_idx, w, rng, b, old_saver, old_lego)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Add the statements' contributions to the transcript in the order of the statements
+        let mut sub_protocols = Vec::with_capacity(initialized.len());
+        for (s_idx, init) in initialized.into_iter().enumerate() {
+            init.transcript.apply_to(&mut transcript);
+            if let Some(r) = init.commitment_randomness {
+                commitment_randomness.insert(s_idx, r);
             }
+            blindings.extend(init.unused_blindings);
+            sub_protocols.push(init.sub_protocol);
         }
 
         // If all blindings are not consumed, it means that there was some witness equality which was
@@ -826,7 +1203,7 @@ impl<E: Pairing> Proof<E> {
         }
 
         // Generate the challenge
-        let challenge = transcript.challenge_scalar(COMPOSITE_PROOF_CHALLENGE_LABEL);
+        let challenge = config.transcript_builder.challenge(&mut transcript);
 
         // Get each sub-protocol's proof
         let mut statement_proofs = Vec::with_capacity(sub_protocols.len());
@@ -959,6 +1336,11 @@ impl<E: Pairing> Proof<E> {
                     sp.gen_proof_contribution(&challenge)?
                 }
                 SubProtocol::Inequality(mut sp) => sp.gen_proof_contribution(&challenge)?,
+                SubProtocol::HiddenInequality(mut sp) => sp.gen_proof_contribution(&challenge)?,
+                SubProtocol::PublicSetMembership(mut sp) => {
+                    sp.gen_proof_contribution(&challenge)?
+                }
+                SubProtocol::RevealedBBSSignatureG1(mut sp) => sp.gen_proof_contribution()?,
                 SubProtocol::DetachedAccumulatorMembership(mut _sp) => {
                     // sp.gen_proof_contribution(rng, &challenge)?
                     todo!()
@@ -973,6 +1355,9 @@ impl<E: Pairing> Proof<E> {
                 SubProtocol::KBUniversalAccumulatorNonMembership(mut sp) => {
                     sp.gen_proof_contribution(&challenge)?
                 }
+                SubProtocol::KBUniversalAccumulatorMembershipAndNonMembership(mut sp) => {
+                    sp.gen_proof_contribution(&challenge)?
+                }
                 SubProtocol::VBAccumulatorMembershipCDH(mut sp) => {
                     sp.gen_proof_contribution(&challenge)?
                 }
@@ -1050,7 +1435,7 @@ impl<E: Pairing> Proof<E> {
 
             let srs = match proof_spec.snark_aggregation_srs {
                 Some(SnarkpackSRS::ProverSrs(srs)) => srs,
-                _ => return Err(ProofSystemError::SnarckpackSrsNotProvided),
+                _ => return Err(ProofSystemError::SnarkpackSrsNotProvided),
             };
             let prepared_srs = PreparedProverSRS::from(srs);
 
@@ -1120,6 +1505,7 @@ impl<E: Pairing> Proof<E> {
                 },
             },
             commitment_randomness,
+            transcript,
         ))
     }
 
@@ -1133,6 +1519,11 @@ impl<E: Pairing> Proof<E> {
         &self.statement_proofs
     }
 
+    /// Iterate over the statement proofs along with the index of the statement each proof is for
+    pub fn statement_proofs_iter(&self) -> impl Iterator<Item = (usize, &StatementProof<E>)> {
+        self.statement_proofs.iter().enumerate()
+    }
+
     /// Hash bytes to a field element. This is vulnerable to timing attack and is only used when input
     /// is public anyway like when generating setup parameters or challenge
     pub fn generate_challenge_from_bytes<D: Digest>(bytes: &[u8]) -> E::ScalarField {
@@ -1163,7 +1554,7 @@ impl<E: Pairing> Proof<E> {
 
     /// Get indices of witnesses of statement index `s_id` for which response should not be generated since it already exists.
     /// If the response didn't exists, then the given `resp_generated` will be updated
-    fn get_responses_to_skip(
+    pub(crate) fn get_responses_to_skip(
         s_id: usize,
         total_msgs: usize,
         disjoint_equalities: &[EqualWitnesses],
@@ -217,6 +217,54 @@ macro_rules! extract_param {
     }};
 }
 
+impl<E: Pairing> SetupParams<E> {
+    /// Wrap a proving key created with `vb_accumulator` for proving membership in a VB accumulator so that
+    /// accumulator statements can refer to it by its index in the setup params
+    pub fn from_vb_accumulator_mem_proving_key(
+        proving_key: MembershipProvingKey<E::G1Affine>,
+    ) -> Self {
+        Self::VbAccumulatorMemProvingKey(proving_key)
+    }
+
+    /// Wrap a proving key created with `vb_accumulator` for proving non-membership in a VB accumulator so that
+    /// accumulator statements can refer to it by its index in the setup params
+    pub fn from_vb_accumulator_non_mem_proving_key(
+        proving_key: NonMembershipProvingKey<E::G1Affine>,
+    ) -> Self {
+        Self::VbAccumulatorNonMemProvingKey(proving_key)
+    }
+
+    /// Get the proving key for membership in a VB accumulator if these are `SetupParams::VbAccumulatorMemProvingKey`
+    pub fn as_vb_accumulator_mem_proving_key(&self) -> Option<&MembershipProvingKey<E::G1Affine>> {
+        match self {
+            Self::VbAccumulatorMemProvingKey(k) => Some(k),
+            _ => None,
+        }
+    }
+
+    /// Get the proving key for non-membership in a VB accumulator if these are `SetupParams::VbAccumulatorNonMemProvingKey`
+    pub fn as_vb_accumulator_non_mem_proving_key(
+        &self,
+    ) -> Option<&NonMembershipProvingKey<E::G1Affine>> {
+        match self {
+            Self::VbAccumulatorNonMemProvingKey(k) => Some(k),
+            _ => None,
+        }
+    }
+}
+
+impl<E: Pairing> From<MembershipProvingKey<E::G1Affine>> for SetupParams<E> {
+    fn from(proving_key: MembershipProvingKey<E::G1Affine>) -> Self {
+        Self::from_vb_accumulator_mem_proving_key(proving_key)
+    }
+}
+
+impl<E: Pairing> From<NonMembershipProvingKey<E::G1Affine>> for SetupParams<E> {
+    fn from(proving_key: NonMembershipProvingKey<E::G1Affine>) -> Self {
+        Self::from_vb_accumulator_non_mem_proving_key(proving_key)
+    }
+}
+
 /// Elgamal encryption parameters generated by the decryptor
 #[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
 #[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
@@ -44,12 +44,14 @@ macro_rules! impl_cdh_struct_and_funcs {
         }
 
         impl<E: Pairing> $prover_name<E> {
+            /// The proof isn't bound to an accumulator version as this statement has no `accumulator_id`.
             pub fn new(accumulator_value: E::G1Affine) -> Statement<E> {
                 Statement::$prover_statement_type(Self { accumulator_value })
             }
         }
 
         impl<E: Pairing> $verifier_name<E> {
+            /// The proof isn't bound to an accumulator version as this statement has no `accumulator_id`.
             pub fn new_statement_from_params(
                 params: AccumParams<E>,
                 public_key: PublicKey<E>,
@@ -64,6 +66,7 @@ macro_rules! impl_cdh_struct_and_funcs {
                 })
             }
 
+            /// The proof isn't bound to an accumulator version as this statement has no `accumulator_id`.
             pub fn new_statement_from_params_ref(
                 params_ref: usize,
                 public_key_ref: usize,
@@ -141,6 +144,7 @@ pub struct VBAccumulatorNonMembershipCDHVerifier<E: Pairing> {
 }
 
 impl<E: Pairing> VBAccumulatorNonMembershipCDHProver<E> {
+    /// The proof isn't bound to an accumulator version as this statement has no `accumulator_id`.
     pub fn new_statement_from_params(
         accumulator_value: E::G1Affine,
         Q: E::G1Affine,
@@ -154,6 +158,7 @@ impl<E: Pairing> VBAccumulatorNonMembershipCDHProver<E> {
         })
     }
 
+    /// The proof isn't bound to an accumulator version as this statement has no `accumulator_id`.
     pub fn new_statement_from_params_ref(
         params_ref: usize,
         accumulator_value: E::G1Affine,
@@ -184,6 +189,7 @@ impl<E: Pairing> VBAccumulatorNonMembershipCDHProver<E> {
 }
 
 impl<E: Pairing> VBAccumulatorNonMembershipCDHVerifier<E> {
+    /// The proof isn't bound to an accumulator version as this statement has no `accumulator_id`.
     pub fn new_statement_from_params(
         params: AccumParams<E>,
         public_key: PublicKey<E>,
@@ -200,6 +206,7 @@ impl<E: Pairing> VBAccumulatorNonMembershipCDHVerifier<E> {
         })
     }
 
+    /// The proof isn't bound to an accumulator version as this statement has no `accumulator_id`.
     pub fn new_statement_from_params_ref(
         params_ref: usize,
         public_key_ref: usize,
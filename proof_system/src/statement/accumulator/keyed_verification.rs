@@ -33,12 +33,14 @@ macro_rules! impl_struct_and_funcs {
         }
 
         impl<G: AffineRepr> $name<G> {
+            /// The proof isn't bound to an accumulator version as this statement has no `accumulator_id`.
             pub fn new<E: Pairing<G1Affine = G>>(accumulator_value: G) -> Statement<E> {
                 Statement::$stmt_variant(Self { accumulator_value })
             }
         }
 
         impl<G: AffineRepr> $name_full_verifier<G> {
+            /// The proof isn't bound to an accumulator version as this statement has no `accumulator_id`.
             pub fn new<E: Pairing<G1Affine = G>>(
                 accumulator_value: G,
                 secret_key: SecretKey<G::ScalarField>,
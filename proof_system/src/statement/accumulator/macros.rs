@@ -60,7 +60,7 @@ macro_rules! impl_struct_and_funcs {
     ($(#[$doc:meta])*
     $name:ident, $param_type: ident, $param_variant: ident, $pk_type: ident, $pk_variant: ident, $statement_variant:ident, $prk_type:ident, $prk_variant:ident) => {
         #[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
-        #[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+        #[derive(Clone, Debug, PartialEq, Eq)]
         #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
         #[cfg_attr(feature = "serde", serde(bound = ""))]
         pub struct $name<E: Pairing> {
@@ -72,6 +72,12 @@ macro_rules! impl_struct_and_funcs {
             pub params_ref: Option<usize>,
             pub public_key_ref: Option<usize>,
             pub proving_key_ref: Option<usize>,
+            /// Identifies the version (epoch) of the accumulator whose value is `accumulator_value`. When set,
+            /// it's added to the proof's challenge so a proof created for one version fails to verify for another.
+            /// Only statements with a proving key have this. The CDH statements (other than for the KB positive
+            /// accumulator) and the keyed-verification statements can't bind their proofs to an accumulator version.
+            #[cfg_attr(feature = "serde", serde(default))]
+            pub accumulator_id: Option<Vec<u8>>,
         }
 
         impl<E: Pairing> $name<E> {
@@ -90,6 +96,7 @@ macro_rules! impl_struct_and_funcs {
                     params_ref: None,
                     public_key_ref: None,
                     proving_key_ref: None,
+                    accumulator_id: None,
                 })
             }
 
@@ -108,9 +115,64 @@ macro_rules! impl_struct_and_funcs {
                     params_ref: Some(params_ref),
                     public_key_ref: Some(public_key_ref),
                     proving_key_ref: Some(proving_key_ref),
+                    accumulator_id: None,
                 })
             }
 
+            /// Same as `Self::new_statement_from_params` but binds the proof to the accumulator version `accumulator_id`.
+            /// Most CDH statements and the keyed-verification statements have no counterpart, see `Self::accumulator_id`.
+            pub fn new_statement_from_params_with_accumulator_id(
+                params: $param_type<E>,
+                public_key: $pk_type<E>,
+                proving_key: $prk_type<E::G1Affine>,
+                accumulator_value: E::G1Affine,
+                accumulator_id: Vec<u8>,
+            ) -> Statement<E> {
+                Statement::$statement_variant(Self {
+                    accumulator_value,
+                    params: Some(params),
+                    public_key: Some(public_key),
+                    proving_key: Some(proving_key),
+                    params_ref: None,
+                    public_key_ref: None,
+                    proving_key_ref: None,
+                    accumulator_id: Some(accumulator_id),
+                })
+            }
+
+            /// Same as `Self::new_statement_from_params_ref` but binds the proof to the accumulator version `accumulator_id`.
+            /// Most CDH statements and the keyed-verification statements have no counterpart, see `Self::accumulator_id`.
+            pub fn new_statement_from_params_ref_with_accumulator_id(
+                params_ref: usize,
+                public_key_ref: usize,
+                proving_key_ref: usize,
+                accumulator_value: E::G1Affine,
+                accumulator_id: Vec<u8>,
+            ) -> Statement<E> {
+                Statement::$statement_variant(Self {
+                    accumulator_value,
+                    params: None,
+                    public_key: None,
+                    proving_key: None,
+                    params_ref: Some(params_ref),
+                    public_key_ref: Some(public_key_ref),
+                    proving_key_ref: Some(proving_key_ref),
+                    accumulator_id: Some(accumulator_id),
+                })
+            }
+
+            /// Add the accumulator id, if any, to the transcript used for the challenge
+            pub(crate) fn accumulator_id_contribution<
+                T: dock_crypto_utils::transcript::Transcript,
+            >(
+                &self,
+                transcript: &mut T,
+            ) {
+                if let Some(id) = &self.accumulator_id {
+                    transcript.append_message(crate::constants::ACCUMULATOR_ID_LABEL, id);
+                }
+            }
+
             impl_getters!(
                 $param_type,
                 $param_variant,
@@ -120,5 +182,118 @@ macro_rules! impl_struct_and_funcs {
                 $prk_variant
             );
         }
+
+        impl_serialization_with_accumulator_id!($name);
+    };
+}
+
+/// The byte marking whether `params` is present also has a bit marking whether `accumulator_id` is, which is
+/// then serialized last. So a statement without an accumulator id serializes the same as before the id was added.
+macro_rules! impl_serialization_with_accumulator_id {
+    ($name:ident) => {
+        impl<E: Pairing> ark_serialize::Valid for $name<E> {
+            fn check(&self) -> Result<(), ark_serialize::SerializationError> {
+                ark_serialize::Valid::check(&self.accumulator_value)?;
+                ark_serialize::Valid::check(&self.params)?;
+                ark_serialize::Valid::check(&self.public_key)?;
+                ark_serialize::Valid::check(&self.proving_key)
+            }
+        }
+
+        impl<E: Pairing> CanonicalSerialize for $name<E> {
+            fn serialize_with_mode<W: ark_serialize::Write>(
+                &self,
+                mut writer: W,
+                compress: ark_serialize::Compress,
+            ) -> Result<(), ark_serialize::SerializationError> {
+                self.accumulator_value
+                    .serialize_with_mode(&mut writer, compress)?;
+                let flags =
+                    (self.params.is_some() as u8) | ((self.accumulator_id.is_some() as u8) << 1);
+                flags.serialize_with_mode(&mut writer, compress)?;
+                if let Some(params) = &self.params {
+                    params.serialize_with_mode(&mut writer, compress)?;
+                }
+                self.public_key.serialize_with_mode(&mut writer, compress)?;
+                self.proving_key
+                    .serialize_with_mode(&mut writer, compress)?;
+                self.params_ref.serialize_with_mode(&mut writer, compress)?;
+                self.public_key_ref
+                    .serialize_with_mode(&mut writer, compress)?;
+                self.proving_key_ref
+                    .serialize_with_mode(&mut writer, compress)?;
+                if let Some(id) = &self.accumulator_id {
+                    id.serialize_with_mode(&mut writer, compress)?;
+                }
+                Ok(())
+            }
+
+            fn serialized_size(&self, compress: ark_serialize::Compress) -> usize {
+                self.accumulator_value.serialized_size(compress)
+                    + self.params.serialized_size(compress)
+                    + self.public_key.serialized_size(compress)
+                    + self.proving_key.serialized_size(compress)
+                    + self.params_ref.serialized_size(compress)
+                    + self.public_key_ref.serialized_size(compress)
+                    + self.proving_key_ref.serialized_size(compress)
+                    + self
+                        .accumulator_id
+                        .as_ref()
+                        .map_or(0, |id| id.serialized_size(compress))
+            }
+        }
+
+        impl<E: Pairing> CanonicalDeserialize for $name<E> {
+            fn deserialize_with_mode<R: ark_serialize::Read>(
+                mut reader: R,
+                compress: ark_serialize::Compress,
+                validate: ark_serialize::Validate,
+            ) -> Result<Self, ark_serialize::SerializationError> {
+                let accumulator_value =
+                    CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+                let flags = u8::deserialize_with_mode(&mut reader, compress, validate)?;
+                if flags > 3 {
+                    return Err(ark_serialize::SerializationError::InvalidData);
+                }
+                let params = if flags & 1 == 1 {
+                    Some(CanonicalDeserialize::deserialize_with_mode(
+                        &mut reader,
+                        compress,
+                        validate,
+                    )?)
+                } else {
+                    None
+                };
+                let public_key =
+                    CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+                let proving_key =
+                    CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+                let params_ref =
+                    CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+                let public_key_ref =
+                    CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+                let proving_key_ref =
+                    CanonicalDeserialize::deserialize_with_mode(&mut reader, compress, validate)?;
+                let accumulator_id = if flags & 2 == 2 {
+                    Some(CanonicalDeserialize::deserialize_with_mode(
+                        &mut reader,
+                        compress,
+                        validate,
+                    )?)
+                } else {
+                    None
+                };
+                Ok(Self {
+                    accumulator_value,
+                    params,
+                    public_key,
+                    proving_key,
+                    params_ref,
+                    public_key_ref,
+                    proving_key_ref,
+                    accumulator_id,
+                })
+            }
+        }
     };
 }
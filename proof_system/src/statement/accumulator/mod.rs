@@ -49,6 +49,44 @@ impl_struct_and_funcs!(
     VbAccumulatorNonMemProvingKey
 );
 
+impl<E: Pairing> VBAccumulatorMembership<E> {
+    /// Same as `Self::new_statement_from_params` but first checks that the proving key is not
+    /// degenerate, i.e. `X`, `Y` and `Z` are distinct and none is the identity.
+    pub fn new_statement_from_validated_params(
+        params: AccumParams<E>,
+        public_key: PublicKey<E>,
+        proving_key: MembershipProvingKey<E::G1Affine>,
+        accumulator_value: E::G1Affine,
+    ) -> Result<Statement<E>, ProofSystemError> {
+        proving_key.validate()?;
+        Ok(Self::new_statement_from_params(
+            params,
+            public_key,
+            proving_key,
+            accumulator_value,
+        ))
+    }
+}
+
+impl<E: Pairing> VBAccumulatorNonMembership<E> {
+    /// Same as `Self::new_statement_from_params` but first checks that the proving key is not
+    /// degenerate, i.e. `X`, `Y`, `Z` and `K` are distinct and none is the identity.
+    pub fn new_statement_from_validated_params(
+        params: AccumParams<E>,
+        public_key: PublicKey<E>,
+        proving_key: NonMembershipProvingKey<E::G1Affine>,
+        accumulator_value: E::G1Affine,
+    ) -> Result<Statement<E>, ProofSystemError> {
+        proving_key.validate()?;
+        Ok(Self::new_statement_from_params(
+            params,
+            public_key,
+            proving_key,
+            accumulator_value,
+        ))
+    }
+}
+
 impl_struct_and_funcs!(
     /// Public values like setup params, public key, proving key and accumulator for proving membership
     /// in universal KB accumulator.
@@ -75,6 +113,78 @@ impl_struct_and_funcs!(
     BBSigProvingKey
 );
 
+/// Public values like setup params, public key, proving key and accumulators for proving membership of
+/// one element and non-membership of another in the same universal KB accumulator. The membership and
+/// non-membership accumulator values are those of the same KB accumulator. The member is the witness at
+/// index 0 and the non-member is the witness at index 1.
+#[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct KBUniversalAccumulatorMembershipAndNonMembership<E: Pairing> {
+    #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))]
+    pub mem_accumulator_value: E::G1Affine,
+    #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))]
+    pub non_mem_accumulator_value: E::G1Affine,
+    pub params: Option<AccumParams<E>>,
+    pub public_key: Option<PublicKey<E>>,
+    pub proving_key: Option<ProvingKey<E::G1Affine>>,
+    pub params_ref: Option<usize>,
+    pub public_key_ref: Option<usize>,
+    pub proving_key_ref: Option<usize>,
+}
+
+impl<E: Pairing> KBUniversalAccumulatorMembershipAndNonMembership<E> {
+    /// Create a statement by passing the accumulator params, public key and proving key directly.
+    pub fn new_statement_from_params(
+        params: AccumParams<E>,
+        public_key: PublicKey<E>,
+        proving_key: ProvingKey<E::G1Affine>,
+        mem_accumulator_value: E::G1Affine,
+        non_mem_accumulator_value: E::G1Affine,
+    ) -> Statement<E> {
+        Statement::KBUniversalAccumulatorMembershipAndNonMembership(Self {
+            mem_accumulator_value,
+            non_mem_accumulator_value,
+            params: Some(params),
+            public_key: Some(public_key),
+            proving_key: Some(proving_key),
+            params_ref: None,
+            public_key_ref: None,
+            proving_key_ref: None,
+        })
+    }
+
+    /// Create a statement by passing the indices of accumulator params, public key and proving key in `SetupParams`.
+    pub fn new_statement_from_params_ref(
+        params_ref: usize,
+        public_key_ref: usize,
+        proving_key_ref: usize,
+        mem_accumulator_value: E::G1Affine,
+        non_mem_accumulator_value: E::G1Affine,
+    ) -> Statement<E> {
+        Statement::KBUniversalAccumulatorMembershipAndNonMembership(Self {
+            mem_accumulator_value,
+            non_mem_accumulator_value,
+            params: None,
+            public_key: None,
+            proving_key: None,
+            params_ref: Some(params_ref),
+            public_key_ref: Some(public_key_ref),
+            proving_key_ref: Some(proving_key_ref),
+        })
+    }
+
+    impl_getters!(
+        AccumParams,
+        VbAccumulatorParams,
+        PublicKey,
+        VbAccumulatorPublicKey,
+        ProvingKey,
+        BBSigProvingKey
+    );
+}
+
 impl_struct_and_funcs!(
     /// Public values like setup params, public key, proving key and accumulator for proving membership
     /// in positive KB accumulator.
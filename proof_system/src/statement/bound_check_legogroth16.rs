@@ -30,7 +30,7 @@ pub struct BoundCheckLegoGroth16Prover<E: Pairing> {
 
 /// Proving knowledge of message that satisfies given bounds [min, max), i.e. `min <= message < max` using LegoGroth16
 #[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
-#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(bound = ""))]
 pub struct BoundCheckLegoGroth16Verifier<E: Pairing> {
@@ -39,6 +39,22 @@ pub struct BoundCheckLegoGroth16Verifier<E: Pairing> {
     #[cfg_attr(feature = "serde", serde_as(as = "Option<ArkObjectBytes>"))]
     pub snark_verifying_key: Option<VerifyingKey<E>>,
     pub snark_verifying_key_ref: Option<usize>,
+    /// Prepared form of `snark_verifying_key` which, when present, is used in verification rather than
+    /// preparing `snark_verifying_key` again. Only set by `Self::new_statement_from_prepared_params` which
+    /// takes `snark_verifying_key` from it. It isn't serialized so a deserialized statement never has it.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    snark_prepared_verifying_key: Option<PreparedVerifyingKey<E>>,
+}
+
+/// The prepared verifying key, when present, has the same verifying key as `snark_verifying_key` so it doesn't
+/// need comparing, and can't be as prepared G2 elements don't implement `PartialEq`
+impl<E: Pairing> PartialEq for BoundCheckLegoGroth16Verifier<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.min == other.min
+            && self.max == other.max
+            && self.snark_verifying_key == other.snark_verifying_key
+            && self.snark_verifying_key_ref == other.snark_verifying_key_ref
+    }
 }
 
 impl<E: Pairing> BoundCheckLegoGroth16Prover<E> {
@@ -58,6 +74,24 @@ impl<E: Pairing> BoundCheckLegoGroth16Prover<E> {
         }))
     }
 
+    /// Create a statement for proving that the message is at least `min`, i.e. `min <= message`. As bounds are
+    /// 64 bit integers, the upper bound is set to `u64::MAX` which is thus excluded.
+    pub fn new_statement_min_only(
+        min: u64,
+        snark_proving_key: ProvingKey<E>,
+    ) -> Result<Statement<E>, ProofSystemError> {
+        Self::new_statement_from_params(min, u64::MAX, snark_proving_key)
+    }
+
+    /// Create a statement for proving that the message is less than `max`, i.e. `message < max`. The lower
+    /// bound is set to 0.
+    pub fn new_statement_max_only(
+        max: u64,
+        snark_proving_key: ProvingKey<E>,
+    ) -> Result<Statement<E>, ProofSystemError> {
+        Self::new_statement_from_params(0, max, snark_proving_key)
+    }
+
     pub fn new_statement_from_params_ref(
         min: u64,
         max: u64,
@@ -102,9 +136,26 @@ impl<E: Pairing> BoundCheckLegoGroth16Verifier<E> {
             max,
             snark_verifying_key: Some(snark_verifying_key),
             snark_verifying_key_ref: None,
+            snark_prepared_verifying_key: None,
         }))
     }
 
+    /// Verifier's counterpart of `BoundCheckLegoGroth16Prover::new_statement_min_only`
+    pub fn new_statement_min_only(
+        min: u64,
+        snark_verifying_key: VerifyingKey<E>,
+    ) -> Result<Statement<E>, ProofSystemError> {
+        Self::new_statement_from_params(min, u64::MAX, snark_verifying_key)
+    }
+
+    /// Verifier's counterpart of `BoundCheckLegoGroth16Prover::new_statement_max_only`
+    pub fn new_statement_max_only(
+        max: u64,
+        snark_verifying_key: VerifyingKey<E>,
+    ) -> Result<Statement<E>, ProofSystemError> {
+        Self::new_statement_from_params(0, max, snark_verifying_key)
+    }
+
     pub fn new_statement_from_params_ref(
         min: u64,
         max: u64,
@@ -116,9 +167,34 @@ impl<E: Pairing> BoundCheckLegoGroth16Verifier<E> {
             max,
             snark_verifying_key: None,
             snark_verifying_key_ref: Some(snark_verifying_key_ref),
+            snark_prepared_verifying_key: None,
+        }))
+    }
+
+    /// Same as `Self::new_statement_from_params` but accepts a prepared verifying key so that a verifier
+    /// using the same key for many proofs doesn't need to prepare it for each.
+    pub fn new_statement_from_prepared_params(
+        min: u64,
+        max: u64,
+        snark_prepared_verifying_key: PreparedVerifyingKey<E>,
+    ) -> Result<Statement<E>, ProofSystemError> {
+        BoundCheckLegoGrothProtocol::validate_verification_key(&snark_prepared_verifying_key.vk)?;
+        validate_bounds(min, max)?;
+        Ok(Statement::BoundCheckLegoGroth16Verifier(Self {
+            min,
+            max,
+            snark_verifying_key: Some(snark_prepared_verifying_key.vk.clone()),
+            snark_verifying_key_ref: None,
+            snark_prepared_verifying_key: Some(snark_prepared_verifying_key),
         }))
     }
 
+    /// Prepared form of the verifying key if the statement was created with
+    /// `Self::new_statement_from_prepared_params`
+    pub fn prepared_verifying_key(&self) -> Option<&PreparedVerifyingKey<E>> {
+        self.snark_prepared_verifying_key.as_ref()
+    }
+
     pub fn get_verifying_key<'a>(
         &'a self,
         setup_params: &'a [SetupParams<E>],
@@ -135,6 +211,66 @@ impl<E: Pairing> BoundCheckLegoGroth16Verifier<E> {
     }
 }
 
+mod serialization {
+    use super::*;
+    use ark_serialize::{Compress, Read, SerializationError, Valid, Validate, Write};
+
+    // The prepared verifying key is left out so the serialized statement is the same as one created from
+    // the verifying key
+
+    impl<E: Pairing> Valid for BoundCheckLegoGroth16Verifier<E> {
+        fn check(&self) -> Result<(), SerializationError> {
+            self.snark_verifying_key.check()
+        }
+    }
+
+    impl<E: Pairing> CanonicalSerialize for BoundCheckLegoGroth16Verifier<E> {
+        fn serialize_with_mode<W: Write>(
+            &self,
+            mut writer: W,
+            compress: Compress,
+        ) -> Result<(), SerializationError> {
+            self.min.serialize_with_mode(&mut writer, compress)?;
+            self.max.serialize_with_mode(&mut writer, compress)?;
+            self.snark_verifying_key
+                .serialize_with_mode(&mut writer, compress)?;
+            self.snark_verifying_key_ref
+                .serialize_with_mode(&mut writer, compress)
+        }
+
+        fn serialized_size(&self, compress: Compress) -> usize {
+            self.min.serialized_size(compress)
+                + self.max.serialized_size(compress)
+                + self.snark_verifying_key.serialized_size(compress)
+                + self.snark_verifying_key_ref.serialized_size(compress)
+        }
+    }
+
+    impl<E: Pairing> CanonicalDeserialize for BoundCheckLegoGroth16Verifier<E> {
+        fn deserialize_with_mode<R: Read>(
+            mut reader: R,
+            compress: Compress,
+            validate: Validate,
+        ) -> Result<Self, SerializationError> {
+            Ok(Self {
+                min: u64::deserialize_with_mode(&mut reader, compress, validate)?,
+                max: u64::deserialize_with_mode(&mut reader, compress, validate)?,
+                snark_verifying_key: CanonicalDeserialize::deserialize_with_mode(
+                    &mut reader,
+                    compress,
+                    validate,
+                )?,
+                snark_verifying_key_ref: CanonicalDeserialize::deserialize_with_mode(
+                    &mut reader,
+                    compress,
+                    validate,
+                )?,
+                snark_prepared_verifying_key: None,
+            })
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,8 +303,28 @@ mod tests {
         assert!(
             BoundCheckLegoGroth16Prover::new_statement_from_params(5, 6, snark_pk.clone()).is_ok()
         );
+        assert!(BoundCheckLegoGroth16Verifier::new_statement_from_params(
+            5,
+            6,
+            snark_pk.vk.clone()
+        )
+        .is_ok());
+
+        // Open-ended ranges must not be empty
+        assert!(
+            BoundCheckLegoGroth16Prover::new_statement_min_only(u64::MAX, snark_pk.clone())
+                .is_err()
+        );
+        assert!(BoundCheckLegoGroth16Prover::new_statement_max_only(0, snark_pk.clone()).is_err());
+        assert!(BoundCheckLegoGroth16Verifier::new_statement_min_only(
+            u64::MAX,
+            snark_pk.vk.clone()
+        )
+        .is_err());
         assert!(
-            BoundCheckLegoGroth16Verifier::new_statement_from_params(5, 6, snark_pk.vk).is_ok()
+            BoundCheckLegoGroth16Verifier::new_statement_max_only(0, snark_pk.vk.clone()).is_err()
         );
+        assert!(BoundCheckLegoGroth16Prover::new_statement_min_only(0, snark_pk.clone()).is_ok());
+        assert!(BoundCheckLegoGroth16Verifier::new_statement_max_only(1, snark_pk.vk).is_ok());
     }
 }
@@ -0,0 +1,35 @@
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+#[cfg(feature = "serde")]
+use dock_crypto_utils::serde_utils::ArkObjectBytes;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::statement::Statement;
+
+/// Statement for proving that the discrete log of a public key `pk = g * x` is a member of an accumulator,
+/// like when a credential attribute is bound to a key. The witness `x` must be in a witness equality with
+/// the member of an accumulator membership statement, which `ProofSpec::validate` checks. As accumulator
+/// proofs don't contain a response for the member, this statement must come before the accumulator
+/// statement unless another statement of the witness equality, like a signature, does.
+#[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct DLEqualToAccumulatorMember<G: AffineRepr> {
+    /// The base `g` in `pk = g * x`
+    #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))]
+    pub base: G,
+    /// The public key `pk` in `pk = g * x`
+    #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))]
+    pub public_key: G,
+}
+
+impl<G: AffineRepr> DLEqualToAccumulatorMember<G> {
+    pub fn new_statement_from_params<E: Pairing<G1Affine = G>>(
+        base: G,
+        public_key: G,
+    ) -> Statement<E> {
+        Statement::DLEqualToAccumulatorMember(Self { base, public_key })
+    }
+}
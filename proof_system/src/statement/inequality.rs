@@ -62,3 +62,51 @@ impl<G: AffineRepr> PublicInequality<G> {
         )
     }
 }
+
+/// Statement for proving that 2 witnesses are not equal to each other. Neither witness is revealed to
+/// the verifier, both are committed using the same Pedersen commitment key and each must be linked
+/// to a witness of another statement (like a signed message) using witness equalities.
+#[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct HiddenInequality<G: AffineRepr> {
+    #[cfg_attr(feature = "serde", serde_as(as = "Option<ArkObjectBytes>"))]
+    pub comm_key: Option<PedersenCommitmentKey<G>>,
+    pub comm_key_ref: Option<usize>,
+}
+
+impl<G: AffineRepr> HiddenInequality<G> {
+    pub fn new_statement_from_params<E: Pairing<G1Affine = G>>(
+        comm_key: PedersenCommitmentKey<G>,
+    ) -> Statement<E> {
+        Statement::HiddenInequality(Self {
+            comm_key: Some(comm_key),
+            comm_key_ref: None,
+        })
+    }
+
+    pub fn new_statement_from_params_ref<E: Pairing<G1Affine = G>>(
+        comm_key_ref: usize,
+    ) -> Statement<E> {
+        Statement::HiddenInequality(Self {
+            comm_key: None,
+            comm_key_ref: Some(comm_key_ref),
+        })
+    }
+
+    pub fn get_comm_key<'a, E: Pairing<G1Affine = G>>(
+        &'a self,
+        setup_params: &'a [SetupParams<E>],
+        st_idx: usize,
+    ) -> Result<&'a PedersenCommitmentKey<G>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.comm_key,
+            self.comm_key_ref,
+            CommitmentKey,
+            IncompatiblePedCommSetupParamAtIndex,
+            st_idx
+        )
+    }
+}
@@ -1,12 +1,19 @@
-use ark_ec::pairing::Pairing;
+use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 use ark_std::{
+    collections::BTreeSet,
     io::{Read, Write},
     vec::Vec,
 };
+use bulletproofs_plus_plus::setup::SetupParams as BppSetupParams;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    error::ProofSystemError,
+    meta_statement::{EqualWitnesses, MetaStatements, WitnessRef},
+};
+
 pub mod accumulator;
 pub mod bbs_23;
 pub mod bbs_23_ietf;
@@ -17,9 +24,11 @@ pub mod bound_check_bpp;
 pub mod bound_check_legogroth16;
 pub mod bound_check_smc;
 pub mod bound_check_smc_with_kv;
+pub mod dl_equal_to_accumulator_member;
 pub mod inequality;
 pub mod ped_comm;
 pub mod ps_signature;
+pub mod public_set_membership;
 pub mod r1cs_legogroth16;
 pub mod saver;
 pub mod verifiable_encryption_tz_21;
@@ -137,6 +146,18 @@ pub enum Statement<E: Pairing> {
     /// Verifiable Encryption using Robust DKGith protocol in the scheme TZ21
     // TODO: This should have the const generics used by the corresponding protocol
     VeTZ21Robust(verifiable_encryption_tz_21::VerifiableEncryptionTZ21<E::G1Affine>),
+    /// For proof of membership of one element and non-membership of another in the same KB universal accumulator
+    KBUniversalAccumulatorMembershipAndNonMembership(
+        accumulator::KBUniversalAccumulatorMembershipAndNonMembership<E>,
+    ),
+    /// To prove inequality of 2 witnesses, neither of which is revealed
+    HiddenInequality(inequality::HiddenInequality<E::G1Affine>),
+    /// To prove that a witness is one of the values of a public set without revealing which one
+    PublicSetMembership(public_set_membership::PublicSetMembership<E::G1Affine>),
+    /// To prove that the discrete log of a public key is a member of an accumulator
+    DLEqualToAccumulatorMember(
+        dl_equal_to_accumulator_member::DLEqualToAccumulatorMember<E::G1Affine>,
+    ),
 }
 
 /// A collection of statements
@@ -162,6 +183,369 @@ impl<E: Pairing> Statements<E> {
     pub fn len(&self) -> usize {
         self.0.len()
     }
+
+    /// Add statements for membership of the same element in several accumulators, like proving that an
+    /// element is present in several whitelists. A witness equality between the element of each of these
+    /// statements and the witness referred by `element_witness_ref`, like a signed message, is added to
+    /// `meta_statements`. Returns the indices of the added statements. Absence of the same element from
+    /// accumulators, like a blacklist, is added with `Self::add_shared_accumulator_non_memberships` using the
+    /// same `element_witness_ref`.
+    /// Nothing is added if any of the given statements isn't an accumulator membership statement.
+    pub fn add_shared_accumulator_memberships(
+        &mut self,
+        meta_statements: &mut MetaStatements,
+        element_witness_ref: WitnessRef,
+        membership_statements: Vec<Statement<E>>,
+    ) -> Result<Vec<usize>, ProofSystemError> {
+        for (i, s) in membership_statements.iter().enumerate() {
+            if !s.is_accumulator_membership_statement() {
+                return Err(ProofSystemError::NotAnAccumulatorStatement(i));
+            }
+        }
+        self.add_with_shared_element(meta_statements, element_witness_ref, membership_statements)
+    }
+
+    /// Same as `Self::add_shared_accumulator_memberships` but for proving that the same element is absent from
+    /// several accumulators, like blacklists maintained by different authorities. As accumulator proofs
+    /// don't contain a response for the element, the verifier uses the response of the witness referred
+    /// by `element_witness_ref` for all of them and thus a proof with different non-members fails to verify.
+    /// Nothing is added if any of the given statements isn't an accumulator non-membership statement.
+    pub fn add_shared_accumulator_non_memberships(
+        &mut self,
+        meta_statements: &mut MetaStatements,
+        element_witness_ref: WitnessRef,
+        non_membership_statements: Vec<Statement<E>>,
+    ) -> Result<Vec<usize>, ProofSystemError> {
+        for (i, s) in non_membership_statements.iter().enumerate() {
+            if !s.is_accumulator_non_membership_statement() {
+                return Err(ProofSystemError::NotAnAccumulatorNonMembershipStatement(i));
+            }
+        }
+        self.add_with_shared_element(
+            meta_statements,
+            element_witness_ref,
+            non_membership_statements,
+        )
+    }
+
+    /// Add the accumulator statements and the witness equality between their elements and `element_witness_ref`
+    fn add_with_shared_element(
+        &mut self,
+        meta_statements: &mut MetaStatements,
+        element_witness_ref: WitnessRef,
+        accumulator_statements: Vec<Statement<E>>,
+    ) -> Result<Vec<usize>, ProofSystemError> {
+        if accumulator_statements.is_empty() {
+            return Err(ProofSystemError::InvalidWitnessEquality);
+        }
+        let mut equality = BTreeSet::new();
+        equality.insert(element_witness_ref);
+        let indices = accumulator_statements
+            .into_iter()
+            .map(|s| {
+                let idx = self.add(s);
+                // The element is the only witness of accumulator statements
+                equality.insert((idx, 0));
+                idx
+            })
+            .collect();
+        meta_statements.add_witness_equality(EqualWitnesses(equality));
+        Ok(indices)
+    }
+
+    /// Add a `BoundCheckBpp` statement proving that the message at index `message_idx` of the BBS+ signature
+    /// statement at index `sig_stmt_idx` satisfies `min <= message < max`. The witness equality between the
+    /// message and the bounded value is added to `meta_statements`. Returns the index of the added statement.
+    pub fn add_bbs_plus_message_in_bpp_range(
+        &mut self,
+        meta_statements: &mut MetaStatements,
+        sig_stmt_idx: usize,
+        message_idx: usize,
+        min: u64,
+        max: u64,
+        bpp_params: BppSetupParams<E::G1Affine>,
+    ) -> Result<usize, ProofSystemError> {
+        let revealed_messages = match self.0.get(sig_stmt_idx) {
+            Some(Statement::PoKBBSSignatureG1Prover(s)) => &s.revealed_messages,
+            Some(Statement::PoKBBSSignatureG1Verifier(s)) => &s.revealed_messages,
+            _ => return Err(ProofSystemError::NotABBSPlusStatement(sig_stmt_idx)),
+        };
+        if revealed_messages.contains_key(&message_idx) {
+            return Err(ProofSystemError::WitnessAlreadyBeingRevealed(
+                sig_stmt_idx,
+                message_idx,
+            ));
+        }
+        let idx = self.add(bound_check_bpp::BoundCheckBpp::new_statement_from_params(
+            min, max, bpp_params,
+        )?);
+        // The bounded value is the only witness of the bound check statement
+        meta_statements.add_witness_equality(EqualWitnesses(BTreeSet::from([
+            (sig_stmt_idx, message_idx),
+            (idx, 0),
+        ])));
+        Ok(idx)
+    }
+
+    /// Add a statement proving that the witness referred by `output_witness_ref` is the sum of the witnesses
+    /// referred by `input_witness_refs`, like proving that a committed total is the sum of committed parts.
+    /// The statement is a Pedersen commitment `G * i_1 + G * i_2 + ... + G * i_n - G * o = 0`, where `G` is
+    /// the generator of G1, with a witness equality between each of its witnesses and the referred witness
+    /// added to `meta_statements`. Its witness is the inputs followed by the output as a
+    /// `Witness::PedersenCommitment`. Returns the index of the added statement.
+    pub fn add_sum_relation(
+        &mut self,
+        meta_statements: &mut MetaStatements,
+        output_witness_ref: WitnessRef,
+        input_witness_refs: Vec<WitnessRef>,
+    ) -> Result<usize, ProofSystemError> {
+        if input_witness_refs.is_empty() {
+            return Err(ProofSystemError::InvalidWitnessEquality);
+        }
+        let g = E::G1Affine::generator();
+        let mut key = vec![g; input_witness_refs.len()];
+        key.push((-g.into_group()).into());
+        let idx = self.add(ped_comm::PedersenCommitment::new_statement_from_params(
+            key,
+            E::G1Affine::zero(),
+        ));
+        for (i, wit_ref) in input_witness_refs
+            .into_iter()
+            .chain(core::iter::once(output_witness_ref))
+            .enumerate()
+        {
+            meta_statements
+                .add_witness_equality(EqualWitnesses(BTreeSet::from([wit_ref, (idx, i)])));
+        }
+        Ok(idx)
+    }
+
+    /// Add statements for a Pedersen commitment in G1 and a Pedersen commitment in G2 and prove that some
+    /// of the scalars committed in them are the same. Each item of `equal_witnesses` is a pair of indices of
+    /// the scalars in the G1 and G2 commitments that are the same, and a witness equality is added to
+    /// `meta_statements` for each so the proof has a single response for both scalars. Returns the indices
+    /// of the G1 and G2 commitment statements.
+    pub fn add_cross_group_pedersen_commitments(
+        &mut self,
+        meta_statements: &mut MetaStatements,
+        g1_key: Vec<E::G1Affine>,
+        g1_commitment: E::G1Affine,
+        g2_key: Vec<E::G2Affine>,
+        g2_commitment: E::G2Affine,
+        equal_witnesses: Vec<(usize, usize)>,
+    ) -> Result<(usize, usize), ProofSystemError> {
+        if equal_witnesses.is_empty()
+            || equal_witnesses
+                .iter()
+                .any(|(i, j)| *i >= g1_key.len() || *j >= g2_key.len())
+        {
+            return Err(ProofSystemError::InvalidWitnessEquality);
+        }
+        let g1_idx = self.add(ped_comm::PedersenCommitment::new_statement_from_params(
+            g1_key,
+            g1_commitment,
+        ));
+        let g2_idx = self.add(ped_comm::PedersenCommitment::new_statement_from_params_g2(
+            g2_key,
+            g2_commitment,
+        ));
+        for (i, j) in equal_witnesses {
+            meta_statements
+                .add_witness_equality(EqualWitnesses(BTreeSet::from([(g1_idx, i), (g2_idx, j)])));
+        }
+        Ok((g1_idx, g2_idx))
+    }
+}
+
+impl<E: Pairing> Statement<E> {
+    /// Whether this statement is about membership in an accumulator with the member as its only witness
+    pub fn is_accumulator_membership_statement(&self) -> bool {
+        matches!(
+            self,
+            Self::VBAccumulatorMembership(_)
+                | Self::KBUniversalAccumulatorMembership(_)
+                | Self::VBAccumulatorMembershipCDHProver(_)
+                | Self::VBAccumulatorMembershipCDHVerifier(_)
+                | Self::KBUniversalAccumulatorMembershipCDHProver(_)
+                | Self::KBUniversalAccumulatorMembershipCDHVerifier(_)
+                | Self::KBPositiveAccumulatorMembership(_)
+                | Self::KBPositiveAccumulatorMembershipCDH(_)
+                | Self::VBAccumulatorMembershipKV(_)
+                | Self::VBAccumulatorMembershipKVFullVerifier(_)
+                | Self::KBUniversalAccumulatorMembershipKV(_)
+                | Self::KBUniversalAccumulatorMembershipKVFullVerifier(_)
+        )
+    }
+
+    /// Whether this statement is about non-membership in an accumulator with the non-member as its only witness
+    pub fn is_accumulator_non_membership_statement(&self) -> bool {
+        matches!(
+            self,
+            Self::VBAccumulatorNonMembership(_)
+                | Self::KBUniversalAccumulatorNonMembership(_)
+                | Self::VBAccumulatorNonMembershipCDHProver(_)
+                | Self::VBAccumulatorNonMembershipCDHVerifier(_)
+                | Self::KBUniversalAccumulatorNonMembershipCDHProver(_)
+                | Self::KBUniversalAccumulatorNonMembershipCDHVerifier(_)
+                | Self::KBUniversalAccumulatorNonMembershipKV(_)
+                | Self::KBUniversalAccumulatorNonMembershipKVFullVerifier(_)
+        )
+    }
+
+    /// Whether this statement is about (non)membership in an accumulator
+    pub fn is_accumulator_statement(&self) -> bool {
+        matches!(
+            self,
+            Self::VBAccumulatorMembership(_)
+                | Self::VBAccumulatorNonMembership(_)
+                | Self::DetachedAccumulatorMembershipProver(_)
+                | Self::DetachedAccumulatorMembershipVerifier(_)
+                | Self::DetachedAccumulatorNonMembershipProver(_)
+                | Self::DetachedAccumulatorNonMembershipVerifier(_)
+                | Self::KBUniversalAccumulatorMembership(_)
+                | Self::KBUniversalAccumulatorNonMembership(_)
+                | Self::VBAccumulatorMembershipCDHProver(_)
+                | Self::VBAccumulatorMembershipCDHVerifier(_)
+                | Self::VBAccumulatorNonMembershipCDHProver(_)
+                | Self::VBAccumulatorNonMembershipCDHVerifier(_)
+                | Self::KBUniversalAccumulatorMembershipCDHProver(_)
+                | Self::KBUniversalAccumulatorMembershipCDHVerifier(_)
+                | Self::KBUniversalAccumulatorNonMembershipCDHProver(_)
+                | Self::KBUniversalAccumulatorNonMembershipCDHVerifier(_)
+                | Self::KBPositiveAccumulatorMembership(_)
+                | Self::KBPositiveAccumulatorMembershipCDH(_)
+                | Self::VBAccumulatorMembershipKV(_)
+                | Self::VBAccumulatorMembershipKVFullVerifier(_)
+                | Self::KBUniversalAccumulatorMembershipKV(_)
+                | Self::KBUniversalAccumulatorMembershipKVFullVerifier(_)
+                | Self::KBUniversalAccumulatorNonMembershipKV(_)
+                | Self::KBUniversalAccumulatorNonMembershipKVFullVerifier(_)
+        )
+    }
+
+    /// Mutable references to all the indices of `SetupParams` that this statement can refer to
+    pub(crate) fn setup_param_refs_mut(&mut self) -> Vec<&mut Option<usize>> {
+        match self {
+            Self::PoKBBSSignatureG1Prover(s) => vec![&mut s.signature_params_ref],
+            Self::PoKBBSSignatureG1Verifier(s) => {
+                vec![&mut s.signature_params_ref, &mut s.public_key_ref]
+            }
+            Self::PoKBBSSignature23G1Prover(s) => vec![&mut s.signature_params_ref],
+            Self::PoKBBSSignature23G1Verifier(s) => {
+                vec![&mut s.signature_params_ref, &mut s.public_key_ref]
+            }
+            Self::PoKBBSSignature23IETFG1Prover(s) => vec![&mut s.signature_params_ref],
+            Self::PoKBBSSignature23IETFG1Verifier(s) => {
+                vec![&mut s.signature_params_ref, &mut s.public_key_ref]
+            }
+            Self::PoKPSSignature(s) => vec![&mut s.signature_params_ref, &mut s.public_key_ref],
+            Self::PoKBBDT16MAC(s) => vec![&mut s.mac_params_ref],
+            Self::PoKBBDT16MACFullVerifier(s) => vec![&mut s.mac_params_ref],
+            Self::PedersenCommitment(s) => vec![&mut s.key_ref],
+            Self::PedersenCommitmentG2(s) => vec![&mut s.key_ref],
+            Self::VBAccumulatorMembership(s) => vec![
+                &mut s.params_ref,
+                &mut s.public_key_ref,
+                &mut s.proving_key_ref,
+            ],
+            Self::VBAccumulatorNonMembership(s) => vec![
+                &mut s.params_ref,
+                &mut s.public_key_ref,
+                &mut s.proving_key_ref,
+            ],
+            Self::KBUniversalAccumulatorMembership(s) => vec![
+                &mut s.params_ref,
+                &mut s.public_key_ref,
+                &mut s.proving_key_ref,
+            ],
+            Self::KBUniversalAccumulatorNonMembership(s) => vec![
+                &mut s.params_ref,
+                &mut s.public_key_ref,
+                &mut s.proving_key_ref,
+            ],
+            Self::KBUniversalAccumulatorMembershipAndNonMembership(s) => vec![
+                &mut s.params_ref,
+                &mut s.public_key_ref,
+                &mut s.proving_key_ref,
+            ],
+            Self::KBPositiveAccumulatorMembership(s) => vec![
+                &mut s.params_ref,
+                &mut s.public_key_ref,
+                &mut s.proving_key_ref,
+            ],
+            Self::KBPositiveAccumulatorMembershipCDH(s) => vec![
+                &mut s.params_ref,
+                &mut s.public_key_ref,
+                &mut s.proving_key_ref,
+            ],
+            Self::DetachedAccumulatorMembershipProver(s) => vec![
+                &mut s.params_ref,
+                &mut s.public_key_ref,
+                &mut s.proving_key_ref,
+            ],
+            Self::DetachedAccumulatorMembershipVerifier(s) => vec![
+                &mut s.params_ref,
+                &mut s.public_key_ref,
+                &mut s.proving_key_ref,
+            ],
+            Self::DetachedAccumulatorNonMembershipProver(s) => vec![
+                &mut s.params_ref,
+                &mut s.public_key_ref,
+                &mut s.proving_key_ref,
+            ],
+            Self::DetachedAccumulatorNonMembershipVerifier(s) => vec![
+                &mut s.params_ref,
+                &mut s.public_key_ref,
+                &mut s.proving_key_ref,
+            ],
+            Self::VBAccumulatorMembershipCDHVerifier(s) => {
+                vec![&mut s.params_ref, &mut s.public_key_ref]
+            }
+            Self::VBAccumulatorNonMembershipCDHProver(s) => vec![&mut s.params_ref],
+            Self::VBAccumulatorNonMembershipCDHVerifier(s) => {
+                vec![&mut s.params_ref, &mut s.public_key_ref]
+            }
+            Self::KBUniversalAccumulatorMembershipCDHVerifier(s) => {
+                vec![&mut s.params_ref, &mut s.public_key_ref]
+            }
+            Self::KBUniversalAccumulatorNonMembershipCDHVerifier(s) => {
+                vec![&mut s.params_ref, &mut s.public_key_ref]
+            }
+            Self::SaverProver(s) => vec![
+                &mut s.encryption_gens_ref,
+                &mut s.chunked_commitment_gens_ref,
+                &mut s.encryption_key_ref,
+                &mut s.snark_proving_key_ref,
+            ],
+            Self::SaverVerifier(s) => vec![
+                &mut s.encryption_gens_ref,
+                &mut s.chunked_commitment_gens_ref,
+                &mut s.encryption_key_ref,
+                &mut s.snark_verifying_key_ref,
+            ],
+            Self::BoundCheckLegoGroth16Prover(s) => vec![&mut s.snark_proving_key_ref],
+            Self::BoundCheckLegoGroth16Verifier(s) => vec![&mut s.snark_verifying_key_ref],
+            Self::R1CSCircomProver(s) => vec![
+                &mut s.r1cs_ref,
+                &mut s.wasm_bytes_ref,
+                &mut s.snark_proving_key_ref,
+            ],
+            Self::R1CSCircomVerifier(s) => {
+                vec![&mut s.public_inputs_ref, &mut s.snark_verifying_key_ref]
+            }
+            Self::BoundCheckBpp(s) => vec![&mut s.params_ref],
+            Self::BoundCheckSmc(s) => vec![&mut s.params_and_comm_key_ref],
+            Self::BoundCheckSmcWithKVProver(s) => vec![&mut s.params_ref],
+            Self::BoundCheckSmcWithKVVerifier(s) => vec![&mut s.params_ref],
+            Self::PublicInequality(s) => vec![&mut s.comm_key_ref],
+            Self::HiddenInequality(s) => vec![&mut s.comm_key_ref],
+            Self::PublicSetMembership(s) => vec![&mut s.comm_key_ref],
+            Self::VeTZ21(s) | Self::VeTZ21Robust(s) => {
+                vec![&mut s.enc_params_ref, &mut s.comm_key_ref]
+            }
+            _ => vec![],
+        }
+    }
 }
 
 macro_rules! delegate {
@@ -215,7 +599,11 @@ macro_rules! delegate {
                 PoKBBSSignature23IETFG1Prover,
                 PoKBBSSignature23IETFG1Verifier,
                 VeTZ21,
-                VeTZ21Robust
+                VeTZ21Robust,
+                KBUniversalAccumulatorMembershipAndNonMembership,
+                HiddenInequality,
+                PublicSetMembership,
+                DLEqualToAccumulatorMember
             : $($tt)+
         }
     }}
@@ -272,7 +660,11 @@ macro_rules! delegate_reverse {
                 PoKBBSSignature23IETFG1Prover,
                 PoKBBSSignature23IETFG1Verifier,
                 VeTZ21,
-                VeTZ21Robust
+                VeTZ21Robust,
+                KBUniversalAccumulatorMembershipAndNonMembership,
+                HiddenInequality,
+                PublicSetMembership,
+                DLEqualToAccumulatorMember
             : $($tt)+
         }
 
@@ -332,7 +724,7 @@ mod serialization {
 mod tests {
     use super::*;
     use ark_bls12_381::{fr::Fr, g1::G1Projective as G1Proj, Bls12_381};
-    use ark_ec::{CurveGroup, VariableBaseMSM};
+    use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
     use ark_std::{
         collections::BTreeMap,
         rand::{rngs::StdRng, SeedableRng},
@@ -432,4 +824,36 @@ mod tests {
         statements.add(stmt_7);
         test_serialization!(Statements<Bls12_381>, statements);
     }
+
+    #[test]
+    fn accumulator_statement_serialization_unchanged_without_accumulator_id() {
+        // Bytes of a statement serialized before `accumulator_id` was added
+        let old_bytes = [
+            "0197f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af0",
+            "0adb22c6bb000000010000000000000000010100000000000000010200000000000000",
+        ]
+        .concat();
+        let old_bytes = (0..old_bytes.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&old_bytes[i..i + 2], 16).unwrap())
+            .collect::<Vec<_>>();
+
+        let g = <Bls12_381 as Pairing>::G1Affine::generator();
+        let stmt = accumulator::VBAccumulatorMembership::new_statement_from_params_ref(0, 1, 2, g);
+        let deserz = Statement::<Bls12_381>::deserialize_compressed(old_bytes.as_slice()).unwrap();
+        assert_eq!(deserz, stmt);
+        let mut serz = vec![];
+        stmt.serialize_compressed(&mut serz).unwrap();
+        assert_eq!(serz, old_bytes);
+
+        let stmt =
+            accumulator::VBAccumulatorMembership::new_statement_from_params_ref_with_accumulator_id(
+                0,
+                1,
+                2,
+                g,
+                b"accumulator-1".to_vec(),
+            );
+        test_serialization!(Statement<Bls12_381>, stmt);
+    }
 }
@@ -0,0 +1,66 @@
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::vec::Vec;
+use dock_crypto_utils::commitment::PedersenCommitmentKey;
+#[cfg(feature = "serde")]
+use dock_crypto_utils::serde_utils::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{error::ProofSystemError, setup_params::SetupParams, statement::Statement};
+
+/// Statement for proving that a witness is equal to one of the values of a small public set without
+/// revealing which one. The witness is committed using a Pedersen commitment key and must be linked to a
+/// witness of another statement (like a signed message) using witness equalities. The proof size is linear
+/// in the size of the set.
+#[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct PublicSetMembership<G: AffineRepr> {
+    /// The public values, one of which is equal to the witness
+    #[cfg_attr(feature = "serde", serde_as(as = "Vec<ArkObjectBytes>"))]
+    pub set: Vec<G::ScalarField>,
+    #[cfg_attr(feature = "serde", serde_as(as = "Option<ArkObjectBytes>"))]
+    pub comm_key: Option<PedersenCommitmentKey<G>>,
+    pub comm_key_ref: Option<usize>,
+}
+
+impl<G: AffineRepr> PublicSetMembership<G> {
+    pub fn new_statement_from_params<E: Pairing<G1Affine = G>>(
+        set: Vec<G::ScalarField>,
+        comm_key: PedersenCommitmentKey<G>,
+    ) -> Statement<E> {
+        Statement::PublicSetMembership(Self {
+            set,
+            comm_key: Some(comm_key),
+            comm_key_ref: None,
+        })
+    }
+
+    pub fn new_statement_from_params_ref<E: Pairing<G1Affine = G>>(
+        set: Vec<G::ScalarField>,
+        comm_key_ref: usize,
+    ) -> Statement<E> {
+        Statement::PublicSetMembership(Self {
+            set,
+            comm_key: None,
+            comm_key_ref: Some(comm_key_ref),
+        })
+    }
+
+    pub fn get_comm_key<'a, E: Pairing<G1Affine = G>>(
+        &'a self,
+        setup_params: &'a [SetupParams<E>],
+        st_idx: usize,
+    ) -> Result<&'a PedersenCommitmentKey<G>, ProofSystemError> {
+        extract_param!(
+            setup_params,
+            &self.comm_key,
+            self.comm_key_ref,
+            CommitmentKey,
+            IncompatiblePedCommSetupParamAtIndex,
+            st_idx
+        )
+    }
+}
@@ -8,7 +8,7 @@ use ark_std::{
     io::{Read, Write},
     vec::Vec,
 };
-use bbs_plus::prelude::{PoKOfSignature23G1Proof, PoKOfSignatureG1Proof};
+use bbs_plus::prelude::{PoKOfSignature23G1Proof, PoKOfSignatureG1Proof, SignatureG1};
 use bulletproofs_plus_plus::prelude::ProofArbitraryRange;
 use coconut_crypto::SignaturePoK as PSSignaturePoK;
 use dock_crypto_utils::ecies;
@@ -32,7 +32,11 @@ use vb_accumulator::{
     prelude::{MembershipProof, NonMembershipProof},
 };
 
-/// Proof corresponding to one `Statement`
+/// Proof corresponding to one `Statement`. Its `serialized_size` (and so `compressed_size` and
+/// `uncompressed_size`) is exact, being the sum of the sizes of the variant's fields including the lengths
+/// of its vectors, so it can be used to pre-allocate the buffer to serialize into. It's computed from the
+/// proof itself so it can't be used to reject oversized proofs before deserializing them; for that, check
+/// the length of the received bytes.
 #[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -80,6 +84,13 @@ pub enum StatementProof<E: Pairing> {
     PedersenCommitmentG2Partial(PedersenCommitmentPartialProof<E::G2Affine>),
     VeTZ21(VeTZ21Proof<E::G1Affine>),
     VeTZ21Robust(VeTZ21RobustProof<E::G1Affine>),
+    KBUniversalAccumulatorMembershipAndNonMembership(
+        KBUniversalAccumulatorMembershipAndNonMembershipProof<E>,
+    ),
+    HiddenInequality(HiddenInequalityProof<E::G1Affine>),
+    PublicSetMembership(PublicSetMembershipProof<E::G1Affine>),
+    /// The BBS+ signature itself, sent instead of a proof of its knowledge when all messages are revealed
+    RevealedBBSSignatureG1(SignatureG1<E>),
 }
 
 macro_rules! delegate {
@@ -121,7 +132,11 @@ macro_rules! delegate {
                 PedersenCommitmentPartial,
                 PedersenCommitmentG2Partial,
                 VeTZ21,
-                VeTZ21Robust
+                VeTZ21Robust,
+                KBUniversalAccumulatorMembershipAndNonMembership,
+                HiddenInequality,
+                PublicSetMembership,
+                RevealedBBSSignatureG1
             : $($tt)+
         }
     }};
@@ -166,7 +181,11 @@ macro_rules! delegate_reverse {
                 PedersenCommitmentPartial,
                 PedersenCommitmentG2Partial,
                 VeTZ21,
-                VeTZ21Robust
+                VeTZ21Robust,
+                KBUniversalAccumulatorMembershipAndNonMembership,
+                HiddenInequality,
+                PublicSetMembership,
+                RevealedBBSSignatureG1
             : $($tt)+
         }
 
@@ -174,6 +193,23 @@ macro_rules! delegate_reverse {
     }};
 }
 
+impl<E: Pairing> StatementProof<E> {
+    /// Number of signed messages that were not revealed in a proof of knowledge of a BBS+, BBS or
+    /// PS signature. Returns `None` for other proofs or if the proof is malformed. The indices of the revealed
+    /// messages and the total number of messages can't be recovered from the proof as only the hidden messages
+    /// have responses in it; they are part of the `Statement`.
+    pub fn hidden_message_count(&self) -> Option<usize> {
+        match self {
+            Self::PoKBBSSignatureG1(p) => p.hidden_message_count().ok(),
+            Self::PoKBBSSignature23G1(p) => p.hidden_message_count().ok(),
+            Self::PoKBBSSignature23IETFG1(p) => p.hidden_message_count().ok(),
+            Self::PoKPSSignature(p) => Some(p.hidden_message_count()),
+            Self::RevealedBBSSignatureG1(_) => Some(0),
+            _ => None,
+        }
+    }
+}
+
 #[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
 #[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -394,6 +430,44 @@ pub struct InequalityProof<G: AffineRepr> {
     pub sp: PedersenCommitmentPartialProof<G>,
 }
 
+#[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct HiddenInequalityProof<G: AffineRepr> {
+    #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))]
+    pub proof: schnorr_pok::inequality::InequalityProof<G>,
+    /// Commitment to the first witness
+    #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))]
+    pub comm1: G,
+    /// Commitment to the second witness
+    #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))]
+    pub comm2: G,
+    pub sp1: PedersenCommitmentPartialProof<G>,
+    pub sp2: PedersenCommitmentPartialProof<G>,
+}
+
+/// Proof that a committed witness is one of the values of a public set. For each value of the set, there
+/// is a commitment, challenge and response of a Schnorr proof of knowledge of the discrete log of
+/// `comm - g * value` to base `h`. Only the proof for the actual value is real, the others are simulated.
+#[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct PublicSetMembershipProof<G: AffineRepr> {
+    /// Commitment to the witness
+    #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))]
+    pub comm: G,
+    #[cfg_attr(feature = "serde", serde_as(as = "Vec<ArkObjectBytes>"))]
+    pub t: Vec<G>,
+    /// Challenges for each value of the set. These sum up to the proof's challenge
+    #[cfg_attr(feature = "serde", serde_as(as = "Vec<ArkObjectBytes>"))]
+    pub challenges: Vec<G::ScalarField>,
+    #[cfg_attr(feature = "serde", serde_as(as = "Vec<ArkObjectBytes>"))]
+    pub responses: Vec<G::ScalarField>,
+    pub sp: PedersenCommitmentPartialProof<G>,
+}
+
 #[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
 #[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -426,6 +500,15 @@ pub struct DetachedAccumulatorNonMembershipProof<E: Pairing> {
     pub encrypted: ecies::Encryption<E::G2Affine, 32, 24>,
 }
 
+/// Proof of membership of one element and non-membership of another in the same KB universal accumulator
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct KBUniversalAccumulatorMembershipAndNonMembershipProof<E: Pairing> {
+    pub membership: KBUniversalAccumulatorMembershipProof<E>,
+    pub non_membership: KBUniversalAccumulatorNonMembershipProof<E>,
+}
+
 /// Verifiable Encryption using DKGith protocol in the scheme TZ21
 #[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
 #[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
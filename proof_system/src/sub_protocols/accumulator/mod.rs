@@ -4,7 +4,10 @@ pub mod cdh;
 pub mod detached;
 pub mod keyed_verification;
 
-use crate::{error::ProofSystemError, statement_proof::StatementProof};
+use crate::{
+    error::ProofSystemError,
+    statement_proof::{KBUniversalAccumulatorMembershipAndNonMembershipProof, StatementProof},
+};
 use ark_ec::pairing::Pairing;
 
 use ark_std::{io::Write, rand::RngCore};
@@ -97,6 +100,175 @@ impl_struct_and_funcs!(
     KBAccumProofContributionFailed
 );
 
+/// To prove membership of one element and non-membership of another in the same KB universal accumulator
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KBUniversalAccumulatorMembershipAndNonMembershipSubProtocol<'a, E: Pairing> {
+    pub id: usize,
+    pub params: &'a AccumParams<E>,
+    pub public_key: &'a PublicKey<E>,
+    pub proving_key: &'a ProvingKey<E::G1Affine>,
+    pub mem_accumulator_value: E::G1Affine,
+    pub non_mem_accumulator_value: E::G1Affine,
+    pub mem_protocol: Option<KBUniMemProtocol<E>>,
+    pub non_mem_protocol: Option<KBUniNonMemProtocol<E>>,
+}
+
+impl<'a, E: Pairing> KBUniversalAccumulatorMembershipAndNonMembershipSubProtocol<'a, E> {
+    pub fn new(
+        id: usize,
+        params: &'a AccumParams<E>,
+        public_key: &'a PublicKey<E>,
+        proving_key: &'a ProvingKey<E::G1Affine>,
+        mem_accumulator_value: E::G1Affine,
+        non_mem_accumulator_value: E::G1Affine,
+    ) -> Self {
+        Self {
+            id,
+            params,
+            public_key,
+            proving_key,
+            mem_accumulator_value,
+            non_mem_accumulator_value,
+            mem_protocol: None,
+            non_mem_protocol: None,
+        }
+    }
+
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        mem_blinding: Option<E::ScalarField>,
+        non_mem_blinding: Option<E::ScalarField>,
+        witness: crate::witness::KBUniMembershipAndNonMembership<E::G1Affine>,
+    ) -> Result<(), ProofSystemError> {
+        if self.mem_protocol.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        self.mem_protocol = Some(KBUniMemProtocol::init(
+            rng,
+            witness.membership.element,
+            mem_blinding,
+            &witness.membership.witness,
+            self.public_key,
+            self.params,
+            self.proving_key,
+        ));
+        self.non_mem_protocol = Some(KBUniNonMemProtocol::init(
+            rng,
+            witness.non_membership.element,
+            non_mem_blinding,
+            &witness.non_membership.witness,
+            self.public_key,
+            self.params,
+            self.proving_key,
+        ));
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        match (&self.mem_protocol, &self.non_mem_protocol) {
+            (Some(m), Some(n)) => {
+                m.challenge_contribution(
+                    &self.mem_accumulator_value,
+                    self.public_key,
+                    self.params,
+                    self.proving_key,
+                    &mut writer,
+                )?;
+                n.challenge_contribution(
+                    &self.non_mem_accumulator_value,
+                    self.public_key,
+                    self.params,
+                    self.proving_key,
+                    &mut writer,
+                )?;
+                Ok(())
+            }
+            _ => Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            )),
+        }
+    }
+
+    pub fn gen_proof_contribution(
+        &mut self,
+        challenge: &E::ScalarField,
+    ) -> Result<StatementProof<E>, ProofSystemError> {
+        match (self.mem_protocol.take(), self.non_mem_protocol.take()) {
+            (Some(m), Some(n)) => Ok(
+                StatementProof::KBUniversalAccumulatorMembershipAndNonMembership(
+                    KBUniversalAccumulatorMembershipAndNonMembershipProof {
+                        membership: m.gen_partial_proof(challenge)?,
+                        non_membership: n.gen_partial_proof(challenge)?,
+                    },
+                ),
+            ),
+            _ => Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            )),
+        }
+    }
+
+    /// `resp_for_member` and `resp_for_non_member` are the responses for the member and the non-member
+    /// respectively, taken from the proofs of the statements they are proven equal to
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &E::ScalarField,
+        proof: &KBUniversalAccumulatorMembershipAndNonMembershipProof<E>,
+        pk: PreparedPublicKey<E>,
+        params: PreparedSetupParams<E>,
+        pairing_checker: &mut Option<RandomizedPairingChecker<E>>,
+        resp_for_member: E::ScalarField,
+        resp_for_non_member: E::ScalarField,
+    ) -> Result<(), ProofSystemError> {
+        match pairing_checker {
+            Some(c) => {
+                proof
+                    .membership
+                    .verify_partial_with_randomized_pairing_checker(
+                        &resp_for_member,
+                        &self.mem_accumulator_value,
+                        challenge,
+                        pk.clone(),
+                        params.clone(),
+                        self.proving_key,
+                        c,
+                    )?;
+                proof
+                    .non_membership
+                    .verify_partial_with_randomized_pairing_checker(
+                        &resp_for_non_member,
+                        &self.non_mem_accumulator_value,
+                        challenge,
+                        pk,
+                        params,
+                        self.proving_key,
+                        c,
+                    )
+            }
+            None => {
+                proof.membership.verify_partial(
+                    &resp_for_member,
+                    &self.mem_accumulator_value,
+                    challenge,
+                    pk.clone(),
+                    params.clone(),
+                    self.proving_key,
+                )?;
+                proof.non_membership.verify_partial(
+                    &resp_for_non_member,
+                    &self.non_mem_accumulator_value,
+                    challenge,
+                    pk,
+                    params,
+                    self.proving_key,
+                )
+            }
+        }
+        .map_err(|e| ProofSystemError::KBAccumProofContributionFailed(self.id as u32, e))
+    }
+}
+
 impl_struct_and_funcs!(
     /// To prove membership in KB universal accumulator
     KBPositiveAccumulatorMembershipSubProtocol,
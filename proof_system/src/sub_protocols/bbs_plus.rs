@@ -1,16 +1,18 @@
 use super::merge_indexed_messages_with_blindings;
 use crate::{error::ProofSystemError, statement_proof::StatementProof};
 use ark_ec::pairing::Pairing;
+use ark_serialize::CanonicalSerialize;
 use ark_std::{
     collections::{BTreeMap, BTreeSet},
     io::Write,
     rand::RngCore,
+    vec::Vec,
 };
 use bbs_plus::{
     error::BBSPlusError,
     prelude::{
         PoKOfSignatureG1Proof, PreparedPublicKeyG2, PreparedSignatureParamsG1, PublicKeyG2,
-        SignatureParamsG1,
+        SignatureG1, SignatureParamsG1,
     },
     proof::PoKOfSignatureG1Protocol,
 };
@@ -226,3 +228,108 @@ impl<'a, E: Pairing> PoKBBSSigG1SubProtocol<'a, E> {
         PreparedSignatureParamsG1
     );
 }
+
+/// Used instead of `PoKBBSSigG1SubProtocol` when all the messages are revealed and the signature itself is
+/// revealed rather than proving its knowledge. See `ProverConfig::reveal_all_fast_path`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RevealedBBSSigG1SubProtocol<'a, E: Pairing> {
+    pub id: usize,
+    pub revealed_messages: &'a BTreeMap<usize, E::ScalarField>,
+    pub signature_params: &'a SignatureParamsG1<E>,
+    pub signature: Option<SignatureG1<E>>,
+}
+
+impl<'a, E: Pairing> RevealedBBSSigG1SubProtocol<'a, E> {
+    pub fn new(
+        id: usize,
+        revealed_messages: &'a BTreeMap<usize, E::ScalarField>,
+        signature_params: &'a SignatureParamsG1<E>,
+    ) -> Self {
+        Self {
+            id,
+            revealed_messages,
+            signature_params,
+            signature: None,
+        }
+    }
+
+    pub fn init(&mut self, signature: SignatureG1<E>) -> Result<(), ProofSystemError> {
+        if self.signature.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        self.messages()?;
+        self.signature = Some(signature);
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, writer: W) -> Result<(), ProofSystemError> {
+        if self.signature.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        Self::compute_challenge_contribution(
+            self.revealed_messages,
+            self.signature_params,
+            self.signature.as_ref().unwrap(),
+            writer,
+        )
+    }
+
+    pub fn gen_proof_contribution(&mut self) -> Result<StatementProof<E>, ProofSystemError> {
+        if self.signature.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        Ok(StatementProof::RevealedBBSSignatureG1(
+            self.signature.take().unwrap(),
+        ))
+    }
+
+    pub fn verify_proof_contribution(
+        &self,
+        signature: &SignatureG1<E>,
+        pk: impl Into<PreparedPublicKeyG2<E>>,
+        params: impl Into<PreparedSignatureParamsG1<E>>,
+    ) -> Result<(), ProofSystemError> {
+        signature
+            .verify(&self.messages()?, pk, params)
+            .map_err(|e| ProofSystemError::BBSPlusProofContributionFailed(self.id as u32, e))
+    }
+
+    pub fn compute_challenge_contribution<W: Write>(
+        revealed_messages: &BTreeMap<usize, E::ScalarField>,
+        signature_params: &SignatureParamsG1<E>,
+        signature: &SignatureG1<E>,
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        revealed_messages.serialize_compressed(&mut writer)?;
+        signature_params.serialize_compressed(&mut writer)?;
+        signature.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+
+    /// The revealed messages in order of their indices, which must be all the messages of the signature
+    fn messages(&self) -> Result<Vec<E::ScalarField>, ProofSystemError> {
+        let count = self.signature_params.supported_message_count();
+        expect_equality!(
+            self.revealed_messages.len(),
+            count,
+            ProofSystemError::BBSPlusProtocolInvalidMessageCount
+        );
+        if !reveals_all_messages::<E>(self.revealed_messages, count) {
+            return Err(ProofSystemError::InvalidStatement);
+        }
+        Ok(self.revealed_messages.values().copied().collect())
+    }
+}
+
+/// Whether `revealed_messages` has all the messages of a signature over `message_count` messages
+pub(crate) fn reveals_all_messages<E: Pairing>(
+    revealed_messages: &BTreeMap<usize, E::ScalarField>,
+    message_count: usize,
+) -> bool {
+    revealed_messages.len() == message_count
+        && revealed_messages.keys().copied().eq(0..message_count)
+}
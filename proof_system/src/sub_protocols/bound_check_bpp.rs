@@ -231,8 +231,11 @@ impl<'a, G: AffineRepr> BoundCheckBppProtocol<'a, G> {
         &self,
         proof: &ProofArbitraryRange<G>,
     ) -> Result<(G, G), ProofSystemError> {
-        let mut comms =
-            proof.get_commitments_to_values(vec![(self.min, self.max)], &self.setup_params)?;
+        let mut comms = proof
+            .get_commitments_to_values(vec![(self.min, self.max)], self.setup_params)
+            .map_err(|e| {
+                ProofSystemError::BulletproofsPlusPlusProofContributionFailed(self.id as u32, e)
+            })?;
         Ok(comms.remove(0))
     }
 
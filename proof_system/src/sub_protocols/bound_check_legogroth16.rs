@@ -191,7 +191,9 @@ impl<'a, E: Pairing> BoundCheckLegoGrothProtocol<'a, E> {
         let snark_proof = &proof.snark_proof;
         match pairing_checker {
             Some(c) => {
-                let d = calculate_d(pvk, snark_proof, pub_inp)?;
+                let d = calculate_d(pvk, snark_proof, pub_inp).map_err(|e| {
+                    ProofSystemError::LegoSnarkProofContributionFailed(self.id as u32, e)
+                })?;
                 c.add_multiple_sources_and_target(
                     &[snark_proof.a, snark_proof.c, d],
                     [
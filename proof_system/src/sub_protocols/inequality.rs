@@ -1,6 +1,6 @@
 use crate::{
     error::ProofSystemError,
-    statement_proof::{InequalityProof, StatementProof},
+    statement_proof::{HiddenInequalityProof, InequalityProof, StatementProof},
     sub_protocols::schnorr::SchnorrProtocol,
 };
 use ark_ec::{pairing::Pairing, AffineRepr};
@@ -63,34 +63,15 @@ impl<'a, G: AffineRepr> InequalityProtocol<'a, G> {
                 &self.comm_key,
             )?,
         );
-        self.comm = Some(comm);
-        self.init_schnorr_protocol(rng, comm_key_as_slice, message, blinding, randomness)
-    }
-
-    fn init_schnorr_protocol<R: RngCore>(
-        &mut self,
-        rng: &mut R,
-        comm_key: &'a [G],
-        message: G::ScalarField,
-        blinding: Option<G::ScalarField>,
-        blinding_for_ineqality_protocol_commitment: G::ScalarField,
-    ) -> Result<(), ProofSystemError> {
-        let blinding = if blinding.is_none() {
-            G::ScalarField::rand(rng)
-        } else {
-            blinding.unwrap()
-        };
-        let mut blindings = BTreeMap::new();
-        blindings.insert(0, blinding);
-
-        // NOTE: value of id is dummy
-        let mut sp = SchnorrProtocol::new(10000, &comm_key, self.comm.unwrap());
-        sp.init(
+        self.sp = Some(init_schnorr_protocol(
             rng,
-            blindings.clone(),
-            vec![message, blinding_for_ineqality_protocol_commitment],
-        )?;
-        self.sp = Some(sp);
+            comm_key_as_slice,
+            comm,
+            message,
+            blinding,
+            randomness,
+        )?);
+        self.comm = Some(comm);
         Ok(())
     }
 
@@ -187,3 +168,217 @@ impl<'a, G: AffineRepr> InequalityProtocol<'a, G> {
         Ok(())
     }
 }
+
+/// Protocol to prove that 2 witnesses are not equal without revealing either of them. Each witness is
+/// committed in a separate Pedersen commitment and the inequality is proven over these commitments.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HiddenInequalityProtocol<'a, G: AffineRepr> {
+    pub id: usize,
+    pub comm_key: &'a PedersenCommitmentKey<G>,
+    pub comm1: Option<G>,
+    pub comm2: Option<G>,
+    pub inequality_protocol: Option<DiscreteLogInequalityProtocol<G>>,
+    pub sp1: Option<SchnorrProtocol<'a, G>>,
+    pub sp2: Option<SchnorrProtocol<'a, G>>,
+}
+
+impl<'a, G: AffineRepr> HiddenInequalityProtocol<'a, G> {
+    pub fn new(id: usize, comm_key: &'a PedersenCommitmentKey<G>) -> Self {
+        Self {
+            id,
+            comm_key,
+            comm1: None,
+            comm2: None,
+            inequality_protocol: None,
+            sp1: None,
+            sp2: None,
+        }
+    }
+
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        comm_key_as_slice: &'a [G],
+        value1: G::ScalarField,
+        value2: G::ScalarField,
+        blinding1: Option<G::ScalarField>,
+        blinding2: Option<G::ScalarField>,
+    ) -> Result<(), ProofSystemError> {
+        if self.sp1.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        let randomness1 = G::ScalarField::rand(rng);
+        let randomness2 = G::ScalarField::rand(rng);
+        let comm1 = self.comm_key.commit(&value1, &randomness1);
+        let comm2 = self.comm_key.commit(&value2, &randomness2);
+        self.inequality_protocol = Some(
+            DiscreteLogInequalityProtocol::init_for_inequality_with_committed_value(
+                rng,
+                value1,
+                randomness1,
+                &comm1,
+                value2,
+                randomness2,
+                &comm2,
+                self.comm_key,
+            )?,
+        );
+        self.sp1 = Some(init_schnorr_protocol(
+            rng,
+            comm_key_as_slice,
+            comm1,
+            value1,
+            blinding1,
+            randomness1,
+        )?);
+        self.sp2 = Some(init_schnorr_protocol(
+            rng,
+            comm_key_as_slice,
+            comm2,
+            value2,
+            blinding2,
+            randomness2,
+        )?);
+        self.comm1 = Some(comm1);
+        self.comm2 = Some(comm2);
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        if self.sp1.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        self.inequality_protocol
+            .as_ref()
+            .unwrap()
+            .challenge_contribution_for_committed_inequality(
+                self.comm1.as_ref().unwrap(),
+                self.comm2.as_ref().unwrap(),
+                self.comm_key,
+                &mut writer,
+            )?;
+        self.sp1
+            .as_ref()
+            .unwrap()
+            .challenge_contribution(&mut writer)?;
+        self.sp2
+            .as_ref()
+            .unwrap()
+            .challenge_contribution(&mut writer)?;
+        Ok(())
+    }
+
+    pub fn gen_proof_contribution<E: Pairing<G1Affine = G>>(
+        &mut self,
+        challenge: &G::ScalarField,
+    ) -> Result<StatementProof<E>, ProofSystemError> {
+        if self.sp1.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        let proof = self
+            .inequality_protocol
+            .take()
+            .unwrap()
+            .gen_proof(challenge);
+        // Don't generate response for index 0 of either commitment since the witnesses' responses will come
+        // from proofs of the statements they are equal to.
+        let skip_for = BTreeSet::from([0]);
+        Ok(StatementProof::HiddenInequality(HiddenInequalityProof {
+            proof,
+            comm1: self.comm1.take().unwrap(),
+            comm2: self.comm2.take().unwrap(),
+            sp1: self
+                .sp1
+                .take()
+                .unwrap()
+                .gen_partial_proof_contribution_as_struct(challenge, &skip_for)?,
+            sp2: self
+                .sp2
+                .take()
+                .unwrap()
+                .gen_partial_proof_contribution_as_struct(challenge, &skip_for)?,
+        }))
+    }
+
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &G::ScalarField,
+        proof: &HiddenInequalityProof<G>,
+        comm_key_as_slice: &[G],
+        resp_for_value1: G::ScalarField,
+        resp_for_value2: G::ScalarField,
+    ) -> Result<(), ProofSystemError> {
+        proof
+            .proof
+            .verify_for_inequality_with_committed_value(
+                &proof.comm1,
+                &proof.comm2,
+                challenge,
+                self.comm_key,
+            )
+            .map_err(|e| ProofSystemError::SchnorrProofContributionFailed(self.id as u32, e))?;
+
+        // NOTE: value of id is dummy
+        let sp = SchnorrProtocol::new(10000, comm_key_as_slice, proof.comm1);
+        sp.verify_partial_proof_contribution(
+            challenge,
+            &proof.sp1,
+            BTreeMap::from([(0, resp_for_value1)]),
+        )
+        .map_err(|e| ProofSystemError::SchnorrProofContributionFailed(self.id as u32, e))?;
+        let sp = SchnorrProtocol::new(10000, comm_key_as_slice, proof.comm2);
+        sp.verify_partial_proof_contribution(
+            challenge,
+            &proof.sp2,
+            BTreeMap::from([(0, resp_for_value2)]),
+        )
+        .map_err(|e| ProofSystemError::SchnorrProofContributionFailed(self.id as u32, e))
+    }
+
+    pub fn compute_challenge_contribution<W: Write>(
+        comm_key_as_slice: &[G],
+        proof: &HiddenInequalityProof<G>,
+        comm_key: &PedersenCommitmentKey<G>,
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        proof
+            .proof
+            .challenge_contribution_for_committed_inequality(
+                &proof.comm1,
+                &proof.comm2,
+                comm_key,
+                &mut writer,
+            )?;
+        comm_key_as_slice.serialize_compressed(&mut writer)?;
+        proof.comm1.serialize_compressed(&mut writer)?;
+        proof.sp1.t.serialize_compressed(&mut writer)?;
+        comm_key_as_slice.serialize_compressed(&mut writer)?;
+        proof.comm2.serialize_compressed(&mut writer)?;
+        proof.sp2.t.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+}
+
+/// Initialize the Schnorr protocol for proving knowledge of the opening of `comm`, i.e. `message`
+/// and `randomness` in `comm = comm_key[0] * message + comm_key[1] * randomness`
+pub(crate) fn init_schnorr_protocol<'a, R: RngCore, G: AffineRepr>(
+    rng: &mut R,
+    comm_key: &'a [G],
+    comm: G,
+    message: G::ScalarField,
+    blinding: Option<G::ScalarField>,
+    randomness: G::ScalarField,
+) -> Result<SchnorrProtocol<'a, G>, ProofSystemError> {
+    let blinding = blinding.unwrap_or_else(|| G::ScalarField::rand(rng));
+    let mut blindings = BTreeMap::new();
+    blindings.insert(0, blinding);
+
+    // NOTE: value of id is dummy
+    let mut sp = SchnorrProtocol::new(10000, comm_key, comm);
+    sp.init(rng, blindings, vec![message, randomness])?;
+    Ok(sp)
+}
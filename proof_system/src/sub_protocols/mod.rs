@@ -10,6 +10,7 @@ pub mod bound_check_smc;
 pub mod bound_check_smc_with_kv;
 pub mod inequality;
 pub mod ps_signature;
+pub mod public_set_membership;
 pub mod r1cs_legogorth16;
 pub mod saver;
 pub mod schnorr;
@@ -31,6 +32,7 @@ use crate::{
                 VBAccumulatorMembershipKVSubProtocol,
             },
             KBPositiveAccumulatorMembershipSubProtocol,
+            KBUniversalAccumulatorMembershipAndNonMembershipSubProtocol,
             KBUniversalAccumulatorMembershipSubProtocol,
             KBUniversalAccumulatorNonMembershipSubProtocol,
         },
@@ -39,7 +41,8 @@ use crate::{
         bound_check_legogroth16::BoundCheckLegoGrothProtocol,
         bound_check_smc::BoundCheckSmcProtocol,
         bound_check_smc_with_kv::BoundCheckSmcWithKVProtocol,
-        inequality::InequalityProtocol,
+        inequality::{HiddenInequalityProtocol, InequalityProtocol},
+        public_set_membership::PublicSetMembershipProtocol,
         r1cs_legogorth16::R1CSLegogroth16Protocol,
         verifiable_encryption_tz_21::VeTZ21Protocol,
     },
@@ -102,6 +105,15 @@ pub enum SubProtocol<'a, E: Pairing> {
         KBUniversalAccumulatorNonMembershipKVSubProtocol<E::G1Affine>,
     ),
     VeTZ21(VeTZ21Protocol<'a, E::G1Affine>),
+    KBUniversalAccumulatorMembershipAndNonMembership(
+        KBUniversalAccumulatorMembershipAndNonMembershipSubProtocol<'a, E>,
+    ),
+    /// To prove inequality of 2 witnesses without revealing either
+    HiddenInequality(HiddenInequalityProtocol<'a, E::G1Affine>),
+    /// To prove that a witness is one of the values of a public set
+    PublicSetMembership(PublicSetMembershipProtocol<'a, E::G1Affine>),
+    /// Reveals the BBS+ signature when all its messages are revealed
+    RevealedBBSSignatureG1(bbs_plus::RevealedBBSSigG1SubProtocol<'a, E>),
 }
 
 macro_rules! delegate {
@@ -137,7 +149,11 @@ macro_rules! delegate {
                 VBAccumulatorMembershipKV,
                 KBUniversalAccumulatorMembershipKV,
                 KBUniversalAccumulatorNonMembershipKV,
-                VeTZ21
+                VeTZ21,
+                KBUniversalAccumulatorMembershipAndNonMembership,
+                HiddenInequality,
+                PublicSetMembership,
+                RevealedBBSSignatureG1
             : $($tt)+
         }
     }};
@@ -0,0 +1,257 @@
+use crate::{
+    error::ProofSystemError,
+    statement_proof::{PublicSetMembershipProof, StatementProof},
+    sub_protocols::{inequality::init_schnorr_protocol, schnorr::SchnorrProtocol},
+};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_serialize::CanonicalSerialize;
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    io::Write,
+    rand::RngCore,
+    vec::Vec,
+    UniformRand, Zero,
+};
+use dock_crypto_utils::commitment::PedersenCommitmentKey;
+
+/// Protocol to prove that a committed witness is one of the values of a public set. For a commitment
+/// `C = g * m + h * r`, proves knowledge of the discrete log of one of `C - g * v_i` to base `h` where `v_i`
+/// are the values of the set. This is done using an OR composition of Schnorr proofs where the proofs for
+/// all values except `m` are simulated and the challenges for all the proofs must sum up to the main challenge.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PublicSetMembershipProtocol<'a, G: AffineRepr> {
+    pub id: usize,
+    pub set: &'a [G::ScalarField],
+    pub comm_key: &'a PedersenCommitmentKey<G>,
+    pub comm: Option<G>,
+    /// Randomness used in the commitment
+    randomness: Option<G::ScalarField>,
+    /// Index of the witness in the set
+    index: Option<usize>,
+    /// Blinding for the proof corresponding to the witness's index
+    blinding: Option<G::ScalarField>,
+    /// Commitments of the Schnorr proofs, one for each value of the set
+    t: Vec<G>,
+    /// Challenges and responses of the simulated proofs. The entries at the witness's index are unused.
+    challenges: Vec<G::ScalarField>,
+    responses: Vec<G::ScalarField>,
+    pub sp: Option<SchnorrProtocol<'a, G>>,
+}
+
+impl<'a, G: AffineRepr> PublicSetMembershipProtocol<'a, G> {
+    pub fn new(
+        id: usize,
+        set: &'a [G::ScalarField],
+        comm_key: &'a PedersenCommitmentKey<G>,
+    ) -> Self {
+        Self {
+            id,
+            set,
+            comm_key,
+            comm: None,
+            randomness: None,
+            index: None,
+            blinding: None,
+            t: Vec::new(),
+            challenges: Vec::new(),
+            responses: Vec::new(),
+            sp: None,
+        }
+    }
+
+    pub fn init<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+        comm_key_as_slice: &'a [G],
+        message: G::ScalarField,
+        blinding: Option<G::ScalarField>,
+    ) -> Result<(), ProofSystemError> {
+        if self.sp.is_some() {
+            return Err(ProofSystemError::SubProtocolAlreadyInitialized(self.id));
+        }
+        if self.set.is_empty() {
+            return Err(ProofSystemError::EmptyPublicSet(self.id));
+        }
+        let index = self
+            .set
+            .iter()
+            .position(|v| *v == message)
+            .ok_or(ProofSystemError::WitnessNotInPublicSet(self.id))?;
+        let randomness = G::ScalarField::rand(rng);
+        let comm = self.comm_key.commit(&message, &randomness);
+        let comm_proj = comm.into_group();
+        let own_blinding = G::ScalarField::rand(rng);
+        let mut challenges = Vec::with_capacity(self.set.len());
+        let mut responses = Vec::with_capacity(self.set.len());
+        let t = self
+            .set
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                if i == index {
+                    challenges.push(G::ScalarField::zero());
+                    responses.push(G::ScalarField::zero());
+                    self.comm_key.h * own_blinding
+                } else {
+                    let c = G::ScalarField::rand(rng);
+                    let z = G::ScalarField::rand(rng);
+                    challenges.push(c);
+                    responses.push(z);
+                    self.comm_key.h * z - (comm_proj - self.comm_key.g * v) * c
+                }
+            })
+            .collect::<Vec<_>>();
+        self.sp = Some(init_schnorr_protocol(
+            rng,
+            comm_key_as_slice,
+            comm,
+            message,
+            blinding,
+            randomness,
+        )?);
+        self.t = G::Group::normalize_batch(&t);
+        self.challenges = challenges;
+        self.responses = responses;
+        self.comm = Some(comm);
+        self.randomness = Some(randomness);
+        self.index = Some(index);
+        self.blinding = Some(own_blinding);
+        Ok(())
+    }
+
+    pub fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), ProofSystemError> {
+        if self.sp.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateChallenge(
+                self.id,
+            ));
+        }
+        Self::set_membership_challenge_contribution(
+            self.set,
+            self.comm_key,
+            self.comm.as_ref().unwrap(),
+            &self.t,
+            &mut writer,
+        )?;
+        self.sp
+            .as_ref()
+            .unwrap()
+            .challenge_contribution(&mut writer)?;
+        Ok(())
+    }
+
+    pub fn gen_proof_contribution<E: Pairing<G1Affine = G>>(
+        &mut self,
+        challenge: &G::ScalarField,
+    ) -> Result<StatementProof<E>, ProofSystemError> {
+        if self.sp.is_none() {
+            return Err(ProofSystemError::SubProtocolNotReadyToGenerateProof(
+                self.id,
+            ));
+        }
+        let index = self.index.take().unwrap();
+        let mut challenges = core::mem::take(&mut self.challenges);
+        let mut responses = core::mem::take(&mut self.responses);
+        // Challenge for the real proof is whatever remains after subtracting the simulated proofs' challenges
+        let c = challenges
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != index)
+            .fold(*challenge, |acc, (_, c_i)| acc - c_i);
+        challenges[index] = c;
+        responses[index] = self.blinding.take().unwrap() + c * self.randomness.take().unwrap();
+        // Don't generate response for index 0 since its response will come from proofs of one of the signatures.
+        let skip_for = BTreeSet::from([0]);
+        Ok(StatementProof::PublicSetMembership(
+            PublicSetMembershipProof {
+                comm: self.comm.take().unwrap(),
+                t: core::mem::take(&mut self.t),
+                challenges,
+                responses,
+                sp: self
+                    .sp
+                    .take()
+                    .unwrap()
+                    .gen_partial_proof_contribution_as_struct(challenge, &skip_for)?,
+            },
+        ))
+    }
+
+    pub fn verify_proof_contribution(
+        &self,
+        challenge: &G::ScalarField,
+        proof: &PublicSetMembershipProof<G>,
+        comm_key_as_slice: &[G],
+        resp_for_message: G::ScalarField,
+    ) -> Result<(), ProofSystemError> {
+        if self.set.is_empty() {
+            return Err(ProofSystemError::EmptyPublicSet(self.id));
+        }
+        let n = self.set.len();
+        if proof.t.len() != n || proof.challenges.len() != n || proof.responses.len() != n {
+            return Err(ProofSystemError::PublicSetMembershipProofFailed(self.id));
+        }
+        let challenge_sum = proof
+            .challenges
+            .iter()
+            .fold(G::ScalarField::zero(), |acc, c| acc + c);
+        if challenge_sum != *challenge {
+            return Err(ProofSystemError::PublicSetMembershipProofFailed(self.id));
+        }
+        let comm = proof.comm.into_group();
+        for (((v, t), c), z) in self
+            .set
+            .iter()
+            .zip(proof.t.iter())
+            .zip(proof.challenges.iter())
+            .zip(proof.responses.iter())
+        {
+            if self.comm_key.h * z - (comm - self.comm_key.g * v) * c != t.into_group() {
+                return Err(ProofSystemError::PublicSetMembershipProofFailed(self.id));
+            }
+        }
+
+        let missing_resp = BTreeMap::from([(0, resp_for_message)]);
+        // NOTE: value of id is dummy
+        let sp = SchnorrProtocol::new(10000, comm_key_as_slice, proof.comm);
+
+        sp.verify_partial_proof_contribution(challenge, &proof.sp, missing_resp)
+            .map_err(|e| ProofSystemError::SchnorrProofContributionFailed(self.id as u32, e))
+    }
+
+    pub fn compute_challenge_contribution<W: Write>(
+        comm_key_as_slice: &[G],
+        proof: &PublicSetMembershipProof<G>,
+        set: &[G::ScalarField],
+        comm_key: &PedersenCommitmentKey<G>,
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        Self::set_membership_challenge_contribution(
+            set,
+            comm_key,
+            &proof.comm,
+            &proof.t,
+            &mut writer,
+        )?;
+        comm_key_as_slice.serialize_compressed(&mut writer)?;
+        proof.comm.serialize_compressed(&mut writer)?;
+        proof.sp.t.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+
+    /// The set and the commitment are part of the challenge so that the proof can't be used with a different
+    /// set or commitment.
+    fn set_membership_challenge_contribution<W: Write>(
+        set: &[G::ScalarField],
+        comm_key: &PedersenCommitmentKey<G>,
+        comm: &G,
+        t: &[G],
+        mut writer: W,
+    ) -> Result<(), ProofSystemError> {
+        comm_key.g.serialize_compressed(&mut writer)?;
+        comm_key.h.serialize_compressed(&mut writer)?;
+        set.serialize_compressed(&mut writer)?;
+        comm.serialize_compressed(&mut writer)?;
+        t.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+}
@@ -173,7 +173,9 @@ impl<'a, E: Pairing> R1CSLegogroth16Protocol<'a, E> {
         let snark_proof = &proof.snark_proof;
         match pairing_checker {
             Some(c) => {
-                let d = calculate_d(pvk, snark_proof, inputs)?;
+                let d = calculate_d(pvk, snark_proof, inputs).map_err(|e| {
+                    ProofSystemError::LegoSnarkProofContributionFailed(self.id as u32, e)
+                })?;
                 c.add_multiple_sources_and_target(
                     &[snark_proof.a, snark_proof.c, d],
                     [
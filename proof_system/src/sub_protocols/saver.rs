@@ -12,8 +12,9 @@ use ark_ec::{
 };
 use ark_ff::{PrimeField, Zero};
 use ark_groth16::{PreparedVerifyingKey, VerifyingKey};
-use ark_serialize::CanonicalSerialize;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{
+    cfg_into_iter, cfg_iter,
     collections::{BTreeMap, BTreeSet},
     io::Write,
     ops::Add,
@@ -22,7 +23,13 @@ use ark_std::{
     vec::Vec,
     UniformRand,
 };
-use dock_crypto_utils::{ff::powers, randomized_pairing_check::RandomizedPairingChecker};
+use digest::Digest;
+#[cfg(feature = "serde")]
+use dock_crypto_utils::serde_utils::ArkObjectBytes;
+use dock_crypto_utils::{
+    ff::powers, hashing_utils::field_elem_from_try_and_incr,
+    randomized_pairing_check::RandomizedPairingChecker,
+};
 use saver::{
     commitment::ChunkedCommitment,
     encryption::{Ciphertext, Encryption},
@@ -32,6 +39,11 @@ use saver::{
     setup::PreparedEncryptionGens,
     utils::decompose,
 };
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 /// Apart from the SAVER protocol (encryption and snark proof), this also runs 3 Schnorr proof of knowledge protocols
 #[derive(Clone, Debug, PartialEq)]
@@ -258,13 +270,18 @@ impl<'a, E: Pairing> SaverProtocol<'a, E> {
     ) -> Result<(), ProofSystemError> {
         let pek = pek.into();
         let pgens = pgens.into();
-        let expected_count = pek.supported_chunks_count()? as usize;
+        let expected_count = pek
+            .supported_chunks_count()
+            .map_err(|e| ProofSystemError::SaverProofContributionFailed(self.id as u32, e))?
+            as usize;
         if proof.ciphertext.enc_chunks.len() != expected_count {
-            return Err(SaverError::IncompatibleEncryptionKey(
-                proof.ciphertext.enc_chunks.len(),
-                expected_count,
-            )
-            .into());
+            return Err(ProofSystemError::SaverProofContributionFailed(
+                self.id as u32,
+                SaverError::IncompatibleEncryptionKey(
+                    proof.ciphertext.enc_chunks.len(),
+                    expected_count,
+                ),
+            ));
         }
         match pairing_checker {
             Some(c) => {
@@ -277,7 +294,9 @@ impl<'a, E: Pairing> SaverProtocol<'a, E> {
                     Encryption::get_g2_for_ciphertext_commitment_pairing_checks(&pek, &pgens),
                 );
                 c.add_multiple_sources_and_target(&a, b, &PairingOutput::zero());
-                let d = calculate_d(pvk, &proof.ciphertext)?;
+                let d = calculate_d(pvk, &proof.ciphertext).map_err(|e| {
+                    ProofSystemError::SaverProofContributionFailed(self.id as u32, e)
+                })?;
                 c.add_multiple_sources_and_target(
                     &[proof.snark_proof.a, proof.snark_proof.c, d],
                     [
@@ -566,3 +585,141 @@ impl<'a, E: Pairing> SaverProtocol<'a, E> {
         Ok(())
     }
 }
+
+/// Proof that a SAVER ciphertext is a re-randomization of another ciphertext and thus encrypts the same
+/// message, created by `re_randomize_saver_ciphertext`. This is a Schnorr proof of knowledge of the
+/// randomness `r'` such that each of `X_r`, the encrypted chunks and the commitment of the new ciphertext
+/// differ from those of the old by `r'` times the corresponding element `X_0`, `X_i` or `P_1` of the
+/// encryption key.
+#[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct SaverCiphertextLinkProof<E: Pairing> {
+    /// Commitments to the blinding, one for each element of the ciphertext
+    #[cfg_attr(feature = "serde", serde_as(as = "Vec<ArkObjectBytes>"))]
+    pub t: Vec<E::G1Affine>,
+    #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))]
+    pub response: E::ScalarField,
+}
+
+/// Re-randomize a SAVER `ciphertext` with `randomness` so that the result can't be linked to the original
+/// by anyone who doesn't see the returned link proof. As for `Encryption::rerandomize_ciphertext_and_proof`,
+/// the new ciphertext decrypts to the same message with the same decryption key. The link proof convinces
+/// a verifier who knows both ciphertexts that they encrypt the same message. The randomness should be
+/// random and non-zero, as a zero randomness leaves the ciphertext unchanged.
+pub fn re_randomize_saver_ciphertext<R: RngCore, E: Pairing, D: Digest>(
+    rng: &mut R,
+    ciphertext: &Ciphertext<E>,
+    randomness: &E::ScalarField,
+    encryption_key: &EncryptionKey<E>,
+) -> Result<(Ciphertext<E>, SaverCiphertextLinkProof<E>), ProofSystemError> {
+    let bases = SaverCiphertextLinkProof::bases(ciphertext, encryption_key)?;
+    let randomness_repr = randomness.into_bigint();
+    let old_elements = SaverCiphertextLinkProof::elements(ciphertext);
+    let mut new_elements = E::G1::normalize_batch(
+        &cfg_iter!(bases)
+            .zip(cfg_iter!(old_elements))
+            .map(|(b, c)| b.mul_bigint(randomness_repr).add(c))
+            .collect::<Vec<_>>(),
+    );
+    let commitment = new_elements.pop().unwrap();
+    let x_r = new_elements.remove(0);
+    let new_ciphertext = Ciphertext {
+        X_r: x_r,
+        enc_chunks: new_elements,
+        commitment,
+    };
+
+    let blinding = E::ScalarField::rand(rng);
+    let blinding_repr = blinding.into_bigint();
+    let t = E::G1::normalize_batch(
+        &cfg_iter!(bases)
+            .map(|b| b.mul_bigint(blinding_repr))
+            .collect::<Vec<_>>(),
+    );
+    let challenge =
+        SaverCiphertextLinkProof::challenge::<D>(&bases, ciphertext, &new_ciphertext, &t)?;
+    Ok((
+        new_ciphertext,
+        SaverCiphertextLinkProof {
+            t,
+            response: blinding + challenge * randomness,
+        },
+    ))
+}
+
+impl<E: Pairing> SaverCiphertextLinkProof<E> {
+    /// Verify that `new_ciphertext` is a re-randomization of `old_ciphertext` using the same encryption key
+    pub fn verify<D: Digest>(
+        &self,
+        old_ciphertext: &Ciphertext<E>,
+        new_ciphertext: &Ciphertext<E>,
+        encryption_key: &EncryptionKey<E>,
+    ) -> Result<(), ProofSystemError> {
+        let bases = Self::bases(old_ciphertext, encryption_key)?;
+        if new_ciphertext.enc_chunks.len() != old_ciphertext.enc_chunks.len()
+            || self.t.len() != bases.len()
+        {
+            return Err(ProofSystemError::SaverCiphertextLinkProofFailed);
+        }
+        let challenge = Self::challenge::<D>(&bases, old_ciphertext, new_ciphertext, &self.t)?;
+        let response_repr = self.response.into_bigint();
+        let old_elements = Self::elements(old_ciphertext);
+        let new_elements = Self::elements(new_ciphertext);
+        // base * response == t + (new - old) * challenge
+        let valid = cfg_into_iter!(0..bases.len()).all(|i| {
+            bases[i].mul_bigint(response_repr)
+                == (new_elements[i].into_group() - old_elements[i]) * challenge + self.t[i]
+        });
+        if valid {
+            Ok(())
+        } else {
+            Err(ProofSystemError::SaverCiphertextLinkProofFailed)
+        }
+    }
+
+    /// The elements `X_0, X_1, ..., X_n, P_1` of the encryption key used to re-randomize the corresponding
+    /// elements of the ciphertext
+    fn bases(
+        ciphertext: &Ciphertext<E>,
+        encryption_key: &EncryptionKey<E>,
+    ) -> Result<Vec<E::G1Affine>, ProofSystemError> {
+        if ciphertext.enc_chunks.len() != encryption_key.X.len() {
+            return Err(ProofSystemError::SaverError(
+                SaverError::IncompatibleEncryptionKey(
+                    ciphertext.enc_chunks.len(),
+                    encryption_key.X.len(),
+                ),
+            ));
+        }
+        let mut bases = Vec::with_capacity(encryption_key.X.len() + 2);
+        bases.push(encryption_key.X_0);
+        bases.extend_from_slice(&encryption_key.X);
+        bases.push(encryption_key.P_1);
+        Ok(bases)
+    }
+
+    /// The elements `X_r`, the encrypted chunks and the commitment of the ciphertext
+    fn elements(ciphertext: &Ciphertext<E>) -> Vec<E::G1Affine> {
+        let mut elements = Vec::with_capacity(ciphertext.enc_chunks.len() + 2);
+        elements.push(ciphertext.X_r);
+        elements.extend_from_slice(&ciphertext.enc_chunks);
+        elements.push(ciphertext.commitment);
+        elements
+    }
+
+    fn challenge<D: Digest>(
+        bases: &[E::G1Affine],
+        old_ciphertext: &Ciphertext<E>,
+        new_ciphertext: &Ciphertext<E>,
+        t: &[E::G1Affine],
+    ) -> Result<E::ScalarField, ProofSystemError> {
+        let mut bytes = vec![];
+        bases.serialize_compressed(&mut bytes)?;
+        old_ciphertext.serialize_compressed(&mut bytes)?;
+        new_ciphertext.serialize_compressed(&mut bytes)?;
+        t.serialize_compressed(&mut bytes)?;
+        Ok(field_elem_from_try_and_incr::<E::ScalarField, D>(&bytes))
+    }
+}
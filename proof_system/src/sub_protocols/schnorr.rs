@@ -199,3 +199,19 @@ impl<'a, G: AffineRepr> SchnorrProtocol<'a, G> {
         Ok(())
     }
 }
+
+/// Verify a standalone proof of knowledge of the opening of `commitment` under the Pedersen commitment key
+/// `comm_key`, without the overhead of creating a `ProofSpec` and verifying a composite `Proof`. The proof is
+/// created with `SchnorrProtocol` and the caller computes `challenge`, like by hashing the bytes written by
+/// `SchnorrProtocol::challenge_contribution` along with any other context.
+pub fn verify_pedersen_opening<G: AffineRepr>(
+    comm_key: &[G],
+    commitment: &G,
+    proof: &PedersenCommitmentProof<G>,
+    challenge: &G::ScalarField,
+) -> Result<(), ProofSystemError> {
+    proof
+        .response
+        .is_valid(comm_key, commitment, &proof.t, challenge)?;
+    Ok(())
+}
@@ -0,0 +1,389 @@
+//! Transcript used by the prover and verifier to generate the challenge of the composite proof. With the
+//! `debug-transcript` feature, the transcript also records the bytes appended to it so that the prover's and
+//! verifier's transcripts can be compared when a proof unexpectedly fails to verify.
+
+use crate::{
+    constants::{
+        COMPOSITE_PROOF_CHALLENGE_LABEL, COMPOSITE_PROOF_LABEL, DOMAIN_SEPARATOR_LABEL,
+        EXTRA_TRANSCRIPT_DATA_LABEL, NONCE_LABEL,
+    },
+    proof_spec::ProofSpec,
+};
+use ark_ec::{pairing::Pairing, AffineRepr};
+use ark_ff::Field;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{
+    io::{Result as ArkResult, Write},
+    vec,
+    vec::Vec,
+};
+use dock_crypto_utils::{concat_slices, transcript::Transcript};
+
+#[cfg(not(feature = "debug-transcript"))]
+pub(crate) type ProofTranscript = dock_crypto_utils::transcript::MerlinTranscript;
+
+#[cfg(feature = "debug-transcript")]
+pub(crate) type ProofTranscript = recording::RecordingTranscript;
+
+/// Builds the transcript from which the challenge of the composite proof is generated and is used by both
+/// the prover and the verifier. The transcript gets the transcript label (if any), the nonce (if any), the
+/// context(s) of the `ProofSpec`, each statement's contribution and lastly the extra data appended with
+/// [`CompositeTranscriptBuilder::append_extra`], in that order. The extra data lets an application bind its
+/// own data, like a commitment created outside this crate, to the proof. The prover and the verifier must
+/// append the same extra data in the same order else the proof won't verify.
+#[derive(Clone, Debug, Default, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct CompositeTranscriptBuilder {
+    extras: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl CompositeTranscriptBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `bytes` under `label` to the transcript after the statements' contributions and before the
+    /// challenge is generated.
+    pub fn append_extra(&mut self, label: &[u8], bytes: &[u8]) -> &mut Self {
+        self.extras.push((label.to_vec(), bytes.to_vec()));
+        self
+    }
+
+    /// The extra data added so far as `(label, bytes)` pairs
+    pub fn extras(&self) -> &[(Vec<u8>, Vec<u8>)] {
+        &self.extras
+    }
+
+    /// Create the transcript and add the transcript label, `nonce` and context(s) of `proof_spec` to it
+    pub(crate) fn start<E: Pairing>(
+        &self,
+        transcript_label: Option<&Vec<u8>>,
+        nonce: Option<&Vec<u8>>,
+        proof_spec: &ProofSpec<E>,
+    ) -> ProofTranscript {
+        let mut transcript = ProofTranscript::new(COMPOSITE_PROOF_LABEL);
+        if let Some(label) = transcript_label {
+            transcript.append_message(DOMAIN_SEPARATOR_LABEL, label);
+        }
+        if let Some(n) = nonce {
+            transcript.append_message(NONCE_LABEL, n);
+        }
+        proof_spec.context_contribution(&mut transcript);
+        transcript
+    }
+
+    /// Add the extra data to `transcript`, once the statements have contributed to it, and generate the
+    /// challenge
+    pub(crate) fn challenge<F: Field>(&self, transcript: &mut ProofTranscript) -> F {
+        for (label, bytes) in &self.extras {
+            transcript.append_message_without_static_label(
+                &concat_slices!(EXTRA_TRANSCRIPT_DATA_LABEL, label),
+                bytes,
+            );
+        }
+        transcript.challenge_scalar(COMPOSITE_PROOF_CHALLENGE_LABEL)
+    }
+}
+
+/// Collects what a statement adds to the transcript so that the statements can be initialized independently of
+/// each other, like in parallel, and their contributions added to the proof's transcript in the order of the
+/// statements later. This gives the same transcript as when the statements add to it directly. No challenge
+/// can be generated from it as none is generated until all statements are added so requesting one gives a
+/// dummy value and is recorded, see `Self::challenge_requested`.
+#[derive(Default)]
+pub(crate) struct DeferredTranscript {
+    entries: Vec<DeferredEntry>,
+    challenge_requested: bool,
+}
+
+enum DeferredEntry {
+    Label(&'static [u8]),
+    Message(Vec<u8>, Vec<u8>),
+    /// Each write is kept separate as the transcript adds each write as a separate message
+    Write(Vec<u8>),
+}
+
+impl DeferredTranscript {
+    pub fn set_label(&mut self, label: &'static [u8]) {
+        self.entries.push(DeferredEntry::Label(label))
+    }
+
+    /// Whether a challenge was requested from this transcript. The caller must fail if so since the
+    /// challenge given was a dummy value.
+    pub fn challenge_requested(&self) -> bool {
+        self.challenge_requested
+    }
+
+    /// Add the collected contributions to `transcript`
+    pub fn apply_to(self, transcript: &mut ProofTranscript) {
+        for entry in self.entries {
+            match entry {
+                DeferredEntry::Label(label) => transcript.set_label(label),
+                DeferredEntry::Message(label, bytes) => {
+                    transcript.append_message_without_static_label(&label, &bytes)
+                }
+                DeferredEntry::Write(bytes) => transcript
+                    .write_all(&bytes)
+                    .expect("writing to transcript failed"),
+            }
+        }
+    }
+}
+
+impl Transcript for DeferredTranscript {
+    fn append<S: CanonicalSerialize>(&mut self, label: &'static [u8], element: &S) {
+        self.append_without_static_label(label, element)
+    }
+
+    fn append_without_static_label<S: CanonicalSerialize>(&mut self, label: &[u8], element: &S) {
+        // Same bytes as `MerlinTranscript::append_without_static_label` which serializes after a zeroed buffer
+        let mut bytes = vec![0; element.compressed_size()];
+        element
+            .serialize_compressed(&mut bytes)
+            .expect("serialization failed");
+        self.append_message_without_static_label(label, &bytes)
+    }
+
+    fn append_message(&mut self, label: &'static [u8], bytes: &[u8]) {
+        self.append_message_without_static_label(label, bytes)
+    }
+
+    fn append_message_without_static_label(&mut self, label: &[u8], bytes: &[u8]) {
+        self.entries
+            .push(DeferredEntry::Message(label.to_vec(), bytes.to_vec()))
+    }
+
+    fn challenge_bytes(&mut self, _label: &'static [u8], _dest: &mut [u8]) {
+        self.challenge_requested = true;
+    }
+
+    fn challenge_bytes_without_static_label(&mut self, _label: &[u8], _dest: &mut [u8]) {
+        self.challenge_requested = true;
+    }
+
+    fn challenge_scalar<F: Field>(&mut self, _label: &'static [u8]) -> F {
+        self.challenge_requested = true;
+        F::zero()
+    }
+
+    fn challenge_scalar_without_static_label<F: Field>(&mut self, _label: &[u8]) -> F {
+        self.challenge_requested = true;
+        F::zero()
+    }
+
+    fn challenge_scalars<F: Field>(&mut self, _label: &'static [u8], count: usize) -> Vec<F> {
+        self.challenge_requested = true;
+        vec![F::zero(); count]
+    }
+
+    fn challenge_scalars_without_static_label<F: Field>(
+        &mut self,
+        _label: &[u8],
+        count: usize,
+    ) -> Vec<F> {
+        self.challenge_requested = true;
+        vec![F::zero(); count]
+    }
+
+    fn challenge_group_elem<G: AffineRepr>(&mut self, _label: &'static [u8]) -> G {
+        self.challenge_requested = true;
+        G::zero()
+    }
+
+    fn challenge_group_elem_without_static_label<G: AffineRepr>(&mut self, _label: &[u8]) -> G {
+        self.challenge_requested = true;
+        G::zero()
+    }
+}
+
+impl Write for DeferredTranscript {
+    fn write(&mut self, data: &[u8]) -> ArkResult<usize> {
+        self.entries.push(DeferredEntry::Write(data.to_vec()));
+        Ok(data.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> ArkResult<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "debug-transcript")]
+mod recording {
+    use ark_ec::AffineRepr;
+    use ark_ff::Field;
+    use ark_serialize::CanonicalSerialize;
+    use ark_std::{
+        io::{Result as ArkResult, Write},
+        vec,
+        vec::Vec,
+    };
+    use dock_crypto_utils::transcript::{MerlinTranscript, Transcript};
+
+    /// A `MerlinTranscript` that also records each appended message as `label || message length as 4 byte
+    /// little endian || message`, which is how Merlin frames them.
+    pub(crate) struct RecordingTranscript {
+        inner: MerlinTranscript,
+        recorded: Vec<u8>,
+        /// Bytes recorded when the first challenge was generated, including the challenge's label
+        challenge_input: Option<Vec<u8>>,
+    }
+
+    impl RecordingTranscript {
+        pub fn new(label: &'static [u8]) -> Self {
+            Self {
+                inner: MerlinTranscript::new(label),
+                recorded: label.to_vec(),
+                challenge_input: None,
+            }
+        }
+
+        pub fn set_label(&mut self, label: &'static [u8]) {
+            self.inner.set_label(label)
+        }
+
+        /// Bytes fed to the transcript before the first challenge was generated. Empty if no challenge
+        /// was generated.
+        pub fn challenge_input(&self) -> Vec<u8> {
+            self.challenge_input.clone().unwrap_or_default()
+        }
+
+        fn record(&mut self, label: &[u8], message: &[u8]) {
+            self.recorded.extend_from_slice(label);
+            self.recorded
+                .extend_from_slice(&(message.len() as u32).to_le_bytes());
+            self.recorded.extend_from_slice(message);
+        }
+
+        fn record_challenge(&mut self, label: &[u8]) {
+            if self.challenge_input.is_none() {
+                let mut input = self.recorded.clone();
+                input.extend_from_slice(label);
+                self.challenge_input = Some(input);
+            }
+        }
+    }
+
+    fn to_bytes<S: CanonicalSerialize>(element: &S) -> Vec<u8> {
+        let mut buff: Vec<u8> = vec![0; element.compressed_size()];
+        element
+            .serialize_compressed(&mut buff)
+            .expect("serialization failed");
+        buff
+    }
+
+    impl Transcript for RecordingTranscript {
+        fn append<S: CanonicalSerialize>(&mut self, label: &'static [u8], element: &S) {
+            self.append_message(label, &to_bytes(element))
+        }
+
+        fn append_without_static_label<S: CanonicalSerialize>(
+            &mut self,
+            label: &[u8],
+            element: &S,
+        ) {
+            self.append_message_without_static_label(label, &to_bytes(element))
+        }
+
+        fn append_message(&mut self, label: &'static [u8], bytes: &[u8]) {
+            self.record(label, bytes);
+            self.inner.append_message(label, bytes)
+        }
+
+        fn append_message_without_static_label(&mut self, label: &[u8], bytes: &[u8]) {
+            self.record(label, bytes);
+            self.inner.append_message_without_static_label(label, bytes)
+        }
+
+        fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+            self.record_challenge(label);
+            self.inner.challenge_bytes(label, dest)
+        }
+
+        fn challenge_bytes_without_static_label(&mut self, label: &[u8], dest: &mut [u8]) {
+            self.record_challenge(label);
+            self.inner.challenge_bytes_without_static_label(label, dest)
+        }
+
+        fn challenge_scalar<F: Field>(&mut self, label: &'static [u8]) -> F {
+            self.record_challenge(label);
+            self.inner.challenge_scalar(label)
+        }
+
+        fn challenge_scalar_without_static_label<F: Field>(&mut self, label: &[u8]) -> F {
+            self.record_challenge(label);
+            self.inner.challenge_scalar_without_static_label(label)
+        }
+
+        fn challenge_scalars<F: Field>(&mut self, label: &'static [u8], count: usize) -> Vec<F> {
+            self.record_challenge(label);
+            self.inner.challenge_scalars(label, count)
+        }
+
+        fn challenge_scalars_without_static_label<F: Field>(
+            &mut self,
+            label: &[u8],
+            count: usize,
+        ) -> Vec<F> {
+            self.record_challenge(label);
+            self.inner
+                .challenge_scalars_without_static_label(label, count)
+        }
+
+        fn challenge_group_elem<G: AffineRepr>(&mut self, label: &'static [u8]) -> G {
+            self.record_challenge(label);
+            self.inner.challenge_group_elem(label)
+        }
+
+        fn challenge_group_elem_without_static_label<G: AffineRepr>(&mut self, label: &[u8]) -> G {
+            self.record_challenge(label);
+            self.inner.challenge_group_elem_without_static_label(label)
+        }
+    }
+
+    impl Write for RecordingTranscript {
+        fn write(&mut self, data: &[u8]) -> ArkResult<usize> {
+            let label = self.inner.next_label.clone();
+            self.record(&label, data);
+            self.inner.write(data)
+        }
+
+        #[inline]
+        fn flush(&mut self) -> ArkResult<()> {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::{Fr, G1Affine};
+
+    #[test]
+    fn deferred_transcript() {
+        let g = G1Affine::generator();
+
+        let mut direct = ProofTranscript::new(COMPOSITE_PROOF_LABEL);
+        direct.set_label(b"statement");
+        direct.append(b"g", &g);
+        direct.write_all(b"written").unwrap();
+
+        let mut deferred = DeferredTranscript::default();
+        deferred.set_label(b"statement");
+        deferred.append(b"g", &g);
+        deferred.write_all(b"written").unwrap();
+        assert!(!deferred.challenge_requested());
+
+        let mut transcript = ProofTranscript::new(COMPOSITE_PROOF_LABEL);
+        deferred.apply_to(&mut transcript);
+        assert_eq!(
+            transcript.challenge_scalar::<Fr>(COMPOSITE_PROOF_CHALLENGE_LABEL),
+            direct.challenge_scalar::<Fr>(COMPOSITE_PROOF_CHALLENGE_LABEL)
+        );
+
+        // Requesting a challenge doesn't panic but is recorded
+        let mut deferred = DeferredTranscript::default();
+        let _ = deferred.challenge_scalar::<Fr>(b"challenge");
+        assert!(deferred.challenge_requested());
+    }
+}
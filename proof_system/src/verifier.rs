@@ -1,17 +1,16 @@
 use crate::{
     constants::{
-        BBDT16_KVAC_LABEL, BBS_23_LABEL, BBS_PLUS_LABEL, COMPOSITE_PROOF_CHALLENGE_LABEL,
-        COMPOSITE_PROOF_LABEL, CONTEXT_LABEL, KB_POS_ACCUM_CDH_MEM_LABEL, KB_POS_ACCUM_MEM_LABEL,
-        KB_UNI_ACCUM_CDH_MEM_LABEL, KB_UNI_ACCUM_CDH_NON_MEM_LABEL, KB_UNI_ACCUM_MEM_LABEL,
-        KB_UNI_ACCUM_NON_MEM_LABEL, NONCE_LABEL, PS_LABEL, VB_ACCUM_CDH_MEM_LABEL,
-        VB_ACCUM_CDH_NON_MEM_LABEL, VB_ACCUM_MEM_LABEL, VB_ACCUM_NON_MEM_LABEL, VE_TZ_21_LABEL,
-        VE_TZ_21_ROBUST_LABEL,
+        BBDT16_KVAC_LABEL, BBS_23_LABEL, BBS_PLUS_LABEL, KB_POS_ACCUM_CDH_MEM_LABEL,
+        KB_POS_ACCUM_MEM_LABEL, KB_UNI_ACCUM_CDH_MEM_LABEL, KB_UNI_ACCUM_CDH_NON_MEM_LABEL,
+        KB_UNI_ACCUM_MEM_AND_NON_MEM_LABEL, KB_UNI_ACCUM_MEM_LABEL, KB_UNI_ACCUM_NON_MEM_LABEL,
+        PS_LABEL, VB_ACCUM_CDH_MEM_LABEL, VB_ACCUM_CDH_NON_MEM_LABEL, VB_ACCUM_MEM_LABEL,
+        VB_ACCUM_NON_MEM_LABEL, VE_TZ_21_LABEL, VE_TZ_21_ROBUST_LABEL,
     },
     error::ProofSystemError,
-    prelude::EqualWitnesses,
+    prelude::{EqualWitnesses, WitnessRef},
     proof::Proof,
-    proof_spec::{ProofSpec, SnarkpackSRS},
-    statement::Statement,
+    proof_spec::{DerivedPreparedParameters, PreparedProofSpec, ProofSpec, SnarkpackSRS},
+    statement::{Statement, Statements},
     statement_proof::StatementProof,
     sub_protocols::{
         accumulator::{
@@ -27,6 +26,7 @@ use crate::{
                 VBAccumulatorMembershipKVSubProtocol,
             },
             KBPositiveAccumulatorMembershipSubProtocol,
+            KBUniversalAccumulatorMembershipAndNonMembershipSubProtocol,
             KBUniversalAccumulatorMembershipSubProtocol,
             KBUniversalAccumulatorNonMembershipSubProtocol, VBAccumulatorMembershipSubProtocol,
             VBAccumulatorNonMembershipSubProtocol,
@@ -34,38 +34,50 @@ use crate::{
         bbdt16_kvac::PoKOfMACSubProtocol,
         bbs_23::PoKBBSSigG1SubProtocol as PoKBBSSig23G1SubProtocol,
         bbs_23_ietf::PoKBBSSigIETFG1SubProtocol as PoKBBSSig23IETFG1SubProtocol,
-        bbs_plus::PoKBBSSigG1SubProtocol,
+        bbs_plus::{reveals_all_messages, PoKBBSSigG1SubProtocol, RevealedBBSSigG1SubProtocol},
         bound_check_bpp::BoundCheckBppProtocol,
         bound_check_legogroth16::BoundCheckLegoGrothProtocol,
         bound_check_smc::BoundCheckSmcProtocol,
         bound_check_smc_with_kv::BoundCheckSmcWithKVProtocol,
-        inequality::InequalityProtocol,
+        inequality::{HiddenInequalityProtocol, InequalityProtocol},
         ps_signature::PSSignaturePoK,
+        public_set_membership::PublicSetMembershipProtocol,
         r1cs_legogorth16::R1CSLegogroth16Protocol,
         saver::SaverProtocol,
         schnorr::SchnorrProtocol,
         verifiable_encryption_tz_21::{dkgith_decls, rdkgith_decls, VeTZ21Protocol},
     },
+    transcript::{CompositeTranscriptBuilder, ProofTranscript},
 };
 use ark_ec::pairing::Pairing;
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Valid};
 use ark_std::{
     collections::{BTreeMap, BTreeSet},
-    format,
     rand::RngCore,
+    sync::atomic::{AtomicBool, Ordering},
     vec,
     vec::Vec,
 };
-use digest::Digest;
+use digest::{
+    consts::U64, generic_array::GenericArray, CtOutput, Digest, ExtendableOutput,
+    ExtendableOutputReset, FixedOutput, FixedOutputReset, HashMarker, Output, OutputSizeUser,
+    Reset, Update,
+};
+#[cfg(feature = "serde")]
+use dock_crypto_utils::serde_utils::ArkObjectBytes;
 use dock_crypto_utils::{
-    aliases::FullDigest,
-    expect_equality,
-    randomized_pairing_check::RandomizedPairingChecker,
+    aliases::FullDigest, expect_equality, randomized_pairing_check::RandomizedPairingChecker,
     signature::MultiMessageSignatureParams,
-    transcript::{MerlinTranscript, Transcript},
 };
-use saver::encryption::Ciphertext;
-use sha3::Shake256;
+use saver::{
+    encryption::Ciphertext,
+    prelude::{PreparedDecryptionKey, PreparedEncryptionGens, SecretKey, VerifyingKey},
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha3::{Sha3_256, Sha3_512, Shake256};
+#[cfg(feature = "timing")]
+use std::time::{Duration, Instant};
 
 /// Passed to the verifier during proof verification
 #[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize, Default)]
@@ -73,18 +85,195 @@ pub struct VerifierConfig {
     /// Uses `RandomizedPairingChecker` to speed up pairing checks.
     /// If true, uses lazy `RandomizedPairingChecker` that trades-off memory for compute time
     pub use_lazy_randomized_pairing_checks: Option<bool>,
+    /// Overrides `use_lazy_randomized_pairing_checks` for specific statements, keyed by statement index.
+    /// Has no effect if `use_lazy_randomized_pairing_checks` is `None`. For aggregated SNARK proofs, the
+    /// choice for the first statement in the aggregation applies.
+    pub per_statement_laziness: Option<BTreeMap<usize, bool>>,
+    /// Application specific domain separator for the proof's transcript. Must be the same as the one
+    /// in the `ProverConfig` used to create the proof.
+    pub transcript_label: Option<Vec<u8>>,
+    /// When true, all group elements in the statements and the proof are checked to be on the curve and in
+    /// the prime order subgroup before verification. Useful when they were deserialized without validation.
+    pub check_subgroup_membership: bool,
+    /// Extra data to add to the proof's transcript. Must have the same data as the one in the `ProverConfig`
+    /// used to create the proof.
+    pub transcript_builder: CompositeTranscriptBuilder,
+    /// A proof spec without any statements is rejected with `ProofSystemError::NoStatements` as such a proof
+    /// proves nothing and is almost always a bug. Set to true to verify such proofs.
+    pub allow_empty_proof: bool,
+    /// When true, verifying without a nonce fails with `ProofSystemError::NonceRequired`. Without a nonce, a
+    /// proof can be replayed to any verifier expecting the same `ProofSpec`. Proofs that aren't bound to the
+    /// nonce, like revealed BBS+ signatures accepted with `reveal_all_fast_path`, are rejected with
+    /// `ProofSystemError::ReplayableProofWithNonceRequired`.
+    pub require_nonce: bool,
+    /// When verifying aggregated SAVER proofs, verify the ciphertexts of an aggregation group in chunks of
+    /// this size rather than all at once, which bounds the memory used for groups with many ciphertexts.
+    /// `None` verifies all ciphertexts of a group at once.
+    pub saver_batch_chunk: Option<usize>,
+    /// Accept the signature itself as the proof for a BBS+ statement that reveals all the signed messages,
+    /// as created by a prover with `ProverConfig::reveal_all_fast_path`. Such a proof isn't bound to the
+    /// nonce so it can be replayed by anyone who has seen it, and is rejected if `require_nonce` is set.
+    pub reveal_all_fast_path: bool,
+}
+
+/// Hash function to use as the digest when verifying a proof with `Proof::verify_with_algorithm`. Lets an
+/// application that records the hash function used by the prover, like in the proof's metadata, pick it at
+/// runtime. The prover must have used the corresponding digest type as `D` in `Proof::new`. Currently the digest
+/// only matters for the verifiable encryption statements using TZ21. Blake2b and Blake3 are only available with
+/// the `blake2` and `blake3` features respectively.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HashAlgorithm {
+    /// `blake2::Blake2b512`
+    #[cfg(feature = "blake2")]
+    Blake2b512,
+    /// `blake3::Hasher` with a 32 byte output
+    #[cfg(feature = "blake3")]
+    Blake3,
+    /// `Shake256Digest`, i.e. Shake256 with a 64 byte output
+    Shake256,
+}
+
+/// Shake256 with its output fixed to 64 bytes so that it can be used as the digest `D` of `Proof::new` and
+/// `Proof::verify`
+#[derive(Clone, Debug, Default)]
+pub struct Shake256Digest(Shake256);
+
+impl HashMarker for Shake256Digest {}
+
+impl OutputSizeUser for Shake256Digest {
+    type OutputSize = U64;
+}
+
+impl Update for Shake256Digest {
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data)
+    }
+}
+
+impl FixedOutput for Shake256Digest {
+    fn finalize_into(self, out: &mut Output<Self>) {
+        self.0.finalize_xof_into(out)
+    }
+}
+
+impl Reset for Shake256Digest {
+    fn reset(&mut self) {
+        Reset::reset(&mut self.0)
+    }
+}
+
+impl FixedOutputReset for Shake256Digest {
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        self.0.finalize_xof_reset_into(out)
+    }
+}
+
+/// State of a verification after the challenge of the proof has been generated, returned by
+/// `Proof::verify_challenge_phase`. Verification is completed later using `Proof::verify_from_checkpoint`, like
+/// after waiting for a key needed for verifying some statement. The checkpoint is authenticated with a key known
+/// only to the verifier so that its challenge can be trusted when resuming rather than generated again.
+#[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct VerificationCheckpoint<E: Pairing> {
+    /// Challenge of the proof
+    #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))]
+    pub challenge: E::ScalarField,
+    /// Nonce the challenge was generated with. Needed again when the transcript has to be recreated.
+    pub nonce: Option<Vec<u8>>,
+    /// Hash of the serialized proof
+    pub proof_digest: Vec<u8>,
+    /// Keyed hash over the rest of the checkpoint, the proof spec and the transcript label and builder of the
+    /// `VerifierConfig`, created with the verifier's checkpoint key
+    pub tag: Vec<u8>,
+}
+
+/// Time taken by each phase of verifying a proof, returned by `Proof::verify_with_timings`
+#[cfg(feature = "timing")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VerificationReport {
+    /// Time taken to add each statement's contribution to the challenge, indexed by the statement index
+    pub challenge_contribution: Vec<Duration>,
+    /// Time taken to verify each statement's proof, indexed by the statement index. When randomized pairing
+    /// checks are used, the pairings of a statement are only accumulated here and checked in `pairing_check`.
+    pub verification: Vec<Duration>,
+    /// Time taken to verify the aggregated SNARK proofs. Zero if there are none.
+    pub snark_aggregation: Duration,
+    /// Time taken by the final randomized pairing check. Zero if randomized pairing checks aren't used.
+    pub pairing_check: Duration,
+}
+
+/// Phase of verification timed by `Timer`
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(not(feature = "timing"), allow(dead_code))]
+enum TimedPhase {
+    ChallengeContribution(usize),
+    Verification(usize),
+    SnarkAggregation,
+    PairingCheck,
+}
+
+/// Start time of a phase being timed by `Timer`
+#[derive(Clone, Copy, Debug)]
+struct TimerStart(#[cfg(feature = "timing")] Option<Instant>);
+
+/// Records the time taken by each phase of verification into a `VerificationReport`. Does nothing unless
+/// created by `Proof::verify_with_timings` so that verifying isn't slowed down otherwise.
+#[derive(Default)]
+struct Timer {
+    #[cfg(feature = "timing")]
+    report: Option<VerificationReport>,
+}
+
+impl Timer {
+    #[cfg(feature = "timing")]
+    fn start(&self) -> TimerStart {
+        TimerStart(self.report.as_ref().map(|_| Instant::now()))
+    }
+
+    #[cfg(not(feature = "timing"))]
+    fn start(&self) -> TimerStart {
+        TimerStart()
+    }
+
+    #[cfg(feature = "timing")]
+    fn record(&mut self, phase: TimedPhase, start: TimerStart) {
+        if let (Some(report), Some(start)) = (self.report.as_mut(), start.0) {
+            let elapsed = start.elapsed();
+            match phase {
+                TimedPhase::ChallengeContribution(i) => report.challenge_contribution[i] = elapsed,
+                TimedPhase::Verification(i) => report.verification[i] = elapsed,
+                TimedPhase::SnarkAggregation => report.snark_aggregation = elapsed,
+                TimedPhase::PairingCheck => report.pairing_check = elapsed,
+            }
+        }
+    }
+
+    #[cfg(not(feature = "timing"))]
+    fn record(&mut self, _phase: TimedPhase, _start: TimerStart) {}
 }
 
+#[cfg(not(feature = "lite-errors"))]
 macro_rules! err_incompat_proof {
     ($s_idx:ident, $s: ident, $proof: ident) => {
         return Err(ProofSystemError::ProofIncompatibleWithStatement(
             $s_idx,
-            format!("{:?}", $proof),
-            format!("{:?}", $s),
+            ark_std::format!("{:?}", $proof),
+            ark_std::format!("{:?}", $s),
         ))
     };
 }
 
+/// Avoids formatting the statement and proof in the error, for verifiers where binary size matters
+#[cfg(feature = "lite-errors")]
+macro_rules! err_incompat_proof {
+    ($s_idx:ident, $s: ident, $proof: ident) => {{
+        let _ = (&$s, &$proof);
+        return Err(ProofSystemError::ProofIncompatibleWithStatementLite($s_idx));
+    }};
+}
+
 /*macro_rules! check_resp_for_equalities {
     ($witness_equalities:ident, $s_idx: ident, $p: expr, $func_name: ident, $self: ident, $responses_for_equalities: ident) => {
         for i in 0..$witness_equalities.len() {
@@ -132,23 +321,466 @@ impl<E: Pairing> Proof<E> {
         nonce: Option<Vec<u8>>,
         config: VerifierConfig,
     ) -> Result<(), ProofSystemError> {
-        match config.use_lazy_randomized_pairing_checks {
-            Some(b) => {
-                let pairing_checker = RandomizedPairingChecker::new_using_rng(rng, b);
-                self._verify::<R, D>(rng, proof_spec, nonce, Some(pairing_checker))
+        let prepared = proof_spec.prepare()?;
+        self._verify::<R, D>(
+            rng,
+            &prepared,
+            nonce,
+            config,
+            BTreeSet::new(),
+            None,
+            &mut Timer::default(),
+        )
+    }
+
+    /// Same as `Self::verify` but stops before verifying the next statement and returns
+    /// `ProofSystemError::Cancelled` once `cancel` is set to true, like from another thread when the result
+    /// is no longer needed. Any pairing checks accumulated till then are discarded.
+    pub fn verify_cancellable<R: RngCore, D: FullDigest + Digest>(
+        self,
+        rng: &mut R,
+        proof_spec: ProofSpec<E>,
+        nonce: Option<Vec<u8>>,
+        config: VerifierConfig,
+        cancel: &AtomicBool,
+    ) -> Result<(), ProofSystemError> {
+        let prepared = proof_spec.prepare()?;
+        self._verify::<R, D>(
+            rng,
+            &prepared,
+            nonce,
+            config,
+            BTreeSet::new(),
+            Some(cancel),
+            &mut Timer::default(),
+        )
+    }
+
+    /// Same as `Self::verify` but also returns the time taken by each statement's challenge contribution
+    /// and verification and by the final pairing check, which helps find the statements that dominate the
+    /// cost of verification.
+    #[cfg(feature = "timing")]
+    pub fn verify_with_timings<R: RngCore, D: FullDigest + Digest>(
+        self,
+        rng: &mut R,
+        proof_spec: ProofSpec<E>,
+        nonce: Option<Vec<u8>>,
+        config: VerifierConfig,
+    ) -> Result<VerificationReport, ProofSystemError> {
+        let prepared = proof_spec.prepare()?;
+        let count = prepared.spec.statements.len();
+        let mut timer = Timer {
+            report: Some(VerificationReport {
+                challenge_contribution: vec![Duration::ZERO; count],
+                verification: vec![Duration::ZERO; count],
+                ..Default::default()
+            }),
+        };
+        self._verify::<R, D>(
+            rng,
+            &prepared,
+            nonce,
+            config,
+            BTreeSet::new(),
+            None,
+            &mut timer,
+        )?;
+        Ok(timer.report.unwrap_or_default())
+    }
+
+    /// Same as `Self::verify` but the digest is chosen at runtime using `algorithm`
+    pub fn verify_with_algorithm<R: RngCore>(
+        self,
+        rng: &mut R,
+        proof_spec: ProofSpec<E>,
+        nonce: Option<Vec<u8>>,
+        config: VerifierConfig,
+        algorithm: HashAlgorithm,
+    ) -> Result<(), ProofSystemError> {
+        match algorithm {
+            #[cfg(feature = "blake2")]
+            HashAlgorithm::Blake2b512 => {
+                self.verify::<R, blake2::Blake2b512>(rng, proof_spec, nonce, config)
+            }
+            #[cfg(feature = "blake3")]
+            HashAlgorithm::Blake3 => {
+                self.verify::<R, blake3::Hasher>(rng, proof_spec, nonce, config)
+            }
+            HashAlgorithm::Shake256 => {
+                self.verify::<R, Shake256Digest>(rng, proof_spec, nonce, config)
             }
-            None => self._verify::<R, D>(rng, proof_spec, nonce, None),
         }
     }
 
-    fn _verify<R: RngCore, D: FullDigest + Digest>(
+    /// Same as `Self::verify` but uses a `PreparedProofSpec` so that the same spec can be used to verify
+    /// several proofs, like ones with different nonces, without deriving its parameters again for each proof.
+    pub fn verify_using_prepared<R: RngCore, D: FullDigest + Digest>(
+        self,
+        prepared: &PreparedProofSpec<E>,
+        rng: &mut R,
+        nonce: Option<Vec<u8>>,
+        config: VerifierConfig,
+    ) -> Result<(), ProofSystemError> {
+        self._verify::<R, D>(
+            rng,
+            prepared,
+            nonce,
+            config,
+            BTreeSet::new(),
+            None,
+            &mut Timer::default(),
+        )
+    }
+
+    /// Same as `Self::verify` but uses the given prepared pairing parameters, like prepared verifying keys of
+    /// SNARKs and accumulator params, rather than deriving them from the `proof_spec`. These must have been
+    /// created by `ProofSpec::derive_prepared_parameters_for_reuse` on a spec with the same statements and
+    /// setup params else `ProofSystemError::PreparedParametersMismatch` is returned.
+    pub fn verify_with_prepared_parameters<R: RngCore, D: FullDigest + Digest>(
+        self,
+        rng: &mut R,
+        proof_spec: ProofSpec<E>,
+        prepared_params: DerivedPreparedParameters<E>,
+        nonce: Option<Vec<u8>>,
+        config: VerifierConfig,
+    ) -> Result<(), ProofSystemError> {
+        let prepared = proof_spec.prepare_with_parameters(prepared_params)?;
+        self._verify::<R, D>(
+            rng,
+            &prepared,
+            nonce,
+            config,
+            BTreeSet::new(),
+            None,
+            &mut Timer::default(),
+        )
+    }
+
+    /// Same as `Self::verify` but does not verify the proofs of statements in `statements_to_skip`. Their
+    /// contribution is still part of the challenge. A statement can only be skipped if none of its witnesses
+    /// are proven equal to witnesses of statements being verified and its SNARK proof isn't being aggregated
+    /// as otherwise the verified statements might depend on an unverified one.
+    pub fn verify_selective<R: RngCore, D: FullDigest + Digest>(
         self,
         rng: &mut R,
         proof_spec: ProofSpec<E>,
         nonce: Option<Vec<u8>>,
-        mut pairing_checker: Option<RandomizedPairingChecker<E>>,
+        config: VerifierConfig,
+        statements_to_skip: BTreeSet<usize>,
+    ) -> Result<(), ProofSystemError> {
+        for s_idx in &statements_to_skip {
+            if *s_idx >= proof_spec.statements.len() {
+                return Err(ProofSystemError::InvalidStatementProofIndex(*s_idx));
+            }
+        }
+        for eq in proof_spec.meta_statements.disjoint_witness_equalities() {
+            let skipped =
+                eq.0.iter()
+                    .find(|(s_idx, _)| statements_to_skip.contains(s_idx));
+            if let Some((s_idx, _)) = skipped {
+                if eq.0.iter().any(|(i, _)| !statements_to_skip.contains(i)) {
+                    return Err(ProofSystemError::CannotSkipLinkedStatement(*s_idx));
+                }
+            }
+        }
+        for s_ids in proof_spec
+            .aggregate_groth16
+            .iter()
+            .chain(proof_spec.aggregate_legogroth16.iter())
+            .flatten()
+        {
+            if let Some(s_idx) = s_ids.intersection(&statements_to_skip).next() {
+                return Err(ProofSystemError::CannotSkipLinkedStatement(*s_idx));
+            }
+        }
+        let prepared = proof_spec.prepare()?;
+        self._verify::<R, D>(
+            rng,
+            &prepared,
+            nonce,
+            config,
+            statements_to_skip,
+            None,
+            &mut Timer::default(),
+        )
+    }
+
+    /// First half of `Self::verify` that does the checks not needing the challenge and generates the
+    /// challenge. Returns a `VerificationCheckpoint` which can be persisted and passed later to
+    /// `Self::verify_from_checkpoint`, along with this proof, to complete the verification. None of the
+    /// statements' proofs are verified here. `checkpoint_key` authenticates the checkpoint and must be kept
+    /// secret by the verifier as anyone knowing it can forge a checkpoint with any challenge.
+    pub fn verify_challenge_phase(
+        &self,
+        proof_spec: ProofSpec<E>,
+        nonce: Option<Vec<u8>>,
+        config: VerifierConfig,
+        checkpoint_key: &[u8; 32],
+    ) -> Result<VerificationCheckpoint<E>, ProofSystemError> {
+        let prepared = proof_spec.prepare()?;
+        self.check_before_verification(&prepared, nonce.as_ref(), &config)?;
+        let mut transcript = self.challenge_contribution(
+            &prepared,
+            nonce.as_ref(),
+            config.transcript_label.as_ref(),
+            &config.transcript_builder,
+            None,
+            &mut Timer::default(),
+        )?;
+        let challenge = config.transcript_builder.challenge(&mut transcript);
+        let mut checkpoint = VerificationCheckpoint {
+            challenge,
+            nonce,
+            proof_digest: self.digest_for_checkpoint()?,
+            tag: vec![],
+        };
+        checkpoint.tag = Self::checkpoint_tag(checkpoint_key, &checkpoint, &prepared, &config)?
+            .into_bytes()
+            .to_vec();
+        Ok(checkpoint)
+    }
+
+    /// Second half of `Self::verify` that verifies the statements' proofs using the challenge from `checkpoint`
+    /// which must have been created by `Self::verify_challenge_phase` for this proof with the same
+    /// `checkpoint_key`. `proof_spec` and `config` must be the same as the ones given to
+    /// `Self::verify_challenge_phase`. The result is the same as verifying the proof with `Self::verify`. As the
+    /// checkpoint is authenticated, its challenge is used without generating it again except when a statement,
+    /// like a bulletproofs++ bound check or TZ21 verifiable encryption, needs the transcript after the challenge.
+    pub fn verify_from_checkpoint<R: RngCore, D: FullDigest + Digest>(
+        self,
+        rng: &mut R,
+        proof_spec: ProofSpec<E>,
+        checkpoint: VerificationCheckpoint<E>,
+        config: VerifierConfig,
+        checkpoint_key: &[u8; 32],
     ) -> Result<(), ProofSystemError> {
+        let prepared = proof_spec.prepare()?;
+        self.check_before_verification(&prepared, checkpoint.nonce.as_ref(), &config)?;
+        if checkpoint.tag.len() != <Sha3_256 as OutputSizeUser>::output_size()
+            || Self::checkpoint_tag(checkpoint_key, &checkpoint, &prepared, &config)?
+                != CtOutput::new(GenericArray::clone_from_slice(&checkpoint.tag))
+            || checkpoint.proof_digest != self.digest_for_checkpoint()?
+        {
+            return Err(ProofSystemError::InvalidVerificationCheckpoint);
+        }
+        let transcript = if Self::needs_transcript(&prepared.spec) {
+            let mut transcript = self.challenge_contribution(
+                &prepared,
+                checkpoint.nonce.as_ref(),
+                config.transcript_label.as_ref(),
+                &config.transcript_builder,
+                None,
+                &mut Timer::default(),
+            )?;
+            let _ = config
+                .transcript_builder
+                .challenge::<E::ScalarField>(&mut transcript);
+            Some(transcript)
+        } else {
+            None
+        };
+        self.verify_given_challenge::<R, D>(
+            rng,
+            &prepared,
+            config,
+            BTreeSet::new(),
+            checkpoint.challenge,
+            transcript,
+            None,
+            &mut Timer::default(),
+        )
+    }
+
+    /// Get the response for each witness equality in `proof_spec`, keyed by the index of the equality in
+    /// `proof_spec.meta_statements.disjoint_witness_equalities()`. The response for an equality is taken from
+    /// the first statement proof (in statement order) that carries one, which is the response the verifier
+    /// checks the other statements' proofs against. Equalities whose witnesses are only in statements that
+    /// don't carry their own responses, like accumulator membership, are absent from the returned map.
+    ///
+    /// This is an advanced API meant for linking this proof to a Schnorr proof created outside this crate
+    /// that uses the same challenge. It does not verify the proof so the returned responses should only be
+    /// relied upon once the proof has been verified against the same `proof_spec`.
+    pub fn collect_equality_responses(
+        &self,
+        proof_spec: &ProofSpec<E>,
+    ) -> Result<BTreeMap<usize, E::ScalarField>, ProofSystemError> {
         proof_spec.validate()?;
+        if proof_spec.statements.len() > self.statement_proofs.len() {
+            return Err(ProofSystemError::UnsatisfiedStatements(
+                proof_spec.statements.len(),
+                self.statement_proofs.len(),
+            ));
+        }
+        let disjoint_equalities = if proof_spec.meta_statements.is_empty() {
+            vec![]
+        } else {
+            proof_spec.meta_statements.disjoint_witness_equalities()
+        };
+        let mut resp_for_equalities = BTreeMap::<usize, E::ScalarField>::new();
+        for (s_idx, (statement, proof)) in proof_spec
+            .statements
+            .0
+            .iter()
+            .zip(self.statement_proofs.iter())
+            .enumerate()
+        {
+            for (i, eq) in disjoint_equalities.iter().enumerate() {
+                if resp_for_equalities.contains_key(&i) {
+                    continue;
+                }
+                for (_, w_id) in eq.0.iter().filter(|(j, _)| *j == s_idx) {
+                    if let Some(r) = Self::get_resp_from_statement_proof(statement, proof, *w_id)? {
+                        resp_for_equalities.insert(i, r);
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(resp_for_equalities)
+    }
+
+    /// Get the Schnorr response for the witness referenced by `wit_ref` from the proof of its statement.
+    /// Returns `None` if that statement's proof doesn't carry a response for the witness. The response is
+    /// `blinding + challenge * witness`, so when the prover supplied the blinding using
+    /// `ProverConfig::witness_blindings`, it can be compared with the response of a Schnorr proof created
+    /// outside this crate using the same blinding and challenge.
+    ///
+    /// Like `Proof::collect_equality_responses`, this does not verify the proof.
+    pub fn get_response_for_witness(
+        &self,
+        proof_spec: &ProofSpec<E>,
+        wit_ref: WitnessRef,
+    ) -> Result<Option<E::ScalarField>, ProofSystemError> {
+        let (s_idx, w_idx) = wit_ref;
+        let statement = proof_spec
+            .statements
+            .0
+            .get(s_idx)
+            .ok_or(ProofSystemError::InvalidStatementProofIndex(s_idx))?;
+        let proof = self
+            .statement_proofs
+            .get(s_idx)
+            .ok_or(ProofSystemError::InvalidStatementProofIndex(s_idx))?;
+        Self::get_resp_from_statement_proof(statement, proof, w_idx)
+    }
+
+    /// Compute the challenge of this proof for `proof_spec` and `nonce` without verifying the proof.
+    pub fn compute_challenge(
+        &self,
+        proof_spec: ProofSpec<E>,
+        nonce: Option<Vec<u8>>,
+        config: VerifierConfig,
+    ) -> Result<E::ScalarField, ProofSystemError> {
+        let prepared = proof_spec.prepare()?;
+        if prepared.spec.statements.len() > self.statement_proofs.len() {
+            return Err(ProofSystemError::UnsatisfiedStatements(
+                prepared.spec.statements.len(),
+                self.statement_proofs.len(),
+            ));
+        }
+        let mut transcript = self.challenge_contribution(
+            &prepared,
+            nonce.as_ref(),
+            config.transcript_label.as_ref(),
+            &config.transcript_builder,
+            None,
+            &mut Timer::default(),
+        )?;
+        Ok(config.transcript_builder.challenge(&mut transcript))
+    }
+
+    /// Get the bytes the verifier feeds to the transcript before generating the challenge. When a proof
+    /// unexpectedly fails to verify, comparing these with the bytes returned by `Proof::new_with_debug_challenge_bytes`
+    /// for the prover shows whether and where the prover's and verifier's transcripts diverge. Only meant for
+    /// debugging and doesn't verify the proof.
+    #[cfg(feature = "debug-transcript")]
+    pub fn debug_challenge_bytes(
+        &self,
+        proof_spec: ProofSpec<E>,
+        nonce: Option<Vec<u8>>,
+        config: VerifierConfig,
+    ) -> Result<Vec<u8>, ProofSystemError> {
+        let prepared = proof_spec.prepare()?;
+        if prepared.spec.statements.len() > self.statement_proofs.len() {
+            return Err(ProofSystemError::UnsatisfiedStatements(
+                prepared.spec.statements.len(),
+                self.statement_proofs.len(),
+            ));
+        }
+        let mut transcript = self.challenge_contribution(
+            &prepared,
+            nonce.as_ref(),
+            config.transcript_label.as_ref(),
+            &config.transcript_builder,
+            None,
+            &mut Timer::default(),
+        )?;
+        let _ = config
+            .transcript_builder
+            .challenge::<E::ScalarField>(&mut transcript);
+        Ok(transcript.challenge_input())
+    }
+
+    fn _verify<R: RngCore, D: FullDigest + Digest>(
+        self,
+        rng: &mut R,
+        prepared: &PreparedProofSpec<E>,
+        nonce: Option<Vec<u8>>,
+        config: VerifierConfig,
+        statements_to_skip: BTreeSet<usize>,
+        cancel: Option<&AtomicBool>,
+        timer: &mut Timer,
+    ) -> Result<(), ProofSystemError> {
+        self.check_before_verification(prepared, nonce.as_ref(), &config)?;
+
+        let mut transcript = self.challenge_contribution(
+            prepared,
+            nonce.as_ref(),
+            config.transcript_label.as_ref(),
+            &config.transcript_builder,
+            cancel,
+            timer,
+        )?;
+
+        // Verifier independently generates challenge
+        let challenge = config.transcript_builder.challenge(&mut transcript);
+
+        self.verify_given_challenge::<R, D>(
+            rng,
+            prepared,
+            config,
+            statements_to_skip,
+            challenge,
+            Some(transcript),
+            cancel,
+            timer,
+        )
+    }
+
+    /// Checks on the proof and the proof spec that don't need the challenge, done before any statement's
+    /// proof is looked at
+    fn check_before_verification(
+        &self,
+        prepared: &PreparedProofSpec<E>,
+        nonce: Option<&Vec<u8>>,
+        config: &VerifierConfig,
+    ) -> Result<(), ProofSystemError> {
+        let proof_spec = &prepared.spec;
+
+        if proof_spec.statements.is_empty() && !config.allow_empty_proof {
+            return Err(ProofSystemError::NoStatements);
+        }
+        if nonce.is_none() && config.require_nonce {
+            return Err(ProofSystemError::NonceRequired);
+        }
+        if config.require_nonce {
+            // A revealed signature is the same in every presentation so a nonce can't make it fresh
+            for (s_idx, proof) in self.statement_proofs.iter().enumerate() {
+                if let StatementProof::RevealedBBSSignatureG1(_) = proof {
+                    return Err(ProofSystemError::ReplayableProofWithNonceRequired(s_idx));
+                }
+            }
+        }
 
         // Number of statement proofs is less than number of statements which means some statements
         // are not satisfied.
@@ -158,13 +790,77 @@ impl<E: Pairing> Proof<E> {
                 self.statement_proofs.len(),
             ));
         }
+        // Statement proofs without a corresponding statement would otherwise be silently ignored, letting
+        // the same proof be padded with arbitrary data.
+        if self.statement_proofs.len() > proof_spec.statements.len() {
+            return Err(ProofSystemError::ExtraStatementProofs(
+                self.statement_proofs.len(),
+                proof_spec.statements.len(),
+            ));
+        }
+
+        if config.check_subgroup_membership {
+            self.check_group_elements(&proof_spec.statements)?;
+        }
+
+        let aggregate_snarks =
+            proof_spec.aggregate_groth16.is_some() || proof_spec.aggregate_legogroth16.is_some();
+
+        if aggregate_snarks {
+            // Fail before doing any per statement work if the verifier's SRS isn't given
+            if !matches!(
+                proof_spec.snark_aggregation_srs,
+                Some(SnarkpackSRS::VerifierSrs(_))
+            ) {
+                return Err(ProofSystemError::SnarkpackSrsNotProvided);
+            }
+            // Check SNARK SRS is compatible and statement proofs are compatible with the proof spec when aggregating
+            proof_spec.validate_snark_aggregation()?;
+            for s_ids in proof_spec
+                .aggregate_groth16
+                .iter()
+                .chain(proof_spec.aggregate_legogroth16.iter())
+                .flatten()
+            {
+                for s_idx in s_ids {
+                    match self.statement_proofs.get(*s_idx) {
+                        Some(StatementProof::SaverWithAggregation(_))
+                        | Some(StatementProof::BoundCheckLegoGroth16WithAggregation(_))
+                        | Some(StatementProof::R1CSLegoGroth16WithAggregation(_)) => (),
+                        _ => return Err(ProofSystemError::StatementProofNotForAggregation(*s_idx)),
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
 
-        let mut transcript = MerlinTranscript::new(COMPOSITE_PROOF_LABEL);
+    /// Verify the statements' proofs and any aggregated SNARK proofs given the proof's `challenge`. `transcript`
+    /// is the one the challenge was generated from and must be given when `Self::needs_transcript` is true.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_given_challenge<R: RngCore, D: FullDigest + Digest>(
+        self,
+        rng: &mut R,
+        prepared: &PreparedProofSpec<E>,
+        config: VerifierConfig,
+        statements_to_skip: BTreeSet<usize>,
+        challenge: E::ScalarField,
+        mut transcript: Option<ProofTranscript>,
+        cancel: Option<&AtomicBool>,
+        timer: &mut Timer,
+    ) -> Result<(), ProofSystemError> {
+        let mut pairing_checker = config
+            .use_lazy_randomized_pairing_checks
+            .map(|b| RandomizedPairingChecker::new_using_rng(rng, b));
+        let per_statement_laziness = config.per_statement_laziness;
+        let saver_batch_chunk = config.saver_batch_chunk;
+        let reveal_all_fast_path = config.reveal_all_fast_path;
 
-        // TODO: Check SNARK SRSs compatible when aggregating and statement proof compatible with proof spec when aggregating
+        let proof_spec = &prepared.spec;
 
         let aggregate_snarks =
             proof_spec.aggregate_groth16.is_some() || proof_spec.aggregate_legogroth16.is_some();
+
         let mut agg_saver = Vec::<Vec<Ciphertext<E>>>::new();
         let mut agg_lego = Vec::<(Vec<E::G1Affine>, Vec<Vec<E::ScalarField>>)>::new();
 
@@ -191,7 +887,7 @@ impl<E: Pairing> Proof<E> {
             }
         }
 
-        // Prepare commitment keys for running Schnorr protocols of all statements.
+        // Commitment keys for running Schnorr protocols of all statements.
         let (
             bound_check_comm,
             ek_comm,
@@ -200,9 +896,9 @@ impl<E: Pairing> Proof<E> {
             bound_check_bpp_comm,
             bound_check_smc_comm,
             ineq_comm,
-        ) = proof_spec.derive_commitment_keys()?;
+        ) = &prepared.commitment_keys;
 
-        // Prepare required parameters for pairings
+        // Prepared parameters for pairings
         let (
             derived_lego_vk,
             derived_gens,
@@ -218,1695 +914,2271 @@ impl<E: Pairing> Proof<E> {
             derived_ps_pk,
             derived_bbs_param,
             derived_smc_param,
-        ) = proof_spec.derive_prepared_parameters()?;
+        ) = &prepared.prepared_params;
 
         // All the distinct equalities in `ProofSpec`
-        // let mut witness_equalities = vec![];
-        let mut disjoint_equalities = vec![];
-        if !proof_spec.meta_statements.is_empty() {
-            disjoint_equalities = proof_spec.meta_statements.disjoint_witness_equalities();
-        }
+        let disjoint_equalities = &prepared.disjoint_equalities;
 
-        // Get nonce's and context's challenge contribution
-        if let Some(n) = nonce.as_ref() {
-            transcript.append_message(NONCE_LABEL, n);
-        }
-        if let Some(ctx) = &proof_spec.context {
-            transcript.append_message(CONTEXT_LABEL, ctx);
+        // This will hold the response for each witness equality.
+        let mut resp_for_equalities = BTreeMap::<usize, E::ScalarField>::new();
+
+        macro_rules! get_missing_responses_for_sigs_and_update_resp_eq_map {
+            ($s: ident, $s_idx: ident, $total_msgs: expr, $proof: ident) => {{
+                let mut missing_responses = BTreeMap::new();
+                for w_id in 0..$total_msgs {
+                    let wit_ref = ($s_idx, w_id);
+                    for (i, eq) in disjoint_equalities.iter().enumerate() {
+                        if eq.has_wit_ref(&wit_ref) {
+                            if let Some(r) = resp_for_equalities.get(&i) {
+                                missing_responses.insert(w_id, *r);
+                            } else {
+                                let revealed_idx = BTreeSet::<usize>::from_iter(
+                                    $s.revealed_messages.keys().cloned(),
+                                );
+                                resp_for_equalities
+                                    .insert(i, *$proof.get_resp_for_message(w_id, &revealed_idx)?);
+                            }
+                            // Exit loop because equalities are disjoint
+                            break;
+                        }
+                    }
+                }
+                missing_responses
+            }};
         }
 
-        macro_rules! sig_protocol_chal_gen {
-            ($s: ident, $s_idx: ident, $p: ident, $label: ident) => {{
-                let params = $s.get_params(&proof_spec.setup_params, $s_idx)?;
-                transcript.set_label($label);
-                $p.challenge_contribution(&$s.revealed_messages, params, &mut transcript)?;
+        macro_rules! get_missing_responses_ped_comm_and_update_resp_eq_map {
+            ($s: ident, $s_idx: ident, $total_msgs: expr, $proof: ident) => {{
+                let mut missing_responses = BTreeMap::new();
+                for w_id in 0..$total_msgs {
+                    let wit_ref = ($s_idx, w_id);
+                    for (i, eq) in disjoint_equalities.iter().enumerate() {
+                        if eq.has_wit_ref(&wit_ref) {
+                            if let Some(r) = resp_for_equalities.get(&i) {
+                                missing_responses.insert(w_id, *r);
+                            } else {
+                                resp_for_equalities.insert(i, *$proof.get_resp_for_message(w_id)?);
+                            }
+                            // Exit loop because equalities are disjoint
+                            break;
+                        }
+                    }
+                }
+                missing_responses
             }};
         }
 
-        macro_rules! ped_comm_protocol_check_resp_and_chal_gen {
-            ($s: ident, $s_idx: ident, $p: ident, $com_key_func: ident) => {{
-                let comm_key = $s.$com_key_func(&proof_spec.setup_params, $s_idx)?;
-                SchnorrProtocol::compute_challenge_contribution(
-                    comm_key,
-                    &$s.commitment,
-                    &$p.t,
-                    &mut transcript,
-                )?;
+        macro_rules! update_resp_eq_map {
+            ($s: ident, $s_idx: ident, $total_msgs: expr, $proof: ident) => {{
+                for w_id in 0..$total_msgs {
+                    let wit_ref = ($s_idx, w_id);
+                    for (i, eq) in disjoint_equalities.iter().enumerate() {
+                        if eq.has_wit_ref(&wit_ref) {
+                            if resp_for_equalities.get(&i).is_none() {
+                                resp_for_equalities.insert(i, *$proof.get_resp_for_message(w_id)?);
+                            }
+                            // Exit loop because equalities are disjoint
+                            break;
+                        }
+                    }
+                }
             }};
         }
 
-        macro_rules! accum_cdh_protocol_chal_gen {
-            ($s: ident, $s_idx: ident, $p: ident, $label: ident) => {{
-                transcript.set_label($label);
-                $p.challenge_contribution(&$s.accumulator_value, &mut transcript)?;
+        macro_rules! sig_protocol_verify {
+            ($s: ident, $s_idx: ident, $protocol: ident, $func_name: ident, $p: ident, $derived_pk: ident, $derived_param: ident, $error_variant: ident) => {{
+                let params = $s.get_params(&proof_spec.setup_params, $s_idx)?;
+                let pk = $s.get_public_key(&proof_spec.setup_params, $s_idx)?;
+                let sp = $protocol::$func_name($s_idx, &$s.revealed_messages, params, pk);
+                let missing_responses = get_missing_responses_for_sigs_and_update_resp_eq_map!(
+                    $s,
+                    $s_idx,
+                    params.supported_message_count(),
+                    $p
+                );
+                if missing_responses.is_empty() {
+                    sp.verify_proof_contribution(
+                        &challenge,
+                        $p,
+                        $derived_pk.try_get($s_idx)?.clone(),
+                        $derived_param.try_get($s_idx)?.clone(),
+                        &mut pairing_checker,
+                    )
+                    .map_err(|e| ProofSystemError::$error_variant($s_idx as u32, e))?
+                } else {
+                    sp.verify_partial_proof_contribution(
+                        &challenge,
+                        $p,
+                        $derived_pk.try_get($s_idx)?.clone(),
+                        $derived_param.try_get($s_idx)?.clone(),
+                        &mut pairing_checker,
+                        missing_responses,
+                    )
+                    .map_err(|e| ProofSystemError::$error_variant($s_idx as u32, e))?
+                }
             }};
         }
 
-        // Get challenge contribution for each statement and check if response is equal for all witnesses.
+        macro_rules! tz_21_verify {
+            ($s: ident, $s_idx: ident, $p: ident, $func_name: ident) => {
+                let comm_key = $s.get_comm_key(&proof_spec.setup_params, $s_idx)?;
+                let enc_params = $s.get_enc_params(&proof_spec.setup_params, $s_idx)?;
+                let sp = VeTZ21Protocol::new($s_idx, comm_key.as_slice(), enc_params);
+                // Won't have response for all indices except for last one since their responses will come from proofs of the signatures.
+                let mut missing_resps = BTreeMap::new();
+                // The last witness is the randomness of the commitment so skip that
+                for i in 0..$p.ve_proof.witness_count() - 1 {
+                    missing_resps.insert(
+                        i,
+                        Self::get_resp_for_message(
+                            $s_idx,
+                            i,
+                            disjoint_equalities,
+                            &resp_for_equalities,
+                        )?,
+                    );
+                }
+                sp.$func_name::<D>(&challenge, $p, transcript.as_mut().unwrap(), missing_resps)?
+            }
+        }
+
+        let default_laziness = pairing_checker.as_ref().map(|c| c.is_lazy());
+
+        // Verify the proof for each statement
         for (s_idx, (statement, proof)) in proof_spec
             .statements
             .0
             .iter()
-            .zip(self.statement_proofs.iter())
+            .zip(self.statement_proofs.into_iter())
             .enumerate()
         {
+            Self::check_cancelled(cancel)?;
+            if statements_to_skip.contains(&s_idx) {
+                continue;
+            }
+            let start = timer.start();
+            Self::set_up_checker_for_statement(
+                &mut pairing_checker,
+                &per_statement_laziness,
+                default_laziness,
+                s_idx,
+            );
             match statement {
                 Statement::PoKBBSSignatureG1Verifier(s) => match proof {
-                    StatementProof::PoKBBSSignatureG1(p) => {
-                        sig_protocol_chal_gen!(s, s_idx, p, BBS_PLUS_LABEL);
+                    StatementProof::PoKBBSSignatureG1(ref p) => {
+                        sig_protocol_verify!(
+                            s,
+                            s_idx,
+                            PoKBBSSigG1SubProtocol,
+                            new_for_verifier,
+                            p,
+                            derived_bbs_pk,
+                            derived_bbs_plus_param,
+                            BBSPlusProofContributionFailed
+                        );
+                    }
+                    StatementProof::RevealedBBSSignatureG1(ref sig) if reveal_all_fast_path => {
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        if !reveals_all_messages::<E>(
+                            &s.revealed_messages,
+                            params.supported_message_count(),
+                        ) {
+                            err_incompat_proof!(s_idx, s, proof)
+                        }
+                        let sp =
+                            RevealedBBSSigG1SubProtocol::new(s_idx, &s.revealed_messages, params);
+                        sp.verify_proof_contribution(
+                            sig,
+                            derived_bbs_pk.try_get(s_idx)?.clone(),
+                            derived_bbs_plus_param.try_get(s_idx)?.clone(),
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::PoKBBSSignature23G1Verifier(s) => match proof {
-                    StatementProof::PoKBBSSignature23G1(p) => {
-                        sig_protocol_chal_gen!(s, s_idx, p, BBS_23_LABEL);
+                    StatementProof::PoKBBSSignature23G1(ref p) => {
+                        sig_protocol_verify!(
+                            s,
+                            s_idx,
+                            PoKBBSSig23G1SubProtocol,
+                            new_for_verifier,
+                            p,
+                            derived_bbs_pk,
+                            derived_bbs_param,
+                            BBSProofContributionFailed
+                        );
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::PoKBBSSignature23IETFG1Verifier(s) => match proof {
-                    StatementProof::PoKBBSSignature23IETFG1(p) => {
-                        sig_protocol_chal_gen!(s, s_idx, p, BBS_23_LABEL);
+                    StatementProof::PoKBBSSignature23IETFG1(ref p) => {
+                        sig_protocol_verify!(
+                            s,
+                            s_idx,
+                            PoKBBSSig23IETFG1SubProtocol,
+                            new_for_verifier,
+                            p,
+                            derived_bbs_pk,
+                            derived_bbs_param,
+                            BBSProofContributionFailed
+                        );
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::VBAccumulatorMembership(s) => match proof {
-                    StatementProof::VBAccumulatorMembership(p) => {
+                    StatementProof::VBAccumulatorMembership(ref p) => {
                         let params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
                         let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        transcript.set_label(VB_ACCUM_MEM_LABEL);
-                        p.challenge_contribution(
-                            &s.accumulator_value,
-                            pk,
+                        let sp = VBAccumulatorMembershipSubProtocol::new(
+                            s_idx,
                             params,
+                            pk,
                             prk,
-                            &mut transcript,
-                        )?;
+                            s.accumulator_value,
+                        );
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            p,
+                            derived_accum_pk.try_get(s_idx)?.clone(),
+                            derived_accum_param.try_get(s_idx)?.clone(),
+                            &mut pairing_checker,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::VBAccumulatorNonMembership(s) => match proof {
-                    StatementProof::VBAccumulatorNonMembership(p) => {
+                    StatementProof::VBAccumulatorNonMembership(ref p) => {
                         let params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
                         let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        transcript.set_label(VB_ACCUM_NON_MEM_LABEL);
-                        p.challenge_contribution(
-                            &s.accumulator_value,
-                            pk,
+                        let sp = VBAccumulatorNonMembershipSubProtocol::new(
+                            s_idx,
                             params,
+                            pk,
                             prk,
-                            &mut transcript,
-                        )?;
+                            s.accumulator_value,
+                        );
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            p,
+                            derived_accum_pk.try_get(s_idx)?.clone(),
+                            derived_accum_param.try_get(s_idx)?.clone(),
+                            &mut pairing_checker,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::KBUniversalAccumulatorMembership(s) => match proof {
-                    StatementProof::KBUniversalAccumulatorMembership(p) => {
+                    StatementProof::KBUniversalAccumulatorMembership(ref p) => {
                         let params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
                         let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        transcript.set_label(KB_UNI_ACCUM_MEM_LABEL);
-                        p.challenge_contribution(
-                            &s.accumulator_value,
-                            pk,
+                        let sp = KBUniversalAccumulatorMembershipSubProtocol::new(
+                            s_idx,
                             params,
+                            pk,
                             prk,
-                            &mut transcript,
-                        )?;
+                            s.accumulator_value,
+                        );
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            p,
+                            derived_accum_pk.try_get(s_idx)?.clone(),
+                            derived_accum_param.try_get(s_idx)?.clone(),
+                            &mut pairing_checker,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::KBUniversalAccumulatorNonMembership(s) => match proof {
-                    StatementProof::KBUniversalAccumulatorNonMembership(p) => {
+                    StatementProof::KBUniversalAccumulatorNonMembership(ref p) => {
                         let params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
                         let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        transcript.set_label(KB_UNI_ACCUM_NON_MEM_LABEL);
-                        p.challenge_contribution(
-                            &s.accumulator_value,
-                            pk,
+                        let sp = KBUniversalAccumulatorNonMembershipSubProtocol::new(
+                            s_idx,
                             params,
+                            pk,
                             prk,
-                            &mut transcript,
-                        )?;
-                    }
-                    _ => err_incompat_proof!(s_idx, s, proof),
-                },
-                Statement::VBAccumulatorMembershipCDHVerifier(s) => match proof {
-                    StatementProof::VBAccumulatorMembershipCDH(p) => {
-                        accum_cdh_protocol_chal_gen!(s, s_idx, p, VB_ACCUM_CDH_MEM_LABEL);
-                    }
-                    _ => err_incompat_proof!(s_idx, s, proof),
-                },
-                Statement::VBAccumulatorNonMembershipCDHVerifier(s) => match proof {
-                    StatementProof::VBAccumulatorNonMembershipCDH(p) => {
-                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
-                        transcript.set_label(VB_ACCUM_CDH_NON_MEM_LABEL);
-                        p.challenge_contribution(
-                            &s.accumulator_value,
-                            params,
-                            &s.Q,
-                            &mut transcript,
-                        )?;
-                    }
-                    _ => err_incompat_proof!(s_idx, s, proof),
-                },
-                Statement::KBUniversalAccumulatorMembershipCDHVerifier(s) => match proof {
-                    StatementProof::KBUniversalAccumulatorMembershipCDH(p) => {
-                        accum_cdh_protocol_chal_gen!(s, s_idx, p, KB_UNI_ACCUM_CDH_MEM_LABEL);
-                    }
-                    _ => err_incompat_proof!(s_idx, s, proof),
-                },
-                Statement::KBUniversalAccumulatorNonMembershipCDHVerifier(s) => match proof {
-                    StatementProof::KBUniversalAccumulatorNonMembershipCDH(p) => {
-                        accum_cdh_protocol_chal_gen!(s, s_idx, p, KB_UNI_ACCUM_CDH_NON_MEM_LABEL);
+                            s.accumulator_value,
+                        );
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            p,
+                            derived_accum_pk.try_get(s_idx)?.clone(),
+                            derived_accum_param.try_get(s_idx)?.clone(),
+                            &mut pairing_checker,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::KBPositiveAccumulatorMembership(s) => match proof {
-                    StatementProof::KBPositiveAccumulatorMembership(p) => {
+                Statement::KBUniversalAccumulatorMembershipAndNonMembership(s) => match proof {
+                    StatementProof::KBUniversalAccumulatorMembershipAndNonMembership(ref p) => {
                         let params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
                         let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        transcript.set_label(KB_POS_ACCUM_MEM_LABEL);
-                        p.challenge_contribution(
-                            &s.accumulator_value,
-                            pk,
+                        let sp = KBUniversalAccumulatorMembershipAndNonMembershipSubProtocol::new(
+                            s_idx,
                             params,
+                            pk,
                             prk,
-                            &mut transcript,
-                        )?;
+                            s.mem_accumulator_value,
+                            s.non_mem_accumulator_value,
+                        );
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            p,
+                            derived_accum_pk.try_get(s_idx)?.clone(),
+                            derived_accum_param.try_get(s_idx)?.clone(),
+                            &mut pairing_checker,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                1,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::KBPositiveAccumulatorMembershipCDH(s) => match proof {
-                    StatementProof::KBPositiveAccumulatorMembershipCDH(p) => {
+                Statement::VBAccumulatorMembershipCDHVerifier(s) => match proof {
+                    StatementProof::VBAccumulatorMembershipCDH(ref p) => {
                         let params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        transcript.set_label(KB_POS_ACCUM_CDH_MEM_LABEL);
-                        p.challenge_contribution(
-                            &s.accumulator_value,
-                            pk,
-                            params,
-                            prk,
-                            &mut transcript,
-                        )?;
-                    }
-                    _ => err_incompat_proof!(s_idx, s, proof),
-                },
-                Statement::PedersenCommitment(s) => match proof {
-                    StatementProof::PedersenCommitment(p) => {
-                        ped_comm_protocol_check_resp_and_chal_gen!(s, s_idx, p, get_commitment_key);
-                    }
-                    StatementProof::PedersenCommitmentPartial(p) => {
-                        ped_comm_protocol_check_resp_and_chal_gen!(s, s_idx, p, get_commitment_key);
-                    }
-                    _ => err_incompat_proof!(s_idx, s, proof),
-                },
-                Statement::PedersenCommitmentG2(s) => match proof {
-                    StatementProof::PedersenCommitmentG2(p) => {
-                        ped_comm_protocol_check_resp_and_chal_gen!(
-                            s,
-                            s_idx,
-                            p,
-                            get_commitment_key_g2
-                        );
-                    }
-                    StatementProof::PedersenCommitmentG2Partial(p) => {
-                        ped_comm_protocol_check_resp_and_chal_gen!(
-                            s,
+                        let sp = VBAccumulatorMembershipCDHSubProtocol::new_for_verifier(
                             s_idx,
-                            p,
-                            get_commitment_key_g2
+                            s.accumulator_value,
+                            params,
+                            pk,
                         );
-                    }
-                    _ => err_incompat_proof!(s_idx, s, proof),
-                },
-                Statement::SaverVerifier(s) => match proof {
-                    StatementProof::Saver(p) => {
-                        let ek_comm_key = ek_comm.get(s_idx).unwrap();
-                        let cc_keys = chunked_comm.get(s_idx).unwrap();
-                        SaverProtocol::compute_challenge_contribution(
-                            ek_comm_key,
-                            &cc_keys.0,
-                            &cc_keys.1,
-                            p,
-                            &mut transcript,
-                        )?;
-                    }
-                    StatementProof::SaverWithAggregation(p) => {
-                        let ek_comm_key = ek_comm.get(s_idx).unwrap();
-                        let cc_keys = chunked_comm.get(s_idx).unwrap();
-                        SaverProtocol::compute_challenge_contribution_when_aggregating_snark(
-                            ek_comm_key,
-                            &cc_keys.0,
-                            &cc_keys.1,
+                        sp.verify_proof_contribution(
+                            &challenge,
                             p,
-                            &mut transcript,
-                        )?;
+                            derived_accum_pk.try_get(s_idx)?.clone(),
+                            derived_accum_param.try_get(s_idx)?.clone(),
+                            &mut pairing_checker,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::BoundCheckLegoGroth16Verifier(s) => match proof {
-                    StatementProof::BoundCheckLegoGroth16(p) => {
-                        let comm_key = bound_check_comm.get(s_idx).unwrap();
-                        BoundCheckLegoGrothProtocol::compute_challenge_contribution(
-                            comm_key,
-                            p,
-                            &mut transcript,
-                        )?;
-                    }
-                    StatementProof::BoundCheckLegoGroth16WithAggregation(p) => {
-                        let comm_key = bound_check_comm.get(s_idx).unwrap();
-                        BoundCheckLegoGrothProtocol::compute_challenge_contribution_when_aggregating_snark(
-                            comm_key,
+                Statement::VBAccumulatorNonMembershipCDHVerifier(s) => match proof {
+                    StatementProof::VBAccumulatorNonMembershipCDH(ref p) => {
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = VBAccumulatorNonMembershipCDHSubProtocol::new_for_verifier(
+                            s_idx,
+                            s.accumulator_value,
+                            s.Q,
+                            params,
+                            pk,
+                        );
+                        sp.verify_proof_contribution(
+                            &challenge,
                             p,
-                            &mut transcript,
-                        )?;
+                            derived_accum_pk.try_get(s_idx)?.clone(),
+                            derived_accum_param.try_get(s_idx)?.clone(),
+                            &mut pairing_checker,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::R1CSCircomVerifier(s) => match proof {
-                    StatementProof::R1CSLegoGroth16(p) => {
-                        R1CSLegogroth16Protocol::compute_challenge_contribution(
-                            r1cs_comm_keys.get(s_idx).unwrap(),
+                Statement::KBUniversalAccumulatorMembershipCDHVerifier(s) => match proof {
+                    StatementProof::KBUniversalAccumulatorMembershipCDH(ref p) => {
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = KBUniversalAccumulatorMembershipCDHSubProtocol::new_for_verifier(
+                            s_idx,
+                            s.accumulator_value,
+                            params,
+                            pk,
+                        );
+                        sp.verify_proof_contribution(
+                            &challenge,
                             p,
-                            &mut transcript,
-                        )?;
-                    }
-                    StatementProof::R1CSLegoGroth16WithAggregation(p) => {
-                        R1CSLegogroth16Protocol::compute_challenge_contribution_when_aggregating_snark(
-                                r1cs_comm_keys.get(s_idx).unwrap(),
-                                p,
-                                &mut transcript,
-                            )?;
+                            derived_accum_pk.try_get(s_idx)?.clone(),
+                            derived_accum_param.try_get(s_idx)?.clone(),
+                            &mut pairing_checker,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::PoKPSSignature(s) => match proof {
-                    StatementProof::PoKPSSignature(p) => {
-                        let sig_params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                Statement::KBUniversalAccumulatorNonMembershipCDHVerifier(s) => match proof {
+                    StatementProof::KBUniversalAccumulatorNonMembershipCDH(ref p) => {
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        transcript.set_label(PS_LABEL);
-                        p.challenge_contribution(&mut transcript, pk, sig_params)?;
-                    }
-                    _ => err_incompat_proof!(s_idx, s, proof),
-                },
-                Statement::BoundCheckBpp(s) => match proof {
-                    StatementProof::BoundCheckBpp(p) => {
-                        let comm_key = bound_check_bpp_comm.get(s_idx).unwrap();
-                        BoundCheckBppProtocol::<E::G1Affine>::compute_challenge_contribution(
-                            s.min,
-                            s.max,
-                            comm_key.as_slice(),
+                        let sp =
+                            KBUniversalAccumulatorNonMembershipCDHSubProtocol::new_for_verifier(
+                                s_idx,
+                                s.accumulator_value,
+                                params,
+                                pk,
+                            );
+                        sp.verify_proof_contribution(
+                            &challenge,
                             p,
-                            &mut transcript,
-                        )?;
+                            derived_accum_pk.try_get(s_idx)?.clone(),
+                            derived_accum_param.try_get(s_idx)?.clone(),
+                            &mut pairing_checker,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::BoundCheckSmc(s) => match proof {
-                    StatementProof::BoundCheckSmc(p) => {
-                        let comm_key_slice = bound_check_smc_comm.get(s_idx).unwrap();
-                        BoundCheckSmcProtocol::compute_challenge_contribution(
-                            comm_key_slice.as_slice(),
+                Statement::KBPositiveAccumulatorMembership(s) => match proof {
+                    StatementProof::KBPositiveAccumulatorMembership(ref p) => {
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = KBPositiveAccumulatorMembershipSubProtocol::new(
+                            s_idx,
+                            params,
+                            pk,
+                            prk,
+                            s.accumulator_value,
+                        );
+                        sp.verify_proof_contribution(
+                            &challenge,
                             p,
-                            s.get_params_and_comm_key(&proof_spec.setup_params, s_idx)
-                                .unwrap(),
-                            &mut transcript,
-                        )?;
+                            derived_kb_accum_pk.try_get(s_idx)?.clone(),
+                            derived_kb_accum_param.try_get(s_idx)?.clone(),
+                            &mut pairing_checker,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::BoundCheckSmcWithKVVerifier(s) => match proof {
-                    StatementProof::BoundCheckSmcWithKV(p) => {
-                        let comm_key_slice = bound_check_smc_comm.get(s_idx).unwrap();
-                        BoundCheckSmcWithKVProtocol::compute_challenge_contribution(
-                            comm_key_slice.as_slice(),
+                Statement::KBPositiveAccumulatorMembershipCDH(s) => match proof {
+                    StatementProof::KBPositiveAccumulatorMembershipCDH(ref p) => {
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = KBPositiveAccumulatorMembershipCDHSubProtocol::new(
+                            s_idx,
+                            params,
+                            pk,
+                            prk,
+                            s.accumulator_value,
+                        );
+                        sp.verify_proof_contribution(
+                            &challenge,
                             p,
-                            s.get_params_and_comm_key_and_sk(&proof_spec.setup_params, s_idx)?,
-                            &mut transcript,
+                            derived_kb_accum_pk.try_get(s_idx)?.clone(),
+                            derived_kb_accum_param.try_get(s_idx)?.clone(),
+                            &mut pairing_checker,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
                         )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::PublicInequality(s) => match proof {
-                    StatementProof::Inequality(p) => {
-                        let comm_key_slice = ineq_comm.get(s_idx).unwrap();
-                        InequalityProtocol::compute_challenge_contribution(
+                Statement::PedersenCommitment(s) => match proof {
+                    StatementProof::PedersenCommitment(ref p) => {
+                        let comm_key = s.get_commitment_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = SchnorrProtocol::new(s_idx, comm_key, s.commitment);
+                        update_resp_eq_map!(s, s_idx, comm_key.len(), p);
+                        sp.verify_proof_contribution(&challenge, p).map_err(|e| {
+                            ProofSystemError::SchnorrProofContributionFailed(s_idx as u32, e)
+                        })?
+                    }
+                    StatementProof::PedersenCommitmentPartial(ref p) => {
+                        let comm_key = s.get_commitment_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = SchnorrProtocol::new(s_idx, comm_key, s.commitment);
+                        let missing_responses = get_missing_responses_ped_comm_and_update_resp_eq_map!(
+                            s,
+                            s_idx,
+                            comm_key.len(),
+                            p
+                        );
+                        if missing_responses.is_empty() {
+                            return Err(ProofSystemError::ResponseForWitnessNotFoundForStatement(
+                                sp.id,
+                            ));
+                        } else {
+                            sp.verify_partial_proof_contribution(&challenge, p, missing_responses)
+                                .map_err(|e| {
+                                    ProofSystemError::SchnorrProofContributionFailed(
+                                        s_idx as u32,
+                                        e,
+                                    )
+                                })?
+                        }
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::PedersenCommitmentG2(s) => match proof {
+                    StatementProof::PedersenCommitmentG2(ref p) => {
+                        let comm_key = s.get_commitment_key_g2(&proof_spec.setup_params, s_idx)?;
+                        let sp = SchnorrProtocol::new(s_idx, comm_key, s.commitment);
+                        update_resp_eq_map!(s, s_idx, comm_key.len(), p);
+                        sp.verify_proof_contribution(&challenge, p).map_err(|e| {
+                            ProofSystemError::SchnorrProofContributionFailed(s_idx as u32, e)
+                        })?
+                    }
+                    StatementProof::PedersenCommitmentG2Partial(ref p) => {
+                        let comm_key = s.get_commitment_key_g2(&proof_spec.setup_params, s_idx)?;
+                        let sp = SchnorrProtocol::new(s_idx, comm_key, s.commitment);
+                        let missing_responses = get_missing_responses_ped_comm_and_update_resp_eq_map!(
+                            s,
+                            s_idx,
+                            comm_key.len(),
+                            p
+                        );
+                        if missing_responses.is_empty() {
+                            return Err(ProofSystemError::ResponseForWitnessNotFoundForStatement(
+                                sp.id,
+                            ));
+                        } else {
+                            sp.verify_partial_proof_contribution(&challenge, p, missing_responses)
+                                .map_err(|e| {
+                                    ProofSystemError::SchnorrProofContributionFailed(
+                                        s_idx as u32,
+                                        e,
+                                    )
+                                })?
+                        }
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::SaverVerifier(s) => {
+                    let enc_gens = s.get_encryption_gens(&proof_spec.setup_params, s_idx)?;
+                    let comm_gens =
+                        s.get_chunked_commitment_gens(&proof_spec.setup_params, s_idx)?;
+                    let enc_key = s.get_encryption_key(&proof_spec.setup_params, s_idx)?;
+                    let vk = s.get_snark_verifying_key(&proof_spec.setup_params, s_idx)?;
+                    let sp = SaverProtocol::new_for_verifier(
+                        s_idx,
+                        s.chunk_bit_size,
+                        enc_gens,
+                        comm_gens,
+                        enc_key,
+                        vk,
+                    );
+                    let ek_comm_key = ek_comm.get(s_idx).unwrap();
+                    let cc_keys = chunked_comm.get(s_idx).unwrap();
+                    match proof {
+                        StatementProof::Saver(ref saver_proof) => sp.verify_proof_contribution(
+                            &challenge,
+                            saver_proof,
+                            ek_comm_key,
+                            &cc_keys.0,
+                            &cc_keys.1,
+                            derived_saver_vk.try_get(s_idx)?,
+                            derived_gens.try_get(s_idx)?.clone(),
+                            derived_ek.try_get(s_idx)?.clone(),
+                            &mut pairing_checker,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?,
+                        StatementProof::SaverWithAggregation(ref saver_proof) => {
+                            let agg_idx = agg_saver_stmts
+                                .get(&s_idx)
+                                .ok_or(ProofSystemError::InvalidStatementProofIndex(s_idx))?;
+                            agg_saver[*agg_idx].push(saver_proof.ciphertext.clone());
+                            sp.verify_ciphertext_and_commitment(
+                                &challenge,
+                                &saver_proof.ciphertext,
+                                saver_proof.comm_combined,
+                                saver_proof.comm_chunks,
+                                &saver_proof.sp_ciphertext,
+                                &saver_proof.sp_chunks,
+                                &saver_proof.sp_combined,
+                                ek_comm_key,
+                                &cc_keys.0,
+                                &cc_keys.1,
+                                Self::get_resp_for_message(
+                                    s_idx,
+                                    0,
+                                    disjoint_equalities,
+                                    &resp_for_equalities,
+                                )?,
+                            )?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    }
+                }
+                Statement::BoundCheckLegoGroth16Verifier(s) => {
+                    let verifying_key = s.get_verifying_key(&proof_spec.setup_params, s_idx)?;
+                    let sp = BoundCheckLegoGrothProtocol::new_for_verifier(
+                        s_idx,
+                        s.min,
+                        s.max,
+                        verifying_key,
+                    );
+                    let comm_key = bound_check_comm.get(s_idx).unwrap();
+                    match proof {
+                        StatementProof::BoundCheckLegoGroth16(ref bc_proof) => sp
+                            .verify_proof_contribution(
+                                &challenge,
+                                bc_proof,
+                                comm_key,
+                                derived_lego_vk.try_get(s_idx)?,
+                                &mut pairing_checker,
+                                Self::get_resp_for_message(
+                                    s_idx,
+                                    0,
+                                    disjoint_equalities,
+                                    &resp_for_equalities,
+                                )?,
+                            )?,
+                        StatementProof::BoundCheckLegoGroth16WithAggregation(ref bc_proof) => {
+                            let pub_inp =
+                                vec![E::ScalarField::from(sp.min), E::ScalarField::from(sp.max)];
+                            let agg_idx = agg_lego_stmts
+                                .get(&s_idx)
+                                .ok_or(ProofSystemError::InvalidStatementProofIndex(s_idx))?;
+                            agg_lego[*agg_idx].0.push(bc_proof.commitment);
+                            agg_lego[*agg_idx].1.push(pub_inp);
+                            sp.verify_proof_contribution_using_prepared_when_aggregating_snark(
+                                &challenge,
+                                bc_proof,
+                                comm_key,
+                                Self::get_resp_for_message(
+                                    s_idx,
+                                    0,
+                                    disjoint_equalities,
+                                    &resp_for_equalities,
+                                )?,
+                            )?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    }
+                }
+                Statement::R1CSCircomVerifier(s) => {
+                    let verifying_key = s.get_verifying_key(&proof_spec.setup_params, s_idx)?;
+                    let sp = R1CSLegogroth16Protocol::new_for_verifier(s_idx, verifying_key);
+                    let pub_inp = s
+                        .get_public_inputs(&proof_spec.setup_params, s_idx)?
+                        .to_vec();
+
+                    match proof {
+                        StatementProof::R1CSLegoGroth16(ref r1cs_proof) => {
+                            for w_id in 0..verifying_key.commit_witness_count as usize {
+                                let w_ref = (s_idx, w_id);
+                                for (i, eq) in disjoint_equalities.iter().enumerate() {
+                                    if eq.has_wit_ref(&w_ref) {
+                                        let resp =
+                                            r1cs_proof.get_schnorr_response_for_message(w_id)?;
+                                        if let Some(r) = resp_for_equalities.get(&i) {
+                                            if resp != r {
+                                                return Err(
+                                                    ProofSystemError::WitnessResponseNotEqual(
+                                                        s_idx, w_id,
+                                                    ),
+                                                );
+                                            }
+                                        } else {
+                                            resp_for_equalities.insert(i, *resp);
+                                        }
+                                    }
+                                }
+                            }
+                            sp.verify_proof_contribution(
+                                &challenge,
+                                &pub_inp,
+                                r1cs_proof,
+                                r1cs_comm_keys.get(s_idx).unwrap(),
+                                derived_lego_vk.try_get(s_idx)?,
+                                &mut pairing_checker,
+                            )?
+                        }
+                        StatementProof::R1CSLegoGroth16WithAggregation(ref r1cs_proof) => {
+                            let agg_idx = agg_lego_stmts
+                                .get(&s_idx)
+                                .ok_or(ProofSystemError::InvalidStatementProofIndex(s_idx))?;
+                            agg_lego[*agg_idx].0.push(r1cs_proof.commitment);
+                            agg_lego[*agg_idx].1.push(pub_inp);
+
+                            for w_id in 0..verifying_key.commit_witness_count as usize {
+                                let w_ref = (s_idx, w_id);
+                                for (i, eq) in disjoint_equalities.iter().enumerate() {
+                                    if eq.has_wit_ref(&w_ref) {
+                                        let resp =
+                                            r1cs_proof.get_schnorr_response_for_message(w_id)?;
+                                        if let Some(r) = resp_for_equalities.get(&i) {
+                                            if resp != r {
+                                                return Err(
+                                                    ProofSystemError::WitnessResponseNotEqual(
+                                                        s_idx, w_id,
+                                                    ),
+                                                );
+                                            }
+                                        } else {
+                                            resp_for_equalities.insert(i, *resp);
+                                        }
+                                    }
+                                }
+                            }
+
+                            sp.verify_proof_contribution_using_prepared_when_aggregating_snark(
+                                &challenge,
+                                r1cs_proof,
+                                r1cs_comm_keys.get(s_idx).unwrap(),
+                            )?
+                        }
+                        _ => err_incompat_proof!(s_idx, s, proof),
+                    }
+                }
+                Statement::PoKPSSignature(s) => match proof {
+                    StatementProof::PoKPSSignature(ref p) => {
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = PSSignaturePoK::new(s_idx, &s.revealed_messages, params, pk);
+                        // Check witness equalities for this statement.
+                        let revealed_msg_ids: Vec<_> =
+                            s.revealed_messages.keys().copied().collect();
+                        for w_id in 0..params.supported_message_count() {
+                            let w_ref = (s_idx, w_id);
+                            for (i, eq) in disjoint_equalities.iter().enumerate() {
+                                if eq.has_wit_ref(&w_ref) {
+                                    let resp = p.response_for_message(
+                                        w_id,
+                                        revealed_msg_ids.iter().copied(),
+                                    )?;
+                                    if let Some(r) = resp_for_equalities.get(&i) {
+                                        if resp != r {
+                                            return Err(ProofSystemError::WitnessResponseNotEqual(
+                                                s_idx, w_id,
+                                            ));
+                                        }
+                                    } else {
+                                        resp_for_equalities.insert(i, *resp);
+                                    }
+                                }
+                            }
+                        }
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            p,
+                            derived_ps_pk.try_get(s_idx)?.clone(),
+                            derived_ps_param.try_get(s_idx)?.clone(),
+                            &mut pairing_checker,
+                        )
+                        .map_err(|e| ProofSystemError::PSProofContributionFailed(s_idx as u32, e))?
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::BoundCheckBpp(s) => match proof {
+                    StatementProof::BoundCheckBpp(ref bc_proof) => {
+                        let setup_params = s.get_setup_params(&proof_spec.setup_params, s_idx)?;
+                        let sp = BoundCheckBppProtocol::new(s_idx, s.min, s.max, setup_params);
+                        let comm_key = bound_check_bpp_comm.get(s_idx).unwrap();
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            bc_proof,
+                            comm_key.as_slice(),
+                            transcript.as_mut().unwrap(),
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::BoundCheckSmc(s) => match proof {
+                    StatementProof::BoundCheckSmc(ref bc_proof) => {
+                        let setup_params =
+                            s.get_params_and_comm_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = BoundCheckSmcProtocol::new(s_idx, s.min, s.max, setup_params);
+                        let comm_key_slice = bound_check_smc_comm.get(s_idx).unwrap();
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            bc_proof,
+                            comm_key_slice.as_slice(),
+                            derived_smc_param.try_get(s_idx)?.clone(),
+                            &mut pairing_checker,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::BoundCheckSmcWithKVVerifier(s) => match proof {
+                    StatementProof::BoundCheckSmcWithKV(ref bc_proof) => {
+                        let setup_params =
+                            s.get_params_and_comm_key_and_sk(&proof_spec.setup_params, s_idx)?;
+                        let sp = BoundCheckSmcWithKVProtocol::new_for_verifier(
+                            s_idx,
+                            s.min,
+                            s.max,
+                            setup_params,
+                        );
+                        let comm_key_slice = bound_check_smc_comm.get(s_idx).unwrap();
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            bc_proof,
                             comm_key_slice.as_slice(),
-                            p,
-                            &s.inequal_to,
-                            s.get_comm_key(&proof_spec.setup_params, s_idx)?,
-                            &mut transcript,
-                        )?;
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::DetachedAccumulatorMembershipVerifier(s) => match proof {
-                    StatementProof::DetachedAccumulatorMembership(_p) => {
-                        // check_resp_for_equalities!(
-                        //     witness_equalities,
-                        //     s_idx,
-                        //     p.accum_proof,
-                        //     get_schnorr_response_for_element,
-                        //     Self,
-                        //     responses_for_equalities
-                        // );
-                        // let params = s.get_params(&proof_spec.setup_params, s_idx)?;
-                        // let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        // let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        // transcript.set_label(VB_ACCUM_MEM_LABEL);
-                        // p.accum_proof.challenge_contribution(
-                        //     &p.accumulator,
-                        //     pk,
-                        //     params,
-                        //     prk,
-                        //     &mut transcript,
-                        // )?;
-                        todo!()
+                Statement::PublicInequality(s) => match proof {
+                    StatementProof::Inequality(ref iq_proof) => {
+                        let comm_key = s.get_comm_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = InequalityProtocol::new(s_idx, s.inequal_to, comm_key);
+                        let comm_key = ineq_comm.get(s_idx).unwrap();
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            iq_proof,
+                            comm_key.as_slice(),
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::DetachedAccumulatorNonMembershipVerifier(s) => match proof {
-                    StatementProof::DetachedAccumulatorNonMembership(_p) => {
-                        // check_resp_for_equalities!(
-                        //     witness_equalities,
-                        //     s_idx,
-                        //     p.accum_proof,
-                        //     get_schnorr_response_for_element,
-                        //     Self,
-                        //     responses_for_equalities
-                        // );
-                        // let params = s.get_params(&proof_spec.setup_params, s_idx)?;
-                        // let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        // let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        // transcript.set_label(VB_ACCUM_NON_MEM_LABEL);
-                        // p.accum_proof.challenge_contribution(
-                        //     &p.accumulator,
-                        //     pk,
-                        //     params,
-                        //     prk,
-                        //     &mut transcript,
-                        // )?;
-                        todo!()
+                Statement::HiddenInequality(s) => match proof {
+                    StatementProof::HiddenInequality(ref iq_proof) => {
+                        let comm_key = s.get_comm_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = HiddenInequalityProtocol::new(s_idx, comm_key);
+                        let comm_key = ineq_comm.get(s_idx).unwrap();
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            iq_proof,
+                            comm_key.as_slice(),
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                1,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::DLEqualToAccumulatorMember(s) => match proof {
+                    StatementProof::PedersenCommitment(ref p) => {
+                        let sp = SchnorrProtocol::new(
+                            s_idx,
+                            ark_std::slice::from_ref(&s.base),
+                            s.public_key,
+                        );
+                        update_resp_eq_map!(s, s_idx, 1, p);
+                        sp.verify_proof_contribution(&challenge, p).map_err(|e| {
+                            ProofSystemError::SchnorrProofContributionFailed(s_idx as u32, e)
+                        })?
+                    }
+                    StatementProof::PedersenCommitmentPartial(ref p) => {
+                        let sp = SchnorrProtocol::new(
+                            s_idx,
+                            ark_std::slice::from_ref(&s.base),
+                            s.public_key,
+                        );
+                        let missing_responses =
+                            get_missing_responses_ped_comm_and_update_resp_eq_map!(s, s_idx, 1, p);
+                        if missing_responses.is_empty() {
+                            return Err(ProofSystemError::ResponseForWitnessNotFoundForStatement(
+                                sp.id,
+                            ));
+                        } else {
+                            sp.verify_partial_proof_contribution(&challenge, p, missing_responses)
+                                .map_err(|e| {
+                                    ProofSystemError::SchnorrProofContributionFailed(
+                                        s_idx as u32,
+                                        e,
+                                    )
+                                })?
+                        }
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::PublicSetMembership(s) => match proof {
+                    StatementProof::PublicSetMembership(ref sm_proof) => {
+                        let comm_key = s.get_comm_key(&proof_spec.setup_params, s_idx)?;
+                        let sp = PublicSetMembershipProtocol::new(s_idx, &s.set, comm_key);
+                        let comm_key = ineq_comm.get(s_idx).unwrap();
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            sm_proof,
+                            comm_key.as_slice(),
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
+                Statement::DetachedAccumulatorMembershipVerifier(_s) => (),
+                Statement::DetachedAccumulatorNonMembershipVerifier(_s) => (),
                 Statement::PoKBBDT16MAC(s) => match proof {
-                    StatementProof::PoKOfBBDT16MAC(p) => {
-                        sig_protocol_chal_gen!(s, s_idx, p, BBDT16_KVAC_LABEL);
+                    StatementProof::PoKOfBBDT16MAC(ref p) => {
+                        let mac_params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let sp = PoKOfMACSubProtocol::new(s_idx, &s.revealed_messages, mac_params);
+                        let total_msgs = mac_params.supported_message_count();
+                        let missing_responses = get_missing_responses_for_sigs_and_update_resp_eq_map!(
+                            s, s_idx, total_msgs, p
+                        );
+                        if missing_responses.is_empty() {
+                            sp.verify_schnorr_proof_contribution(&challenge, p)
+                                .map_err(|e| {
+                                    ProofSystemError::BBDT16KVACProofContributionFailed(
+                                        s_idx as u32,
+                                        e,
+                                    )
+                                })?
+                        } else {
+                            sp.verify_partial_schnorr_proof_contribution(
+                                &challenge,
+                                p,
+                                missing_responses,
+                            )
+                            .map_err(|e| {
+                                ProofSystemError::BBDT16KVACProofContributionFailed(s_idx as u32, e)
+                            })?
+                        }
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::PoKBBDT16MACFullVerifier(s) => match proof {
-                    StatementProof::PoKOfBBDT16MAC(p) => {
-                        sig_protocol_chal_gen!(s, s_idx, p, BBDT16_KVAC_LABEL);
+                    StatementProof::PoKOfBBDT16MAC(ref p) => {
+                        let mac_params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let sp = PoKOfMACSubProtocol::new(s_idx, &s.revealed_messages, mac_params);
+                        let total_msgs = mac_params.supported_message_count();
+                        let missing_responses = get_missing_responses_for_sigs_and_update_resp_eq_map!(
+                            s, s_idx, total_msgs, p
+                        );
+                        if missing_responses.is_empty() {
+                            sp.verify_mac_and_schnorr_proof_contribution(
+                                &challenge,
+                                p,
+                                &s.secret_key,
+                            )
+                            .map_err(|e| {
+                                ProofSystemError::BBDT16KVACProofContributionFailed(s_idx as u32, e)
+                            })?
+                        } else {
+                            sp.verify_mac_and_partial_schnorr_proof_contribution(
+                                &challenge,
+                                p,
+                                &s.secret_key,
+                                missing_responses,
+                            )
+                            .map_err(|e| {
+                                ProofSystemError::BBDT16KVACProofContributionFailed(s_idx as u32, e)
+                            })?
+                        }
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::VBAccumulatorMembershipKV(s) => match proof {
-                    StatementProof::VBAccumulatorMembershipKV(p) => {
-                        accum_cdh_protocol_chal_gen!(s, s_idx, p, VB_ACCUM_MEM_LABEL);
+                    StatementProof::VBAccumulatorMembershipKV(ref p) => {
+                        let sp =
+                            VBAccumulatorMembershipKVSubProtocol::new(s_idx, s.accumulator_value);
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            p,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::VBAccumulatorMembershipKVFullVerifier(s) => match proof {
-                    StatementProof::VBAccumulatorMembershipKV(p) => {
-                        accum_cdh_protocol_chal_gen!(s, s_idx, p, VB_ACCUM_MEM_LABEL);
+                    StatementProof::VBAccumulatorMembershipKV(ref p) => {
+                        let sp =
+                            VBAccumulatorMembershipKVSubProtocol::new(s_idx, s.accumulator_value);
+                        sp.verify_full_proof_contribution(
+                            &challenge,
+                            p,
+                            &s.secret_key,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::KBUniversalAccumulatorMembershipKV(s) => match proof {
-                    StatementProof::KBUniversalAccumulatorMembershipKV(p) => {
-                        accum_cdh_protocol_chal_gen!(s, s_idx, p, KB_UNI_ACCUM_MEM_LABEL);
+                    StatementProof::KBUniversalAccumulatorMembershipKV(ref p) => {
+                        let sp = KBUniversalAccumulatorMembershipKVSubProtocol::new(
+                            s_idx,
+                            s.accumulator_value,
+                        );
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            p,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::KBUniversalAccumulatorMembershipKVFullVerifier(s) => match proof {
-                    StatementProof::KBUniversalAccumulatorMembershipKV(p) => {
-                        accum_cdh_protocol_chal_gen!(s, s_idx, p, KB_UNI_ACCUM_MEM_LABEL);
+                    StatementProof::KBUniversalAccumulatorMembershipKV(ref p) => {
+                        let sp = KBUniversalAccumulatorMembershipKVSubProtocol::new(
+                            s_idx,
+                            s.accumulator_value,
+                        );
+                        sp.verify_full_proof_contribution(
+                            &challenge,
+                            p,
+                            &s.secret_key,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::KBUniversalAccumulatorNonMembershipKV(s) => match proof {
-                    StatementProof::KBUniversalAccumulatorNonMembershipKV(p) => {
-                        accum_cdh_protocol_chal_gen!(s, s_idx, p, KB_UNI_ACCUM_NON_MEM_LABEL);
+                    StatementProof::KBUniversalAccumulatorNonMembershipKV(ref p) => {
+                        let sp = KBUniversalAccumulatorNonMembershipKVSubProtocol::new(
+                            s_idx,
+                            s.accumulator_value,
+                        );
+                        sp.verify_proof_contribution(
+                            &challenge,
+                            p,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::KBUniversalAccumulatorNonMembershipKVFullVerifier(s) => match proof {
-                    StatementProof::KBUniversalAccumulatorNonMembershipKV(p) => {
-                        accum_cdh_protocol_chal_gen!(s, s_idx, p, KB_UNI_ACCUM_NON_MEM_LABEL);
+                    StatementProof::KBUniversalAccumulatorNonMembershipKV(ref p) => {
+                        let sp = KBUniversalAccumulatorNonMembershipKVSubProtocol::new(
+                            s_idx,
+                            s.accumulator_value,
+                        );
+                        sp.verify_full_proof_contribution(
+                            &challenge,
+                            p,
+                            &s.secret_key,
+                            Self::get_resp_for_message(
+                                s_idx,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::VeTZ21(s) => match proof {
-                    StatementProof::VeTZ21(p) => {
-                        let comm_key = s.get_comm_key(&proof_spec.setup_params, s_idx)?.as_slice();
-                        let enc_params = s.get_enc_params(&proof_spec.setup_params, s_idx)?;
-                        transcript.set_label(VE_TZ_21_LABEL);
-                        VeTZ21Protocol::compute_challenge_contribution(
-                            enc_params,
-                            comm_key,
-                            p,
-                            &mut transcript,
-                        )?
+                    StatementProof::VeTZ21(ref p) => {
+                        tz_21_verify!(s, s_idx, p, verify_proof_contribution);
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::VeTZ21Robust(s) => match proof {
-                    StatementProof::VeTZ21Robust(p) => {
-                        let comm_key = s.get_comm_key(&proof_spec.setup_params, s_idx)?.as_slice();
-                        let enc_params = s.get_enc_params(&proof_spec.setup_params, s_idx)?;
-                        transcript.set_label(VE_TZ_21_ROBUST_LABEL);
-                        VeTZ21Protocol::compute_challenge_contribution_robust(
-                            enc_params,
-                            comm_key,
-                            p,
-                            &mut transcript,
-                        )?
+                    StatementProof::VeTZ21Robust(ref p) => {
+                        tz_21_verify!(s, s_idx, p, verify_proof_contribution_robust);
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 _ => return Err(ProofSystemError::InvalidStatement),
             }
+            timer.record(TimedPhase::Verification(s_idx), start);
+        }
+
+        // If even one of witness equality had no corresponding response, it means that wasn't satisfied
+        // and proof should not verify
+        let mut unsatisfied = vec![];
+        for (i, eq) in disjoint_equalities.iter().enumerate() {
+            if !resp_for_equalities.contains_key(&i) {
+                unsatisfied.push(eq.0.clone())
+            }
+        }
+        if !unsatisfied.is_empty() {
+            return Err(ProofSystemError::UnsatisfiedWitnessEqualities(unsatisfied));
+        }
+
+        if aggregate_snarks {
+            Self::check_cancelled(cancel)?;
+            let start = timer.start();
+            // The validity of `ProofSpec` ensures that statements are not being repeated
+            let mut transcript = transcript.unwrap();
+
+            let srs = match &proof_spec.snark_aggregation_srs {
+                Some(SnarkpackSRS::VerifierSrs(srs)) => srs,
+                _ => return Err(ProofSystemError::SnarkpackSrsNotProvided),
+            };
+
+            if let Some(to_aggregate) = &proof_spec.aggregate_groth16 {
+                if let Some(aggr_proofs) = self.aggregated_groth16 {
+                    expect_equality!(
+                        to_aggregate.len(),
+                        aggr_proofs.len(),
+                        ProofSystemError::InvalidNumberOfAggregateGroth16Proofs
+                    );
+                    for (i, a) in aggr_proofs.into_iter().enumerate() {
+                        if to_aggregate[i] != a.statements {
+                            return Err(
+                                ProofSystemError::NotFoundAggregateGroth16ProofForRequiredStatements(
+                                    i,
+                                    to_aggregate[i].clone(),
+                                ),
+                            );
+                        }
+                        let s_id = a.statements.into_iter().next().unwrap();
+                        Self::set_up_checker_for_statement(
+                            &mut pairing_checker,
+                            &per_statement_laziness,
+                            default_laziness,
+                            s_id,
+                        );
+                        let pvk = derived_saver_vk.try_get(s_id)?;
+                        let ciphertexts = &agg_saver[i];
+                        let chunk_size = saver_batch_chunk.unwrap_or(ciphertexts.len()).max(1);
+                        for chunk in ciphertexts.chunks(chunk_size) {
+                            SaverProtocol::verify_ciphertext_commitments_in_batch(
+                                rng,
+                                chunk,
+                                derived_gens.try_get(s_id)?.clone(),
+                                derived_ek.try_get(s_id)?.clone(),
+                                &mut pairing_checker,
+                            )?;
+                        }
+                        saver::saver_groth16::verify_aggregate_proof(
+                            srs,
+                            pvk,
+                            &a.proof,
+                            ciphertexts,
+                            rng,
+                            &mut transcript,
+                            pairing_checker.as_mut(),
+                        )?;
+                    }
+                } else {
+                    return Err(ProofSystemError::NoAggregateGroth16ProofFound);
+                }
+            }
+
+            if let Some(to_aggregate) = &proof_spec.aggregate_legogroth16 {
+                if let Some(aggr_proofs) = self.aggregated_legogroth16 {
+                    expect_equality!(
+                        to_aggregate.len(),
+                        aggr_proofs.len(),
+                        ProofSystemError::InvalidNumberOfAggregateLegoGroth16Proofs
+                    );
+                    for (i, a) in aggr_proofs.into_iter().enumerate() {
+                        if to_aggregate[i] != a.statements {
+                            return Err(ProofSystemError::NotFoundAggregateLegoGroth16ProofForRequiredStatements(i, to_aggregate[i].clone()));
+                        }
+                        let s_id = a.statements.into_iter().next().unwrap();
+                        Self::set_up_checker_for_statement(
+                            &mut pairing_checker,
+                            &per_statement_laziness,
+                            default_laziness,
+                            s_id,
+                        );
+                        let pvk = derived_lego_vk.try_get(s_id)?;
+                        legogroth16::aggregation::legogroth16::using_groth16::verify_aggregate_proof(
+                            srs,
+                            pvk,
+                            &agg_lego[i].1,
+                            &a.proof,
+                            &agg_lego[i].0,
+                            rng,
+                            &mut transcript,
+                            pairing_checker.as_mut(),
+                        )
+                            .map_err(|e| ProofSystemError::LegoGroth16Error(e.into()))?
+                    }
+                } else {
+                    return Err(ProofSystemError::NoAggregateLegoGroth16ProofFound);
+                }
+            }
+            timer.record(TimedPhase::SnarkAggregation, start);
+        }
+
+        // If randomized pairing checker was used, verify all its pairing checks
+        if let Some(c) = pairing_checker {
+            let start = timer.start();
+            if !c.verify() {
+                return Err(ProofSystemError::RandomizedPairingCheckFailed);
+            }
+            timer.record(TimedPhase::PairingCheck, start);
+        }
+        Ok(())
+    }
+
+    pub fn get_saver_ciphertext_and_proof(
+        &self,
+        index: usize,
+    ) -> Result<(&Ciphertext<E>, &ark_groth16::Proof<E>), ProofSystemError> {
+        let st = self.statement_proof(index)?;
+        if let StatementProof::Saver(s) = st {
+            Ok((&s.ciphertext, &s.snark_proof))
+        } else {
+            Err(ProofSystemError::NotASaverStatementProof)
+        }
+    }
+
+    /// Decrypt the SAVER ciphertext of the statement at `index` and return the message and `nu`, the proof
+    /// that the ciphertext decrypts to the message, which can be checked using
+    /// [`Self::verify_saver_ciphertext_decryption`] without the decryptor's secret key. `nu` only proves
+    /// correct decryption of the ciphertext and says nothing about `comm_combined` of the statement proof;
+    /// that the ciphertext encrypts the committed witness is shown by verifying this proof. Works for the
+    /// statements whose Groth16 proofs are aggregated as well.
+    pub fn decrypt_saver_ciphertext(
+        &self,
+        index: usize,
+        sk: &SecretKey<E::ScalarField>,
+        dk: impl Into<PreparedDecryptionKey<E>>,
+        snark_vk: &VerifyingKey<E>,
+        chunk_bit_size: u8,
+    ) -> Result<(E::ScalarField, E::G1Affine), ProofSystemError> {
+        let ct = self.saver_ciphertext(index)?;
+        Ok(ct.decrypt_given_groth16_vk(sk, dk, snark_vk, chunk_bit_size)?)
+    }
+
+    /// Verify that `message` is the decryption of the SAVER ciphertext of the statement at `index` using
+    /// `nu` returned by [`Self::decrypt_saver_ciphertext`]. This doesn't verify the proof itself which
+    /// should have been done before.
+    pub fn verify_saver_ciphertext_decryption(
+        &self,
+        index: usize,
+        message: &E::ScalarField,
+        nu: &E::G1Affine,
+        chunk_bit_size: u8,
+        dk: impl Into<PreparedDecryptionKey<E>>,
+        snark_vk: &VerifyingKey<E>,
+        enc_gens: impl Into<PreparedEncryptionGens<E>>,
+    ) -> Result<(), ProofSystemError> {
+        let ct = self.saver_ciphertext(index)?;
+        Ok(ct.verify_decryption_given_groth16_vk(
+            message,
+            nu,
+            chunk_bit_size,
+            dk,
+            snark_vk,
+            enc_gens,
+        )?)
+    }
+
+    /// Get the SAVER ciphertext of the statement at `index` whether or not its Groth16 proof is aggregated
+    fn saver_ciphertext(&self, index: usize) -> Result<&Ciphertext<E>, ProofSystemError> {
+        match self.statement_proof(index)? {
+            StatementProof::Saver(s) => Ok(&s.ciphertext),
+            StatementProof::SaverWithAggregation(s) => Ok(&s.ciphertext),
+            _ => Err(ProofSystemError::NotASaverStatementProof),
+        }
+    }
+
+    pub fn get_legogroth16_proof(
+        &self,
+        index: usize,
+    ) -> Result<&legogroth16::Proof<E>, ProofSystemError> {
+        let st = self.statement_proof(index)?;
+        match st {
+            StatementProof::BoundCheckLegoGroth16(s) => Ok(&s.snark_proof),
+            StatementProof::R1CSLegoGroth16(s) => Ok(&s.snark_proof),
+            _ => Err(ProofSystemError::NotALegoGroth16StatementProof),
+        }
+    }
+
+    /// Verify only the LegoGroth16 proof of the statement at `index` along with the Schnorr proof linking
+    /// its commitment to the witnesses. `challenge` should be the one returned by `Proof::compute_challenge`
+    /// for the same `proof_spec`. Useful for isolating which statement fails when a proof doesn't verify.
+    ///
+    /// The other statements aren't verified, so the responses taken from them for witness equalities
+    /// aren't either and this is not a substitute for `Proof::verify`. Aggregated LegoGroth16 proofs
+    /// aren't supported as their SNARK is verified only as part of the aggregate.
+    pub fn verify_single_legogroth16(
+        &self,
+        index: usize,
+        proof_spec: ProofSpec<E>,
+        challenge: &E::ScalarField,
+    ) -> Result<(), ProofSystemError> {
+        let resp_for_equalities = self.collect_equality_responses(&proof_spec)?;
+        let prepared = proof_spec.prepare()?;
+        let proof_spec = &prepared.spec;
+        let (bound_check_comm, _, _, r1cs_comm_keys, _, _, _) = &prepared.commitment_keys;
+        let derived_lego_vk = &prepared.prepared_params.0;
+        let disjoint_equalities = &prepared.disjoint_equalities;
+        let statement = proof_spec
+            .statements
+            .0
+            .get(index)
+            .ok_or(ProofSystemError::InvalidStatementProofIndex(index))?;
+        let proof = self.statement_proof(index)?;
+        match statement {
+            Statement::BoundCheckLegoGroth16Verifier(s) => {
+                let verifying_key = s.get_verifying_key(&proof_spec.setup_params, index)?;
+                let sp = BoundCheckLegoGrothProtocol::new_for_verifier(
+                    index,
+                    s.min,
+                    s.max,
+                    verifying_key,
+                );
+                match proof {
+                    StatementProof::BoundCheckLegoGroth16(bc_proof) => sp
+                        .verify_proof_contribution(
+                            challenge,
+                            bc_proof,
+                            bound_check_comm.get(index).unwrap(),
+                            derived_lego_vk.try_get(index)?,
+                            &mut None,
+                            Self::get_resp_for_message(
+                                index,
+                                0,
+                                disjoint_equalities,
+                                &resp_for_equalities,
+                            )?,
+                        ),
+                    StatementProof::BoundCheckLegoGroth16WithAggregation(_) => {
+                        Err(ProofSystemError::NotALegoGroth16StatementProof)
+                    }
+                    _ => err_incompat_proof!(index, s, proof),
+                }
+            }
+            Statement::R1CSCircomVerifier(s) => {
+                let verifying_key = s.get_verifying_key(&proof_spec.setup_params, index)?;
+                let sp = R1CSLegogroth16Protocol::new_for_verifier(index, verifying_key);
+                let pub_inp = s.get_public_inputs(&proof_spec.setup_params, index)?;
+                match proof {
+                    StatementProof::R1CSLegoGroth16(r1cs_proof) => {
+                        for w_id in 0..verifying_key.commit_witness_count as usize {
+                            let w_ref = (index, w_id);
+                            for (i, eq) in disjoint_equalities.iter().enumerate() {
+                                if eq.has_wit_ref(&w_ref) {
+                                    let resp = r1cs_proof.get_schnorr_response_for_message(w_id)?;
+                                    if let Some(r) = resp_for_equalities.get(&i) {
+                                        if resp != r {
+                                            return Err(ProofSystemError::WitnessResponseNotEqual(
+                                                index, w_id,
+                                            ));
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        sp.verify_proof_contribution(
+                            challenge,
+                            pub_inp,
+                            r1cs_proof,
+                            r1cs_comm_keys.get(index).unwrap(),
+                            derived_lego_vk.try_get(index)?,
+                            &mut None,
+                        )
+                    }
+                    StatementProof::R1CSLegoGroth16WithAggregation(_) => {
+                        Err(ProofSystemError::NotALegoGroth16StatementProof)
+                    }
+                    _ => err_incompat_proof!(index, s, proof),
+                }
+            }
+            _ => Err(ProofSystemError::NotALegoGroth16StatementProof),
+        }
+    }
+
+    /// Get all SAVER ciphertexts in the proof along with the index of their statement. Useful when the
+    /// indices of the verifiable encryption statements aren't known in advance, like decrypting all of them.
+    /// Includes the ciphertexts whose Groth16 proofs are aggregated.
+    pub fn saver_ciphertexts(&self) -> impl Iterator<Item = (usize, &Ciphertext<E>)> {
+        self.statement_proofs_iter().filter_map(|(i, st)| match st {
+            StatementProof::Saver(s) => Some((i, &s.ciphertext)),
+            StatementProof::SaverWithAggregation(s) => Some((i, &s.ciphertext)),
+            _ => None,
+        })
+    }
+
+    /// Get all LegoGroth16 proofs in the proof along with the index of their statement. Proofs that are
+    /// aggregated aren't included as those aren't part of the statement proof.
+    pub fn legogroth16_proofs(&self) -> impl Iterator<Item = (usize, &legogroth16::Proof<E>)> {
+        self.statement_proofs_iter().filter_map(|(i, st)| match st {
+            StatementProof::BoundCheckLegoGroth16(s) => Some((i, &s.snark_proof)),
+            StatementProof::R1CSLegoGroth16(s) => Some((i, &s.snark_proof)),
+            _ => None,
+        })
+    }
+
+    /// Get the compressed ciphertext and commitment to needed to decrypt message encrypted using DKGitH protocol
+    pub fn get_tz21_ciphertext_and_commitment<D: FullDigest + Digest>(
+        &self,
+        index: usize,
+    ) -> Result<(dkgith_decls::Ciphertext<E::G1Affine>, E::G1Affine), ProofSystemError> {
+        let st = self.statement_proof(index)?;
+        if let StatementProof::VeTZ21(s) = st {
+            let ve_proof = &s.ve_proof;
+            // TODO: Make Shake256 a generic and ensure it matches the one used on proof generation
+            let ct = ve_proof.compress::<{ dkgith_decls::SUBSET_SIZE }, D, Shake256>()?;
+            Ok((ct, s.commitment))
+        } else {
+            Err(ProofSystemError::NotAVeTZ21StatementProof)
+        }
+    }
+
+    /// Get the compressed ciphertext and commitment to needed to decrypt message encrypted using Robust DKGitH protocol.
+    /// Errors with `ProofSystemError::TZ21SubsetSizeMismatch` if the proof doesn't have as many ciphertexts to
+    /// choose the subset from as the parameters in `rdkgith_decls` expect.
+    pub fn get_tz21_robust_ciphertext_and_commitment<D: FullDigest + Digest>(
+        &self,
+        index: usize,
+    ) -> Result<(rdkgith_decls::Ciphertext<E::G1Affine>, E::G1Affine), ProofSystemError> {
+        let st = self.statement_proof(index)?;
+        if let StatementProof::VeTZ21Robust(s) = st {
+            let ve_proof = &s.ve_proof;
+            let expected = rdkgith_decls::NUM_PARTIES - rdkgith_decls::THRESHOLD;
+            if ve_proof.ciphertexts.len() != expected {
+                return Err(ProofSystemError::TZ21SubsetSizeMismatch(
+                    ve_proof.ciphertexts.len(),
+                    expected,
+                ));
+            }
+            let ct = ve_proof.compress::<{ rdkgith_decls::SUBSET_SIZE }, D>()?;
+            Ok((ct, s.commitment))
+        } else {
+            Err(ProofSystemError::NotAVeTZ21StatementProof)
+        }
+    }
+
+    /*/// Used to check if response (from Schnorr protocol) for a witness is equal to other witnesses that
+    /// it must be equal to. This is required when the `ProofSpec` demands certain witnesses to be equal.
+    fn check_response_for_equality<'a>(
+        stmt_id: usize,
+        wit_id: usize,
+        equality_id: usize,
+        responses_for_equalities: &mut [Option<&'a E::ScalarField>],
+        resp: &'a E::ScalarField,
+    ) -> Result<(), ProofSystemError> {
+        if responses_for_equalities[equality_id].is_none() {
+            // First response encountered for the witness
+            responses_for_equalities[equality_id] = Some(resp);
+        } else if responses_for_equalities[equality_id] != Some(resp) {
+            return Err(ProofSystemError::WitnessResponseNotEqual(stmt_id, wit_id));
         }
+        Ok(())
+    }*/
 
-        // Verifier independently generates challenge
-        let challenge = transcript.challenge_scalar(COMPOSITE_PROOF_CHALLENGE_LABEL);
+    /// Create the transcript for the proof's challenge and add the transcript label, `nonce`, context and each
+    /// statement's contribution to it. The caller generates the challenge from the returned transcript.
+    fn challenge_contribution(
+        &self,
+        prepared: &PreparedProofSpec<E>,
+        nonce: Option<&Vec<u8>>,
+        transcript_label: Option<&Vec<u8>>,
+        transcript_builder: &CompositeTranscriptBuilder,
+        cancel: Option<&AtomicBool>,
+        timer: &mut Timer,
+    ) -> Result<ProofTranscript, ProofSystemError> {
+        let proof_spec = &prepared.spec;
 
-        // This will hold the response for each witness equality.
-        let mut resp_for_equalities = BTreeMap::<usize, E::ScalarField>::new();
+        // Get transcript label's, nonce's and context's challenge contribution
+        let mut transcript = transcript_builder.start(transcript_label, nonce, proof_spec);
 
-        macro_rules! get_missing_responses_for_sigs_and_update_resp_eq_map {
-            ($s: ident, $s_idx: ident, $total_msgs: expr, $proof: ident) => {{
-                let mut missing_responses = BTreeMap::new();
-                for w_id in 0..$total_msgs {
-                    let wit_ref = ($s_idx, w_id);
-                    for (i, eq) in disjoint_equalities.iter().enumerate() {
-                        if eq.has_wit_ref(&wit_ref) {
-                            if let Some(r) = resp_for_equalities.get(&i) {
-                                missing_responses.insert(w_id, *r);
-                            } else {
-                                let revealed_idx = BTreeSet::<usize>::from_iter(
-                                    $s.revealed_messages.keys().cloned(),
-                                );
-                                resp_for_equalities
-                                    .insert(i, *$proof.get_resp_for_message(w_id, &revealed_idx)?);
-                            }
-                            // Exit loop because equalities are disjoint
-                            break;
-                        }
-                    }
-                }
-                missing_responses
-            }};
-        }
+        // Commitment keys for running Schnorr protocols of all statements.
+        let (
+            bound_check_comm,
+            ek_comm,
+            chunked_comm,
+            r1cs_comm_keys,
+            bound_check_bpp_comm,
+            bound_check_smc_comm,
+            ineq_comm,
+        ) = &prepared.commitment_keys;
 
-        macro_rules! get_missing_responses_ped_comm_and_update_resp_eq_map {
-            ($s: ident, $s_idx: ident, $total_msgs: expr, $proof: ident) => {{
-                let mut missing_responses = BTreeMap::new();
-                for w_id in 0..$total_msgs {
-                    let wit_ref = ($s_idx, w_id);
-                    for (i, eq) in disjoint_equalities.iter().enumerate() {
-                        if eq.has_wit_ref(&wit_ref) {
-                            if let Some(r) = resp_for_equalities.get(&i) {
-                                missing_responses.insert(w_id, *r);
-                            } else {
-                                resp_for_equalities.insert(i, *$proof.get_resp_for_message(w_id)?);
-                            }
-                            // Exit loop because equalities are disjoint
-                            break;
-                        }
-                    }
-                }
-                missing_responses
+        macro_rules! sig_protocol_chal_gen {
+            ($s: ident, $s_idx: ident, $p: ident, $label: ident) => {{
+                let params = $s.get_params(&proof_spec.setup_params, $s_idx)?;
+                transcript.set_label($label);
+                $p.challenge_contribution(&$s.revealed_messages, params, &mut transcript)?;
             }};
         }
 
-        macro_rules! update_resp_eq_map {
-            ($s: ident, $s_idx: ident, $total_msgs: expr, $proof: ident) => {{
-                for w_id in 0..$total_msgs {
-                    let wit_ref = ($s_idx, w_id);
-                    for (i, eq) in disjoint_equalities.iter().enumerate() {
-                        if eq.has_wit_ref(&wit_ref) {
-                            if resp_for_equalities.get(&i).is_none() {
-                                resp_for_equalities.insert(i, *$proof.get_resp_for_message(w_id)?);
-                            }
-                            // Exit loop because equalities are disjoint
-                            break;
-                        }
-                    }
-                }
+        macro_rules! ped_comm_protocol_check_resp_and_chal_gen {
+            ($s: ident, $s_idx: ident, $p: ident, $com_key_func: ident) => {{
+                let comm_key = $s.$com_key_func(&proof_spec.setup_params, $s_idx)?;
+                SchnorrProtocol::compute_challenge_contribution(
+                    comm_key,
+                    &$s.commitment,
+                    &$p.t,
+                    &mut transcript,
+                )?;
             }};
         }
 
-        macro_rules! sig_protocol_verify {
-            ($s: ident, $s_idx: ident, $protocol: ident, $func_name: ident, $p: ident, $derived_pk: ident, $derived_param: ident, $error_variant: ident) => {{
-                let params = $s.get_params(&proof_spec.setup_params, $s_idx)?;
-                let pk = $s.get_public_key(&proof_spec.setup_params, $s_idx)?;
-                let sp = $protocol::$func_name($s_idx, &$s.revealed_messages, params, pk);
-                let missing_responses = get_missing_responses_for_sigs_and_update_resp_eq_map!(
-                    $s,
-                    $s_idx,
-                    params.supported_message_count(),
-                    $p
-                );
-                if missing_responses.is_empty() {
-                    sp.verify_proof_contribution(
-                        &challenge,
-                        $p,
-                        $derived_pk.get($s_idx).unwrap().clone(),
-                        $derived_param.get($s_idx).unwrap().clone(),
-                        &mut pairing_checker,
-                    )
-                    .map_err(|e| ProofSystemError::$error_variant($s_idx as u32, e))?
-                } else {
-                    sp.verify_partial_proof_contribution(
-                        &challenge,
-                        $p,
-                        $derived_pk.get($s_idx).unwrap().clone(),
-                        $derived_param.get($s_idx).unwrap().clone(),
-                        &mut pairing_checker,
-                        missing_responses,
-                    )
-                    .map_err(|e| ProofSystemError::$error_variant($s_idx as u32, e))?
-                }
+        // The CDH based accumulator protocols don't add the accumulator's public key or params to the challenge as
+        // the prover doesn't have them (that's what lets the prover avoid pairings). The Schnorr relations only
+        // involve the accumulator value and group elements from the proof which are all in the transcript. The public
+        // key is bound by the pairing check done by the verifier.
+        macro_rules! accum_cdh_protocol_chal_gen {
+            ($s: ident, $s_idx: ident, $p: ident, $label: ident) => {{
+                transcript.set_label($label);
+                $p.challenge_contribution(&$s.accumulator_value, &mut transcript)?;
             }};
         }
 
-        macro_rules! tz_21_verify {
-            ($s: ident, $s_idx: ident, $p: ident, $func_name: ident) => {
-                let comm_key = $s.get_comm_key(&proof_spec.setup_params, $s_idx)?;
-                let enc_params = $s.get_enc_params(&proof_spec.setup_params, $s_idx)?;
-                let sp = VeTZ21Protocol::new($s_idx, comm_key.as_slice(), enc_params);
-                // Won't have response for all indices except for last one since their responses will come from proofs of the signatures.
-                let mut missing_resps = BTreeMap::new();
-                // The last witness is the randomness of the commitment so skip that
-                for i in 0..$p.ve_proof.witness_count() - 1 {
-                    missing_resps.insert(
-                        i,
-                        Self::get_resp_for_message(
-                            $s_idx,
-                            i,
-                            &disjoint_equalities,
-                            &resp_for_equalities,
-                        )?,
-                    );
-                }
-                sp.$func_name::<D>(&challenge, $p, &mut transcript, missing_resps)?
-            }
-        }
-
-        // Verify the proof for each statement
+        // Get challenge contribution for each statement and check if response is equal for all witnesses.
         for (s_idx, (statement, proof)) in proof_spec
             .statements
             .0
             .iter()
-            .zip(self.statement_proofs.into_iter())
+            .zip(self.statement_proofs.iter())
             .enumerate()
         {
+            Self::check_cancelled(cancel)?;
+            let start = timer.start();
             match statement {
                 Statement::PoKBBSSignatureG1Verifier(s) => match proof {
-                    StatementProof::PoKBBSSignatureG1(ref p) => {
-                        sig_protocol_verify!(
-                            s,
-                            s_idx,
-                            PoKBBSSigG1SubProtocol,
-                            new_for_verifier,
-                            p,
-                            derived_bbs_pk,
-                            derived_bbs_plus_param,
-                            BBSPlusProofContributionFailed
-                        );
+                    StatementProof::PoKBBSSignatureG1(p) => {
+                        sig_protocol_chal_gen!(s, s_idx, p, BBS_PLUS_LABEL);
+                    }
+                    StatementProof::RevealedBBSSignatureG1(sig) => {
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        transcript.set_label(BBS_PLUS_LABEL);
+                        RevealedBBSSigG1SubProtocol::compute_challenge_contribution(
+                            &s.revealed_messages,
+                            params,
+                            sig,
+                            &mut transcript,
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::PoKBBSSignature23G1Verifier(s) => match proof {
-                    StatementProof::PoKBBSSignature23G1(ref p) => {
-                        sig_protocol_verify!(
-                            s,
-                            s_idx,
-                            PoKBBSSig23G1SubProtocol,
-                            new_for_verifier,
-                            p,
-                            derived_bbs_pk,
-                            derived_bbs_param,
-                            BBSProofContributionFailed
-                        );
+                    StatementProof::PoKBBSSignature23G1(p) => {
+                        sig_protocol_chal_gen!(s, s_idx, p, BBS_23_LABEL);
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::PoKBBSSignature23IETFG1Verifier(s) => match proof {
-                    StatementProof::PoKBBSSignature23IETFG1(ref p) => {
-                        sig_protocol_verify!(
-                            s,
-                            s_idx,
-                            PoKBBSSig23IETFG1SubProtocol,
-                            new_for_verifier,
-                            p,
-                            derived_bbs_pk,
-                            derived_bbs_param,
-                            BBSProofContributionFailed
-                        );
+                    StatementProof::PoKBBSSignature23IETFG1(p) => {
+                        sig_protocol_chal_gen!(s, s_idx, p, BBS_23_LABEL);
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::VBAccumulatorMembership(s) => match proof {
-                    StatementProof::VBAccumulatorMembership(ref p) => {
+                    StatementProof::VBAccumulatorMembership(p) => {
                         let params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
                         let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = VBAccumulatorMembershipSubProtocol::new(
-                            s_idx,
-                            params,
+                        transcript.set_label(VB_ACCUM_MEM_LABEL);
+                        s.accumulator_id_contribution(&mut transcript);
+                        p.challenge_contribution(
+                            &s.accumulator_value,
                             pk,
+                            params,
                             prk,
-                            s.accumulator_value,
-                        );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_accum_pk.get(s_idx).unwrap().clone(),
-                            derived_accum_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
-                        )?
+                            &mut transcript,
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::VBAccumulatorNonMembership(s) => match proof {
-                    StatementProof::VBAccumulatorNonMembership(ref p) => {
+                    StatementProof::VBAccumulatorNonMembership(p) => {
                         let params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
                         let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = VBAccumulatorNonMembershipSubProtocol::new(
-                            s_idx,
-                            params,
+                        transcript.set_label(VB_ACCUM_NON_MEM_LABEL);
+                        s.accumulator_id_contribution(&mut transcript);
+                        p.challenge_contribution(
+                            &s.accumulator_value,
                             pk,
+                            params,
                             prk,
-                            s.accumulator_value,
-                        );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_accum_pk.get(s_idx).unwrap().clone(),
-                            derived_accum_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
-                        )?
+                            &mut transcript,
+                        )?;
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::KBUniversalAccumulatorMembership(s) => match proof {
+                    StatementProof::KBUniversalAccumulatorMembership(p) => {
+                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                        transcript.set_label(KB_UNI_ACCUM_MEM_LABEL);
+                        s.accumulator_id_contribution(&mut transcript);
+                        p.challenge_contribution(
+                            &s.accumulator_value,
+                            pk,
+                            params,
+                            prk,
+                            &mut transcript,
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::KBUniversalAccumulatorMembership(s) => match proof {
-                    StatementProof::KBUniversalAccumulatorMembership(ref p) => {
+                Statement::KBUniversalAccumulatorNonMembership(s) => match proof {
+                    StatementProof::KBUniversalAccumulatorNonMembership(p) => {
                         let params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
                         let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = KBUniversalAccumulatorMembershipSubProtocol::new(
-                            s_idx,
-                            params,
+                        transcript.set_label(KB_UNI_ACCUM_NON_MEM_LABEL);
+                        s.accumulator_id_contribution(&mut transcript);
+                        p.challenge_contribution(
+                            &s.accumulator_value,
                             pk,
+                            params,
                             prk,
-                            s.accumulator_value,
-                        );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_accum_pk.get(s_idx).unwrap().clone(),
-                            derived_accum_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
-                        )?
+                            &mut transcript,
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::KBUniversalAccumulatorNonMembership(s) => match proof {
-                    StatementProof::KBUniversalAccumulatorNonMembership(ref p) => {
+                Statement::KBUniversalAccumulatorMembershipAndNonMembership(s) => match proof {
+                    StatementProof::KBUniversalAccumulatorMembershipAndNonMembership(p) => {
                         let params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
                         let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = KBUniversalAccumulatorNonMembershipSubProtocol::new(
-                            s_idx,
+                        transcript.set_label(KB_UNI_ACCUM_MEM_AND_NON_MEM_LABEL);
+                        p.membership.challenge_contribution(
+                            &s.mem_accumulator_value,
+                            pk,
                             params,
+                            prk,
+                            &mut transcript,
+                        )?;
+                        p.non_membership.challenge_contribution(
+                            &s.non_mem_accumulator_value,
                             pk,
+                            params,
                             prk,
-                            s.accumulator_value,
-                        );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_accum_pk.get(s_idx).unwrap().clone(),
-                            derived_accum_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
-                        )?
+                            &mut transcript,
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::VBAccumulatorMembershipCDHVerifier(s) => match proof {
-                    StatementProof::VBAccumulatorMembershipCDH(ref p) => {
-                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
-                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = VBAccumulatorMembershipCDHSubProtocol::new_for_verifier(
-                            s_idx,
-                            s.accumulator_value,
-                            params,
-                            pk,
-                        );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_accum_pk.get(s_idx).unwrap().clone(),
-                            derived_accum_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
-                        )?
+                    StatementProof::VBAccumulatorMembershipCDH(p) => {
+                        accum_cdh_protocol_chal_gen!(s, s_idx, p, VB_ACCUM_CDH_MEM_LABEL);
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::VBAccumulatorNonMembershipCDHVerifier(s) => match proof {
-                    StatementProof::VBAccumulatorNonMembershipCDH(ref p) => {
+                    StatementProof::VBAccumulatorNonMembershipCDH(p) => {
                         let params = s.get_params(&proof_spec.setup_params, s_idx)?;
-                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = VBAccumulatorNonMembershipCDHSubProtocol::new_for_verifier(
-                            s_idx,
-                            s.accumulator_value,
-                            s.Q,
+                        transcript.set_label(VB_ACCUM_CDH_NON_MEM_LABEL);
+                        p.challenge_contribution(
+                            &s.accumulator_value,
                             params,
-                            pk,
-                        );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_accum_pk.get(s_idx).unwrap().clone(),
-                            derived_accum_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
-                        )?
+                            &s.Q,
+                            &mut transcript,
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::KBUniversalAccumulatorMembershipCDHVerifier(s) => match proof {
-                    StatementProof::KBUniversalAccumulatorMembershipCDH(ref p) => {
-                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
-                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = KBUniversalAccumulatorMembershipCDHSubProtocol::new_for_verifier(
-                            s_idx,
-                            s.accumulator_value,
-                            params,
-                            pk,
-                        );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_accum_pk.get(s_idx).unwrap().clone(),
-                            derived_accum_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
-                        )?
+                    StatementProof::KBUniversalAccumulatorMembershipCDH(p) => {
+                        accum_cdh_protocol_chal_gen!(s, s_idx, p, KB_UNI_ACCUM_CDH_MEM_LABEL);
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::KBUniversalAccumulatorNonMembershipCDHVerifier(s) => match proof {
-                    StatementProof::KBUniversalAccumulatorNonMembershipCDH(ref p) => {
-                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
-                        let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let sp =
-                            KBUniversalAccumulatorNonMembershipCDHSubProtocol::new_for_verifier(
-                                s_idx,
-                                s.accumulator_value,
-                                params,
-                                pk,
-                            );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_accum_pk.get(s_idx).unwrap().clone(),
-                            derived_accum_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
-                        )?
+                    StatementProof::KBUniversalAccumulatorNonMembershipCDH(p) => {
+                        accum_cdh_protocol_chal_gen!(s, s_idx, p, KB_UNI_ACCUM_CDH_NON_MEM_LABEL);
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::KBPositiveAccumulatorMembership(s) => match proof {
-                    StatementProof::KBPositiveAccumulatorMembership(ref p) => {
+                    StatementProof::KBPositiveAccumulatorMembership(p) => {
                         let params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
                         let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = KBPositiveAccumulatorMembershipSubProtocol::new(
-                            s_idx,
-                            params,
+                        transcript.set_label(KB_POS_ACCUM_MEM_LABEL);
+                        s.accumulator_id_contribution(&mut transcript);
+                        p.challenge_contribution(
+                            &s.accumulator_value,
                             pk,
+                            params,
                             prk,
-                            s.accumulator_value,
-                        );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_kb_accum_pk.get(s_idx).unwrap().clone(),
-                            derived_kb_accum_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
-                        )?
+                            &mut transcript,
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::KBPositiveAccumulatorMembershipCDH(s) => match proof {
-                    StatementProof::KBPositiveAccumulatorMembershipCDH(ref p) => {
+                    StatementProof::KBPositiveAccumulatorMembershipCDH(p) => {
                         let params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
                         let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = KBPositiveAccumulatorMembershipCDHSubProtocol::new(
-                            s_idx,
-                            params,
+                        transcript.set_label(KB_POS_ACCUM_CDH_MEM_LABEL);
+                        s.accumulator_id_contribution(&mut transcript);
+                        p.challenge_contribution(
+                            &s.accumulator_value,
                             pk,
+                            params,
                             prk,
-                            s.accumulator_value,
-                        );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_kb_accum_pk.get(s_idx).unwrap().clone(),
-                            derived_kb_accum_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
-                        )?
+                            &mut transcript,
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::PedersenCommitment(s) => match proof {
-                    StatementProof::PedersenCommitment(ref p) => {
-                        let comm_key = s.get_commitment_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = SchnorrProtocol::new(s_idx, comm_key, s.commitment);
-                        update_resp_eq_map!(s, s_idx, comm_key.len(), p);
-                        sp.verify_proof_contribution(&challenge, p).map_err(|e| {
-                            ProofSystemError::SchnorrProofContributionFailed(s_idx as u32, e)
-                        })?
+                    StatementProof::PedersenCommitment(p) => {
+                        ped_comm_protocol_check_resp_and_chal_gen!(s, s_idx, p, get_commitment_key);
                     }
-                    StatementProof::PedersenCommitmentPartial(ref p) => {
-                        let comm_key = s.get_commitment_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = SchnorrProtocol::new(s_idx, comm_key, s.commitment);
-                        let missing_responses = get_missing_responses_ped_comm_and_update_resp_eq_map!(
-                            s,
-                            s_idx,
-                            comm_key.len(),
-                            p
-                        );
-                        if missing_responses.is_empty() {
-                            return Err(ProofSystemError::ResponseForWitnessNotFoundForStatement(
-                                sp.id,
-                            ));
-                        } else {
-                            sp.verify_partial_proof_contribution(&challenge, p, missing_responses)
-                                .map_err(|e| {
-                                    ProofSystemError::SchnorrProofContributionFailed(
-                                        s_idx as u32,
-                                        e,
-                                    )
-                                })?
-                        }
+                    StatementProof::PedersenCommitmentPartial(p) => {
+                        ped_comm_protocol_check_resp_and_chal_gen!(s, s_idx, p, get_commitment_key);
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::PedersenCommitmentG2(s) => match proof {
-                    StatementProof::PedersenCommitmentG2(ref p) => {
-                        let comm_key = s.get_commitment_key_g2(&proof_spec.setup_params, s_idx)?;
-                        let sp = SchnorrProtocol::new(s_idx, comm_key, s.commitment);
-                        update_resp_eq_map!(s, s_idx, comm_key.len(), p);
-                        sp.verify_proof_contribution(&challenge, p).map_err(|e| {
-                            ProofSystemError::SchnorrProofContributionFailed(s_idx as u32, e)
-                        })?
-                    }
-                    StatementProof::PedersenCommitmentG2Partial(ref p) => {
-                        let comm_key = s.get_commitment_key_g2(&proof_spec.setup_params, s_idx)?;
-                        let sp = SchnorrProtocol::new(s_idx, comm_key, s.commitment);
-                        let missing_responses = get_missing_responses_ped_comm_and_update_resp_eq_map!(
+                    StatementProof::PedersenCommitmentG2(p) => {
+                        ped_comm_protocol_check_resp_and_chal_gen!(
                             s,
                             s_idx,
-                            comm_key.len(),
-                            p
+                            p,
+                            get_commitment_key_g2
+                        );
+                    }
+                    StatementProof::PedersenCommitmentG2Partial(p) => {
+                        ped_comm_protocol_check_resp_and_chal_gen!(
+                            s,
+                            s_idx,
+                            p,
+                            get_commitment_key_g2
                         );
-                        if missing_responses.is_empty() {
-                            return Err(ProofSystemError::ResponseForWitnessNotFoundForStatement(
-                                sp.id,
-                            ));
-                        } else {
-                            sp.verify_partial_proof_contribution(&challenge, p, missing_responses)
-                                .map_err(|e| {
-                                    ProofSystemError::SchnorrProofContributionFailed(
-                                        s_idx as u32,
-                                        e,
-                                    )
-                                })?
-                        }
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::SaverVerifier(s) => {
-                    let enc_gens = s.get_encryption_gens(&proof_spec.setup_params, s_idx)?;
-                    let comm_gens =
-                        s.get_chunked_commitment_gens(&proof_spec.setup_params, s_idx)?;
-                    let enc_key = s.get_encryption_key(&proof_spec.setup_params, s_idx)?;
-                    let vk = s.get_snark_verifying_key(&proof_spec.setup_params, s_idx)?;
-                    let sp = SaverProtocol::new_for_verifier(
-                        s_idx,
-                        s.chunk_bit_size,
-                        enc_gens,
-                        comm_gens,
-                        enc_key,
-                        vk,
-                    );
-                    let ek_comm_key = ek_comm.get(s_idx).unwrap();
-                    let cc_keys = chunked_comm.get(s_idx).unwrap();
-                    match proof {
-                        StatementProof::Saver(ref saver_proof) => sp.verify_proof_contribution(
-                            &challenge,
-                            saver_proof,
+                Statement::SaverVerifier(s) => match proof {
+                    StatementProof::Saver(p) => {
+                        let ek_comm_key = ek_comm.get(s_idx).unwrap();
+                        let cc_keys = chunked_comm.get(s_idx).unwrap();
+                        SaverProtocol::compute_challenge_contribution(
                             ek_comm_key,
                             &cc_keys.0,
                             &cc_keys.1,
-                            derived_saver_vk.get(s_idx).unwrap(),
-                            derived_gens.get(s_idx).unwrap().clone(),
-                            derived_ek.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
-                        )?,
-                        StatementProof::SaverWithAggregation(ref saver_proof) => {
-                            let agg_idx = agg_saver_stmts.get(&s_idx).ok_or_else(|| {
-                                ProofSystemError::InvalidStatementProofIndex(s_idx)
-                            })?;
-                            agg_saver[*agg_idx].push(saver_proof.ciphertext.clone());
-                            sp.verify_ciphertext_and_commitment(
-                                &challenge,
-                                &saver_proof.ciphertext,
-                                saver_proof.comm_combined.clone(),
-                                saver_proof.comm_chunks.clone(),
-                                &saver_proof.sp_ciphertext,
-                                &saver_proof.sp_chunks,
-                                &saver_proof.sp_combined,
-                                ek_comm_key,
-                                &cc_keys.0,
-                                &cc_keys.1,
-                                Self::get_resp_for_message(
-                                    s_idx,
-                                    0,
-                                    &disjoint_equalities,
-                                    &resp_for_equalities,
-                                )?,
-                            )?
-                        }
-                        _ => {
-                            return Err(ProofSystemError::ProofIncompatibleWithStatement(
-                                s_idx,
-                                format!("{:?}", proof),
-                                format!("{:?}", s),
-                            ))
-                        }
+                            p,
+                            &mut transcript,
+                        )?;
                     }
-                }
-                Statement::BoundCheckLegoGroth16Verifier(s) => {
-                    let verifying_key = s.get_verifying_key(&proof_spec.setup_params, s_idx)?;
-                    let sp = BoundCheckLegoGrothProtocol::new_for_verifier(
-                        s_idx,
-                        s.min,
-                        s.max,
-                        verifying_key,
-                    );
-                    let comm_key = bound_check_comm.get(s_idx).unwrap();
-                    match proof {
-                        StatementProof::BoundCheckLegoGroth16(ref bc_proof) => sp
-                            .verify_proof_contribution(
-                                &challenge,
-                                bc_proof,
-                                comm_key,
-                                derived_lego_vk.get(s_idx).unwrap(),
-                                &mut pairing_checker,
-                                Self::get_resp_for_message(
-                                    s_idx,
-                                    0,
-                                    &disjoint_equalities,
-                                    &resp_for_equalities,
-                                )?,
-                            )?,
-                        StatementProof::BoundCheckLegoGroth16WithAggregation(ref bc_proof) => {
-                            let pub_inp =
-                                vec![E::ScalarField::from(sp.min), E::ScalarField::from(sp.max)];
-                            let agg_idx = agg_lego_stmts.get(&s_idx).ok_or_else(|| {
-                                ProofSystemError::InvalidStatementProofIndex(s_idx)
-                            })?;
-                            agg_lego[*agg_idx].0.push(bc_proof.commitment);
-                            agg_lego[*agg_idx].1.push(pub_inp);
-                            sp.verify_proof_contribution_using_prepared_when_aggregating_snark(
-                                &challenge,
-                                bc_proof,
-                                comm_key,
-                                Self::get_resp_for_message(
-                                    s_idx,
-                                    0,
-                                    &disjoint_equalities,
-                                    &resp_for_equalities,
-                                )?,
-                            )?
-                        }
-                        _ => {
-                            return Err(ProofSystemError::ProofIncompatibleWithStatement(
-                                s_idx,
-                                format!("{:?}", proof),
-                                format!("{:?}", s),
-                            ))
-                        }
+                    StatementProof::SaverWithAggregation(p) => {
+                        let ek_comm_key = ek_comm.get(s_idx).unwrap();
+                        let cc_keys = chunked_comm.get(s_idx).unwrap();
+                        SaverProtocol::compute_challenge_contribution_when_aggregating_snark(
+                            ek_comm_key,
+                            &cc_keys.0,
+                            &cc_keys.1,
+                            p,
+                            &mut transcript,
+                        )?;
                     }
-                }
-                Statement::R1CSCircomVerifier(s) => {
-                    let verifying_key = s.get_verifying_key(&proof_spec.setup_params, s_idx)?;
-                    let sp = R1CSLegogroth16Protocol::new_for_verifier(s_idx, verifying_key);
-                    let pub_inp = s
-                        .get_public_inputs(&proof_spec.setup_params, s_idx)?
-                        .to_vec();
-
-                    match proof {
-                        StatementProof::R1CSLegoGroth16(ref r1cs_proof) => {
-                            for w_id in 0..verifying_key.commit_witness_count as usize {
-                                let w_ref = (s_idx, w_id);
-                                for (i, eq) in disjoint_equalities.iter().enumerate() {
-                                    if eq.has_wit_ref(&w_ref) {
-                                        let resp =
-                                            r1cs_proof.get_schnorr_response_for_message(w_id)?;
-                                        if let Some(r) = resp_for_equalities.get(&i) {
-                                            if resp != r {
-                                                return Err(
-                                                    ProofSystemError::WitnessResponseNotEqual(
-                                                        s_idx, w_id,
-                                                    ),
-                                                );
-                                            }
-                                        } else {
-                                            resp_for_equalities.insert(i, *resp);
-                                        }
-                                    }
-                                }
-                            }
-                            sp.verify_proof_contribution(
-                                &challenge,
-                                &pub_inp,
-                                r1cs_proof,
-                                r1cs_comm_keys.get(s_idx).unwrap(),
-                                derived_lego_vk.get(s_idx).unwrap(),
-                                &mut pairing_checker,
-                            )?
-                        }
-                        StatementProof::R1CSLegoGroth16WithAggregation(ref r1cs_proof) => {
-                            let agg_idx = agg_lego_stmts.get(&s_idx).ok_or_else(|| {
-                                ProofSystemError::InvalidStatementProofIndex(s_idx)
-                            })?;
-                            agg_lego[*agg_idx].0.push(r1cs_proof.commitment);
-                            agg_lego[*agg_idx].1.push(pub_inp);
-
-                            for w_id in 0..verifying_key.commit_witness_count as usize {
-                                let w_ref = (s_idx, w_id);
-                                for (i, eq) in disjoint_equalities.iter().enumerate() {
-                                    if eq.has_wit_ref(&w_ref) {
-                                        let resp =
-                                            r1cs_proof.get_schnorr_response_for_message(w_id)?;
-                                        if let Some(r) = resp_for_equalities.get(&i) {
-                                            if resp != r {
-                                                return Err(
-                                                    ProofSystemError::WitnessResponseNotEqual(
-                                                        s_idx, w_id,
-                                                    ),
-                                                );
-                                            }
-                                        } else {
-                                            resp_for_equalities.insert(i, *resp);
-                                        }
-                                    }
-                                }
-                            }
-
-                            sp.verify_proof_contribution_using_prepared_when_aggregating_snark(
-                                &challenge,
-                                r1cs_proof,
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::BoundCheckLegoGroth16Verifier(s) => match proof {
+                    StatementProof::BoundCheckLegoGroth16(p) => {
+                        let comm_key = bound_check_comm.get(s_idx).unwrap();
+                        BoundCheckLegoGrothProtocol::compute_challenge_contribution(
+                            comm_key,
+                            p,
+                            &mut transcript,
+                        )?;
+                    }
+                    StatementProof::BoundCheckLegoGroth16WithAggregation(p) => {
+                        let comm_key = bound_check_comm.get(s_idx).unwrap();
+                        BoundCheckLegoGrothProtocol::compute_challenge_contribution_when_aggregating_snark(
+                            comm_key,
+                            p,
+                            &mut transcript,
+                        )?;
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::R1CSCircomVerifier(s) => match proof {
+                    StatementProof::R1CSLegoGroth16(p) => {
+                        R1CSLegogroth16Protocol::compute_challenge_contribution(
+                            r1cs_comm_keys.get(s_idx).unwrap(),
+                            p,
+                            &mut transcript,
+                        )?;
+                    }
+                    StatementProof::R1CSLegoGroth16WithAggregation(p) => {
+                        R1CSLegogroth16Protocol::compute_challenge_contribution_when_aggregating_snark(
                                 r1cs_comm_keys.get(s_idx).unwrap(),
-                            )?
-                        }
-                        _ => {
-                            return Err(ProofSystemError::ProofIncompatibleWithStatement(
-                                s_idx,
-                                format!("{:?}", proof),
-                                format!("{:?}", s),
-                            ))
-                        }
+                                p,
+                                &mut transcript,
+                            )?;
                     }
-                }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
                 Statement::PoKPSSignature(s) => match proof {
-                    StatementProof::PoKPSSignature(ref p) => {
-                        let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                    StatementProof::PoKPSSignature(p) => {
+                        let sig_params = s.get_params(&proof_spec.setup_params, s_idx)?;
                         let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = PSSignaturePoK::new(s_idx, &s.revealed_messages, params, pk);
-                        // Check witness equalities for this statement.
-                        let revealed_msg_ids: Vec<_> =
-                            s.revealed_messages.keys().copied().collect();
-                        for w_id in 0..params.supported_message_count() {
-                            let w_ref = (s_idx, w_id);
-                            for (i, eq) in disjoint_equalities.iter().enumerate() {
-                                if eq.has_wit_ref(&w_ref) {
-                                    let resp = p.response_for_message(
-                                        w_id,
-                                        revealed_msg_ids.iter().copied(),
-                                    )?;
-                                    if let Some(r) = resp_for_equalities.get(&i) {
-                                        if resp != r {
-                                            return Err(ProofSystemError::WitnessResponseNotEqual(
-                                                s_idx, w_id,
-                                            ));
-                                        }
-                                    } else {
-                                        resp_for_equalities.insert(i, *resp);
-                                    }
-                                }
-                            }
-                        }
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            derived_ps_pk.get(s_idx).unwrap().clone(),
-                            derived_ps_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                        )
-                        .map_err(|e| ProofSystemError::PSProofContributionFailed(s_idx as u32, e))?
+                        transcript.set_label(PS_LABEL);
+                        p.challenge_contribution(&mut transcript, pk, sig_params)?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::BoundCheckBpp(s) => match proof {
-                    StatementProof::BoundCheckBpp(ref bc_proof) => {
-                        let setup_params = s.get_setup_params(&proof_spec.setup_params, s_idx)?;
-                        let sp = BoundCheckBppProtocol::new(s_idx, s.min, s.max, setup_params);
+                    StatementProof::BoundCheckBpp(p) => {
                         let comm_key = bound_check_bpp_comm.get(s_idx).unwrap();
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            bc_proof,
+                        BoundCheckBppProtocol::<E::G1Affine>::compute_challenge_contribution(
+                            s.min,
+                            s.max,
                             comm_key.as_slice(),
+                            p,
                             &mut transcript,
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
-                        )?
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::BoundCheckSmc(s) => match proof {
-                    StatementProof::BoundCheckSmc(ref bc_proof) => {
-                        let setup_params =
-                            s.get_params_and_comm_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = BoundCheckSmcProtocol::new(s_idx, s.min, s.max, setup_params);
+                    StatementProof::BoundCheckSmc(p) => {
                         let comm_key_slice = bound_check_smc_comm.get(s_idx).unwrap();
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            bc_proof,
+                        BoundCheckSmcProtocol::compute_challenge_contribution(
                             comm_key_slice.as_slice(),
-                            derived_smc_param.get(s_idx).unwrap().clone(),
-                            &mut pairing_checker,
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
-                        )?
+                            p,
+                            s.get_params_and_comm_key(&proof_spec.setup_params, s_idx)
+                                .unwrap(),
+                            &mut transcript,
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::BoundCheckSmcWithKVVerifier(s) => match proof {
-                    StatementProof::BoundCheckSmcWithKV(ref bc_proof) => {
-                        let setup_params =
-                            s.get_params_and_comm_key_and_sk(&proof_spec.setup_params, s_idx)?;
-                        let sp = BoundCheckSmcWithKVProtocol::new_for_verifier(
-                            s_idx,
-                            s.min,
-                            s.max,
-                            setup_params,
-                        );
+                    StatementProof::BoundCheckSmcWithKV(p) => {
                         let comm_key_slice = bound_check_smc_comm.get(s_idx).unwrap();
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            bc_proof,
+                        BoundCheckSmcWithKVProtocol::compute_challenge_contribution(
                             comm_key_slice.as_slice(),
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
+                            p,
+                            s.get_params_and_comm_key_and_sk(&proof_spec.setup_params, s_idx)?,
+                            &mut transcript,
                         )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::PublicInequality(s) => match proof {
-                    StatementProof::Inequality(ref iq_proof) => {
-                        let comm_key = s.get_comm_key(&proof_spec.setup_params, s_idx)?;
-                        let sp = InequalityProtocol::new(s_idx, s.inequal_to, comm_key);
-                        let comm_key = ineq_comm.get(s_idx).unwrap();
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            iq_proof,
-                            comm_key.as_slice(),
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
-                        )?
+                    StatementProof::Inequality(p) => {
+                        let comm_key_slice = ineq_comm.get(s_idx).unwrap();
+                        InequalityProtocol::compute_challenge_contribution(
+                            comm_key_slice.as_slice(),
+                            p,
+                            &s.inequal_to,
+                            s.get_comm_key(&proof_spec.setup_params, s_idx)?,
+                            &mut transcript,
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::DetachedAccumulatorMembershipVerifier(_s) => (),
-                Statement::DetachedAccumulatorNonMembershipVerifier(_s) => (),
-                Statement::PoKBBDT16MAC(s) => match proof {
-                    StatementProof::PoKOfBBDT16MAC(ref p) => {
-                        let mac_params = s.get_params(&proof_spec.setup_params, s_idx)?;
-                        let sp = PoKOfMACSubProtocol::new(s_idx, &s.revealed_messages, mac_params);
-                        let total_msgs = mac_params.supported_message_count();
-                        let missing_responses = get_missing_responses_for_sigs_and_update_resp_eq_map!(
-                            s, s_idx, total_msgs, p
-                        );
-                        if missing_responses.is_empty() {
-                            sp.verify_schnorr_proof_contribution(&challenge, p)
-                                .map_err(|e| {
-                                    ProofSystemError::BBDT16KVACProofContributionFailed(
-                                        s_idx as u32,
-                                        e,
-                                    )
-                                })?
-                        } else {
-                            sp.verify_partial_schnorr_proof_contribution(
-                                &challenge,
-                                p,
-                                missing_responses,
-                            )
-                            .map_err(|e| {
-                                ProofSystemError::BBDT16KVACProofContributionFailed(s_idx as u32, e)
-                            })?
-                        }
+                Statement::HiddenInequality(s) => match proof {
+                    StatementProof::HiddenInequality(p) => {
+                        let comm_key_slice = ineq_comm.get(s_idx).unwrap();
+                        HiddenInequalityProtocol::compute_challenge_contribution(
+                            comm_key_slice.as_slice(),
+                            p,
+                            s.get_comm_key(&proof_spec.setup_params, s_idx)?,
+                            &mut transcript,
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::PoKBBDT16MACFullVerifier(s) => match proof {
-                    StatementProof::PoKOfBBDT16MAC(ref p) => {
-                        let mac_params = s.get_params(&proof_spec.setup_params, s_idx)?;
-                        let sp = PoKOfMACSubProtocol::new(s_idx, &s.revealed_messages, mac_params);
-                        let total_msgs = mac_params.supported_message_count();
-                        let missing_responses = get_missing_responses_for_sigs_and_update_resp_eq_map!(
-                            s, s_idx, total_msgs, p
-                        );
-                        if missing_responses.is_empty() {
-                            sp.verify_mac_and_schnorr_proof_contribution(
-                                &challenge,
-                                p,
-                                &s.secret_key,
-                            )
-                            .map_err(|e| {
-                                ProofSystemError::BBDT16KVACProofContributionFailed(s_idx as u32, e)
-                            })?
-                        } else {
-                            sp.verify_mac_and_partial_schnorr_proof_contribution(
-                                &challenge,
-                                p,
-                                &s.secret_key,
-                                missing_responses,
-                            )
-                            .map_err(|e| {
-                                ProofSystemError::BBDT16KVACProofContributionFailed(s_idx as u32, e)
-                            })?
-                        }
+                Statement::DLEqualToAccumulatorMember(s) => match proof {
+                    StatementProof::PedersenCommitment(p) => {
+                        SchnorrProtocol::compute_challenge_contribution(
+                            ark_std::slice::from_ref(&s.base),
+                            &s.public_key,
+                            &p.t,
+                            &mut transcript,
+                        )?;
+                    }
+                    StatementProof::PedersenCommitmentPartial(p) => {
+                        SchnorrProtocol::compute_challenge_contribution(
+                            ark_std::slice::from_ref(&s.base),
+                            &s.public_key,
+                            &p.t,
+                            &mut transcript,
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::VBAccumulatorMembershipKV(s) => match proof {
-                    StatementProof::VBAccumulatorMembershipKV(ref p) => {
-                        let sp =
-                            VBAccumulatorMembershipKVSubProtocol::new(s_idx, s.accumulator_value);
-                        sp.verify_proof_contribution(
-                            &challenge,
+                Statement::PublicSetMembership(s) => match proof {
+                    StatementProof::PublicSetMembership(p) => {
+                        let comm_key_slice = ineq_comm.get(s_idx).unwrap();
+                        PublicSetMembershipProtocol::compute_challenge_contribution(
+                            comm_key_slice.as_slice(),
                             p,
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
-                        )?
+                            &s.set,
+                            s.get_comm_key(&proof_spec.setup_params, s_idx)?,
+                            &mut transcript,
+                        )?;
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::VBAccumulatorMembershipKVFullVerifier(s) => match proof {
-                    StatementProof::VBAccumulatorMembershipKV(ref p) => {
-                        let sp =
-                            VBAccumulatorMembershipKVSubProtocol::new(s_idx, s.accumulator_value);
-                        sp.verify_full_proof_contribution(
-                            &challenge,
-                            p,
-                            &s.secret_key,
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
-                        )?
+                Statement::DetachedAccumulatorMembershipVerifier(s) => match proof {
+                    StatementProof::DetachedAccumulatorMembership(_p) => {
+                        // check_resp_for_equalities!(
+                        //     witness_equalities,
+                        //     s_idx,
+                        //     p.accum_proof,
+                        //     get_schnorr_response_for_element,
+                        //     Self,
+                        //     responses_for_equalities
+                        // );
+                        // let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        // let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        // let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                        // transcript.set_label(VB_ACCUM_MEM_LABEL);
+                        // p.accum_proof.challenge_contribution(
+                        //     &p.accumulator,
+                        //     pk,
+                        //     params,
+                        //     prk,
+                        //     &mut transcript,
+                        // )?;
+                        todo!()
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
-                Statement::KBUniversalAccumulatorMembershipKV(s) => match proof {
-                    StatementProof::KBUniversalAccumulatorMembershipKV(ref p) => {
-                        let sp = KBUniversalAccumulatorMembershipKVSubProtocol::new(
-                            s_idx,
-                            s.accumulator_value,
-                        );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
-                        )?
+                Statement::DetachedAccumulatorNonMembershipVerifier(s) => match proof {
+                    StatementProof::DetachedAccumulatorNonMembership(_p) => {
+                        // check_resp_for_equalities!(
+                        //     witness_equalities,
+                        //     s_idx,
+                        //     p.accum_proof,
+                        //     get_schnorr_response_for_element,
+                        //     Self,
+                        //     responses_for_equalities
+                        // );
+                        // let params = s.get_params(&proof_spec.setup_params, s_idx)?;
+                        // let pk = s.get_public_key(&proof_spec.setup_params, s_idx)?;
+                        // let prk = s.get_proving_key(&proof_spec.setup_params, s_idx)?;
+                        // transcript.set_label(VB_ACCUM_NON_MEM_LABEL);
+                        // p.accum_proof.challenge_contribution(
+                        //     &p.accumulator,
+                        //     pk,
+                        //     params,
+                        //     prk,
+                        //     &mut transcript,
+                        // )?;
+                        todo!()
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::PoKBBDT16MAC(s) => match proof {
+                    StatementProof::PoKOfBBDT16MAC(p) => {
+                        sig_protocol_chal_gen!(s, s_idx, p, BBDT16_KVAC_LABEL);
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::PoKBBDT16MACFullVerifier(s) => match proof {
+                    StatementProof::PoKOfBBDT16MAC(p) => {
+                        sig_protocol_chal_gen!(s, s_idx, p, BBDT16_KVAC_LABEL);
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::VBAccumulatorMembershipKV(s) => match proof {
+                    StatementProof::VBAccumulatorMembershipKV(p) => {
+                        accum_cdh_protocol_chal_gen!(s, s_idx, p, VB_ACCUM_MEM_LABEL);
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::VBAccumulatorMembershipKVFullVerifier(s) => match proof {
+                    StatementProof::VBAccumulatorMembershipKV(p) => {
+                        accum_cdh_protocol_chal_gen!(s, s_idx, p, VB_ACCUM_MEM_LABEL);
+                    }
+                    _ => err_incompat_proof!(s_idx, s, proof),
+                },
+                Statement::KBUniversalAccumulatorMembershipKV(s) => match proof {
+                    StatementProof::KBUniversalAccumulatorMembershipKV(p) => {
+                        accum_cdh_protocol_chal_gen!(s, s_idx, p, KB_UNI_ACCUM_MEM_LABEL);
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::KBUniversalAccumulatorMembershipKVFullVerifier(s) => match proof {
-                    StatementProof::KBUniversalAccumulatorMembershipKV(ref p) => {
-                        let sp = KBUniversalAccumulatorMembershipKVSubProtocol::new(
-                            s_idx,
-                            s.accumulator_value,
-                        );
-                        sp.verify_full_proof_contribution(
-                            &challenge,
-                            p,
-                            &s.secret_key,
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
-                        )?
+                    StatementProof::KBUniversalAccumulatorMembershipKV(p) => {
+                        accum_cdh_protocol_chal_gen!(s, s_idx, p, KB_UNI_ACCUM_MEM_LABEL);
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::KBUniversalAccumulatorNonMembershipKV(s) => match proof {
-                    StatementProof::KBUniversalAccumulatorNonMembershipKV(ref p) => {
-                        let sp = KBUniversalAccumulatorNonMembershipKVSubProtocol::new(
-                            s_idx,
-                            s.accumulator_value,
-                        );
-                        sp.verify_proof_contribution(
-                            &challenge,
-                            p,
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
-                        )?
+                    StatementProof::KBUniversalAccumulatorNonMembershipKV(p) => {
+                        accum_cdh_protocol_chal_gen!(s, s_idx, p, KB_UNI_ACCUM_NON_MEM_LABEL);
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::KBUniversalAccumulatorNonMembershipKVFullVerifier(s) => match proof {
-                    StatementProof::KBUniversalAccumulatorNonMembershipKV(ref p) => {
-                        let sp = KBUniversalAccumulatorNonMembershipKVSubProtocol::new(
-                            s_idx,
-                            s.accumulator_value,
-                        );
-                        sp.verify_full_proof_contribution(
-                            &challenge,
-                            p,
-                            &s.secret_key,
-                            Self::get_resp_for_message(
-                                s_idx,
-                                0,
-                                &disjoint_equalities,
-                                &resp_for_equalities,
-                            )?,
-                        )?
+                    StatementProof::KBUniversalAccumulatorNonMembershipKV(p) => {
+                        accum_cdh_protocol_chal_gen!(s, s_idx, p, KB_UNI_ACCUM_NON_MEM_LABEL);
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::VeTZ21(s) => match proof {
-                    StatementProof::VeTZ21(ref p) => {
-                        tz_21_verify!(s, s_idx, p, verify_proof_contribution);
+                    StatementProof::VeTZ21(p) => {
+                        let comm_key = s.get_comm_key(&proof_spec.setup_params, s_idx)?.as_slice();
+                        let enc_params = s.get_enc_params(&proof_spec.setup_params, s_idx)?;
+                        transcript.set_label(VE_TZ_21_LABEL);
+                        VeTZ21Protocol::compute_challenge_contribution(
+                            enc_params,
+                            comm_key,
+                            p,
+                            &mut transcript,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 Statement::VeTZ21Robust(s) => match proof {
-                    StatementProof::VeTZ21Robust(ref p) => {
-                        tz_21_verify!(s, s_idx, p, verify_proof_contribution_robust);
+                    StatementProof::VeTZ21Robust(p) => {
+                        let comm_key = s.get_comm_key(&proof_spec.setup_params, s_idx)?.as_slice();
+                        let enc_params = s.get_enc_params(&proof_spec.setup_params, s_idx)?;
+                        transcript.set_label(VE_TZ_21_ROBUST_LABEL);
+                        VeTZ21Protocol::compute_challenge_contribution_robust(
+                            enc_params,
+                            comm_key,
+                            p,
+                            &mut transcript,
+                        )?
                     }
                     _ => err_incompat_proof!(s_idx, s, proof),
                 },
                 _ => return Err(ProofSystemError::InvalidStatement),
             }
+            timer.record(TimedPhase::ChallengeContribution(s_idx), start);
         }
+        Ok(transcript)
+    }
 
-        // If even one of witness equality had no corresponding response, it means that wasn't satisfied
-        // and proof should not verify
-        let mut unsatisfied = vec![];
-        for (i, eq) in disjoint_equalities.into_iter().enumerate() {
-            if !resp_for_equalities.contains_key(&i) {
-                unsatisfied.push(eq.0)
-            }
-        }
-        if !unsatisfied.is_empty() {
-            return Err(ProofSystemError::UnsatisfiedWitnessEqualities(unsatisfied));
-        }
-
-        if aggregate_snarks {
-            // The validity of `ProofSpec` ensures that statements are not being repeated
-
-            let srs = match proof_spec.snark_aggregation_srs {
-                Some(SnarkpackSRS::VerifierSrs(srs)) => srs,
-                _ => return Err(ProofSystemError::SnarckpackSrsNotProvided),
-            };
-
-            if let Some(to_aggregate) = proof_spec.aggregate_groth16 {
-                if let Some(aggr_proofs) = self.aggregated_groth16 {
-                    expect_equality!(
-                        to_aggregate.len(),
-                        aggr_proofs.len(),
-                        ProofSystemError::InvalidNumberOfAggregateGroth16Proofs
-                    );
-                    for (i, a) in aggr_proofs.into_iter().enumerate() {
-                        if to_aggregate[i] != a.statements {
-                            return Err(
-                                ProofSystemError::NotFoundAggregateGroth16ProofForRequiredStatements(
-                                    i,
-                                    to_aggregate[i].clone(),
-                                ),
-                            );
-                        }
-                        let s_id = a.statements.into_iter().next().unwrap();
-                        let pvk = derived_saver_vk.get(s_id).unwrap();
-                        let ciphertexts = &agg_saver[i];
-                        SaverProtocol::verify_ciphertext_commitments_in_batch(
-                            rng,
-                            ciphertexts,
-                            derived_gens.get(s_id).unwrap().clone(),
-                            derived_ek.get(s_id).unwrap().clone(),
-                            &mut pairing_checker,
-                        )?;
-                        saver::saver_groth16::verify_aggregate_proof(
-                            &srs,
-                            pvk,
-                            &a.proof,
-                            ciphertexts,
-                            rng,
-                            &mut transcript,
-                            pairing_checker.as_mut(),
-                        )?;
-                    }
-                } else {
-                    return Err(ProofSystemError::NoAggregateGroth16ProofFound);
-                }
-            }
-
-            if let Some(to_aggregate) = proof_spec.aggregate_legogroth16 {
-                if let Some(aggr_proofs) = self.aggregated_legogroth16 {
-                    expect_equality!(
-                        to_aggregate.len(),
-                        aggr_proofs.len(),
-                        ProofSystemError::InvalidNumberOfAggregateLegoGroth16Proofs
-                    );
-                    for (i, a) in aggr_proofs.into_iter().enumerate() {
-                        if to_aggregate[i] != a.statements {
-                            return Err(ProofSystemError::NotFoundAggregateLegoGroth16ProofForRequiredStatements(i, to_aggregate[i].clone()));
-                        }
-                        let s_id = a.statements.into_iter().next().unwrap();
-                        let pvk = derived_lego_vk.get(s_id).unwrap();
-                        legogroth16::aggregation::legogroth16::using_groth16::verify_aggregate_proof(
-                            &srs,
-                            pvk,
-                            &agg_lego[i].1,
-                            &a.proof,
-                            &agg_lego[i].0,
-                            rng,
-                            &mut transcript,
-                            pairing_checker.as_mut(),
-                        )
-                            .map_err(|e| ProofSystemError::LegoGroth16Error(e.into()))?
-                    }
-                } else {
-                    return Err(ProofSystemError::NoAggregateLegoGroth16ProofFound);
-                }
-            }
+    /// Check that the group elements of each statement and its proof are on the curve and in the prime order subgroup
+    fn check_group_elements(&self, statements: &Statements<E>) -> Result<(), ProofSystemError> {
+        for (s_idx, statement) in statements.0.iter().enumerate() {
+            statement
+                .check()
+                .map_err(|_| ProofSystemError::InvalidGroupElement(s_idx))?;
         }
-
-        // If randomized pairing checker was used, verify all its pairing checks
-        if let Some(c) = pairing_checker {
-            if !c.verify() {
-                return Err(ProofSystemError::RandomizedPairingCheckFailed);
-            }
+        for (s_idx, proof) in self.statement_proofs.iter().enumerate() {
+            proof
+                .check()
+                .map_err(|_| ProofSystemError::InvalidGroupElement(s_idx))?;
         }
         Ok(())
     }
 
-    pub fn get_saver_ciphertext_and_proof(
-        &self,
-        index: usize,
-    ) -> Result<(&Ciphertext<E>, &ark_groth16::Proof<E>), ProofSystemError> {
-        let st = self.statement_proof(index)?;
-        if let StatementProof::Saver(s) = st {
-            Ok((&s.ciphertext, &s.snark_proof))
-        } else {
-            Err(ProofSystemError::NotASaverStatementProof)
-        }
+    /// Whether verifying the statements' proofs needs the transcript the challenge was generated from and not
+    /// just the challenge
+    fn needs_transcript(proof_spec: &ProofSpec<E>) -> bool {
+        proof_spec.aggregate_groth16.is_some()
+            || proof_spec.aggregate_legogroth16.is_some()
+            || proof_spec.statements.0.iter().any(|s| {
+                matches!(
+                    s,
+                    Statement::BoundCheckBpp(_) | Statement::VeTZ21(_) | Statement::VeTZ21Robust(_)
+                )
+            })
     }
 
-    pub fn get_legogroth16_proof(
-        &self,
-        index: usize,
-    ) -> Result<&legogroth16::Proof<E>, ProofSystemError> {
-        let st = self.statement_proof(index)?;
-        match st {
-            StatementProof::BoundCheckLegoGroth16(s) => Ok(&s.snark_proof),
-            StatementProof::R1CSLegoGroth16(s) => Ok(&s.snark_proof),
-            _ => Err(ProofSystemError::NotALegoGroth16StatementProof),
-        }
+    /// Hash of the serialized proof to bind a `VerificationCheckpoint` to the proof
+    fn digest_for_checkpoint(&self) -> Result<Vec<u8>, ProofSystemError> {
+        let mut bytes = Vec::new();
+        self.serialize_compressed(&mut bytes)?;
+        Ok(Sha3_512::digest(&bytes).to_vec())
     }
 
-    /// Get the compressed ciphertext and commitment to needed to decrypt message encrypted using DKGitH protocol
-    pub fn get_tz21_ciphertext_and_commitment<D: FullDigest + Digest>(
-        &self,
-        index: usize,
-    ) -> Result<(dkgith_decls::Ciphertext<E::G1Affine>, E::G1Affine), ProofSystemError> {
-        let st = self.statement_proof(index)?;
-        if let StatementProof::VeTZ21(s) = st {
-            let ve_proof = &s.ve_proof;
-            // TODO: Make Shake256 a generic and ensure it matches the one used on proof generation
-            let ct = ve_proof.compress::<{ dkgith_decls::SUBSET_SIZE }, D, Shake256>()?;
-            Ok((ct, s.commitment))
-        } else {
-            Err(ProofSystemError::NotAVeTZ21StatementProof)
-        }
+    /// Keyed hash authenticating everything in `checkpoint` except its tag along with the proof spec and the
+    /// parts of `config` that the challenge depends on
+    fn checkpoint_tag(
+        key: &[u8; 32],
+        checkpoint: &VerificationCheckpoint<E>,
+        prepared: &PreparedProofSpec<E>,
+        config: &VerifierConfig,
+    ) -> Result<CtOutput<Sha3_256>, ProofSystemError> {
+        let mut bytes = Vec::new();
+        checkpoint.challenge.serialize_compressed(&mut bytes)?;
+        checkpoint.nonce.serialize_compressed(&mut bytes)?;
+        checkpoint.proof_digest.serialize_compressed(&mut bytes)?;
+        prepared.spec.serialize_compressed(&mut bytes)?;
+        config.transcript_label.serialize_compressed(&mut bytes)?;
+        config.transcript_builder.serialize_compressed(&mut bytes)?;
+        // SHA-3 isn't vulnerable to length extension so prefixing the key gives a MAC
+        let mut hasher = Sha3_256::new_with_prefix(key);
+        Digest::update(&mut hasher, &bytes);
+        Ok(CtOutput::new(hasher.finalize()))
     }
 
-    /// Get the compressed ciphertext and commitment to needed to decrypt message encrypted using Robust DKGitH protocol
-    pub fn get_tz21_robust_ciphertext_and_commitment<D: FullDigest + Digest>(
-        &self,
-        index: usize,
-    ) -> Result<(rdkgith_decls::Ciphertext<E::G1Affine>, E::G1Affine), ProofSystemError> {
-        let st = self.statement_proof(index)?;
-        if let StatementProof::VeTZ21Robust(s) = st {
-            let ve_proof = &s.ve_proof;
-            let ct = ve_proof.compress::<{ rdkgith_decls::SUBSET_SIZE }, D>()?;
-            Ok((ct, s.commitment))
-        } else {
-            Err(ProofSystemError::NotAVeTZ21StatementProof)
+    fn check_cancelled(cancel: Option<&AtomicBool>) -> Result<(), ProofSystemError> {
+        match cancel {
+            Some(c) if c.load(Ordering::Acquire) => Err(ProofSystemError::Cancelled),
+            _ => Ok(()),
         }
     }
 
-    /*/// Used to check if response (from Schnorr protocol) for a witness is equal to other witnesses that
-    /// it must be equal to. This is required when the `ProofSpec` demands certain witnesses to be equal.
-    fn check_response_for_equality<'a>(
-        stmt_id: usize,
-        wit_id: usize,
-        equality_id: usize,
-        responses_for_equalities: &mut [Option<&'a E::ScalarField>],
-        resp: &'a E::ScalarField,
-    ) -> Result<(), ProofSystemError> {
-        if responses_for_equalities[equality_id].is_none() {
-            // First response encountered for the witness
-            responses_for_equalities[equality_id] = Some(resp);
-        } else if responses_for_equalities[equality_id] != Some(resp) {
-            return Err(ProofSystemError::WitnessResponseNotEqual(stmt_id, wit_id));
+    /// Make the pairing checker use the laziness chosen for the statement at index `s_idx`, falling back
+    /// to the one the checker was created with. The multipliers of the statement's pairing equations are
+    /// bound to `s_idx` so that they don't depend on the order of the statements.
+    fn set_up_checker_for_statement(
+        pairing_checker: &mut Option<RandomizedPairingChecker<E>>,
+        per_statement_laziness: &Option<BTreeMap<usize, bool>>,
+        default_laziness: Option<bool>,
+        s_idx: usize,
+    ) {
+        if let (Some(c), Some(default)) = (pairing_checker.as_mut(), default_laziness) {
+            let lazy = per_statement_laziness
+                .as_ref()
+                .and_then(|l| l.get(&s_idx).copied())
+                .unwrap_or(default);
+            c.set_lazy(lazy);
+            c.set_domain(Some(s_idx));
         }
-        Ok(())
-    }*/
+    }
+
+    /// Get the response for witness `witness_idx` from the statement proof if that kind of proof provides
+    /// responses for equalities. Returns `None` for proofs that only consume responses from other proofs.
+    fn get_resp_from_statement_proof(
+        statement: &Statement<E>,
+        proof: &StatementProof<E>,
+        witness_idx: usize,
+    ) -> Result<Option<E::ScalarField>, ProofSystemError> {
+        let resp = match (statement, proof) {
+            (Statement::PoKBBSSignatureG1Verifier(s), StatementProof::PoKBBSSignatureG1(p)) => {
+                let revealed_idx = BTreeSet::from_iter(s.revealed_messages.keys().cloned());
+                *p.get_resp_for_message(witness_idx, &revealed_idx)?
+            }
+            (Statement::PoKBBSSignature23G1Verifier(s), StatementProof::PoKBBSSignature23G1(p)) => {
+                let revealed_idx = BTreeSet::from_iter(s.revealed_messages.keys().cloned());
+                *p.get_resp_for_message(witness_idx, &revealed_idx)?
+            }
+            (
+                Statement::PoKBBSSignature23IETFG1Verifier(s),
+                StatementProof::PoKBBSSignature23IETFG1(p),
+            ) => {
+                let revealed_idx = BTreeSet::from_iter(s.revealed_messages.keys().cloned());
+                *p.get_resp_for_message(witness_idx, &revealed_idx)?
+            }
+            (Statement::PoKBBDT16MAC(s), StatementProof::PoKOfBBDT16MAC(p)) => {
+                let revealed_idx = BTreeSet::from_iter(s.revealed_messages.keys().cloned());
+                *p.get_resp_for_message(witness_idx, &revealed_idx)?
+            }
+            (Statement::PoKBBDT16MACFullVerifier(s), StatementProof::PoKOfBBDT16MAC(p)) => {
+                let revealed_idx = BTreeSet::from_iter(s.revealed_messages.keys().cloned());
+                *p.get_resp_for_message(witness_idx, &revealed_idx)?
+            }
+            (Statement::PoKPSSignature(s), StatementProof::PoKPSSignature(p)) => {
+                *p.response_for_message(witness_idx, s.revealed_messages.keys().copied())?
+            }
+            (Statement::PedersenCommitment(_), StatementProof::PedersenCommitment(p)) => {
+                *p.get_resp_for_message(witness_idx)?
+            }
+            (Statement::PedersenCommitment(_), StatementProof::PedersenCommitmentPartial(p)) => {
+                *p.get_resp_for_message(witness_idx)?
+            }
+            (Statement::DLEqualToAccumulatorMember(_), StatementProof::PedersenCommitment(p)) => {
+                *p.get_resp_for_message(witness_idx)?
+            }
+            (
+                Statement::DLEqualToAccumulatorMember(_),
+                StatementProof::PedersenCommitmentPartial(p),
+            ) => *p.get_resp_for_message(witness_idx)?,
+            (Statement::PedersenCommitmentG2(_), StatementProof::PedersenCommitmentG2(p)) => {
+                *p.get_resp_for_message(witness_idx)?
+            }
+            (
+                Statement::PedersenCommitmentG2(_),
+                StatementProof::PedersenCommitmentG2Partial(p),
+            ) => *p.get_resp_for_message(witness_idx)?,
+            (Statement::R1CSCircomVerifier(_), StatementProof::R1CSLegoGroth16(p)) => {
+                *p.get_schnorr_response_for_message(witness_idx)?
+            }
+            (
+                Statement::R1CSCircomVerifier(_),
+                StatementProof::R1CSLegoGroth16WithAggregation(p),
+            ) => *p.get_schnorr_response_for_message(witness_idx)?,
+            _ => return Ok(None),
+        };
+        Ok(Some(resp))
+    }
 
     /// Get the response for a witness from the tracked responses of witness equalities.
     /// Expects the response to exist else throws error. This is not to be called for signature proof protocols
@@ -1938,3 +3210,66 @@ impl<E: Pairing> Proof<E> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::{Bls12_381, G1Affine, G2Affine};
+    use ark_ec::AffineRepr;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn per_statement_laziness_overrides_default() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let g1 = G1Affine::generator();
+        let g2 = G2Affine::generator();
+        let out = Bls12_381::pairing(g1, g2);
+
+        // Lazy by default but not for the statement at index 1
+        let per_statement_laziness = Some(BTreeMap::from([(1, false)]));
+        let mut checker = Some(RandomizedPairingChecker::<Bls12_381>::new_using_rng(
+            &mut rng, true,
+        ));
+
+        Proof::<Bls12_381>::set_up_checker_for_statement(
+            &mut checker,
+            &per_statement_laziness,
+            Some(true),
+            0,
+        );
+        checker
+            .as_mut()
+            .unwrap()
+            .add_sources_and_target(&g1, g2, &out);
+        assert!(checker.as_ref().unwrap().is_lazy());
+        assert_eq!(checker.as_ref().unwrap().pending_pairing_count(), 1);
+
+        Proof::<Bls12_381>::set_up_checker_for_statement(
+            &mut checker,
+            &per_statement_laziness,
+            Some(true),
+            1,
+        );
+        checker
+            .as_mut()
+            .unwrap()
+            .add_sources_and_target(&g1, g2, &out);
+        assert!(!checker.as_ref().unwrap().is_lazy());
+        assert_eq!(checker.as_ref().unwrap().pending_pairing_count(), 1);
+        assert_eq!(checker.as_ref().unwrap().total_added(), 2);
+
+        // Statements without an override use the default again
+        Proof::<Bls12_381>::set_up_checker_for_statement(
+            &mut checker,
+            &per_statement_laziness,
+            Some(true),
+            2,
+        );
+        checker
+            .as_mut()
+            .unwrap()
+            .add_sources_and_target(&g1, g2, &out);
+        assert_eq!(checker.as_ref().unwrap().pending_pairing_count(), 2);
+        assert!(checker.unwrap().verify());
+    }
+}
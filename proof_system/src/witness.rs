@@ -56,6 +56,16 @@ pub enum Witness<E: Pairing> {
     VeTZ21Robust(
         #[cfg_attr(feature = "serde", serde_as(as = "Vec<ArkObjectBytes>"))] Vec<E::ScalarField>,
     ),
+    KBUniAccumulatorMembershipAndNonMembership(KBUniMembershipAndNonMembership<E::G1Affine>),
+    HiddenInequality(HiddenInequality<E::G1Affine>),
+    /// The witness which is proven to be a member of a public set
+    PublicSetMembership(
+        #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))] E::ScalarField,
+    ),
+    /// The discrete log of the public key which is also the accumulator member
+    DLEqualToAccumulatorMember(
+        #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))] E::ScalarField,
+    ),
 }
 
 macro_rules! delegate {
@@ -80,7 +90,11 @@ macro_rules! delegate {
                 KBUniAccumulatorNonMembership,
                 PoKOfBBDT16MAC,
                 VeTZ21,
-                VeTZ21Robust
+                VeTZ21Robust,
+                KBUniAccumulatorMembershipAndNonMembership,
+                HiddenInequality,
+                PublicSetMembership,
+                DLEqualToAccumulatorMember
             : $($tt)+
         }
     }}
@@ -108,7 +122,11 @@ macro_rules! delegate_reverse {
                 KBUniAccumulatorNonMembership,
                 PoKOfBBDT16MAC,
                 VeTZ21,
-                VeTZ21Robust
+                VeTZ21Robust,
+                KBUniAccumulatorMembershipAndNonMembership,
+                HiddenInequality,
+                PublicSetMembership,
+                DLEqualToAccumulatorMember
             : $($tt)+
         }
 
@@ -252,6 +270,32 @@ pub struct KBUniNonMembership<G: AffineRepr> {
     pub witness: vb_accumulator::kb_universal_accumulator::witness::KBUniversalAccumulatorNonMembershipWitness<G>,
 }
 
+/// Secret data when proving membership of one element and non-membership of another in the same KB
+/// universal accumulator
+#[derive(
+    Clone, Debug, PartialEq, Eq, Zeroize, ZeroizeOnDrop, CanonicalSerialize, CanonicalDeserialize,
+)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct KBUniMembershipAndNonMembership<G: AffineRepr> {
+    pub membership: KBUniMembership<G>,
+    pub non_membership: KBUniNonMembership<G>,
+}
+
+/// Secret data when proving that 2 witnesses are not equal
+#[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, Zeroize, ZeroizeOnDrop, CanonicalSerialize, CanonicalDeserialize,
+)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct HiddenInequality<G: AffineRepr> {
+    #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))]
+    pub value1: G::ScalarField,
+    #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))]
+    pub value2: G::ScalarField,
+}
+
 /// Secret data when proving KB positive accumulator membership
 #[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
 #[derive(
@@ -385,6 +429,38 @@ impl<G: AffineRepr> KBUniNonMembership<G> {
     }
 }
 
+impl<G: AffineRepr> KBUniMembershipAndNonMembership<G> {
+    /// Create a `Witness` variant for proving membership of `member` and non-membership of `non_member`
+    /// in the same KB universal accumulator
+    pub fn new_as_witness<E: Pairing<G1Affine = G>>(
+        member: G::ScalarField,
+        membership_witness: vb_accumulator::kb_universal_accumulator::witness::KBUniversalAccumulatorMembershipWitness<G>,
+        non_member: G::ScalarField,
+        non_membership_witness: vb_accumulator::kb_universal_accumulator::witness::KBUniversalAccumulatorNonMembershipWitness<G>,
+    ) -> Witness<E> {
+        Witness::KBUniAccumulatorMembershipAndNonMembership(Self {
+            membership: KBUniMembership {
+                element: member,
+                witness: membership_witness,
+            },
+            non_membership: KBUniNonMembership {
+                element: non_member,
+                witness: non_membership_witness,
+            },
+        })
+    }
+}
+
+impl<G: AffineRepr> HiddenInequality<G> {
+    /// Create a `Witness` variant for proving that `value1` and `value2` are not equal
+    pub fn new_as_witness<E: Pairing<G1Affine = G>>(
+        value1: G::ScalarField,
+        value2: G::ScalarField,
+    ) -> Witness<E> {
+        Witness::HiddenInequality(Self { value1, value2 })
+    }
+}
+
 impl<E: Pairing> KBPosMembership<E> {
     /// Create a `Witness` variant for proving membership in KB positive accumulator
     pub fn new_as_witness(
@@ -1,6 +1,7 @@
 #![allow(non_snake_case)]
 
 use ark_bls12_381::{Bls12_381, Fr, G1Affine};
+use ark_ec::CurveGroup;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{
     rand::{prelude::StdRng, SeedableRng},
@@ -8,28 +9,35 @@ use ark_std::{
 };
 use blake2::Blake2b512;
 use proof_system::{
+    error::ProofSystemError,
     meta_statement::{EqualWitnesses, MetaStatements, WitnessRef},
     proof::Proof,
     proof_spec::ProofSpec,
     setup_params::SetupParams,
     statement::{
-        accumulator::cdh::{
-            KBPositiveAccumulatorMembershipCDH, KBUniversalAccumulatorMembershipCDHProver,
-            KBUniversalAccumulatorMembershipCDHVerifier,
-            KBUniversalAccumulatorNonMembershipCDHProver,
-            KBUniversalAccumulatorNonMembershipCDHVerifier, VBAccumulatorMembershipCDHProver,
-            VBAccumulatorMembershipCDHVerifier, VBAccumulatorNonMembershipCDHProver,
-            VBAccumulatorNonMembershipCDHVerifier,
+        accumulator::{
+            cdh::{
+                KBPositiveAccumulatorMembershipCDH, KBUniversalAccumulatorMembershipCDHProver,
+                KBUniversalAccumulatorMembershipCDHVerifier,
+                KBUniversalAccumulatorNonMembershipCDHProver,
+                KBUniversalAccumulatorNonMembershipCDHVerifier, VBAccumulatorMembershipCDHProver,
+                VBAccumulatorMembershipCDHVerifier, VBAccumulatorNonMembershipCDHProver,
+                VBAccumulatorNonMembershipCDHVerifier,
+            },
+            DetachedAccumulatorMembershipProver, KBPositiveAccumulatorMembership,
+            KBUniversalAccumulatorMembershipAndNonMembership,
         },
         bbs_plus::{
             PoKBBSSignatureG1Prover as PoKSignatureBBSG1ProverStmt,
             PoKBBSSignatureG1Verifier as PoKSignatureBBSG1VerifierStmt,
         },
+        dl_equal_to_accumulator_member::DLEqualToAccumulatorMember,
         Statements,
     },
     verifier::VerifierConfig,
     witness::{
         KBPosMembership, KBUniMembership as KBUniMembershipWit,
+        KBUniMembershipAndNonMembership as KBUniMembershipAndNonMembershipWit,
         KBUniNonMembership as KBUniNonMembershipWit, Membership as MembershipWit,
         NonMembership as NonMembershipWit, PoKBBSSignatureG1 as PoKSignatureBBSG1Wit, Witness,
         Witnesses,
@@ -40,8 +48,8 @@ use std::{
     collections::{BTreeMap, BTreeSet},
     time::Instant,
 };
-use test_utils::{accumulators::*, bbs::*, test_serialization};
-use vb_accumulator::positive::Accumulator;
+use test_utils::{accumulators::*, bbs::*, test_proof_size_estimate, test_serialization};
+use vb_accumulator::{positive::Accumulator, setup::Keypair};
 
 #[test]
 fn pok_of_bbs_plus_sig_and_vb_and_kb_universal_accumulator_with_cdh_proof() {
@@ -195,6 +203,7 @@ fn pok_of_bbs_plus_sig_and_vb_and_kb_universal_accumulator_with_cdh_proof() {
             nonce.clone(),
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -315,6 +324,7 @@ fn pok_of_bbs_plus_sig_and_vb_and_kb_universal_accumulator_with_cdh_proof() {
             nonce.clone(),
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -437,6 +447,7 @@ fn pok_of_bbs_plus_sig_and_vb_and_kb_universal_accumulator_with_cdh_proof() {
             nonce.clone(),
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -560,6 +571,7 @@ fn pok_of_bbs_plus_sig_and_vb_and_kb_universal_accumulator_with_cdh_proof() {
             nonce.clone(),
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -676,6 +688,7 @@ start.elapsed()
             nonce.clone(),
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -811,6 +824,7 @@ start.elapsed()
             nonce.clone(),
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -1040,8 +1054,934 @@ start.elapsed()
             nonce,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
     println!("Time to verify proof with a BBS+ signature and 6 accumulator membership and non-membership checks with randomized pairing check: {:?}", start.elapsed());
 }
+
+#[test]
+fn shared_element_in_whitelist_and_blacklist() {
+    // Prove knowledge of BBS+ signature and that one of the messages is a member of a whitelist and
+    // not a member of a blacklist without wiring the witness equalities by hand
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let max = 10;
+    let (wl_params, wl_keypair, mut whitelist, mut wl_state) = setup_positive_accum(&mut rng);
+    let (bl_params, bl_keypair, blacklist, _, bl_state) = setup_universal_accum(&mut rng, max);
+
+    let msg_count = 6;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let Q = G1Affine::rand(&mut rng);
+
+    let member_idx = 1;
+    let member = msgs[member_idx];
+    whitelist = whitelist
+        .add(member, &wl_keypair.secret_key, &mut wl_state)
+        .unwrap();
+    let mem_wit = whitelist
+        .get_membership_witness(&member, &wl_keypair.secret_key, &wl_state)
+        .unwrap();
+    let non_mem_wit = blacklist
+        .get_non_membership_witness(&member, &bl_keypair.secret_key, &bl_state, &bl_params)
+        .unwrap();
+
+    let mut prover_statements = Statements::new();
+    let mut meta_statements = MetaStatements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    let indices = prover_statements
+        .add_shared_accumulator_memberships(
+            &mut meta_statements,
+            (0, member_idx),
+            vec![VBAccumulatorMembershipCDHProver::new(*whitelist.value())],
+        )
+        .unwrap();
+    assert_eq!(indices, vec![1]);
+    let indices = prover_statements
+        .add_shared_accumulator_non_memberships(
+            &mut meta_statements,
+            (0, member_idx),
+            vec![
+                VBAccumulatorNonMembershipCDHProver::new_statement_from_params(
+                    *blacklist.value(),
+                    Q,
+                    bl_params.clone(),
+                ),
+            ],
+        )
+        .unwrap();
+    assert_eq!(indices, vec![2]);
+    assert_eq!(meta_statements.len(), 2);
+    assert_eq!(
+        meta_statements.disjoint_witness_equalities(),
+        vec![EqualWitnesses(
+            vec![(0, member_idx), (1, 0), (2, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>()
+        )]
+    );
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements
+        .add_shared_accumulator_memberships(
+            &mut MetaStatements::new(),
+            (0, member_idx),
+            vec![
+                VBAccumulatorMembershipCDHVerifier::new_statement_from_params(
+                    wl_params.clone(),
+                    wl_keypair.public_key.clone(),
+                    *whitelist.value(),
+                ),
+            ],
+        )
+        .unwrap();
+    verifier_statements
+        .add_shared_accumulator_non_memberships(
+            &mut MetaStatements::new(),
+            (0, member_idx),
+            vec![
+                VBAccumulatorNonMembershipCDHVerifier::new_statement_from_params(
+                    bl_params.clone(),
+                    bl_keypair.public_key.clone(),
+                    *blacklist.value(),
+                    Q,
+                ),
+            ],
+        )
+        .unwrap();
+
+    // Only accumulator membership statements can be given. Non-membership statements are added with
+    // `add_shared_accumulator_non_memberships` and detached accumulator statements aren't supported
+    for statement in [
+        VBAccumulatorNonMembershipCDHProver::new_statement_from_params(
+            *blacklist.value(),
+            Q,
+            bl_params.clone(),
+        ),
+        DetachedAccumulatorMembershipProver::new_statement_from_params_ref(
+            0,
+            1,
+            2,
+            *whitelist.value(),
+        ),
+    ] {
+        assert!(matches!(
+            Statements::<Bls12_381>::new().add_shared_accumulator_memberships(
+                &mut MetaStatements::new(),
+                (0, member_idx),
+                vec![
+                    VBAccumulatorMembershipCDHProver::new(*whitelist.value()),
+                    statement
+                ],
+            ),
+            Err(ProofSystemError::NotAnAccumulatorStatement(1))
+        ));
+    }
+    assert!(matches!(
+        Statements::<Bls12_381>::new().add_shared_accumulator_memberships(
+            &mut MetaStatements::new(),
+            (0, member_idx),
+            vec![
+                VBAccumulatorMembershipCDHVerifier::new_statement_from_params(
+                    wl_params.clone(),
+                    wl_keypair.public_key.clone(),
+                    *whitelist.value(),
+                ),
+                PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+                    sig_params.clone(),
+                    sig_keypair.public_key.clone(),
+                    BTreeMap::new(),
+                ),
+            ],
+        ),
+        Err(ProofSystemError::NotAnAccumulatorStatement(1))
+    ));
+
+    let prover_proof_spec =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+    prover_proof_spec.validate().unwrap();
+    let verifier_proof_spec = ProofSpec::new(verifier_statements, meta_statements, vec![], None);
+    verifier_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit.clone()));
+    witnesses.add(NonMembershipWit::new_as_witness(member, non_mem_wit));
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec.clone(),
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap();
+
+    // Element not in the blacklist is different from the one in the whitelist so the proof fails
+    let other = msgs[3];
+    let other_non_mem_wit = blacklist
+        .get_non_membership_witness(&other, &bl_keypair.secret_key, &bl_state, &bl_params)
+        .unwrap();
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit));
+    witnesses.add(NonMembershipWit::new_as_witness(other, other_non_mem_wit));
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    let res = proof.verify::<StdRng, Blake2b512>(
+        &mut rng,
+        verifier_proof_spec,
+        nonce,
+        Default::default(),
+    );
+    // Response for the blacklist's element doesn't match the shared one
+    assert!(matches!(
+        res,
+        Err(ProofSystemError::VBAccumProofContributionFailed(2, _))
+    ));
+}
+
+#[test]
+fn shared_element_in_two_blacklists() {
+    // Prove knowledge of BBS+ signature and that one of the messages is absent from two blacklists
+    // maintained by different authorities
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let max = 10;
+    let (bl_params_1, bl_keypair_1, blacklist_1, _, bl_state_1) =
+        setup_universal_accum(&mut rng, max);
+    let (bl_params_2, bl_keypair_2, blacklist_2, _, bl_state_2) =
+        setup_universal_accum(&mut rng, max);
+
+    let msg_count = 6;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let Q = G1Affine::rand(&mut rng);
+
+    let non_member_idx = 1;
+    let non_member = msgs[non_member_idx];
+    let non_mem_wit_1 = blacklist_1
+        .get_non_membership_witness(
+            &non_member,
+            &bl_keypair_1.secret_key,
+            &bl_state_1,
+            &bl_params_1,
+        )
+        .unwrap();
+    let non_mem_wit_2 = blacklist_2
+        .get_non_membership_witness(
+            &non_member,
+            &bl_keypair_2.secret_key,
+            &bl_state_2,
+            &bl_params_2,
+        )
+        .unwrap();
+
+    let mut prover_statements = Statements::new();
+    let mut meta_statements = MetaStatements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    let indices = prover_statements
+        .add_shared_accumulator_non_memberships(
+            &mut meta_statements,
+            (0, non_member_idx),
+            vec![
+                VBAccumulatorNonMembershipCDHProver::new_statement_from_params(
+                    *blacklist_1.value(),
+                    Q,
+                    bl_params_1.clone(),
+                ),
+                VBAccumulatorNonMembershipCDHProver::new_statement_from_params(
+                    *blacklist_2.value(),
+                    Q,
+                    bl_params_2.clone(),
+                ),
+            ],
+        )
+        .unwrap();
+    assert_eq!(indices, vec![1, 2]);
+    assert_eq!(
+        meta_statements.disjoint_witness_equalities(),
+        vec![EqualWitnesses(
+            vec![(0, non_member_idx), (1, 0), (2, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>()
+        )]
+    );
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements
+        .add_shared_accumulator_non_memberships(
+            &mut MetaStatements::new(),
+            (0, non_member_idx),
+            vec![
+                VBAccumulatorNonMembershipCDHVerifier::new_statement_from_params(
+                    bl_params_1.clone(),
+                    bl_keypair_1.public_key.clone(),
+                    *blacklist_1.value(),
+                    Q,
+                ),
+                VBAccumulatorNonMembershipCDHVerifier::new_statement_from_params(
+                    bl_params_2.clone(),
+                    bl_keypair_2.public_key.clone(),
+                    *blacklist_2.value(),
+                    Q,
+                ),
+            ],
+        )
+        .unwrap();
+
+    // Only non-membership statements can be given
+    assert!(matches!(
+        Statements::<Bls12_381>::new().add_shared_accumulator_non_memberships(
+            &mut MetaStatements::new(),
+            (0, non_member_idx),
+            vec![
+                VBAccumulatorNonMembershipCDHProver::new_statement_from_params(
+                    *blacklist_1.value(),
+                    Q,
+                    bl_params_1.clone(),
+                ),
+                VBAccumulatorMembershipCDHProver::new(*blacklist_2.value()),
+            ],
+        ),
+        Err(ProofSystemError::NotAnAccumulatorNonMembershipStatement(1))
+    ));
+
+    let prover_proof_spec =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+    prover_proof_spec.validate().unwrap();
+    let verifier_proof_spec = ProofSpec::new(verifier_statements, meta_statements, vec![], None);
+    verifier_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(NonMembershipWit::new_as_witness(
+        non_member,
+        non_mem_wit_1.clone(),
+    ));
+    witnesses.add(NonMembershipWit::new_as_witness(non_member, non_mem_wit_2));
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec.clone(),
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap();
+
+    // Element absent from the second blacklist is different from the one absent from the first so the
+    // proof fails
+    let other = msgs[3];
+    let other_non_mem_wit = blacklist_2
+        .get_non_membership_witness(&other, &bl_keypair_2.secret_key, &bl_state_2, &bl_params_2)
+        .unwrap();
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(NonMembershipWit::new_as_witness(non_member, non_mem_wit_1));
+    witnesses.add(NonMembershipWit::new_as_witness(other, other_non_mem_wit));
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    let res = proof.verify::<StdRng, Blake2b512>(
+        &mut rng,
+        verifier_proof_spec,
+        nonce,
+        Default::default(),
+    );
+    // Response for the second blacklist's element doesn't match the shared one
+    assert!(matches!(
+        res,
+        Err(ProofSystemError::VBAccumProofContributionFailed(2, _))
+    ));
+}
+
+#[test]
+fn verify_selective_skips_unlinked_statements() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (accum_params, accum_keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+    let (msgs_2, sig_params_2, sig_keypair_2, sig_2) =
+        bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let member_idx = 1;
+    let member = msgs[member_idx];
+    accumulator = accumulator
+        .add(member, &accum_keypair.secret_key, &mut state)
+        .unwrap();
+    let mem_wit = accumulator
+        .get_membership_witness(&member, &accum_keypair.secret_key, &state)
+        .unwrap();
+
+    // 1st signature's message is linked to the accumulator member but the 2nd signature is unlinked
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(VBAccumulatorMembershipCDHProver::new(*accumulator.value()));
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params_2.clone(),
+        BTreeMap::new(),
+    ));
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, member_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let prover_proof_spec =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(
+        VBAccumulatorMembershipCDHVerifier::new_statement_from_params(
+            accum_params,
+            accum_keypair.public_key.clone(),
+            *accumulator.value(),
+        ),
+    );
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params_2,
+        sig_keypair_2.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    let verifier_proof_spec = ProofSpec::new(verifier_statements, meta_statements, vec![], None);
+
+    // The messages don't match the 2nd signature so the proof for it is invalid
+    let mut wrong_msgs_2 = msgs_2.clone();
+    wrong_msgs_2[0] = Fr::rand(&mut rng);
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit));
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig_2,
+        wrong_msgs_2.into_iter().enumerate().collect(),
+    ));
+
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    assert!(proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            None,
+            Default::default(),
+        )
+        .is_err());
+
+    // Skipping the unlinked 2nd signature works
+    proof
+        .clone()
+        .verify_selective::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            None,
+            Default::default(),
+            BTreeSet::from([2]),
+        )
+        .unwrap();
+
+    // Can't skip only one of the linked statements
+    for (to_skip, err_idx) in [(vec![1, 2], 1), (vec![0], 0)] {
+        assert!(matches!(
+            proof.clone().verify_selective::<StdRng, Blake2b512>(
+                &mut rng,
+                verifier_proof_spec.clone(),
+                None,
+                Default::default(),
+                to_skip.into_iter().collect(),
+            ),
+            Err(ProofSystemError::CannotSkipLinkedStatement(i)) if i == err_idx
+        ));
+    }
+
+    // Linked statements can be skipped together but the 2nd signature's proof is still invalid
+    assert!(proof
+        .clone()
+        .verify_selective::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            None,
+            Default::default(),
+            BTreeSet::from([0, 1]),
+        )
+        .is_err());
+
+    assert!(matches!(
+        proof.verify_selective::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec,
+            None,
+            Default::default(),
+            BTreeSet::from([3]),
+        ),
+        Err(ProofSystemError::InvalidStatementProofIndex(3))
+    ));
+}
+
+#[test]
+fn cdh_membership_proof_fails_with_different_public_key() {
+    // The public key is not part of the challenge for CDH based membership proofs as the prover doesn't know it.
+    // A proof is bound to the public key by the pairing check so it fails verification with another public key.
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (accum_params, accum_keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+    let other_keypair = Keypair::<Bls12_381>::generate_using_rng(&mut rng, &accum_params);
+
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, 5);
+    let member_idx = 1;
+    let member = msgs[member_idx];
+    accumulator = accumulator
+        .add(member, &accum_keypair.secret_key, &mut state)
+        .unwrap();
+    let wit = accumulator
+        .get_membership_witness(&member, &accum_keypair.secret_key, &state)
+        .unwrap();
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, member_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let mut statements = Statements::new();
+    statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    statements.add(VBAccumulatorMembershipCDHProver::new(*accumulator.value()));
+    let proof_spec = ProofSpec::new(statements, meta_statements.clone(), vec![], None);
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(member, wit));
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let verifier_proof_spec = |pk| {
+        let mut statements = Statements::new();
+        statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        statements.add(
+            VBAccumulatorMembershipCDHVerifier::new_statement_from_params(
+                accum_params.clone(),
+                pk,
+                *accumulator.value(),
+            ),
+        );
+        ProofSpec::new(statements, meta_statements.clone(), vec![], None)
+    };
+
+    for lazy in [None, Some(false), Some(true)] {
+        let config = || VerifierConfig {
+            use_lazy_randomized_pairing_checks: lazy,
+            ..Default::default()
+        };
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                verifier_proof_spec(accum_keypair.public_key.clone()),
+                nonce.clone(),
+                config(),
+            )
+            .unwrap();
+        assert!(proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                verifier_proof_spec(other_keypair.public_key.clone()),
+                nonce.clone(),
+                config(),
+            )
+            .is_err());
+    }
+}
+
+#[test]
+fn dl_of_public_key_equal_to_accumulator_member() {
+    // Prove that the secret key of a public key is a member of an accumulator
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (params, keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+
+    let g = G1Affine::rand(&mut rng);
+    let sk = Fr::rand(&mut rng);
+    let pk = (g * sk).into_affine();
+
+    accumulator = accumulator
+        .add(sk, &keypair.secret_key, &mut state)
+        .unwrap();
+    let mem_wit = accumulator
+        .get_membership_witness(&sk, &keypair.secret_key, &state)
+        .unwrap();
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 0), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    // The discrete log statement comes first as it provides the response for the member
+    let mut prover_statements = Statements::new();
+    prover_statements.add(DLEqualToAccumulatorMember::new_statement_from_params(g, pk));
+    prover_statements.add(VBAccumulatorMembershipCDHProver::new(*accumulator.value()));
+    let prover_proof_spec =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+    prover_proof_spec.validate().unwrap();
+
+    let verifier_statements_with_pk = |pk: G1Affine| {
+        let mut verifier_statements = Statements::new();
+        verifier_statements.add(DLEqualToAccumulatorMember::new_statement_from_params(g, pk));
+        verifier_statements.add(
+            VBAccumulatorMembershipCDHVerifier::new_statement_from_params(
+                params.clone(),
+                keypair.public_key.clone(),
+                *accumulator.value(),
+            ),
+        );
+        ProofSpec::new(verifier_statements, meta_statements.clone(), vec![], None)
+    };
+    let verifier_proof_spec = verifier_statements_with_pk(pk);
+    verifier_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::DLEqualToAccumulatorMember(sk));
+    witnesses.add(MembershipWit::new_as_witness(sk, mem_wit.clone()));
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec.clone(),
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    test_serialization!(Proof<Bls12_381>, proof);
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec,
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap();
+
+    // Fails for a different public key
+    let other_pk = (g * Fr::rand(&mut rng)).into_affine();
+    assert!(matches!(
+        proof.verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_statements_with_pk(other_pk),
+            nonce.clone(),
+            Default::default(),
+        ),
+        Err(ProofSystemError::SchnorrProofContributionFailed(0, _))
+    ));
+
+    // Fails when the discrete log is not a member of the accumulator
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::DLEqualToAccumulatorMember(Fr::rand(&mut rng)));
+    witnesses.add(MembershipWit::new_as_witness(sk, mem_wit));
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_statements_with_pk(pk),
+            nonce,
+            Default::default(),
+        )
+        .is_err());
+
+    // The discrete log must be equal to an accumulator member
+    let mut statements = Statements::<Bls12_381>::new();
+    statements.add(DLEqualToAccumulatorMember::new_statement_from_params(g, pk));
+    statements.add(VBAccumulatorMembershipCDHProver::new(*accumulator.value()));
+    assert!(matches!(
+        ProofSpec::new(statements, MetaStatements::new(), vec![], None).validate(),
+        Err(ProofSystemError::DLNotEqualToAccumulatorMember(0))
+    ));
+}
+
+#[test]
+fn proof_size_estimate_for_cdh_and_kb_accumulators() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let max = 10;
+    let (_, pos_accum_keypair, mut pos_accumulator, mut pos_state) = setup_positive_accum(&mut rng);
+    let (uni_accum_params, uni_accum_keypair, uni_accumulator, _, uni_state) =
+        setup_universal_accum(&mut rng, max);
+
+    let (msgs, sig_params, _, sig) = bbs_plus_sig_setup(&mut rng, 6);
+
+    let mut domain = msgs.clone();
+    while domain.len() < max as usize {
+        domain.push(Fr::rand(&mut rng));
+    }
+    let (kb_accum_params, kb_keypair, mut kb_accumulator, mut kb_mem_state, mut kb_non_mem_state) =
+        setup_kb_universal_accum_given_domain(&mut rng, domain);
+    let (
+        kb_pos_accum_params,
+        kb_pos_accum_sk,
+        kb_pos_accum_pk,
+        kb_pos_accumulator,
+        mut kb_pos_state,
+    ) = setup_kb_positive_accum(&mut rng);
+    let prk = ProvingKey::generate_using_rng(&mut rng);
+    let Q = G1Affine::rand(&mut rng);
+
+    // Message at index 1 is a member of the VB and KB positive accumulators, message at index 2 is a member
+    // of the KB universal accumulator while messages at indices 3 and 4 are non-members of the universal
+    // accumulators
+    pos_accumulator = pos_accumulator
+        .add(msgs[1], &pos_accum_keypair.secret_key, &mut pos_state)
+        .unwrap();
+    let mem_wit = pos_accumulator
+        .get_membership_witness(&msgs[1], &pos_accum_keypair.secret_key, &pos_state)
+        .unwrap();
+    let kb_pos_mem_wit = kb_pos_accumulator
+        .add::<Blake2b512, Bls12_381>(
+            &msgs[1],
+            &kb_pos_accum_sk,
+            &kb_pos_accum_params,
+            &mut kb_pos_state,
+        )
+        .unwrap();
+    kb_accumulator = kb_accumulator
+        .add(
+            msgs[2],
+            &kb_keypair.secret_key,
+            &mut kb_mem_state,
+            &mut kb_non_mem_state,
+        )
+        .unwrap();
+    let kb_mem_wit = kb_accumulator
+        .get_membership_witness(&msgs[2], &kb_keypair.secret_key, &kb_mem_state)
+        .unwrap();
+    let non_mem_wit = uni_accumulator
+        .get_non_membership_witness(
+            &msgs[3],
+            &uni_accum_keypair.secret_key,
+            &uni_state,
+            &uni_accum_params,
+        )
+        .unwrap();
+    let kb_non_mem_wit = kb_accumulator
+        .get_non_membership_witness(&msgs[4], &kb_keypair.secret_key, &kb_non_mem_state)
+        .unwrap();
+
+    let mut statements = Statements::new();
+    statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params,
+        BTreeMap::new(),
+    ));
+    statements.add(VBAccumulatorMembershipCDHProver::new(
+        *pos_accumulator.value(),
+    ));
+    statements.add(
+        VBAccumulatorNonMembershipCDHProver::new_statement_from_params(
+            *uni_accumulator.value(),
+            Q,
+            uni_accum_params,
+        ),
+    );
+    statements.add(KBUniversalAccumulatorMembershipCDHProver::new(
+        *kb_accumulator.mem_value(),
+    ));
+    statements.add(KBUniversalAccumulatorNonMembershipCDHProver::new(
+        *kb_accumulator.non_mem_value(),
+    ));
+    statements.add(
+        KBPositiveAccumulatorMembershipCDH::new_statement_from_params(
+            kb_pos_accum_params.clone(),
+            kb_pos_accum_pk.clone(),
+            prk.clone(),
+            *kb_pos_accumulator.value(),
+        ),
+    );
+    statements.add(KBPositiveAccumulatorMembership::new_statement_from_params(
+        kb_pos_accum_params,
+        kb_pos_accum_pk,
+        prk.clone(),
+        *kb_pos_accumulator.value(),
+    ));
+    statements.add(
+        KBUniversalAccumulatorMembershipAndNonMembership::new_statement_from_params(
+            kb_accum_params,
+            kb_keypair.public_key.clone(),
+            prk,
+            *kb_accumulator.mem_value(),
+            *kb_accumulator.non_mem_value(),
+        ),
+    );
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(msgs[1], mem_wit));
+    witnesses.add(NonMembershipWit::new_as_witness(msgs[3], non_mem_wit));
+    witnesses.add(Witness::KBUniAccumulatorMembership(KBUniMembershipWit {
+        element: msgs[2],
+        witness: kb_mem_wit.clone(),
+    }));
+    witnesses.add(Witness::KBUniAccumulatorNonMembership(
+        KBUniNonMembershipWit {
+            element: msgs[4],
+            witness: kb_non_mem_wit.clone(),
+        },
+    ));
+    witnesses.add(KBPosMembership::new_as_witness(
+        msgs[1],
+        kb_pos_mem_wit.clone(),
+    ));
+    witnesses.add(KBPosMembership::new_as_witness(msgs[1], kb_pos_mem_wit));
+    witnesses.add(KBUniMembershipAndNonMembershipWit::new_as_witness(
+        msgs[2],
+        kb_mem_wit,
+        msgs[4],
+        kb_non_mem_wit,
+    ));
+
+    // Without witness equalities, all responses are present
+    test_proof_size_estimate!(
+        &mut rng,
+        ProofSpec::new(statements.clone(), MetaStatements::new(), vec![], None),
+        witnesses.clone()
+    );
+
+    // With witness equalities, the proofs of the accumulator statements don't have the responses for
+    // their (non)members
+    let mut meta_statements = MetaStatements::new();
+    for refs in [
+        vec![(0, 1), (1, 0), (5, 0), (6, 0)],
+        vec![(0, 2), (3, 0), (7, 0)],
+        vec![(0, 3), (2, 0)],
+        vec![(0, 4), (4, 0), (7, 1)],
+    ] {
+        meta_statements.add_witness_equality(EqualWitnesses(
+            refs.into_iter().collect::<BTreeSet<WitnessRef>>(),
+        ));
+    }
+    test_proof_size_estimate!(
+        &mut rng,
+        ProofSpec::new(statements, meta_statements, vec![], None),
+        witnesses
+    );
+}
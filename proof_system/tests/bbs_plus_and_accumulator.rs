@@ -1,5 +1,5 @@
-use ark_bls12_381::{Bls12_381, Fr, G1Affine, G1Projective};
-use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_bls12_381::{Bls12_381, Fq, Fr, G1Affine, G1Projective, G2Projective};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
 use ark_ff::PrimeField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{
@@ -9,14 +9,23 @@ use ark_std::{
 };
 use bbs_plus::prelude::{Signature23G1, SignatureG1};
 use blake2::Blake2b512;
+use bulletproofs_plus_plus::prelude::SetupParams as BppSetupParams;
 use short_group_sig::common::ProvingKey;
-use std::time::Instant;
-use vb_accumulator::prelude::{Accumulator, MembershipProvingKey, NonMembershipProvingKey};
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+use vb_accumulator::{
+    error::VBAccumulatorError,
+    prelude::{Accumulator, MembershipProvingKey, NonMembershipProvingKey},
+};
 
 use dock_crypto_utils::commitment::PedersenCommitmentKey;
 use proof_system::{
     prelude::{
-        EqualWitnesses, MetaStatements, Proof, VerifierConfig, Witness, WitnessRef, Witnesses,
+        CompositeTranscriptBuilder, EqualWitnesses, MetaStatements, Proof, ProofSystemError,
+        ProverConfig, VerificationCheckpoint, VerifierConfig, Witness, WitnessRef, Witnesses,
     },
     proof_spec::ProofSpec,
     setup_params::SetupParams,
@@ -26,6 +35,7 @@ use proof_system::{
             DetachedAccumulatorNonMembershipProver, DetachedAccumulatorNonMembershipVerifier,
             KBPositiveAccumulatorMembership,
             KBUniversalAccumulatorMembership as KBAccumulatorMembershipStmt,
+            KBUniversalAccumulatorMembershipAndNonMembership as KBAccumulatorMembershipAndNonMembershipStmt,
             KBUniversalAccumulatorNonMembership as KBAccumulatorNonMembershipStmt,
             VBAccumulatorMembership as AccumulatorMembershipStmt,
             VBAccumulatorNonMembership as AccumulatorNonMembershipStmt,
@@ -42,8 +52,13 @@ use proof_system::{
             PoKBBSSignatureG1Prover as PoKSignatureBBSG1ProverStmt,
             PoKBBSSignatureG1Verifier as PoKSignatureBBSG1VerifierStmt,
         },
-        inequality::PublicInequality as InequalityStmt,
+        bound_check_bpp::BoundCheckBpp as BoundCheckBppStmt,
+        dl_equal_to_accumulator_member::DLEqualToAccumulatorMember,
+        inequality::{
+            HiddenInequality as HiddenInequalityStmt, PublicInequality as InequalityStmt,
+        },
         ped_comm::PedersenCommitment as PedersenCommitmentStmt,
+        public_set_membership::PublicSetMembership as PublicSetMembershipStmt,
         Statements,
     },
     statement_proof::StatementProof,
@@ -51,13 +66,18 @@ use proof_system::{
         DetachedAccumulatorMembershipSubProtocol, DetachedAccumulatorNonMembershipSubProtocol,
     },
     witness::{
-        KBPosMembership, KBUniMembership as KBMembershipWit,
+        HiddenInequality as HiddenInequalityWit, KBPosMembership,
+        KBUniMembership as KBMembershipWit,
+        KBUniMembershipAndNonMembership as KBMembershipAndNonMembershipWit,
         KBUniNonMembership as KBNonMembershipWit, Membership as MembershipWit,
         NonMembership as NonMembershipWit, PoKBBSSignature23G1 as PoKSignatureBBS23G1Wit,
         PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
     },
 };
-use test_utils::{accumulators::*, bbs::*, test_serialization};
+use test_utils::{
+    accumulators::*, bbs::*, test_proof_size_estimate, test_serialization,
+    test_statement_proof_sizes,
+};
 
 macro_rules! gen_tests {
     ($test1_name: ident, $test2_name: ident, $test3_name: ident, $test4_name: ident, $test5_name: ident, $test6_name: ident, $setup_fn_name: ident, $sig: ident, $prover_stmt: ident, $verifier_stmt: ident, $wit: ident, $setup_param_name: ident) => {
@@ -250,6 +270,7 @@ macro_rules! gen_tests {
                     None,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .is_err());
@@ -272,6 +293,7 @@ macro_rules! gen_tests {
                     Some(b"random...".to_vec()),
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .is_err());
@@ -302,6 +324,7 @@ macro_rules! gen_tests {
                     nonce,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -462,6 +485,7 @@ macro_rules! gen_tests {
                     nonce.clone(),
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -512,6 +536,7 @@ macro_rules! gen_tests {
                     nonce.clone(),
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .is_err());
@@ -564,6 +589,7 @@ macro_rules! gen_tests {
                     nonce.clone(),
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .is_err());
@@ -684,6 +710,7 @@ macro_rules! gen_tests {
                     nonce.clone(),
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -800,6 +827,7 @@ macro_rules! gen_tests {
                     nonce.clone(),
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -921,6 +949,7 @@ macro_rules! gen_tests {
                     nonce.clone(),
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -1036,6 +1065,7 @@ macro_rules! gen_tests {
                     nonce.clone(),
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -1161,6 +1191,7 @@ macro_rules! gen_tests {
                     nonce.clone(),
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -1391,6 +1422,7 @@ macro_rules! gen_tests {
                     nonce,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -1917,6 +1949,7 @@ macro_rules! gen_tests {
                     None,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -2645,6 +2678,203 @@ fn proof_spec_validation() {
     assert!(ps_3.validate().is_err());
 }
 
+#[test]
+fn proof_spec_with_dangling_setup_param_refs() {
+    // Catch statements referring to setup params that don't exist or are of an unexpected type
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (_, params, keypair, _) = bbs_plus_sig_setup(&mut rng, 5);
+
+    let setup_params = vec![
+        SetupParams::BBSPlusSignatureParams(params),
+        SetupParams::BBSPlusPublicKey(keypair.public_key.clone()),
+    ];
+
+    let mut statements = Statements::<Bls12_381>::new();
+    statements
+        .add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params_ref(0, 1, BTreeMap::new()));
+    let ps = ProofSpec::new(
+        statements,
+        MetaStatements::new(),
+        setup_params.clone(),
+        None,
+    );
+    ps.validate().unwrap();
+
+    // Public key reference is past the end of setup params
+    let mut statements = Statements::<Bls12_381>::new();
+    statements
+        .add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params_ref(0, 1, BTreeMap::new()));
+    statements
+        .add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params_ref(0, 2, BTreeMap::new()));
+    let ps = ProofSpec::new(
+        statements,
+        MetaStatements::new(),
+        setup_params.clone(),
+        None,
+    );
+    assert!(matches!(
+        ps.validate_setup_param_refs(),
+        Err(ProofSystemError::DanglingSetupParamRef(1, 2))
+    ));
+    assert!(matches!(
+        ps.validate(),
+        Err(ProofSystemError::DanglingSetupParamRef(1, 2))
+    ));
+
+    // Signature params reference points to the public key
+    let mut statements = Statements::<Bls12_381>::new();
+    statements
+        .add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params_ref(1, 1, BTreeMap::new()));
+    let ps = ProofSpec::new(statements, MetaStatements::new(), setup_params, None);
+    assert!(matches!(
+        ps.validate(),
+        Err(ProofSystemError::DanglingSetupParamRef(0, 1))
+    ));
+}
+
+#[test]
+fn merging_proof_specs() {
+    // Merge 2 independently created proof specs, one for each credential policy, and check that the
+    // result is same as the proof spec created by hand
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs_1, params, keypair, sig_1) = bbs_plus_sig_setup(&mut rng, msg_count);
+    let (_, params_2, keypair_2, _) = bbs_plus_sig_setup(&mut rng, msg_count);
+    // Message at index 1 is same in all signatures
+    let mut msgs_2 = (0..msg_count)
+        .map(|_| Fr::rand(&mut rng))
+        .collect::<Vec<_>>();
+    msgs_2[1] = msgs_1[1];
+    let mut msgs_3 = (0..msg_count)
+        .map(|_| Fr::rand(&mut rng))
+        .collect::<Vec<_>>();
+    msgs_3[1] = msgs_1[1];
+    let sig_2 =
+        SignatureG1::<Bls12_381>::new(&mut rng, &msgs_2, &keypair.secret_key, &params).unwrap();
+    let sig_3 =
+        SignatureG1::<Bls12_381>::new(&mut rng, &msgs_3, &keypair.secret_key, &params).unwrap();
+
+    let context = Some(b"test".to_vec());
+
+    // 1st spec has 1 signature and 2nd has 2 signatures whose messages are proven equal. The 2nd spec
+    // also has setup params not used by any statement.
+    let mut statements_1 = Statements::<Bls12_381>::new();
+    statements_1
+        .add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params_ref(0, 1, BTreeMap::new()));
+    let spec_1 = ProofSpec::new(
+        statements_1,
+        MetaStatements::new(),
+        vec![
+            SetupParams::BBSPlusSignatureParams(params.clone()),
+            SetupParams::BBSPlusPublicKey(keypair.public_key.clone()),
+        ],
+        context.clone(),
+    );
+
+    let mut statements_2 = Statements::<Bls12_381>::new();
+    statements_2
+        .add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params_ref(2, 1, BTreeMap::new()));
+    statements_2
+        .add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params_ref(2, 1, BTreeMap::new()));
+    let mut meta_statements_2 = MetaStatements::new();
+    meta_statements_2.add_witness_equality(EqualWitnesses(
+        vec![(0, 1), (1, 1)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let spec_2 = ProofSpec::new(
+        statements_2,
+        meta_statements_2,
+        vec![
+            SetupParams::BBSPlusSignatureParams(params_2),
+            SetupParams::BBSPlusPublicKey(keypair.public_key.clone()),
+            SetupParams::BBSPlusSignatureParams(params.clone()),
+            SetupParams::BBSPlusPublicKey(keypair_2.public_key.clone()),
+        ],
+        None,
+    );
+
+    let mut statements = Statements::<Bls12_381>::new();
+    for _ in 0..3 {
+        statements.add(
+            PoKSignatureBBSG1VerifierStmt::new_statement_from_params_ref(0, 1, BTreeMap::new()),
+        );
+    }
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(1, 1), (2, 1)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let mut hand_merged_spec = ProofSpec::new(
+        statements,
+        meta_statements,
+        spec_1.setup_params.clone(),
+        context.clone(),
+    );
+    hand_merged_spec
+        .setup_params
+        .push(spec_2.setup_params[0].clone());
+    hand_merged_spec
+        .setup_params
+        .push(spec_2.setup_params[3].clone());
+
+    let merged_spec = spec_1.clone().merge(spec_2.clone()).unwrap();
+    merged_spec.validate().unwrap();
+    assert_eq!(merged_spec, hand_merged_spec);
+
+    let mut prover_statements = Statements::<Bls12_381>::new();
+    for _ in 0..3 {
+        prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params_ref(
+            0,
+            BTreeMap::new(),
+        ));
+    }
+    let prover_spec = ProofSpec::new(
+        prover_statements,
+        merged_spec.meta_statements.clone(),
+        vec![SetupParams::BBSPlusSignatureParams(params)],
+        context.clone(),
+    );
+
+    let mut witnesses = Witnesses::new();
+    for (sig, msgs) in [(sig_1, &msgs_1), (sig_2, &msgs_2), (sig_3, &msgs_3)] {
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig,
+            msgs.iter().cloned().enumerate().collect(),
+        ));
+    }
+
+    let nonce = Some(b"some nonce".to_vec());
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, merged_spec, nonce.clone(), Default::default())
+        .unwrap();
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, hand_merged_spec, nonce, Default::default())
+        .unwrap();
+
+    // Specs with different contexts can't be merged
+    let mut spec_3 = spec_2;
+    spec_3.context = Some(b"another test".to_vec());
+    assert!(matches!(
+        spec_1.merge(spec_3),
+        Err(ProofSystemError::IncompatibleProofSpecsForMerge)
+    ));
+}
+
 #[ignore]
 #[test]
 fn detached_accumulator() {
@@ -2833,6 +3063,7 @@ fn detached_accumulator() {
             nonce.clone(),
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .is_err());
@@ -2883,6 +3114,7 @@ fn detached_accumulator() {
             nonce.clone(),
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .is_err());*/
@@ -3344,3 +3576,3932 @@ fn detached_accumulator() {
         _ => assert!(false, "Needed a detached accumulator proof"),
     }
 }
+
+#[test]
+fn proof_with_transcript_label() {
+    // A proof created with an application specific transcript label only verifies with the same label
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let mut prover_statements = Statements::<Bls12_381>::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    let prover_proof_spec = ProofSpec::new(prover_statements, MetaStatements::new(), vec![], None);
+
+    let mut verifier_statements = Statements::<Bls12_381>::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    let verifier_proof_spec =
+        ProofSpec::new(verifier_statements, MetaStatements::new(), vec![], None);
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().collect(),
+    ));
+
+    let label_a = b"application-A".to_vec();
+    let label_b = b"application-B".to_vec();
+
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec.clone(),
+        witnesses.clone(),
+        None,
+        ProverConfig {
+            transcript_label: Some(label_a.clone()),
+            ..Default::default()
+        },
+    )
+    .unwrap()
+    .0;
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            None,
+            VerifierConfig {
+                transcript_label: Some(label_a),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    // Replaying the proof in another application fails
+    assert!(proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            None,
+            VerifierConfig {
+                transcript_label: Some(label_b),
+                ..Default::default()
+            },
+        )
+        .is_err());
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            None,
+            Default::default(),
+        )
+        .is_err());
+
+    // Proof without a label doesn't verify with a label
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec,
+            None,
+            VerifierConfig {
+                transcript_label: Some(b"application-A".to_vec()),
+                ..Default::default()
+            },
+        )
+        .is_err());
+}
+
+#[test]
+fn proof_with_extra_transcript_data() {
+    // Application data appended to the transcript by the prover must be appended identically by the verifier
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let mut prover_statements = Statements::<Bls12_381>::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    let prover_proof_spec = ProofSpec::new(prover_statements, MetaStatements::new(), vec![], None);
+
+    let mut verifier_statements = Statements::<Bls12_381>::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    let verifier_proof_spec =
+        ProofSpec::new(verifier_statements, MetaStatements::new(), vec![], None);
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().collect(),
+    ));
+
+    // Say an external commitment that the application wants bound to the proof
+    let commitment = G1Affine::rand(&mut rng);
+    let mut commitment_bytes = vec![];
+    commitment
+        .serialize_compressed(&mut commitment_bytes)
+        .unwrap();
+
+    let builder_with = |bytes: &[u8]| {
+        let mut builder = CompositeTranscriptBuilder::new();
+        builder.append_extra(b"app-commitment", bytes);
+        builder
+    };
+
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        None,
+        ProverConfig {
+            transcript_builder: builder_with(&commitment_bytes),
+            ..Default::default()
+        },
+    )
+    .unwrap()
+    .0;
+
+    let verify = |builder: CompositeTranscriptBuilder, rng: &mut StdRng| {
+        proof.clone().verify::<StdRng, Blake2b512>(
+            rng,
+            verifier_proof_spec.clone(),
+            None,
+            VerifierConfig {
+                transcript_builder: builder,
+                ..Default::default()
+            },
+        )
+    };
+
+    verify(builder_with(&commitment_bytes), &mut rng).unwrap();
+
+    // Verifier with a different value, under a different label or without any extra data rejects the proof
+    let mut other_bytes = vec![];
+    G1Affine::rand(&mut rng)
+        .serialize_compressed(&mut other_bytes)
+        .unwrap();
+    assert!(verify(builder_with(&other_bytes), &mut rng).is_err());
+    let mut other_label = CompositeTranscriptBuilder::new();
+    other_label.append_extra(b"other-commitment", &commitment_bytes);
+    assert!(verify(other_label, &mut rng).is_err());
+    assert!(verify(CompositeTranscriptBuilder::new(), &mut rng).is_err());
+}
+
+#[test]
+fn compute_challenge_of_proof() {
+    // The challenge computed from a proof is the one the prover used to create the responses
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let bases = (0..5)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let scalars = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    let commitment = G1Projective::msm_unchecked(&bases, &scalars).into_affine();
+
+    let mut statements = Statements::<Bls12_381>::new();
+    statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        bases.clone(),
+        commitment,
+    ));
+    let proof_spec = ProofSpec::new(
+        statements,
+        MetaStatements::new(),
+        vec![],
+        Some(b"context".to_vec()),
+    );
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::PedersenCommitment(scalars));
+
+    let nonce = Some(b"nonce".to_vec());
+    let label = b"application".to_vec();
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec.clone(),
+        witnesses,
+        nonce.clone(),
+        ProverConfig {
+            transcript_label: Some(label.clone()),
+            ..Default::default()
+        },
+    )
+    .unwrap()
+    .0;
+
+    let config = || VerifierConfig {
+        transcript_label: Some(label.clone()),
+        ..Default::default()
+    };
+
+    let challenge = proof
+        .compute_challenge(proof_spec.clone(), nonce.clone(), config())
+        .unwrap();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec.clone(), nonce.clone(), config())
+        .unwrap();
+
+    // The Schnorr responses are valid for the computed challenge
+    let p = match &proof.statement_proofs[0] {
+        StatementProof::PedersenCommitment(p) => p,
+        _ => panic!("Needed a Pedersen commitment proof"),
+    };
+    p.response
+        .is_valid(&bases, &commitment, &p.t, &challenge)
+        .unwrap();
+
+    // A different nonce or no label results in a different challenge
+    let other = proof
+        .compute_challenge(proof_spec.clone(), None, config())
+        .unwrap();
+    assert_ne!(challenge, other);
+    assert!(p
+        .response
+        .is_valid(&bases, &commitment, &p.t, &other)
+        .is_err());
+    let other = proof
+        .compute_challenge(proof_spec, nonce, Default::default())
+        .unwrap();
+    assert_ne!(challenge, other);
+}
+
+#[test]
+fn verification_resumed_from_checkpoint() {
+    // Verification split into the challenge phase and the rest gives the same result as verifying at once
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (msgs_1, params_1, keypair_1, sig_1) = bbs_plus_sig_setup(&mut rng, 5);
+    let mut msgs_2 = (0..4).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    msgs_2[1] = msgs_1[2];
+    let (params_2, keypair_2, sig_2) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs_2);
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 2), (1, 1)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        params_1.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        params_2.clone(),
+        BTreeMap::new(),
+    ));
+    let prover_proof_spec =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+    prover_proof_spec.validate().unwrap();
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        params_1,
+        keypair_1.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        params_2,
+        keypair_2.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    let verifier_proof_spec = ProofSpec::new(verifier_statements, meta_statements, vec![], None);
+    verifier_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig_1,
+        msgs_1.into_iter().enumerate().collect(),
+    ));
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig_2,
+        msgs_2.into_iter().enumerate().collect(),
+    ));
+
+    let nonce = Some(b"test nonce".to_vec());
+    // Known only to the verifier
+    let checkpoint_key = [7u8; 32];
+    let create_proof = |rng: &mut StdRng| {
+        Proof::new::<StdRng, Blake2b512>(
+            rng,
+            prover_proof_spec.clone(),
+            witnesses.clone(),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap()
+        .0
+    };
+    let proof = create_proof(&mut rng);
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap();
+
+    let checkpoint = proof
+        .verify_challenge_phase(
+            verifier_proof_spec.clone(),
+            nonce.clone(),
+            Default::default(),
+            &checkpoint_key,
+        )
+        .unwrap();
+    assert_eq!(
+        checkpoint.challenge,
+        proof
+            .compute_challenge(
+                verifier_proof_spec.clone(),
+                nonce.clone(),
+                Default::default()
+            )
+            .unwrap()
+    );
+
+    // The checkpoint is persisted and the verification resumed later
+    test_serialization!(VerificationCheckpoint<Bls12_381>, checkpoint);
+    let mut bytes = vec![];
+    checkpoint.serialize_compressed(&mut bytes).unwrap();
+    let restored = VerificationCheckpoint::<Bls12_381>::deserialize_compressed(&bytes[..]).unwrap();
+    proof
+        .clone()
+        .verify_from_checkpoint::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            restored,
+            Default::default(),
+            &checkpoint_key,
+        )
+        .unwrap();
+
+    // A checkpoint can't be used with another proof even if it's for the same proof spec
+    let other_proof = create_proof(&mut rng);
+    assert!(matches!(
+        other_proof.verify_from_checkpoint::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            checkpoint.clone(),
+            Default::default(),
+            &checkpoint_key,
+        ),
+        Err(ProofSystemError::InvalidVerificationCheckpoint)
+    ));
+
+    // A checkpoint with a different challenge is rejected as its tag no longer matches
+    let mut wrong_checkpoint = checkpoint.clone();
+    wrong_checkpoint.challenge = Fr::rand(&mut rng);
+    assert!(matches!(
+        proof.clone().verify_from_checkpoint::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            wrong_checkpoint,
+            Default::default(),
+            &checkpoint_key,
+        ),
+        Err(ProofSystemError::InvalidVerificationCheckpoint)
+    ));
+
+    // So is a checkpoint resumed with a different key than it was created with
+    assert!(matches!(
+        proof.clone().verify_from_checkpoint::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            checkpoint.clone(),
+            Default::default(),
+            &[8u8; 32],
+        ),
+        Err(ProofSystemError::InvalidVerificationCheckpoint)
+    ));
+
+    // And a checkpoint used with a different transcript label than the one it was created with
+    assert!(matches!(
+        proof.clone().verify_from_checkpoint::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            checkpoint.clone(),
+            VerifierConfig {
+                transcript_label: Some(b"other label".to_vec()),
+                ..Default::default()
+            },
+            &checkpoint_key,
+        ),
+        Err(ProofSystemError::InvalidVerificationCheckpoint)
+    ));
+
+    // Same as verifying with a different nonce, the challenge phase passes but the rest fails
+    let checkpoint = proof
+        .verify_challenge_phase(
+            verifier_proof_spec.clone(),
+            Some(b"other nonce".to_vec()),
+            Default::default(),
+            &checkpoint_key,
+        )
+        .unwrap();
+    assert!(proof
+        .verify_from_checkpoint::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec,
+            checkpoint,
+            Default::default(),
+            &checkpoint_key,
+        )
+        .is_err());
+}
+
+#[test]
+fn proof_with_statements_initialized_in_parallel() {
+    // Proofs created with the statements initialized in parallel or in order both verify
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (accum_params, accum_keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+    let prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let member_idx = 1;
+    let member = msgs[member_idx];
+    accumulator = accumulator
+        .add(member, &accum_keypair.secret_key, &mut state)
+        .unwrap();
+    let mem_wit = accumulator
+        .get_membership_witness(&member, &accum_keypair.secret_key, &state)
+        .unwrap();
+
+    // Commitment to 2 of the signed messages
+    let bases = (0..2)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let committed = vec![msgs[2], msgs[3]];
+    let commitment = G1Projective::msm_unchecked(&bases, &committed).into_affine();
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, member_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 2), (2, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 3), (2, 1)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        accum_params.clone(),
+        accum_keypair.public_key.clone(),
+        prk.clone(),
+        *accumulator.value(),
+    ));
+    prover_statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        bases.clone(),
+        commitment,
+    ));
+    let prover_proof_spec =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+    prover_proof_spec.validate().unwrap();
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        accum_params,
+        accum_keypair.public_key.clone(),
+        prk,
+        *accumulator.value(),
+    ));
+    verifier_statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        bases, commitment,
+    ));
+    let verifier_proof_spec = ProofSpec::new(verifier_statements, meta_statements, vec![], None);
+    verifier_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit));
+    witnesses.add(Witness::PedersenCommitment(committed));
+
+    let nonce = Some(b"nonce".to_vec());
+    for init_in_parallel in [false, true] {
+        let proof = Proof::new::<StdRng, Blake2b512>(
+            &mut rng,
+            prover_proof_spec.clone(),
+            witnesses.clone(),
+            nonce.clone(),
+            ProverConfig {
+                init_in_parallel,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .0;
+        proof
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                verifier_proof_spec.clone(),
+                nonce.clone(),
+                Default::default(),
+            )
+            .unwrap();
+    }
+}
+
+#[test]
+fn proof_with_multiple_contexts() {
+    // Proof bound to 2 contexts verifies only when the verifier uses the same contexts in the same order
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+
+    let context = Some(b"test".to_vec());
+    let verifier_1 = b"verifier-1".to_vec();
+    let verifier_2 = b"verifier-2".to_vec();
+
+    let prover_proof_spec = ProofSpec::new_with_contexts(
+        prover_statements,
+        MetaStatements::new(),
+        vec![],
+        context.clone(),
+        vec![verifier_1.clone(), verifier_2.clone()],
+    );
+    prover_proof_spec.validate().unwrap();
+
+    test_serialization!(ProofSpec<Bls12_381>, prover_proof_spec);
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().collect(),
+    ));
+
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let verifier_proof_spec = |contexts: Option<Vec<Vec<u8>>>| {
+        let mut spec = ProofSpec::new(
+            verifier_statements.clone(),
+            MetaStatements::new(),
+            vec![],
+            context.clone(),
+        );
+        spec.contexts = contexts;
+        spec
+    };
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(Some(vec![verifier_1.clone(), verifier_2.clone()])),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+
+    // One context differs, contexts are in a different order or are missing
+    for contexts in [
+        Some(vec![verifier_1.clone(), b"verifier-3".to_vec()]),
+        Some(vec![verifier_2.clone(), verifier_1.clone()]),
+        Some(vec![verifier_1.clone()]),
+        None,
+    ] {
+        assert!(proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                verifier_proof_spec(contexts),
+                None,
+                Default::default(),
+            )
+            .is_err());
+    }
+}
+
+#[test]
+fn proof_spec_serialization_unchanged_without_contexts() {
+    // Bytes of a `ProofSpec` serialized before `contexts` was added
+    let old_bytes = hex_to_bytes("01000000000000000397f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb01020000000000000097f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bba572cbea904d67468808c8eb50a9450c9721db309128012543902d0ac358a62ae28f75bb8f1c7c42c39a8c5529bf0f4e0000000000000000000000000000000000010c000000000000007465737420636f6e74657874000000");
+
+    let g = G1Affine::generator();
+    let g2 = (G1Projective::from(g) + g).into_affine();
+    let mut statements = Statements::<Bls12_381>::new();
+    statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        vec![g, g2],
+        g,
+    ));
+    let mut spec = ProofSpec::new(
+        statements,
+        MetaStatements::new(),
+        vec![],
+        Some(b"test context".to_vec()),
+    );
+
+    let deserz = ProofSpec::<Bls12_381>::deserialize_compressed(old_bytes.as_slice()).unwrap();
+    assert_eq!(deserz, spec);
+    let mut serz = vec![];
+    spec.serialize_compressed(&mut serz).unwrap();
+    assert_eq!(serz, old_bytes);
+
+    spec.contexts = Some(vec![b"verifier-1".to_vec(), b"verifier-2".to_vec()]);
+    test_serialization!(ProofSpec<Bls12_381>, spec);
+    spec.context = None;
+    test_serialization!(ProofSpec<Bls12_381>, spec);
+}
+
+fn hex_to_bytes(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn verify_using_prepared_proof_spec() {
+    // Proofs with different nonces verified against the same prepared proof spec
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (accum_params, accum_keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+    let prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let member_idx = 2;
+    let member = msgs[member_idx];
+    accumulator = accumulator
+        .add(member, &accum_keypair.secret_key, &mut state)
+        .unwrap();
+    let mem_wit = accumulator
+        .get_membership_witness(&member, &accum_keypair.secret_key, &state)
+        .unwrap();
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        accum_params.clone(),
+        accum_keypair.public_key.clone(),
+        prk.clone(),
+        *accumulator.value(),
+    ));
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, member_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let context = Some(b"test".to_vec());
+    let prover_proof_spec = ProofSpec::new(
+        prover_statements,
+        meta_statements.clone(),
+        vec![],
+        context.clone(),
+    );
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        accum_params,
+        accum_keypair.public_key.clone(),
+        prk,
+        *accumulator.value(),
+    ));
+    let verifier_proof_spec = ProofSpec::new(verifier_statements, meta_statements, vec![], context);
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit));
+
+    let prepared = verifier_proof_spec.clone().prepare().unwrap();
+    assert_eq!(prepared.spec(), &verifier_proof_spec);
+
+    for i in 0..3 {
+        let nonce = Some(format!("nonce-{}", i).into_bytes());
+        let proof = Proof::new::<StdRng, Blake2b512>(
+            &mut rng,
+            prover_proof_spec.clone(),
+            witnesses.clone(),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                verifier_proof_spec.clone(),
+                nonce.clone(),
+                Default::default(),
+            )
+            .unwrap();
+        proof
+            .clone()
+            .verify_using_prepared::<StdRng, Blake2b512>(
+                &prepared,
+                &mut rng,
+                nonce.clone(),
+                Default::default(),
+            )
+            .unwrap();
+        proof
+            .clone()
+            .verify_using_prepared::<StdRng, Blake2b512>(
+                &prepared,
+                &mut rng,
+                nonce,
+                VerifierConfig {
+                    use_lazy_randomized_pairing_checks: Some(true),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        // Both reject the proof with a different nonce
+        let wrong_nonce = Some(b"wrong-nonce".to_vec());
+        assert!(proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                verifier_proof_spec.clone(),
+                wrong_nonce.clone(),
+                Default::default(),
+            )
+            .is_err());
+        assert!(proof
+            .verify_using_prepared::<StdRng, Blake2b512>(
+                &prepared,
+                &mut rng,
+                wrong_nonce,
+                Default::default(),
+            )
+            .is_err());
+    }
+}
+
+#[test]
+fn verify_with_prepared_parameters() {
+    // Prepared parameters derived once are used to verify several proofs
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (accum_params, accum_keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+    let prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let member_idx = 2;
+    let member = msgs[member_idx];
+    accumulator = accumulator
+        .add(member, &accum_keypair.secret_key, &mut state)
+        .unwrap();
+    let mem_wit = accumulator
+        .get_membership_witness(&member, &accum_keypair.secret_key, &state)
+        .unwrap();
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        accum_params.clone(),
+        accum_keypair.public_key.clone(),
+        prk.clone(),
+        *accumulator.value(),
+    ));
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, member_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let prover_proof_spec =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+
+    let verifier_proof_spec = |accum_pk: vb_accumulator::setup::PublicKey<Bls12_381>| {
+        let mut verifier_statements = Statements::new();
+        verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        verifier_statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+            accum_params.clone(),
+            accum_pk,
+            prk.clone(),
+            *accumulator.value(),
+        ));
+        ProofSpec::new(verifier_statements, meta_statements.clone(), vec![], None)
+    };
+    let verifier_proof_spec_1 = verifier_proof_spec(accum_keypair.public_key.clone());
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit));
+
+    let prepared_params = verifier_proof_spec_1
+        .derive_prepared_parameters_for_reuse()
+        .unwrap();
+
+    for i in 0..3 {
+        let nonce = Some(format!("nonce-{}", i).into_bytes());
+        let proof = Proof::new::<StdRng, Blake2b512>(
+            &mut rng,
+            prover_proof_spec.clone(),
+            witnesses.clone(),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                verifier_proof_spec_1.clone(),
+                nonce.clone(),
+                Default::default(),
+            )
+            .unwrap();
+        proof
+            .clone()
+            .verify_with_prepared_parameters::<StdRng, Blake2b512>(
+                &mut rng,
+                verifier_proof_spec_1.clone(),
+                prepared_params.clone(),
+                nonce.clone(),
+                Default::default(),
+            )
+            .unwrap();
+
+        // Both reject the proof with a different nonce
+        let wrong_nonce = Some(b"wrong-nonce".to_vec());
+        assert!(proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                verifier_proof_spec_1.clone(),
+                wrong_nonce.clone(),
+                Default::default(),
+            )
+            .is_err());
+        assert!(proof
+            .verify_with_prepared_parameters::<StdRng, Blake2b512>(
+                &mut rng,
+                verifier_proof_spec_1.clone(),
+                prepared_params.clone(),
+                wrong_nonce,
+                Default::default(),
+            )
+            .is_err());
+    }
+
+    // Parameters prepared from a spec with a different accumulator public key are rejected
+    let other_keypair = vb_accumulator::setup::Keypair::generate_using_rng(&mut rng, &accum_params);
+    let other_params = verifier_proof_spec(other_keypair.public_key.clone())
+        .derive_prepared_parameters_for_reuse()
+        .unwrap();
+    let nonce = Some(b"nonce".to_vec());
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(matches!(
+        proof
+            .clone()
+            .verify_with_prepared_parameters::<StdRng, Blake2b512>(
+                &mut rng,
+                verifier_proof_spec_1.clone(),
+                other_params.clone(),
+                nonce.clone(),
+                Default::default(),
+            ),
+        Err(ProofSystemError::PreparedParametersMismatch)
+    ));
+    assert!(matches!(
+        verifier_proof_spec_1
+            .clone()
+            .prepare_with_parameters(other_params),
+        Err(ProofSystemError::PreparedParametersMismatch)
+    ));
+
+    // So are parameters prepared from a spec with fewer statements
+    let mut fewer_statements = Statements::new();
+    fewer_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    let fewer_params = ProofSpec::new(fewer_statements, MetaStatements::new(), vec![], None)
+        .derive_prepared_parameters_for_reuse()
+        .unwrap();
+    assert!(matches!(
+        proof.verify_with_prepared_parameters::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec_1,
+            fewer_params,
+            nonce,
+            Default::default(),
+        ),
+        Err(ProofSystemError::PreparedParametersMismatch)
+    ));
+}
+
+#[test]
+fn verification_can_be_cancelled() {
+    // Verification stops with an error once the cancellation flag is set
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (accum_params, accum_keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+    let prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let member_idx = 2;
+    let member = msgs[member_idx];
+    accumulator = accumulator
+        .add(member, &accum_keypair.secret_key, &mut state)
+        .unwrap();
+    let mem_wit = accumulator
+        .get_membership_witness(&member, &accum_keypair.secret_key, &state)
+        .unwrap();
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        accum_params.clone(),
+        accum_keypair.public_key.clone(),
+        prk.clone(),
+        *accumulator.value(),
+    ));
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, member_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let prover_proof_spec =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        accum_params,
+        accum_keypair.public_key.clone(),
+        prk,
+        *accumulator.value(),
+    ));
+    let verifier_proof_spec = ProofSpec::new(verifier_statements, meta_statements, vec![], None);
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit));
+
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let cancel = AtomicBool::new(false);
+    proof
+        .clone()
+        .verify_cancellable::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            None,
+            Default::default(),
+            &cancel,
+        )
+        .unwrap();
+
+    cancel.store(true, Ordering::Release);
+    assert!(matches!(
+        proof.clone().verify_cancellable::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            None,
+            Default::default(),
+            &cancel,
+        ),
+        Err(ProofSystemError::Cancelled)
+    ));
+
+    // Set the flag from another thread while verifications keep running
+    cancel.store(false, Ordering::Release);
+    thread::scope(|s| {
+        s.spawn(|| {
+            thread::sleep(Duration::from_millis(50));
+            cancel.store(true, Ordering::Release);
+        });
+        loop {
+            match proof.clone().verify_cancellable::<StdRng, Blake2b512>(
+                &mut rng,
+                verifier_proof_spec.clone(),
+                None,
+                Default::default(),
+                &cancel,
+            ) {
+                Ok(_) => continue,
+                Err(ProofSystemError::Cancelled) => break,
+                Err(e) => panic!("unexpected error {:?}", e),
+            }
+        }
+    });
+}
+
+#[test]
+fn statements_from_validated_accumulator_params() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (pos_accum_params, pos_accum_keypair, pos_accumulator, _) = setup_positive_accum(&mut rng);
+    let (uni_accum_params, uni_accum_keypair, uni_accumulator, _, _) =
+        setup_universal_accum(&mut rng, 10);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+    let non_mem_prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+
+    AccumulatorMembershipStmt::new_statement_from_validated_params(
+        pos_accum_params.clone(),
+        pos_accum_keypair.public_key.clone(),
+        mem_prk.clone(),
+        *pos_accumulator.value(),
+    )
+    .unwrap();
+    AccumulatorNonMembershipStmt::new_statement_from_validated_params(
+        uni_accum_params.clone(),
+        uni_accum_keypair.public_key.clone(),
+        non_mem_prk.clone(),
+        *uni_accumulator.value(),
+    )
+    .unwrap();
+
+    // Proving keys with a repeated element are rejected
+    let mut bad_mem_prk = mem_prk.clone();
+    bad_mem_prk.0.Y = bad_mem_prk.0.X;
+    assert!(matches!(
+        AccumulatorMembershipStmt::new_statement_from_validated_params(
+            pos_accum_params,
+            pos_accum_keypair.public_key.clone(),
+            bad_mem_prk,
+            *pos_accumulator.value(),
+        ),
+        Err(ProofSystemError::VBAccumError(
+            VBAccumulatorError::DegenerateProvingKey
+        ))
+    ));
+    let mut bad_non_mem_prk = non_mem_prk.clone();
+    bad_non_mem_prk.K = bad_non_mem_prk.XYZ.Z;
+    assert!(matches!(
+        AccumulatorNonMembershipStmt::new_statement_from_validated_params(
+            uni_accum_params,
+            uni_accum_keypair.public_key.clone(),
+            bad_non_mem_prk,
+            *uni_accumulator.value(),
+        ),
+        Err(ProofSystemError::VBAccumError(
+            VBAccumulatorError::DegenerateProvingKey
+        ))
+    ));
+}
+
+#[test]
+fn collect_witness_equality_responses() {
+    // Responses for witness equalities can be extracted to link the proof with an external Schnorr proof
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (accum_params, accum_keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+    let prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let member_idx = 2;
+    let member = msgs[member_idx];
+    accumulator = accumulator
+        .add(member, &accum_keypair.secret_key, &mut state)
+        .unwrap();
+    let mem_wit = accumulator
+        .get_membership_witness(&member, &accum_keypair.secret_key, &state)
+        .unwrap();
+
+    let committed_idx = 1;
+    let gens = vec![
+        G1Projective::rand(&mut rng).into_affine(),
+        G1Projective::rand(&mut rng).into_affine(),
+    ];
+    let blinding = Fr::rand(&mut rng);
+    let commitment = G1Projective::msm_bigint(
+        &gens,
+        &[msgs[committed_idx].into_bigint(), blinding.into_bigint()],
+    )
+    .into_affine();
+
+    // Reveal the first message so that the response indices of the signature proof are shifted
+    let mut revealed_msgs = BTreeMap::new();
+    revealed_msgs.insert(0, msgs[0]);
+    let mut unrevealed_msgs = BTreeMap::new();
+    for (i, m) in msgs.iter().enumerate().skip(1) {
+        unrevealed_msgs.insert(i, *m);
+    }
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        revealed_msgs.clone(),
+    ));
+    prover_statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        gens.clone(),
+        commitment,
+    ));
+    prover_statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        accum_params.clone(),
+        accum_keypair.public_key.clone(),
+        prk.clone(),
+        *accumulator.value(),
+    ));
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, committed_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, member_idx), (2, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let prover_proof_spec =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        revealed_msgs,
+    ));
+    verifier_statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        gens, commitment,
+    ));
+    verifier_statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        accum_params,
+        accum_keypair.public_key.clone(),
+        prk,
+        *accumulator.value(),
+    ));
+    let verifier_proof_spec = ProofSpec::new(verifier_statements, meta_statements, vec![], None);
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(sig, unrevealed_msgs));
+    witnesses.add(Witness::PedersenCommitment(vec![
+        msgs[committed_idx],
+        blinding,
+    ]));
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit));
+
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let responses = proof
+        .collect_equality_responses(&verifier_proof_spec)
+        .unwrap();
+    let equalities = verifier_proof_spec
+        .meta_statements
+        .disjoint_witness_equalities();
+    assert_eq!(responses.len(), equalities.len());
+
+    let revealed_idx = BTreeSet::from([0]);
+    let sig_proof = match &proof.statement_proofs[0] {
+        StatementProof::PoKBBSSignatureG1(p) => p,
+        _ => panic!("expected a BBS+ signature proof"),
+    };
+    // The commitment's proof omits the response for the committed message as it's the same as in the
+    // signature's proof and the verifier gets it from there
+    let comm_proof = match &proof.statement_proofs[1] {
+        StatementProof::PedersenCommitmentPartial(p) => p,
+        _ => panic!("expected a partial Pedersen commitment proof"),
+    };
+    assert!(comm_proof.get_resp_for_message(0).is_err());
+    for (i, eq) in equalities.iter().enumerate() {
+        if eq.has_wit_ref(&(0, committed_idx)) {
+            assert_eq!(
+                responses[&i],
+                *sig_proof
+                    .get_resp_for_message(committed_idx, &revealed_idx)
+                    .unwrap()
+            );
+        } else {
+            assert!(eq.has_wit_ref(&(0, member_idx)));
+            // The accumulator proof gets this response from the signature proof during verification
+            assert_eq!(
+                responses[&i],
+                *sig_proof
+                    .get_resp_for_message(member_idx, &revealed_idx)
+                    .unwrap()
+            );
+        }
+    }
+
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, None, Default::default())
+        .unwrap();
+}
+
+#[test]
+fn accumulator_id_binds_proof_to_accumulator_version() {
+    // Proof created for one version of the accumulator fails to verify when the verifier expects another
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (accum_params, accum_keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+    let prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let member_idx = 1;
+    let member = msgs[member_idx];
+    accumulator = accumulator
+        .add(member, &accum_keypair.secret_key, &mut state)
+        .unwrap();
+    let mem_wit = accumulator
+        .get_membership_witness(&member, &accum_keypair.secret_key, &state)
+        .unwrap();
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, member_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let accum_stmt = |id: Option<Vec<u8>>| match id {
+        Some(id) => AccumulatorMembershipStmt::new_statement_from_params_with_accumulator_id(
+            accum_params.clone(),
+            accum_keypair.public_key.clone(),
+            prk.clone(),
+            *accumulator.value(),
+            id,
+        ),
+        None => AccumulatorMembershipStmt::new_statement_from_params(
+            accum_params.clone(),
+            accum_keypair.public_key.clone(),
+            prk.clone(),
+            *accumulator.value(),
+        ),
+    };
+    let verifier_proof_spec = |id: Option<Vec<u8>>| {
+        let mut statements = Statements::new();
+        statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        statements.add(accum_stmt(id));
+        ProofSpec::new(statements, meta_statements.clone(), vec![], None)
+    };
+
+    let id_a = b"epoch-1".to_vec();
+    let id_b = b"epoch-2".to_vec();
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(accum_stmt(Some(id_a.clone())));
+    let prover_proof_spec =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+    prover_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit));
+
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(Some(id_a)),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+
+    // A verifier expecting a different accumulator version, or none at all, rejects the proof
+    assert!(proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(Some(id_b)),
+            None,
+            Default::default(),
+        )
+        .is_err());
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(None),
+            None,
+            Default::default()
+        )
+        .is_err());
+}
+
+#[test]
+fn verification_with_subgroup_membership_check() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (accum_params, accum_keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+    let prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let member_idx = 1;
+    let member = msgs[member_idx];
+    accumulator = accumulator
+        .add(member, &accum_keypair.secret_key, &mut state)
+        .unwrap();
+    let mem_wit = accumulator
+        .get_membership_witness(&member, &accum_keypair.secret_key, &state)
+        .unwrap();
+
+    // A point on the curve but not in the prime order subgroup
+    let mut x = Fq::from(1u64);
+    let off_subgroup = loop {
+        if let Some(p) = G1Affine::get_point_from_x_unchecked(x, true) {
+            if !p.is_in_correct_subgroup_assuming_on_curve() {
+                break p;
+            }
+        }
+        x += Fq::from(1u64);
+    };
+    assert!(off_subgroup.is_on_curve());
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, member_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        accum_params.clone(),
+        accum_keypair.public_key.clone(),
+        prk.clone(),
+        *accumulator.value(),
+    ));
+    let prover_proof_spec =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+
+    let verifier_proof_spec = |accumulator_value: G1Affine| {
+        let mut statements = Statements::new();
+        statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+            accum_params.clone(),
+            accum_keypair.public_key.clone(),
+            prk.clone(),
+            accumulator_value,
+        ));
+        ProofSpec::new(statements, meta_statements.clone(), vec![], None)
+    };
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit));
+
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let config = || VerifierConfig {
+        check_subgroup_membership: true,
+        ..Default::default()
+    };
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(*accumulator.value()),
+            None,
+            config(),
+        )
+        .unwrap();
+
+    // Off-subgroup accumulator value in the statement
+    assert!(matches!(
+        proof.clone().verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(off_subgroup),
+            None,
+            config(),
+        ),
+        Err(ProofSystemError::InvalidGroupElement(1))
+    ));
+
+    // Off-subgroup element in the signature's proof
+    let mut bad_proof = proof.clone();
+    match &mut bad_proof.statement_proofs[0] {
+        StatementProof::PoKBBSSignatureG1(p) => p.A_prime = off_subgroup,
+        _ => panic!("expected a BBS+ signature proof"),
+    }
+    assert!(matches!(
+        bad_proof.clone().verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(*accumulator.value()),
+            None,
+            config(),
+        ),
+        Err(ProofSystemError::InvalidGroupElement(0))
+    ));
+    // Without the check, the error comes from the signature's proof
+    assert!(!matches!(
+        bad_proof.verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(*accumulator.value()),
+            None,
+            Default::default(),
+        ),
+        Ok(_) | Err(ProofSystemError::InvalidGroupElement(_))
+    ));
+}
+
+#[test]
+fn proof_with_extra_statement_proofs() {
+    // A proof padded with statement proofs that don't correspond to any statement is rejected
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    let prover_proof_spec = ProofSpec::new(prover_statements, MetaStatements::new(), vec![], None);
+    prover_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().collect(),
+    ));
+
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    let verifier_proof_spec =
+        ProofSpec::new(verifier_statements, MetaStatements::new(), vec![], None);
+    verifier_proof_spec.validate().unwrap();
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+
+    let mut padded_proof = proof.clone();
+    padded_proof
+        .statement_proofs
+        .push(proof.statement_proofs[0].clone());
+    assert!(matches!(
+        padded_proof.verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec,
+            None,
+            Default::default()
+        ),
+        Err(ProofSystemError::ExtraStatementProofs(2, 1))
+    ));
+}
+
+#[test]
+fn prover_validates_accumulator_witness() {
+    // A stale accumulator witness is caught by the prover when asked to validate witnesses rather than
+    // resulting in a proof that fails verification
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (accum_params, accum_keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+    let prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let member_idx = 1;
+    let member = msgs[member_idx];
+    accumulator = accumulator
+        .add(member, &accum_keypair.secret_key, &mut state)
+        .unwrap();
+    let stale_wit = accumulator
+        .get_membership_witness(&member, &accum_keypair.secret_key, &state)
+        .unwrap();
+    // Adding another element changes the accumulator so the witness above isn't valid anymore
+    accumulator = accumulator
+        .add(Fr::rand(&mut rng), &accum_keypair.secret_key, &mut state)
+        .unwrap();
+    let mem_wit = accumulator
+        .get_membership_witness(&member, &accum_keypair.secret_key, &state)
+        .unwrap();
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, member_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        accum_params.clone(),
+        accum_keypair.public_key.clone(),
+        prk.clone(),
+        *accumulator.value(),
+    ));
+    let prover_proof_spec =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+    prover_proof_spec.validate().unwrap();
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        accum_params,
+        accum_keypair.public_key.clone(),
+        prk,
+        *accumulator.value(),
+    ));
+    let verifier_proof_spec = ProofSpec::new(verifier_statements, meta_statements, vec![], None);
+    verifier_proof_spec.validate().unwrap();
+
+    let witnesses = |accum_wit| {
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig.clone(),
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses.add(MembershipWit::new_as_witness(member, accum_wit));
+        witnesses
+    };
+    let validating_config = || ProverConfig::<Bls12_381> {
+        validate_witnesses: true,
+        ..Default::default()
+    };
+
+    // Without validation, the proof is created but fails verification
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec.clone(),
+        witnesses(stale_wit.clone()),
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            None,
+            Default::default()
+        )
+        .is_err());
+
+    // With validation, the prover rejects the witness of the accumulator statement
+    assert!(matches!(
+        Proof::new::<StdRng, Blake2b512>(
+            &mut rng,
+            prover_proof_spec.clone(),
+            witnesses(stale_wit),
+            None,
+            validating_config(),
+        ),
+        Err(ProofSystemError::InvalidWitnessForStatement(1))
+    ));
+
+    // A valid witness passes validation
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses(mem_wit),
+        None,
+        validating_config(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, None, Default::default())
+        .unwrap();
+}
+
+#[test]
+fn kb_universal_accumulator_membership_and_non_membership() {
+    // Prove knowledge of BBS+ signature and that one message is a member and another a non-member of the same
+    // KB universal accumulator
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let max = 10;
+    let msg_count = 6;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let mut domain = msgs.clone();
+    while domain.len() < max as usize {
+        domain.push(Fr::rand(&mut rng));
+    }
+    let (accum_params, accum_keypair, mut accumulator, mut mem_state, mut non_mem_state) =
+        setup_kb_universal_accum_given_domain(&mut rng, domain);
+    let prk = ProvingKey::generate_using_rng(&mut rng);
+
+    let member_idx = 1;
+    let member = msgs[member_idx];
+    let non_member_idx = 3;
+    let non_member = msgs[non_member_idx];
+
+    accumulator = accumulator
+        .add(
+            member,
+            &accum_keypair.secret_key,
+            &mut mem_state,
+            &mut non_mem_state,
+        )
+        .unwrap();
+    let mem_wit = accumulator
+        .get_membership_witness(&member, &accum_keypair.secret_key, &mem_state)
+        .unwrap();
+    let non_mem_wit = accumulator
+        .get_non_membership_witness(&non_member, &accum_keypair.secret_key, &non_mem_state)
+        .unwrap();
+    assert!(accumulator.verify_membership(
+        &member,
+        &mem_wit,
+        &accum_keypair.public_key,
+        &accum_params
+    ));
+    assert!(accumulator.verify_non_membership(
+        &non_member,
+        &non_mem_wit,
+        &accum_keypair.public_key,
+        &accum_params
+    ));
+
+    // Witness 0 of the accumulator statement is the member and witness 1 is the non-member
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, member_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, non_member_idx), (1, 1)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(
+        KBAccumulatorMembershipAndNonMembershipStmt::new_statement_from_params(
+            accum_params.clone(),
+            accum_keypair.public_key.clone(),
+            prk.clone(),
+            *accumulator.mem_value(),
+            *accumulator.non_mem_value(),
+        ),
+    );
+
+    test_serialization!(Statements<Bls12_381>, prover_statements);
+
+    let context = Some(b"test".to_vec());
+    let prover_proof_spec = ProofSpec::new(
+        prover_statements,
+        meta_statements.clone(),
+        vec![],
+        context.clone(),
+    );
+    prover_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().collect(),
+    ));
+    witnesses.add(KBMembershipAndNonMembershipWit::new_as_witness(
+        member,
+        mem_wit,
+        non_member,
+        non_mem_wit,
+    ));
+
+    test_serialization!(Witnesses<Bls12_381>, witnesses);
+
+    let nonce = Some(b"test-nonce".to_vec());
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    test_serialization!(Proof<Bls12_381>, proof);
+
+    let verifier_proof_spec = |non_mem_value: G1Affine| {
+        let mut statements = Statements::new();
+        statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        statements.add(
+            KBAccumulatorMembershipAndNonMembershipStmt::new_statement_from_params(
+                accum_params.clone(),
+                accum_keypair.public_key.clone(),
+                prk.clone(),
+                *accumulator.mem_value(),
+                non_mem_value,
+            ),
+        );
+        let proof_spec =
+            ProofSpec::new(statements, meta_statements.clone(), vec![], context.clone());
+        proof_spec.validate().unwrap();
+        proof_spec
+    };
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(*accumulator.non_mem_value()),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(*accumulator.non_mem_value()),
+            nonce.clone(),
+            VerifierConfig {
+                use_lazy_randomized_pairing_checks: Some(true),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    // Verifier using a different non-membership accumulator value rejects the proof
+    let wrong_non_mem_value = G1Affine::rand(&mut rng);
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(wrong_non_mem_value),
+            nonce,
+            Default::default(),
+        )
+        .is_err());
+}
+
+#[cfg(feature = "debug-transcript")]
+#[test]
+fn prover_and_verifier_challenge_bytes_match() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (accum_params, accum_keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+    let prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let member_idx = 2;
+    let member = msgs[member_idx];
+    accumulator = accumulator
+        .add(member, &accum_keypair.secret_key, &mut state)
+        .unwrap();
+    let mem_wit = accumulator
+        .get_membership_witness(&member, &accum_keypair.secret_key, &state)
+        .unwrap();
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        accum_params.clone(),
+        accum_keypair.public_key.clone(),
+        prk.clone(),
+        *accumulator.value(),
+    ));
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, member_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let context = Some(b"test".to_vec());
+    let prover_proof_spec = ProofSpec::new(
+        prover_statements,
+        meta_statements.clone(),
+        vec![],
+        context.clone(),
+    );
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        accum_params,
+        accum_keypair.public_key.clone(),
+        prk,
+        *accumulator.value(),
+    ));
+    let verifier_proof_spec = ProofSpec::new(verifier_statements, meta_statements, vec![], context);
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit));
+
+    let nonce = Some(b"nonce".to_vec());
+    let label = b"debug-transcript-test".to_vec();
+    let (proof, _, prover_bytes) = Proof::new_with_debug_challenge_bytes::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        nonce.clone(),
+        ProverConfig {
+            transcript_label: Some(label.clone()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert!(!prover_bytes.is_empty());
+
+    let verifier_config = VerifierConfig {
+        transcript_label: Some(label),
+        ..Default::default()
+    };
+    let verifier_bytes = proof
+        .debug_challenge_bytes(
+            verifier_proof_spec.clone(),
+            nonce.clone(),
+            verifier_config.clone(),
+        )
+        .unwrap();
+    assert_eq!(prover_bytes, verifier_bytes);
+
+    // A different nonce makes the transcripts diverge
+    let other_bytes = proof
+        .debug_challenge_bytes(
+            verifier_proof_spec.clone(),
+            Some(b"other nonce".to_vec()),
+            verifier_config.clone(),
+        )
+        .unwrap();
+    assert_ne!(prover_bytes, other_bytes);
+
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, nonce, verifier_config)
+        .unwrap();
+}
+
+#[cfg(feature = "timing")]
+#[test]
+fn verification_with_timings() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (accum_params, accum_keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+    let prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, 5);
+
+    let member_idx = 2;
+    let member = msgs[member_idx];
+    accumulator = accumulator
+        .add(member, &accum_keypair.secret_key, &mut state)
+        .unwrap();
+    let mem_wit = accumulator
+        .get_membership_witness(&member, &accum_keypair.secret_key, &state)
+        .unwrap();
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        accum_params.clone(),
+        accum_keypair.public_key.clone(),
+        prk.clone(),
+        *accumulator.value(),
+    ));
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, member_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    let prover_proof_spec =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        accum_params,
+        accum_keypair.public_key.clone(),
+        prk,
+        *accumulator.value(),
+    ));
+    let verifier_proof_spec = ProofSpec::new(verifier_statements, meta_statements, vec![], None);
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit));
+
+    let nonce = Some(b"nonce".to_vec());
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let report = proof
+        .clone()
+        .verify_with_timings::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(report.challenge_contribution.len(), 2);
+    assert_eq!(report.verification.len(), 2);
+    assert!(report
+        .challenge_contribution
+        .iter()
+        .chain(report.verification.iter())
+        .all(|d| *d > Duration::ZERO));
+    // Randomized pairing checks aren't used
+    assert_eq!(report.pairing_check, Duration::ZERO);
+
+    let report = proof
+        .clone()
+        .verify_with_timings::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            nonce.clone(),
+            VerifierConfig {
+                use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(report.verification.len(), 2);
+    assert!(report.pairing_check > Duration::ZERO);
+
+    // Timings aren't returned for a proof that fails verification
+    assert!(proof
+        .verify_with_timings::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec,
+            Some(b"other nonce".to_vec()),
+            Default::default(),
+        )
+        .is_err());
+}
+
+#[test]
+fn pok_of_bbs_plus_sigs_and_inequality_of_hidden_messages() {
+    // Prove knowledge of 2 BBS+ signatures and that a message from the 1st signature is not equal to
+    // a message from the 2nd signature without revealing either message
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let comm_key = PedersenCommitmentKey::<G1Affine>::new::<Blake2b512>(b"test");
+
+    let (msgs_1, params_1, keypair_1, sig_1) = bbs_plus_sig_setup(&mut rng, 5);
+    let (msgs_2, params_2, keypair_2, sig_2) = bbs_plus_sig_setup(&mut rng, 4);
+    let msg_idx_1 = 1;
+    let msg_idx_2 = 2;
+    assert_ne!(msgs_1[msg_idx_1], msgs_2[msg_idx_2]);
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, msg_idx_1), (2, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(1, msg_idx_2), (2, 1)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        params_1.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        params_2.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(HiddenInequalityStmt::new_statement_from_params(comm_key));
+
+    test_serialization!(Statements<Bls12_381>, prover_statements);
+
+    let prover_proof_spec = ProofSpec::new(
+        prover_statements.clone(),
+        meta_statements.clone(),
+        vec![],
+        None,
+    );
+    prover_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig_1.clone(),
+        msgs_1.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig_2.clone(),
+        msgs_2.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(HiddenInequalityWit::new_as_witness(
+        msgs_1[msg_idx_1],
+        msgs_2[msg_idx_2],
+    ));
+
+    test_serialization!(Witnesses<Bls12_381>, witnesses);
+
+    let nonce = Some(b"test nonce".to_vec());
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    test_serialization!(Proof<Bls12_381>, proof);
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        params_1.clone(),
+        keypair_1.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        params_2.clone(),
+        keypair_2.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(HiddenInequalityStmt::new_statement_from_params(comm_key));
+    let verifier_proof_spec = ProofSpec::new(
+        verifier_statements.clone(),
+        meta_statements.clone(),
+        vec![],
+        None,
+    );
+    verifier_proof_spec.validate().unwrap();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap();
+
+    // Swapping the commitments changes the challenge so the proof fails to verify
+    let mut swapped_proof = proof.clone();
+    match &mut swapped_proof.statement_proofs[2] {
+        StatementProof::HiddenInequality(p) => {
+            ark_std::mem::swap(&mut p.comm1, &mut p.comm2);
+        }
+        _ => panic!("expected a hidden inequality proof"),
+    }
+    assert!(swapped_proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            nonce.clone(),
+            Default::default(),
+        )
+        .is_err());
+
+    // 2nd signature's message is now equal to the 1st signature's message
+    let mut equal_msgs_2 = msgs_2.clone();
+    equal_msgs_2[msg_idx_2] = msgs_1[msg_idx_1];
+    let (params_3, keypair_3, sig_3) = bbs_plus_sig_setup_given_messages(&mut rng, &equal_msgs_2);
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        params_1.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        params_3.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(HiddenInequalityStmt::new_statement_from_params(comm_key));
+    let prover_proof_spec =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+    prover_proof_spec.validate().unwrap();
+
+    // Proof can't be created when the values are equal
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig_1.clone(),
+        msgs_1.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig_3.clone(),
+        equal_msgs_2.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(HiddenInequalityWit::new_as_witness(
+        msgs_1[msg_idx_1],
+        equal_msgs_2[msg_idx_2],
+    ));
+    assert!(Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec.clone(),
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .is_err());
+
+    // A prover using a different value than the signed one in the inequality witness creates a proof
+    // which fails to verify
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig_1,
+        msgs_1.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig_3,
+        equal_msgs_2.into_iter().enumerate().collect(),
+    ));
+    witnesses.add(HiddenInequalityWit::new_as_witness(
+        msgs_1[msg_idx_1],
+        Fr::rand(&mut rng),
+    ));
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        params_1,
+        keypair_1.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        params_3,
+        keypair_3.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(HiddenInequalityStmt::new_statement_from_params(comm_key));
+    let verifier_proof_spec = ProofSpec::new(verifier_statements, meta_statements, vec![], None);
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, nonce, Default::default())
+        .is_err());
+}
+
+#[test]
+fn pok_of_bbs_plus_sig_and_public_set_membership() {
+    // Prove knowledge of a BBS+ signature and that a message is one of the values of a public set
+    // without revealing the message
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let comm_key = PedersenCommitmentKey::<G1Affine>::new::<Blake2b512>(b"test");
+
+    let (msgs, params, keypair, sig) = bbs_plus_sig_setup(&mut rng, 5);
+    let msg_idx = 1;
+    let set = vec![Fr::rand(&mut rng), msgs[msg_idx], Fr::rand(&mut rng)];
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, msg_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        params.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(PublicSetMembershipStmt::new_statement_from_params(
+        set.clone(),
+        comm_key,
+    ));
+
+    test_serialization!(Statements<Bls12_381>, prover_statements);
+
+    let prover_proof_spec = ProofSpec::new(
+        prover_statements.clone(),
+        meta_statements.clone(),
+        vec![],
+        None,
+    );
+    prover_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::PublicSetMembership(msgs[msg_idx]));
+
+    test_serialization!(Witnesses<Bls12_381>, witnesses);
+
+    let nonce = Some(b"test nonce".to_vec());
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec.clone(),
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    test_serialization!(Proof<Bls12_381>, proof);
+
+    let verifier_proof_spec_with_set = |set: Vec<Fr>| {
+        let mut verifier_statements = Statements::new();
+        verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+            params.clone(),
+            keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        verifier_statements.add(PublicSetMembershipStmt::new_statement_from_params(
+            set, comm_key,
+        ));
+        let verifier_proof_spec =
+            ProofSpec::new(verifier_statements, meta_statements.clone(), vec![], None);
+        verifier_proof_spec.validate().unwrap();
+        verifier_proof_spec
+    };
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec_with_set(set.clone()),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap();
+
+    // The set is part of the challenge so the proof fails to verify with a different set, even one
+    // containing the message
+    let mut other_set = set.clone();
+    other_set[0] = Fr::rand(&mut rng);
+    assert!(proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec_with_set(other_set),
+            nonce.clone(),
+            Default::default(),
+        )
+        .is_err());
+
+    // Proof can't be created when the message is not in the set
+    let set_without_msg = vec![Fr::rand(&mut rng), Fr::rand(&mut rng), Fr::rand(&mut rng)];
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        params.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(PublicSetMembershipStmt::new_statement_from_params(
+        set_without_msg.clone(),
+        comm_key,
+    ));
+    let prover_proof_spec_without_msg =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::PublicSetMembership(msgs[msg_idx]));
+    assert!(matches!(
+        Proof::new::<StdRng, Blake2b512>(
+            &mut rng,
+            prover_proof_spec_without_msg,
+            witnesses,
+            nonce.clone(),
+            Default::default(),
+        ),
+        Err(ProofSystemError::WitnessNotInPublicSet(1))
+    ));
+
+    // A prover using a value from the set which is different from the signed message creates a proof
+    // which fails to verify
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::PublicSetMembership(set[2]));
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec_with_set(set),
+            nonce,
+            Default::default(),
+        )
+        .is_err());
+}
+
+#[test]
+fn comm_key_with_incompatible_setup_param() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let (_, params, _, _) = bbs_plus_sig_setup(&mut rng, 1);
+    let comm_key = PedersenCommitmentKey::<G1Affine>::new::<Blake2b512>(b"test");
+    let setup_params = vec![
+        SetupParams::<Bls12_381>::BBSPlusSignatureParams(params),
+        SetupParams::<Bls12_381>::CommitmentKey(comm_key),
+    ];
+    let set = (0..4).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+
+    // Index 1 holds the commitment key while index 0 holds the signature params
+    for (comm_key_ref, is_compatible) in [(1, true), (0, false)] {
+        let hidden_inequality = HiddenInequalityStmt {
+            comm_key: None,
+            comm_key_ref: Some(comm_key_ref),
+        };
+        let public_set_membership = PublicSetMembershipStmt {
+            set: set.clone(),
+            comm_key: None,
+            comm_key_ref: Some(comm_key_ref),
+        };
+        for res in [
+            hidden_inequality.get_comm_key(&setup_params, 2),
+            public_set_membership.get_comm_key(&setup_params, 2),
+        ] {
+            if is_compatible {
+                assert_eq!(res.unwrap(), &comm_key);
+            } else {
+                assert!(matches!(
+                    res,
+                    Err(ProofSystemError::IncompatiblePedCommSetupParamAtIndex(0))
+                ));
+            }
+        }
+    }
+}
+
+#[test]
+fn hidden_message_count_of_bbs_plus_and_bbs_sig_proofs() {
+    // The number of hidden messages can be read from a signature's proof without the proof spec
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (msgs_1, params_1, keypair_1, sig_1) = bbs_plus_sig_setup(&mut rng, 6);
+    let (msgs_2, params_2, keypair_2, sig_2) = bbs_sig_setup(&mut rng, 5);
+
+    let revealed_indices_1 = BTreeSet::from([0, 2]);
+    let revealed_indices_2 = BTreeSet::from([4]);
+    let split = |msgs: &[Fr], revealed_indices: &BTreeSet<usize>| {
+        let mut revealed = BTreeMap::new();
+        let mut unrevealed = BTreeMap::new();
+        for (i, m) in msgs.iter().enumerate() {
+            if revealed_indices.contains(&i) {
+                revealed.insert(i, *m);
+            } else {
+                unrevealed.insert(i, *m);
+            }
+        }
+        (revealed, unrevealed)
+    };
+    let (revealed_msgs_1, unrevealed_msgs_1) = split(&msgs_1, &revealed_indices_1);
+    let (revealed_msgs_2, unrevealed_msgs_2) = split(&msgs_2, &revealed_indices_2);
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        params_1.clone(),
+        revealed_msgs_1.clone(),
+    ));
+    prover_statements.add(PoKSignatureBBS23G1ProverStmt::new_statement_from_params(
+        params_2.clone(),
+        revealed_msgs_2.clone(),
+    ));
+    prover_statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        vec![params_1.h_0, params_1.h[0]],
+        (params_1.h_0 * unrevealed_msgs_1[&1] + params_1.h[0] * unrevealed_msgs_1[&3])
+            .into_affine(),
+    ));
+    let prover_proof_spec = ProofSpec::new(prover_statements, MetaStatements::new(), vec![], None);
+    prover_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig_1,
+        unrevealed_msgs_1.clone(),
+    ));
+    witnesses.add(PoKSignatureBBS23G1Wit::new_as_witness(
+        sig_2,
+        unrevealed_msgs_2,
+    ));
+    witnesses.add(Witness::PedersenCommitment(vec![
+        unrevealed_msgs_1[&1],
+        unrevealed_msgs_1[&3],
+    ]));
+
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    assert_eq!(proof.statement_proofs[0].hidden_message_count(), Some(4));
+    assert_eq!(proof.statement_proofs[1].hidden_message_count(), Some(4));
+    assert_eq!(proof.statement_proofs[2].hidden_message_count(), None);
+
+    // The counts are preserved across serialization
+    let mut bytes = vec![];
+    proof.serialize_compressed(&mut bytes).unwrap();
+    let deserialized = Proof::<Bls12_381>::deserialize_compressed(&bytes[..]).unwrap();
+    assert_eq!(
+        deserialized.statement_proofs[0].hidden_message_count(),
+        Some(4)
+    );
+    assert_eq!(
+        deserialized.statement_proofs[1].hidden_message_count(),
+        Some(4)
+    );
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        params_1.clone(),
+        keypair_1.public_key.clone(),
+        revealed_msgs_1,
+    ));
+    verifier_statements.add(PoKSignatureBBS23G1VerifierStmt::new_statement_from_params(
+        params_2,
+        keypair_2.public_key.clone(),
+        revealed_msgs_2,
+    ));
+    verifier_statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        vec![params_1.h_0, params_1.h[0]],
+        (params_1.h_0 * unrevealed_msgs_1[&1] + params_1.h[0] * unrevealed_msgs_1[&3])
+            .into_affine(),
+    ));
+    let verifier_proof_spec =
+        ProofSpec::new(verifier_statements, MetaStatements::new(), vec![], None);
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, None, Default::default())
+        .unwrap();
+}
+
+#[test]
+fn proof_without_statements_is_rejected_by_default() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let proof_spec =
+        ProofSpec::<Bls12_381>::new(Statements::new(), MetaStatements::new(), vec![], None);
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec.clone(),
+        Witnesses::new(),
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(proof.statement_proofs.is_empty());
+
+    assert!(matches!(
+        proof.clone().verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec.clone(),
+            None,
+            Default::default()
+        ),
+        Err(ProofSystemError::NoStatements)
+    ));
+
+    proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec,
+            None,
+            VerifierConfig {
+                allow_empty_proof: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn prover_supplied_witness_blindings() {
+    // The prover can fix the blinding of a witness so that its response can be checked against an
+    // external Schnorr proof. Reusing that blinding in another proof reveals the witness.
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let committed_idx = 1;
+    let gens = vec![
+        G1Projective::rand(&mut rng).into_affine(),
+        G1Projective::rand(&mut rng).into_affine(),
+    ];
+    let comm_blinding = Fr::rand(&mut rng);
+    let commitment = G1Projective::msm_bigint(
+        &gens,
+        &[
+            msgs[committed_idx].into_bigint(),
+            comm_blinding.into_bigint(),
+        ],
+    )
+    .into_affine();
+
+    let mut unrevealed_msgs = BTreeMap::new();
+    for (i, m) in msgs.iter().enumerate() {
+        unrevealed_msgs.insert(i, *m);
+    }
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        gens.clone(),
+        commitment,
+    ));
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, committed_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let prover_proof_spec =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        gens, commitment,
+    ));
+    let verifier_proof_spec = ProofSpec::new(verifier_statements, meta_statements, vec![], None);
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        unrevealed_msgs.clone(),
+    ));
+    witnesses.add(Witness::PedersenCommitment(vec![
+        msgs[committed_idx],
+        comm_blinding,
+    ]));
+
+    // Fixing the blinding of one witness of the equality fixes it for all of them
+    let fixed_blinding = Fr::rand(&mut rng);
+    let prover_config = ProverConfig::<Bls12_381> {
+        witness_blindings: Some(BTreeMap::from([((1, 0), fixed_blinding)])),
+        ..Default::default()
+    };
+
+    let mut challenges = vec![];
+    let mut responses = vec![];
+    for nonce in [b"nonce-1".to_vec(), b"nonce-2".to_vec()] {
+        let proof = Proof::new::<StdRng, Blake2b512>(
+            &mut rng,
+            prover_proof_spec.clone(),
+            witnesses.clone(),
+            Some(nonce.clone()),
+            prover_config.clone(),
+        )
+        .unwrap()
+        .0;
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                verifier_proof_spec.clone(),
+                Some(nonce.clone()),
+                Default::default(),
+            )
+            .unwrap();
+
+        let challenge = proof
+            .compute_challenge(verifier_proof_spec.clone(), Some(nonce), Default::default())
+            .unwrap();
+        let resp = proof
+            .get_response_for_witness(&verifier_proof_spec, (0, committed_idx))
+            .unwrap()
+            .unwrap();
+        assert_eq!(resp, fixed_blinding + challenge * msgs[committed_idx]);
+        // The commitment's proof omits the response as it's in the signature's proof
+        assert!(proof
+            .get_response_for_witness(&verifier_proof_spec, (1, 0))
+            .is_err());
+        challenges.push(challenge);
+        responses.push(resp);
+    }
+
+    // The challenges differ so the responses don't match and instead reveal the witness
+    assert_ne!(responses[0], responses[1]);
+    assert_eq!(
+        (responses[0] - responses[1]) / (challenges[0] - challenges[1]),
+        msgs[committed_idx]
+    );
+
+    // Different blindings for witnesses that are proven equal are rejected
+    let prover_config = ProverConfig::<Bls12_381> {
+        witness_blindings: Some(BTreeMap::from([
+            ((0, committed_idx), fixed_blinding),
+            ((1, 0), Fr::rand(&mut rng)),
+        ])),
+        ..Default::default()
+    };
+    assert!(matches!(
+        Proof::new::<StdRng, Blake2b512>(
+            &mut rng,
+            prover_proof_spec,
+            witnesses,
+            None,
+            prover_config,
+        ),
+        Err(ProofSystemError::ConflictingWitnessBlindings(_))
+    ));
+}
+
+#[test]
+fn verification_failure_reports_statement_index() {
+    // Corrupting the proof of any one statement makes verification fail with an error carrying that
+    // statement's index
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (pos_accum_params, pos_accum_keypair, mut pos_accumulator, mut pos_state) =
+        setup_positive_accum(&mut rng);
+    let (uni_accum_params, uni_accum_keypair, uni_accumulator, _, uni_state) =
+        setup_universal_accum(&mut rng, 10);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+    let non_mem_prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let member_idx = 1;
+    let member = msgs[member_idx];
+    pos_accumulator = pos_accumulator
+        .add(member, &pos_accum_keypair.secret_key, &mut pos_state)
+        .unwrap();
+    let mem_wit = pos_accumulator
+        .get_membership_witness(&member, &pos_accum_keypair.secret_key, &pos_state)
+        .unwrap();
+
+    let non_member_idx = 3;
+    let non_member = msgs[non_member_idx];
+    let non_mem_wit = uni_accumulator
+        .get_non_membership_witness(
+            &non_member,
+            &uni_accum_keypair.secret_key,
+            &uni_state,
+            &uni_accum_params,
+        )
+        .unwrap();
+
+    let comm_key = (0..2)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let committed = vec![msgs[4], Fr::rand(&mut rng)];
+    let commitment = G1Projective::msm_bigint(
+        &comm_key,
+        &committed
+            .iter()
+            .map(|s| s.into_bigint())
+            .collect::<Vec<_>>(),
+    )
+    .into_affine();
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, member_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, non_member_idx), (2, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 4), (3, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let statements = |sig_stmt| {
+        let mut statements = Statements::new();
+        statements.add(sig_stmt);
+        statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+            pos_accum_params.clone(),
+            pos_accum_keypair.public_key.clone(),
+            mem_prk.clone(),
+            *pos_accumulator.value(),
+        ));
+        statements.add(AccumulatorNonMembershipStmt::new_statement_from_params(
+            uni_accum_params.clone(),
+            uni_accum_keypair.public_key.clone(),
+            non_mem_prk.clone(),
+            *uni_accumulator.value(),
+        ));
+        statements.add(PedersenCommitmentStmt::new_statement_from_params(
+            comm_key.clone(),
+            commitment,
+        ));
+        statements
+    };
+    let prover_proof_spec = ProofSpec::new(
+        statements(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+            sig_params.clone(),
+            BTreeMap::new(),
+        )),
+        meta_statements.clone(),
+        vec![],
+        None,
+    );
+    let verifier_proof_spec = ProofSpec::new(
+        statements(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        )),
+        meta_statements,
+        vec![],
+        None,
+    );
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit));
+    witnesses.add(NonMembershipWit::new_as_witness(non_member, non_mem_wit));
+    witnesses.add(Witness::PedersenCommitment(committed));
+
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+
+    // Only responses are changed so that the challenge stays the same and the proofs of the other
+    // statements still verify
+    for s_idx in 0..4 {
+        let mut bad_proof = proof.clone();
+        match &mut bad_proof.statement_proofs[s_idx] {
+            StatementProof::PoKBBSSignatureG1(p) => p.sc_resp_1.response1 += Fr::from(1u64),
+            StatementProof::VBAccumulatorMembership(p) => {
+                p.schnorr_response.0.s_sigma += Fr::from(1u64)
+            }
+            StatementProof::VBAccumulatorNonMembership(p) => {
+                p.schnorr_response.s_u += Fr::from(1u64)
+            }
+            StatementProof::PedersenCommitmentPartial(p) => {
+                *p.response.responses.get_mut(&1).unwrap() += Fr::from(1u64)
+            }
+            _ => panic!("unexpected statement proof"),
+        }
+        let err = bad_proof
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                verifier_proof_spec.clone(),
+                None,
+                Default::default(),
+            )
+            .unwrap_err();
+        assert_eq!(err.failed_statement_index(), Some(s_idx), "{:?}", err);
+    }
+}
+
+#[test]
+fn verifier_requiring_nonce() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+
+    let context = Some(b"test-context".to_vec());
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    let prover_proof_spec = ProofSpec::new(
+        prover_statements,
+        MetaStatements::new(),
+        vec![],
+        context.clone(),
+    );
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    let verifier_proof_spec =
+        ProofSpec::new(verifier_statements, MetaStatements::new(), vec![], context);
+
+    // The derived nonce depends on the context and the salt
+    let nonce = verifier_proof_spec.derive_nonce::<Blake2b512>(b"session-1");
+    assert_eq!(
+        nonce,
+        prover_proof_spec.derive_nonce::<Blake2b512>(b"session-1")
+    );
+    assert_ne!(
+        nonce,
+        verifier_proof_spec.derive_nonce::<Blake2b512>(b"session-2")
+    );
+    let mut other_context_spec = verifier_proof_spec.clone();
+    other_context_spec.context = Some(b"other-context".to_vec());
+    assert_ne!(
+        nonce,
+        other_context_spec.derive_nonce::<Blake2b512>(b"session-1")
+    );
+
+    let witnesses = |sig: SignatureG1<Bls12_381>| {
+        let mut witnesses = Witnesses::new();
+        witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+            sig,
+            msgs.clone().into_iter().enumerate().collect(),
+        ));
+        witnesses
+    };
+    let config = || VerifierConfig {
+        require_nonce: true,
+        ..Default::default()
+    };
+
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec.clone(),
+        witnesses(sig.clone()),
+        Some(nonce.clone()),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec.clone(), Some(nonce), config())
+        .unwrap();
+
+    // A proof created without a nonce is rejected though it verifies when the nonce isn't required
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses(sig),
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    assert!(matches!(
+        proof.clone().verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            None,
+            config()
+        ),
+        Err(ProofSystemError::NonceRequired)
+    ));
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, None, Default::default())
+        .unwrap();
+}
+
+#[test]
+fn proof_size_estimate() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (pos_accum_params, pos_accum_keypair, mut pos_accumulator, mut pos_state) =
+        setup_positive_accum(&mut rng);
+    let (uni_accum_params, uni_accum_keypair, uni_accumulator, _, uni_state) =
+        setup_universal_accum(&mut rng, 10);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+    let non_mem_prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+
+    let msg_count = 6;
+    let (msgs, sig_params, _, sig) = bbs_plus_sig_setup(&mut rng, msg_count as u32);
+    let revealed_msgs = BTreeMap::from([(0, msgs[0]), (5, msgs[5])]);
+    let unrevealed_msgs = msgs
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !revealed_msgs.contains_key(i))
+        .map(|(i, m)| (i, *m))
+        .collect::<BTreeMap<_, _>>();
+
+    let member = msgs[1];
+    pos_accumulator = pos_accumulator
+        .add(member, &pos_accum_keypair.secret_key, &mut pos_state)
+        .unwrap();
+    let mem_wit = pos_accumulator
+        .get_membership_witness(&member, &pos_accum_keypair.secret_key, &pos_state)
+        .unwrap();
+    let non_member = msgs[3];
+    let non_mem_wit = uni_accumulator
+        .get_non_membership_witness(
+            &non_member,
+            &uni_accum_keypair.secret_key,
+            &uni_state,
+            &uni_accum_params,
+        )
+        .unwrap();
+
+    let comm_key = (0..3)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let committed = vec![msgs[4], Fr::rand(&mut rng), Fr::rand(&mut rng)];
+    let commitment = G1Projective::msm_bigint(
+        &comm_key,
+        &committed
+            .iter()
+            .map(|s| s.into_bigint())
+            .collect::<Vec<_>>(),
+    )
+    .into_affine();
+
+    let mut statements = Statements::new();
+    statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        revealed_msgs,
+    ));
+    statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        pos_accum_params,
+        pos_accum_keypair.public_key.clone(),
+        mem_prk,
+        *pos_accumulator.value(),
+    ));
+    statements.add(AccumulatorNonMembershipStmt::new_statement_from_params(
+        uni_accum_params,
+        uni_accum_keypair.public_key.clone(),
+        non_mem_prk,
+        *uni_accumulator.value(),
+    ));
+    statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        comm_key, commitment,
+    ));
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(sig, unrevealed_msgs));
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit));
+    witnesses.add(NonMembershipWit::new_as_witness(non_member, non_mem_wit));
+    witnesses.add(Witness::PedersenCommitment(committed));
+
+    // Without witness equalities, all responses are present
+    test_proof_size_estimate!(
+        &mut rng,
+        ProofSpec::new(statements.clone(), MetaStatements::new(), vec![], None),
+        witnesses.clone()
+    );
+
+    // With witness equalities, the Pedersen commitment proof has a partial response
+    let mut meta_statements = MetaStatements::new();
+    for (s_idx, msg_idx) in [(1, 1), (2, 3), (3, 4)] {
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(0, msg_idx), (s_idx, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+    }
+    test_proof_size_estimate!(
+        &mut rng,
+        ProofSpec::new(statements, meta_statements, vec![], None),
+        witnesses
+    );
+
+    // More messages revealed than the signature params support
+    let mut statements = Statements::new();
+    statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params,
+        (0..=msg_count).map(|i| (i, Fr::rand(&mut rng))).collect(),
+    ));
+    assert!(matches!(
+        ProofSpec::new(statements, MetaStatements::new(), vec![], None).estimate_proof_size(),
+        Err(ProofSystemError::BBSPlusProtocolInvalidMessageCount(7, 6))
+    ));
+}
+
+#[test]
+fn proof_size_estimate_for_bbs_kb_accumulator_and_bound_check() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    // Messages are small so that one of them can be bound checked
+    let msgs = (0..6).map(|i| Fr::from(100 + i as u64)).collect::<Vec<_>>();
+    let (sig_params, _, sig) = bbs_sig_setup_given_messages(&mut rng, &msgs);
+    let revealed_msgs = BTreeMap::from([(0, msgs[0])]);
+    let unrevealed_msgs = msgs
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !revealed_msgs.contains_key(i))
+        .map(|(i, m)| (i, *m))
+        .collect::<BTreeMap<_, _>>();
+
+    let (kb_params, kb_keypair, mut kb_accumulator, mut kb_mem_state, mut kb_non_mem_state) =
+        setup_kb_universal_accum_given_domain(&mut rng, msgs.clone());
+    let prk = ProvingKey::generate_using_rng(&mut rng);
+    let member = msgs[1];
+    kb_accumulator = kb_accumulator
+        .add(
+            member,
+            &kb_keypair.secret_key,
+            &mut kb_mem_state,
+            &mut kb_non_mem_state,
+        )
+        .unwrap();
+    let mem_wit = kb_accumulator
+        .get_membership_witness(&member, &kb_keypair.secret_key, &kb_mem_state)
+        .unwrap();
+    let non_member = msgs[2];
+    let non_mem_wit = kb_accumulator
+        .get_non_membership_witness(&non_member, &kb_keypair.secret_key, &kb_non_mem_state)
+        .unwrap();
+
+    let comm_key = (0..3)
+        .map(|_| G2Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let committed = vec![msgs[3], Fr::rand(&mut rng), Fr::rand(&mut rng)];
+    let commitment = G2Projective::msm_bigint(
+        &comm_key,
+        &committed
+            .iter()
+            .map(|s| s.into_bigint())
+            .collect::<Vec<_>>(),
+    )
+    .into_affine();
+
+    let g = G1Projective::rand(&mut rng).into_affine();
+    let dl_pk = (g * member).into_affine();
+
+    let mut statements = Statements::new();
+    statements.add(PoKSignatureBBS23G1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        revealed_msgs,
+    ));
+    statements.add(
+        PoKSignatureBBS23IETFG1ProverStmt::new_statement_from_params(sig_params, BTreeMap::new()),
+    );
+    statements.add(KBAccumulatorMembershipStmt::new_statement_from_params(
+        kb_params.clone(),
+        kb_keypair.public_key.clone(),
+        prk.clone(),
+        *kb_accumulator.mem_value(),
+    ));
+    statements.add(KBAccumulatorNonMembershipStmt::new_statement_from_params(
+        kb_params,
+        kb_keypair.public_key.clone(),
+        prk,
+        *kb_accumulator.non_mem_value(),
+    ));
+    statements.add(PedersenCommitmentStmt::new_statement_from_params_g2(
+        comm_key, commitment,
+    ));
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBS23G1Wit::new_as_witness(
+        sig.clone(),
+        unrevealed_msgs,
+    ));
+    witnesses.add(PoKSignatureBBS23G1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::KBUniAccumulatorMembership(KBMembershipWit {
+        element: member,
+        witness: mem_wit,
+    }));
+    witnesses.add(Witness::KBUniAccumulatorNonMembership(KBNonMembershipWit {
+        element: non_member,
+        witness: non_mem_wit,
+    }));
+    witnesses.add(Witness::PedersenCommitment(committed));
+
+    // Without witness equalities, all responses are present
+    test_proof_size_estimate!(
+        &mut rng,
+        ProofSpec::new(statements.clone(), MetaStatements::new(), vec![], None),
+        witnesses.clone()
+    );
+
+    // With witness equalities, the proofs of the BBS signature using the IETF protocol, the Pedersen commitment
+    // and the discrete log have partial responses. The bound check proof never has the response for the message.
+    // Both of these statements need a witness equality
+    statements.add(DLEqualToAccumulatorMember::new_statement_from_params(
+        g, dl_pk,
+    ));
+    witnesses.add(Witness::DLEqualToAccumulatorMember(member));
+    let bpp_setup_params =
+        BppSetupParams::<G1Affine>::new_for_arbitrary_range_proof::<Blake2b512>(b"test", 2, 64, 1);
+    statements
+        .add(BoundCheckBppStmt::new_statement_from_params(100, 200, bpp_setup_params).unwrap());
+    witnesses.add(Witness::BoundCheckBpp(msgs[5]));
+
+    let mut meta_statements = MetaStatements::new();
+    for refs in [
+        vec![(0, 1), (2, 0), (5, 0)],
+        vec![(0, 2), (3, 0)],
+        vec![(1, 3), (4, 0)],
+        vec![(0, 4), (1, 4)],
+        vec![(0, 5), (6, 0)],
+    ] {
+        meta_statements.add_witness_equality(EqualWitnesses(
+            refs.into_iter().collect::<BTreeSet<WitnessRef>>(),
+        ));
+    }
+    test_proof_size_estimate!(
+        &mut rng,
+        ProofSpec::new(statements, meta_statements, vec![], None),
+        witnesses
+    );
+}
+
+#[test]
+fn proof_size_estimate_for_inequalities_and_public_set_membership() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let comm_key = PedersenCommitmentKey::<G1Affine>::new::<Blake2b512>(b"test");
+
+    let (msgs, sig_params, _, sig) = bbs_plus_sig_setup(&mut rng, 6);
+    let set = vec![
+        Fr::rand(&mut rng),
+        msgs[4],
+        Fr::rand(&mut rng),
+        Fr::rand(&mut rng),
+    ];
+
+    let mut statements = Statements::new();
+    statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params,
+        BTreeMap::new(),
+    ));
+    statements.add(InequalityStmt::new_statement_from_params(
+        Fr::rand(&mut rng),
+        comm_key.clone(),
+    ));
+    statements.add(HiddenInequalityStmt::new_statement_from_params(
+        comm_key.clone(),
+    ));
+    statements.add(PublicSetMembershipStmt::new_statement_from_params(
+        set, comm_key,
+    ));
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::PublicInequality(msgs[1]));
+    witnesses.add(HiddenInequalityWit::new_as_witness(msgs[2], msgs[3]));
+    witnesses.add(Witness::PublicSetMembership(msgs[4]));
+
+    // These statements need a witness equality for each of their witnesses
+    let mut meta_statements = MetaStatements::new();
+    for refs in [
+        vec![(0, 1), (1, 0)],
+        vec![(0, 2), (2, 0)],
+        vec![(0, 3), (2, 1)],
+        vec![(0, 4), (3, 0)],
+    ] {
+        meta_statements.add_witness_equality(EqualWitnesses(
+            refs.into_iter().collect::<BTreeSet<WitnessRef>>(),
+        ));
+    }
+    test_proof_size_estimate!(
+        &mut rng,
+        ProofSpec::new(statements, meta_statements, vec![], None),
+        witnesses
+    );
+}
+
+#[test]
+fn statement_proof_serialized_sizes() {
+    // The serialized size of each statement proof must match the length of its serialization exactly,
+    // both with full responses and with partial responses due to witness equalities, so that it can be
+    // used to pre-allocate buffers
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    // Messages are small so that one of them can be bound checked
+    let msgs = (0..6).map(|i| Fr::from(100 + i as u64)).collect::<Vec<_>>();
+    let (bbs_plus_params, _, bbs_plus_sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+    let (bbs_params, _, bbs_sig) = bbs_sig_setup_given_messages(&mut rng, &msgs);
+    let revealed_msgs = BTreeMap::from([(0, msgs[0])]);
+    let unrevealed_msgs = msgs
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !revealed_msgs.contains_key(i))
+        .map(|(i, m)| (i, *m))
+        .collect::<BTreeMap<_, _>>();
+
+    let (pos_accum_params, pos_accum_keypair, mut pos_accumulator, mut pos_state) =
+        setup_positive_accum(&mut rng);
+    let (uni_accum_params, uni_accum_keypair, uni_accumulator, _, uni_state) =
+        setup_universal_accum(&mut rng, 10);
+    let mem_prk = MembershipProvingKey::generate_using_rng(&mut rng);
+    let non_mem_prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+
+    let member = msgs[1];
+    pos_accumulator = pos_accumulator
+        .add(member, &pos_accum_keypair.secret_key, &mut pos_state)
+        .unwrap();
+    let mem_wit = pos_accumulator
+        .get_membership_witness(&member, &pos_accum_keypair.secret_key, &pos_state)
+        .unwrap();
+    let non_member = msgs[2];
+    let non_mem_wit = uni_accumulator
+        .get_non_membership_witness(
+            &non_member,
+            &uni_accum_keypair.secret_key,
+            &uni_state,
+            &uni_accum_params,
+        )
+        .unwrap();
+
+    let (kb_params, kb_keypair, mut kb_accumulator, mut kb_mem_state, mut kb_non_mem_state) =
+        setup_kb_universal_accum_given_domain(&mut rng, msgs.clone());
+    let prk = ProvingKey::generate_using_rng(&mut rng);
+    kb_accumulator = kb_accumulator
+        .add(
+            member,
+            &kb_keypair.secret_key,
+            &mut kb_mem_state,
+            &mut kb_non_mem_state,
+        )
+        .unwrap();
+    let kb_mem_wit = kb_accumulator
+        .get_membership_witness(&member, &kb_keypair.secret_key, &kb_mem_state)
+        .unwrap();
+    let kb_non_mem_wit = kb_accumulator
+        .get_non_membership_witness(&non_member, &kb_keypair.secret_key, &kb_non_mem_state)
+        .unwrap();
+
+    let comm_key = (0..3)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let committed = vec![msgs[3], Fr::rand(&mut rng), Fr::rand(&mut rng)];
+    let commitment = G1Projective::msm_bigint(
+        &comm_key,
+        &committed
+            .iter()
+            .map(|s| s.into_bigint())
+            .collect::<Vec<_>>(),
+    )
+    .into_affine();
+
+    let bpp_setup_params =
+        BppSetupParams::<G1Affine>::new_for_arbitrary_range_proof::<Blake2b512>(b"test", 2, 64, 1);
+
+    let mut statements = Statements::new();
+    statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        bbs_plus_params,
+        revealed_msgs,
+    ));
+    statements.add(PoKSignatureBBS23G1ProverStmt::new_statement_from_params(
+        bbs_params,
+        BTreeMap::new(),
+    ));
+    statements.add(AccumulatorMembershipStmt::new_statement_from_params(
+        pos_accum_params,
+        pos_accum_keypair.public_key.clone(),
+        mem_prk,
+        *pos_accumulator.value(),
+    ));
+    statements.add(AccumulatorNonMembershipStmt::new_statement_from_params(
+        uni_accum_params,
+        uni_accum_keypair.public_key.clone(),
+        non_mem_prk,
+        *uni_accumulator.value(),
+    ));
+    statements.add(KBAccumulatorMembershipStmt::new_statement_from_params(
+        kb_params.clone(),
+        kb_keypair.public_key.clone(),
+        prk.clone(),
+        *kb_accumulator.mem_value(),
+    ));
+    statements.add(KBAccumulatorNonMembershipStmt::new_statement_from_params(
+        kb_params,
+        kb_keypair.public_key.clone(),
+        prk,
+        *kb_accumulator.non_mem_value(),
+    ));
+    statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        comm_key, commitment,
+    ));
+    statements
+        .add(BoundCheckBppStmt::new_statement_from_params(100, 200, bpp_setup_params).unwrap());
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        bbs_plus_sig,
+        unrevealed_msgs,
+    ));
+    witnesses.add(PoKSignatureBBS23G1Wit::new_as_witness(
+        bbs_sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit));
+    witnesses.add(NonMembershipWit::new_as_witness(non_member, non_mem_wit));
+    witnesses.add(Witness::KBUniAccumulatorMembership(KBMembershipWit {
+        element: member,
+        witness: kb_mem_wit,
+    }));
+    witnesses.add(Witness::KBUniAccumulatorNonMembership(KBNonMembershipWit {
+        element: non_member,
+        witness: kb_non_mem_wit,
+    }));
+    witnesses.add(Witness::PedersenCommitment(committed));
+    witnesses.add(Witness::BoundCheckBpp(msgs[5]));
+
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        ProofSpec::new(statements.clone(), MetaStatements::new(), vec![], None),
+        witnesses.clone(),
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    test_statement_proof_sizes!(proof);
+
+    let mut meta_statements = MetaStatements::new();
+    for refs in [
+        vec![(0, 1), (1, 1), (2, 0), (4, 0)],
+        vec![(0, 2), (1, 2), (3, 0), (5, 0)],
+        vec![(0, 3), (1, 3), (6, 0)],
+        vec![(0, 5), (1, 5), (7, 0)],
+    ] {
+        meta_statements.add_witness_equality(EqualWitnesses(
+            refs.into_iter().collect::<BTreeSet<WitnessRef>>(),
+        ));
+    }
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        ProofSpec::new(statements, meta_statements, vec![], None),
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    test_statement_proof_sizes!(proof);
+}
+
+#[test]
+fn reveal_all_fast_path_for_bbs_plus_sig() {
+    // When all messages of a BBS+ signature are revealed, the fast path sends the signature rather than
+    // a proof of knowledge of it
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (msgs, params, keypair, sig) = bbs_plus_sig_setup(&mut rng, 10);
+    let revealed_msgs = msgs
+        .iter()
+        .enumerate()
+        .map(|(i, m)| (i, *m))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        params.clone(),
+        revealed_msgs.clone(),
+    ));
+    let prover_proof_spec = ProofSpec::new(prover_statements, MetaStatements::new(), vec![], None);
+    prover_proof_spec.validate().unwrap();
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        params.clone(),
+        keypair.public_key.clone(),
+        revealed_msgs,
+    ));
+    let verifier_proof_spec =
+        ProofSpec::new(verifier_statements, MetaStatements::new(), vec![], None);
+    verifier_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig.clone(),
+        BTreeMap::new(),
+    ));
+
+    let nonce = Some(b"test nonce".to_vec());
+    let fast_path_verifier_config = || VerifierConfig {
+        reveal_all_fast_path: true,
+        ..Default::default()
+    };
+
+    let start = Instant::now();
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec.clone(),
+        witnesses.clone(),
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    let proving_time = start.elapsed();
+
+    let start = Instant::now();
+    let fast_proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        nonce.clone(),
+        ProverConfig {
+            reveal_all_fast_path: true,
+            ..Default::default()
+        },
+    )
+    .unwrap()
+    .0;
+    let fast_proving_time = start.elapsed();
+
+    assert!(matches!(
+        fast_proof.statement_proofs[0],
+        StatementProof::RevealedBBSSignatureG1(ref s) if *s == sig
+    ));
+    test_serialization!(Proof<Bls12_381>, fast_proof);
+
+    let start = Instant::now();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap();
+    let verifying_time = start.elapsed();
+
+    let start = Instant::now();
+    fast_proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            nonce.clone(),
+            fast_path_verifier_config(),
+        )
+        .unwrap();
+    let fast_verifying_time = start.elapsed();
+
+    // The proof of knowledge is still accepted by a verifier allowing the fast path
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            nonce.clone(),
+            fast_path_verifier_config(),
+        )
+        .unwrap();
+
+    let size = proof.compressed_size();
+    let fast_size = fast_proof.compressed_size();
+    println!(
+        "Proof of knowledge of BBS+ signature with all 10 messages revealed: size {} bytes, proving time {:?}, verifying time {:?}",
+        size, proving_time, verifying_time
+    );
+    println!(
+        "Fast path for BBS+ signature with all 10 messages revealed: size {} bytes, proving time {:?}, verifying time {:?}",
+        fast_size, fast_proving_time, fast_verifying_time
+    );
+    assert!(fast_size < size);
+
+    // The verifier has to opt in to the fast path
+    assert!(matches!(
+        fast_proof.clone().verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            nonce.clone(),
+            Default::default(),
+        ),
+        Err(ProofSystemError::ProofIncompatibleWithStatement(0, _, _))
+    ));
+
+    // A verifier asking for a fresh proof doesn't accept the replayable signature, even with a nonce
+    assert!(matches!(
+        fast_proof.clone().verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            nonce.clone(),
+            VerifierConfig {
+                require_nonce: true,
+                ..fast_path_verifier_config()
+            },
+        ),
+        Err(ProofSystemError::ReplayableProofWithNonceRequired(0))
+    ));
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            nonce.clone(),
+            VerifierConfig {
+                require_nonce: true,
+                ..fast_path_verifier_config()
+            },
+        )
+        .unwrap();
+
+    // Signature doesn't verify with different messages
+    let mut wrong_msgs = msgs
+        .iter()
+        .enumerate()
+        .map(|(i, m)| (i, *m))
+        .collect::<BTreeMap<_, _>>();
+    wrong_msgs.insert(3, Fr::rand(&mut rng));
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        params,
+        keypair.public_key.clone(),
+        wrong_msgs,
+    ));
+    let wrong_proof_spec = ProofSpec::new(verifier_statements, MetaStatements::new(), vec![], None);
+    assert!(fast_proof
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            wrong_proof_spec,
+            nonce,
+            fast_path_verifier_config()
+        )
+        .is_err());
+}
+
+#[test]
+fn accumulator_proving_keys_from_vb_accumulator_as_setup_params() {
+    // Proving keys generated with `vb_accumulator` are used as setup params and referenced by statements
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let max = 10;
+    let (accum_params, accum_keypair, mut accumulator, initial_elements, mut state) =
+        setup_universal_accum(&mut rng, max);
+    let non_mem_prk = NonMembershipProvingKey::<G1Affine>::generate_using_rng(&mut rng);
+    let mem_prk = non_mem_prk.derive_membership_proving_key();
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count);
+    let member_idx = 1;
+    let non_member_idx = 2;
+    let member = msgs[member_idx];
+    accumulator = accumulator
+        .add(
+            member,
+            &accum_keypair.secret_key,
+            &initial_elements,
+            &mut state,
+        )
+        .unwrap();
+    let mem_wit = accumulator
+        .get_membership_witness(&member, &accum_keypair.secret_key, &state)
+        .unwrap();
+    let non_member = msgs[non_member_idx];
+    let non_mem_wit = accumulator
+        .get_non_membership_witness(
+            &non_member,
+            &accum_keypair.secret_key,
+            &state,
+            &accum_params,
+        )
+        .unwrap();
+
+    let setup_params = vec![
+        SetupParams::VbAccumulatorParams(accum_params),
+        SetupParams::VbAccumulatorPublicKey(accum_keypair.public_key.clone()),
+        SetupParams::from_vb_accumulator_mem_proving_key(mem_prk.clone()),
+        non_mem_prk.clone().into(),
+    ];
+    assert_eq!(
+        setup_params[2].as_vb_accumulator_mem_proving_key(),
+        Some(&mem_prk)
+    );
+    assert_eq!(
+        setup_params[3].as_vb_accumulator_non_mem_proving_key(),
+        Some(&non_mem_prk)
+    );
+    assert!(setup_params[3]
+        .as_vb_accumulator_mem_proving_key()
+        .is_none());
+    assert!(setup_params[0]
+        .as_vb_accumulator_non_mem_proving_key()
+        .is_none());
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    for statements in [&mut prover_statements, &mut verifier_statements] {
+        statements.add(AccumulatorMembershipStmt::new_statement_from_params_ref(
+            0,
+            1,
+            2,
+            *accumulator.value(),
+        ));
+        statements.add(AccumulatorNonMembershipStmt::new_statement_from_params_ref(
+            0,
+            1,
+            3,
+            *accumulator.value(),
+        ));
+    }
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, member_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, non_member_idx), (2, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let prover_proof_spec = ProofSpec::new(
+        prover_statements,
+        meta_statements.clone(),
+        setup_params.clone(),
+        None,
+    );
+    prover_proof_spec.validate().unwrap();
+    let verifier_proof_spec =
+        ProofSpec::new(verifier_statements, meta_statements, setup_params, None);
+    verifier_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(member, mem_wit));
+    witnesses.add(NonMembershipWit::new_as_witness(non_member, non_mem_wit));
+
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, None, Default::default())
+        .unwrap();
+}
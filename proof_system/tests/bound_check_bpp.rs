@@ -10,6 +10,7 @@ use bulletproofs_plus_plus::prelude::SetupParams;
 use std::time::Instant;
 
 use proof_system::{
+    error::ProofSystemError,
     prelude::{EqualWitnesses, MetaStatements, ProofSpec, Witness, WitnessRef, Witnesses},
     proof::Proof,
     statement::{
@@ -185,3 +186,149 @@ fn pok_of_bbs_plus_sig_and_bounded_message_using_bulletproofs_plus_plus() {
         false,
     );
 }
+
+#[test]
+fn bbs_plus_message_in_bpp_range_using_convenience_method() {
+    // Prove that a signed message like an age is in range with the statement and the witness equality
+    // added by `Statements::add_bbs_plus_message_in_bpp_range`
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let min = 18;
+    let max = 65;
+    let msg_count = 5;
+    let age_idx = 2;
+    let mut msgs = (0..msg_count)
+        .map(|i| Fr::from(100 + i as u64))
+        .collect::<Vec<_>>();
+    msgs[age_idx] = Fr::from(30u64);
+    let (sig_params, sig_keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    let bpp_setup_params =
+        SetupParams::<G1Affine>::new_for_arbitrary_range_proof::<Blake2b512>(b"test", 2, 64, 1);
+
+    let mut prover_statements = Statements::new();
+    let mut prover_meta_statements = MetaStatements::new();
+    let sig_stmt_idx = prover_statements.add(
+        PoKSignatureBBSG1ProverStmt::new_statement_from_params(sig_params.clone(), BTreeMap::new()),
+    );
+    let bound_stmt_idx = prover_statements
+        .add_bbs_plus_message_in_bpp_range(
+            &mut prover_meta_statements,
+            sig_stmt_idx,
+            age_idx,
+            min,
+            max,
+            bpp_setup_params.clone(),
+        )
+        .unwrap();
+    assert_eq!(bound_stmt_idx, 1);
+    let mut expected_meta_statements = MetaStatements::new();
+    expected_meta_statements.add_witness_equality(EqualWitnesses(BTreeSet::from([
+        (sig_stmt_idx, age_idx),
+        (bound_stmt_idx, 0),
+    ])));
+    assert_eq!(prover_meta_statements, expected_meta_statements);
+
+    let proof_spec_prover = ProofSpec::new(prover_statements, prover_meta_statements, vec![], None);
+    proof_spec_prover.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::BoundCheckBpp(msgs[age_idx]));
+
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec_prover,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let mut verifier_statements = Statements::new();
+    let mut verifier_meta_statements = MetaStatements::new();
+    let sig_stmt_idx =
+        verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+    verifier_statements
+        .add_bbs_plus_message_in_bpp_range(
+            &mut verifier_meta_statements,
+            sig_stmt_idx,
+            age_idx,
+            min,
+            max,
+            bpp_setup_params.clone(),
+        )
+        .unwrap();
+    let proof_spec_verifier = ProofSpec::new(
+        verifier_statements.clone(),
+        verifier_meta_statements,
+        vec![],
+        None,
+    );
+    proof_spec_verifier.validate().unwrap();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec_verifier.clone(),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+
+    // Bulletproofs++ verification needs the transcript so it's recreated when resuming from a checkpoint
+    let checkpoint = proof
+        .verify_challenge_phase(
+            proof_spec_verifier.clone(),
+            None,
+            Default::default(),
+            &[1u8; 32],
+        )
+        .unwrap();
+    proof
+        .verify_from_checkpoint::<StdRng, Blake2b512>(
+            &mut rng,
+            proof_spec_verifier,
+            checkpoint,
+            Default::default(),
+            &[1u8; 32],
+        )
+        .unwrap();
+
+    // Messages being revealed or statements that aren't BBS+ signatures are rejected
+    let mut statements = Statements::new();
+    statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params,
+        BTreeMap::from([(age_idx, msgs[age_idx])]),
+    ));
+    assert!(matches!(
+        statements.add_bbs_plus_message_in_bpp_range(
+            &mut MetaStatements::new(),
+            0,
+            age_idx,
+            min,
+            max,
+            bpp_setup_params.clone(),
+        ),
+        Err(ProofSystemError::WitnessAlreadyBeingRevealed(0, i)) if i == age_idx
+    ));
+    assert!(matches!(
+        verifier_statements.add_bbs_plus_message_in_bpp_range(
+            &mut MetaStatements::new(),
+            1,
+            0,
+            min,
+            max,
+            bpp_setup_params,
+        ),
+        Err(ProofSystemError::NotABBSPlusStatement(1))
+    ));
+}
@@ -9,6 +9,7 @@ use blake2::Blake2b512;
 use std::time::Instant;
 
 use proof_system::{
+    error::ProofSystemError,
     prelude::{
         EqualWitnesses, MetaStatements, Proof, ProofSpec, ProverConfig, VerifierConfig, Witness,
         WitnessRef, Witnesses,
@@ -26,7 +27,7 @@ use proof_system::{
         },
         bound_check_legogroth16::{
             BoundCheckLegoGroth16Prover as BoundCheckProverStmt,
-            BoundCheckLegoGroth16Verifier as BoundCheckVerifierStmt,
+            BoundCheckLegoGroth16Verifier as BoundCheckVerifierStmt, PreparedVerifyingKey,
         },
         Statements,
     },
@@ -163,6 +164,7 @@ macro_rules! gen_tests {
                     None,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -177,6 +179,7 @@ macro_rules! gen_tests {
             let config = ProverConfig::<Bls12_381> {
                 reuse_saver_proofs: None,
                 reuse_legogroth16_proofs: Some(m),
+                ..Default::default()
             };
             let proof = Proof::new::<StdRng, Blake2b512>(
                 &mut rng,
@@ -208,6 +211,7 @@ macro_rules! gen_tests {
                     None,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -260,6 +264,7 @@ macro_rules! gen_tests {
                     None,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .is_err());
@@ -303,6 +308,7 @@ macro_rules! gen_tests {
                     None,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .is_err());
@@ -507,6 +513,7 @@ macro_rules! gen_tests {
                         None,
                         VerifierConfig {
                             use_lazy_randomized_pairing_checks: Some(false),
+                            ..Default::default()
                         },
                     )
                     .unwrap();
@@ -528,6 +535,7 @@ macro_rules! gen_tests {
                 let config = ProverConfig::<Bls12_381> {
                     reuse_saver_proofs: None,
                     reuse_legogroth16_proofs: Some(m),
+                    ..Default::default()
                 };
                 let proof = Proof::new::<StdRng, Blake2b512>(
                     &mut rng,
@@ -668,3 +676,360 @@ fn pok_of_bbs_plus_sig_and_message_same_as_bound() {
         .verify::<StdRng, Blake2b512>(&mut rng, proof_spec_verifier, None, Default::default())
         .unwrap();
 }
+
+#[test]
+fn bound_check_verifier_statement_from_prepared_verifying_key() {
+    // Verifier statements created from a prepared verifying key verify the same as ones created from the
+    // verifying key
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let min = 100;
+    let max = 200;
+    let msg_count = 5;
+    let msgs = (0..msg_count)
+        .map(|i| Fr::from(min + 1 + i as u64))
+        .collect::<Vec<_>>();
+    let (sig_params, sig_keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    let snark_pk = generate_snark_srs_bound_check::<Bls12_381, _>(&mut rng).unwrap();
+    let prepared_vk = PreparedVerifyingKey::from(&snark_pk.vk);
+
+    // Bounds of 2 messages are checked using the same key
+    let msg_indices = [1, 3];
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    let mut meta_statements = MetaStatements::new();
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    for (i, msg_idx) in msg_indices.iter().enumerate() {
+        prover_statements.add(
+            BoundCheckProverStmt::new_statement_from_params(min, max, snark_pk.clone()).unwrap(),
+        );
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(0, *msg_idx), (1 + i, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+        witnesses.add(Witness::BoundCheckLegoGroth16(msgs[*msg_idx]));
+    }
+    let proof_spec_prover =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+    proof_spec_prover.validate().unwrap();
+
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec_prover,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let verifier_proof_spec = |use_prepared: bool, max: u64| {
+        let mut statements = Statements::new();
+        statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        for _ in msg_indices {
+            statements.add(if use_prepared {
+                BoundCheckVerifierStmt::new_statement_from_prepared_params(
+                    min,
+                    max,
+                    prepared_vk.clone(),
+                )
+                .unwrap()
+            } else {
+                BoundCheckVerifierStmt::new_statement_from_params(min, max, snark_pk.vk.clone())
+                    .unwrap()
+            });
+        }
+        let spec = ProofSpec::new(statements, meta_statements.clone(), vec![], None);
+        spec.validate().unwrap();
+        spec
+    };
+
+    test_serialization!(
+        Statements<Bls12_381>,
+        verifier_proof_spec(true, max).statements
+    );
+
+    // The prepared verifying key isn't serialized so the statements serialize the same either way
+    let mut bytes_prepared = vec![];
+    verifier_proof_spec(true, max)
+        .statements
+        .serialize_compressed(&mut bytes_prepared)
+        .unwrap();
+    let mut bytes = vec![];
+    verifier_proof_spec(false, max)
+        .statements
+        .serialize_compressed(&mut bytes)
+        .unwrap();
+    assert_eq!(bytes_prepared, bytes);
+
+    for use_prepared in [false, true] {
+        proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                verifier_proof_spec(use_prepared, max),
+                None,
+                Default::default(),
+            )
+            .unwrap();
+        // Different bounds than the ones the proof was created for
+        assert!(proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                verifier_proof_spec(use_prepared, max + 1),
+                None,
+                Default::default(),
+            )
+            .is_err());
+    }
+}
+
+#[test]
+fn open_ended_bound_checks() {
+    // Prove that one message is at least a minimum and another is less than a maximum without specifying
+    // the other bound
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msgs = [25u64, 1500, 7, 1_000_000]
+        .into_iter()
+        .map(Fr::from)
+        .collect::<Vec<_>>();
+    let (sig_params, sig_keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    let snark_pk = generate_snark_srs_bound_check::<Bls12_381, _>(&mut rng).unwrap();
+
+    // Message at index `age_idx` is >= `min_age` and the one at `amount_idx` is < `max_amount`
+    let age_idx = 0;
+    let min_age = 18;
+    let amount_idx = 1;
+    let max_amount = 2000;
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements
+        .add(BoundCheckProverStmt::new_statement_min_only(min_age, snark_pk.clone()).unwrap());
+    prover_statements
+        .add(BoundCheckProverStmt::new_statement_max_only(max_amount, snark_pk.clone()).unwrap());
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, age_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, amount_idx), (2, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let proof_spec_prover =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+    proof_spec_prover.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::BoundCheckLegoGroth16(msgs[age_idx]));
+    witnesses.add(Witness::BoundCheckLegoGroth16(msgs[amount_idx]));
+
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec_prover,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let verifier_proof_spec = |min_age: u64, max_amount: u64| {
+        let mut statements = Statements::new();
+        statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+            sig_params.clone(),
+            sig_keypair.public_key.clone(),
+            BTreeMap::new(),
+        ));
+        statements.add(
+            BoundCheckVerifierStmt::new_statement_min_only(min_age, snark_pk.vk.clone()).unwrap(),
+        );
+        statements.add(
+            BoundCheckVerifierStmt::new_statement_max_only(max_amount, snark_pk.vk.clone())
+                .unwrap(),
+        );
+        let spec = ProofSpec::new(statements, meta_statements.clone(), vec![], None);
+        spec.validate().unwrap();
+        spec
+    };
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(min_age, max_amount),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+
+    // Same as the open-ended statements but with the bounds given explicitly
+    let mut statements = Statements::new();
+    statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params.clone(),
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    statements.add(
+        BoundCheckVerifierStmt::new_statement_from_params(min_age, u64::MAX, snark_pk.vk.clone())
+            .unwrap(),
+    );
+    statements.add(
+        BoundCheckVerifierStmt::new_statement_from_params(0, max_amount, snark_pk.vk.clone())
+            .unwrap(),
+    );
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            ProofSpec::new(statements, meta_statements.clone(), vec![], None),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+
+    // Verifier expecting different bounds
+    for (min_age, max_amount) in [(21, max_amount), (min_age, 1000)] {
+        assert!(proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                verifier_proof_spec(min_age, max_amount),
+                None,
+                Default::default(),
+            )
+            .is_err());
+    }
+}
+
+#[test]
+fn verify_single_legogroth16_statement() {
+    // Verify the LegoGroth16 proof of one bound check statement in isolation from a proof with several statements
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let msgs = (0..msg_count)
+        .map(|i| Fr::from(101 + i as u64))
+        .collect::<Vec<_>>();
+    let (sig_params, sig_keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    let snark_pk = generate_snark_srs_bound_check::<Bls12_381, _>(&mut rng).unwrap();
+
+    let bounds = [(1, 100, 200), (3, 50, 1000)];
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    let mut meta_statements = MetaStatements::new();
+    for (i, (msg_idx, min, max)) in bounds.iter().enumerate() {
+        prover_statements.add(
+            BoundCheckProverStmt::new_statement_from_params(*min, *max, snark_pk.clone()).unwrap(),
+        );
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(0, *msg_idx), (1 + i, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+    }
+    let proof_spec_prover =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+    proof_spec_prover.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    for (msg_idx, _, _) in bounds.iter() {
+        witnesses.add(Witness::BoundCheckLegoGroth16(msgs[*msg_idx]));
+    }
+
+    let nonce = Some(b"test nonce".to_vec());
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec_prover,
+        witnesses,
+        nonce.clone(),
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    for (_, min, max) in bounds.iter() {
+        verifier_statements.add(
+            BoundCheckVerifierStmt::new_statement_from_params(*min, *max, snark_pk.vk.clone())
+                .unwrap(),
+        );
+    }
+    let proof_spec_verifier = ProofSpec::new(verifier_statements, meta_statements, vec![], None);
+    proof_spec_verifier.validate().unwrap();
+
+    let challenge = proof
+        .compute_challenge(
+            proof_spec_verifier.clone(),
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap();
+    for index in 1..=bounds.len() {
+        proof
+            .verify_single_legogroth16(index, proof_spec_verifier.clone(), &challenge)
+            .unwrap();
+    }
+
+    // A different challenge fails verification
+    assert!(proof
+        .verify_single_legogroth16(
+            1,
+            proof_spec_verifier.clone(),
+            &(challenge + Fr::from(1u64))
+        )
+        .is_err());
+
+    // The signature statement doesn't have a LegoGroth16 proof
+    assert!(matches!(
+        proof.verify_single_legogroth16(0, proof_spec_verifier.clone(), &challenge),
+        Err(ProofSystemError::NotALegoGroth16StatementProof)
+    ));
+
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec_verifier, nonce, Default::default())
+        .unwrap();
+}
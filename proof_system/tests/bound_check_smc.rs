@@ -4,6 +4,7 @@ use ark_std::rand::{prelude::StdRng, SeedableRng};
 use bbs_plus::prelude::{KeypairG2, SignatureG1, SignatureParamsG1};
 use blake2::Blake2b512;
 use proof_system::{
+    error::ProofSystemError,
     prelude::{
         bound_check_smc::SmcParamsAndCommitmentKey, EqualWitnesses, MetaStatements, ProofSpec,
         Statements, Witness, WitnessRef, Witnesses,
@@ -215,3 +216,23 @@ fn pok_of_bbs_plus_sig_and_bounded_message_using_set_membership_check_range_proo
         false,
     );
 }
+
+#[test]
+fn proof_size_estimate_not_supported() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let sig_params = SignatureParamsG1::<Bls12_381>::generate_using_rng(&mut rng, 5);
+    let (smc_setup_params, _) =
+        SmcParamsAndCommitmentKey::new::<_, Blake2b512>(&mut rng, b"test", 2);
+
+    let mut statements = Statements::new();
+    statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params,
+        BTreeMap::new(),
+    ));
+    statements.add(BoundCheckStmt::new_statement_from_params(10, 100, smc_setup_params).unwrap());
+    assert!(matches!(
+        ProofSpec::new(statements, MetaStatements::new(), vec![], None).estimate_proof_size(),
+        Err(ProofSystemError::ProofSizeEstimationNotSupported(1))
+    ));
+}
@@ -34,7 +34,7 @@ use std::{
 use test_utils::{
     accumulators::{setup_kb_universal_accum_given_domain, setup_positive_accum},
     kvac::bbdt16_mac_setup,
-    test_serialization,
+    test_proof_size_estimate, test_serialization,
 };
 use vb_accumulator::positive::Accumulator;
 
@@ -791,3 +791,105 @@ fn requesting_blind_mac() {
     let mac = blinded_mac.unblind(&blinding);
     mac.verify(&msgs, &sk, &mac_params).unwrap();
 }
+
+#[test]
+fn proof_size_estimate_for_macs_and_kv_accumulators() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let (msgs_1, params_1, _, mac_1) = bbdt16_mac_setup(&mut rng, 6);
+    let (mut msgs_2, params_2, sk_2, _) = bbdt16_mac_setup(&mut rng, 4);
+    msgs_2[0] = msgs_1[1];
+    let mac_2 = MAC::<G1Affine>::new(&mut rng, &msgs_2, &sk_2, &params_2).unwrap();
+
+    let (_, pos_accum_keypair, mut pos_accumulator, mut pos_state) = setup_positive_accum(&mut rng);
+    let mut domain = msgs_1.clone();
+    while domain.len() < 10 {
+        domain.push(Fr::rand(&mut rng));
+    }
+    let (_, uni_accum_keypair, mut uni_accumulator, mut uni_mem_state, mut uni_non_mem_state) =
+        setup_kb_universal_accum_given_domain(&mut rng, domain);
+
+    pos_accumulator = pos_accumulator
+        .add(msgs_1[1], &pos_accum_keypair.secret_key, &mut pos_state)
+        .unwrap();
+    let mem_1_wit = pos_accumulator
+        .get_membership_witness(&msgs_1[1], &pos_accum_keypair.secret_key, &pos_state)
+        .unwrap();
+    uni_accumulator = uni_accumulator
+        .add(
+            msgs_1[3],
+            &uni_accum_keypair.secret_key,
+            &mut uni_mem_state,
+            &mut uni_non_mem_state,
+        )
+        .unwrap();
+    let mem_2_wit = uni_accumulator
+        .get_membership_witness(&msgs_1[3], &uni_accum_keypair.secret_key, &uni_mem_state)
+        .unwrap();
+    let non_mem_wit = uni_accumulator
+        .get_non_membership_witness(
+            &msgs_1[4],
+            &uni_accum_keypair.secret_key,
+            &uni_non_mem_state,
+        )
+        .unwrap();
+
+    let revealed_msgs_1 = BTreeMap::from([(0, msgs_1[0]), (2, msgs_1[2])]);
+    let unrevealed_msgs_1 = msgs_1
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !revealed_msgs_1.contains_key(i))
+        .map(|(i, m)| (i, *m))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut statements = Statements::<Bls12_381>::new();
+    statements.add(PoKOfMAC::new_statement_from_params(
+        params_1,
+        revealed_msgs_1,
+    ));
+    statements.add(PoKOfMAC::new_statement_from_params(
+        params_2,
+        BTreeMap::new(),
+    ));
+    statements.add(VBAccumulatorMembershipKV::new(*pos_accumulator.value()));
+    statements.add(KBUniversalAccumulatorMembershipKV::new(
+        *uni_accumulator.mem_value(),
+    ));
+    statements.add(KBUniversalAccumulatorNonMembershipKV::new(
+        *uni_accumulator.non_mem_value(),
+    ));
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKOfBBDT16MAC::new_as_witness(mac_1, unrevealed_msgs_1));
+    witnesses.add(PoKOfBBDT16MAC::new_as_witness(
+        mac_2,
+        msgs_2.into_iter().enumerate().collect(),
+    ));
+    witnesses.add(MembershipWit::new_as_witness(msgs_1[1], mem_1_wit));
+    witnesses.add(KBUniMembership::new_as_witness(msgs_1[3], mem_2_wit));
+    witnesses.add(KBUniNonMembership::new_as_witness(msgs_1[4], non_mem_wit));
+
+    // Without witness equalities, all responses are present
+    test_proof_size_estimate!(
+        &mut rng,
+        ProofSpec::new(statements.clone(), MetaStatements::new(), vec![], None),
+        witnesses.clone()
+    );
+
+    // With witness equalities, the proofs of the 2nd MAC and the accumulator statements have partial responses
+    let mut meta_statements = MetaStatements::new();
+    for refs in [
+        vec![(0, 1), (1, 0), (2, 0)],
+        vec![(0, 3), (3, 0)],
+        vec![(0, 4), (4, 0)],
+    ] {
+        meta_statements.add_witness_equality(EqualWitnesses(
+            refs.into_iter().collect::<BTreeSet<WitnessRef>>(),
+        ));
+    }
+    test_proof_size_estimate!(
+        &mut rng,
+        ProofSpec::new(statements, meta_statements, vec![], None),
+        witnesses
+    );
+}
@@ -9,12 +9,16 @@ use ark_std::{
 };
 use blake2::Blake2b512;
 use proof_system::{
+    error::ProofSystemError,
     prelude::{EqualWitnesses, MetaStatement, MetaStatements, Witness, WitnessRef, Witnesses},
     proof::Proof,
     proof_spec::ProofSpec,
     setup_params::SetupParams,
     statement::{ped_comm::PedersenCommitment as PedersenCommitmentStmt, Statements},
+    statement_proof::StatementProof,
+    sub_protocols::schnorr::{verify_pedersen_opening, SchnorrProtocol},
 };
+use schnorr_pok::compute_random_oracle_challenge;
 
 use test_utils::test_serialization;
 
@@ -398,3 +402,262 @@ fn pok_of_knowledge_in_pedersen_commitment_and_equality_with_commitment_key_reus
         .verify::<StdRng, Blake2b512>(&mut rng, proof_spec, nonce, Default::default())
         .unwrap();
 }
+
+#[test]
+fn standalone_pok_of_pedersen_commitment_opening() {
+    // Prove knowledge of the opening of a Pedersen commitment without creating a composite proof
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let comm_key = (0..5)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let scalars = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    let commitment = G1Projective::msm_bigint(
+        &comm_key,
+        &scalars.iter().map(|s| s.into_bigint()).collect::<Vec<_>>(),
+    )
+    .into_affine();
+
+    let mut prover = SchnorrProtocol::new(0, &comm_key, commitment);
+    prover
+        .init(&mut rng, Default::default(), scalars.clone())
+        .unwrap();
+    let mut chal_bytes = vec![];
+    prover.challenge_contribution(&mut chal_bytes).unwrap();
+    let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+    let proof = prover.gen_proof_contribution_as_struct(&challenge).unwrap();
+
+    // The verifier recomputes the challenge from the proof's commitment to randomness
+    let mut chal_bytes = vec![];
+    SchnorrProtocol::compute_challenge_contribution(
+        &comm_key,
+        &commitment,
+        &proof.t,
+        &mut chal_bytes,
+    )
+    .unwrap();
+    let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+    verify_pedersen_opening(&comm_key, &commitment, &proof, &challenge).unwrap();
+
+    // Fails for a different commitment or challenge
+    let other_commitment = G1Projective::rand(&mut rng).into_affine();
+    assert!(verify_pedersen_opening(&comm_key, &other_commitment, &proof, &challenge).is_err());
+    assert!(verify_pedersen_opening(&comm_key, &commitment, &proof, &Fr::rand(&mut rng)).is_err());
+}
+
+#[cfg(feature = "lite-errors")]
+#[test]
+fn proof_incompatible_with_statement_gives_lite_error() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let bases = (0..5)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let scalars = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    let commitment = G1Projective::msm_bigint(
+        &bases,
+        &scalars.iter().map(|s| s.into_bigint()).collect::<Vec<_>>(),
+    )
+    .into_affine();
+
+    let mut statements = Statements::<Bls12_381>::new();
+    statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        bases, commitment,
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::PedersenCommitment(scalars.clone()));
+
+    let proof =
+        Proof::new::<StdRng, Blake2b512>(&mut rng, proof_spec, witnesses, None, Default::default())
+            .unwrap()
+            .0;
+
+    // Verify the proof of a commitment in group G1 against a statement about a commitment in group G2
+    let bases_g2 = (0..5)
+        .map(|_| G2Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let commitment_g2 = G2Projective::msm_bigint(
+        &bases_g2,
+        &scalars.iter().map(|s| s.into_bigint()).collect::<Vec<_>>(),
+    )
+    .into_affine();
+    let mut statements_g2 = Statements::<Bls12_381>::new();
+    statements_g2.add(PedersenCommitmentStmt::new_statement_from_params_g2(
+        bases_g2,
+        commitment_g2,
+    ));
+    let proof_spec_g2 = ProofSpec::new(statements_g2, MetaStatements::new(), vec![], None);
+
+    assert!(matches!(
+        proof.verify::<StdRng, Blake2b512>(&mut rng, proof_spec_g2, None, Default::default()),
+        Err(ProofSystemError::ProofIncompatibleWithStatementLite(0))
+    ));
+}
+
+#[test]
+fn pok_of_same_scalar_in_pedersen_commitments_in_different_groups() {
+    // Prove that a scalar committed in a G1 commitment is the same as the one committed in a G2 commitment
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let g1_key = (0..3)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let g2_key = (0..2)
+        .map(|_| G2Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let shared = Fr::rand(&mut rng);
+    let g1_scalars = vec![shared, Fr::rand(&mut rng), Fr::rand(&mut rng)];
+    let g2_scalars = vec![Fr::rand(&mut rng), shared];
+    let g1_commit = |scalars: &[Fr]| {
+        G1Projective::msm_bigint(
+            &g1_key,
+            &scalars.iter().map(|s| s.into_bigint()).collect::<Vec<_>>(),
+        )
+        .into_affine()
+    };
+    let g2_commit = |scalars: &[Fr]| {
+        G2Projective::msm_bigint(
+            &g2_key,
+            &scalars.iter().map(|s| s.into_bigint()).collect::<Vec<_>>(),
+        )
+        .into_affine()
+    };
+
+    let prove_and_verify = |rng: &mut StdRng, g2_scalars: Vec<Fr>| {
+        let mut statements = Statements::<Bls12_381>::new();
+        let mut meta_statements = MetaStatements::new();
+        let (g1_idx, g2_idx) = statements
+            .add_cross_group_pedersen_commitments(
+                &mut meta_statements,
+                g1_key.clone(),
+                g1_commit(&g1_scalars),
+                g2_key.clone(),
+                g2_commit(&g2_scalars),
+                vec![(0, 1)],
+            )
+            .unwrap();
+        assert_eq!((g1_idx, g2_idx), (0, 1));
+        let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+        proof_spec.validate().unwrap();
+
+        let mut witnesses = Witnesses::new();
+        witnesses.add(Witness::PedersenCommitment(g1_scalars.clone()));
+        witnesses.add(Witness::PedersenCommitment(g2_scalars));
+
+        let nonce = Some(b"test nonce".to_vec());
+        let proof = Proof::new::<StdRng, Blake2b512>(
+            rng,
+            proof_spec.clone(),
+            witnesses,
+            nonce.clone(),
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+        // The response for the shared scalar is only in the proof of the G1 commitment
+        assert!(matches!(
+            &proof.statement_proofs[1],
+            StatementProof::PedersenCommitmentG2Partial(p) if !p.response.responses.contains_key(&1)
+        ));
+        proof.verify::<StdRng, Blake2b512>(rng, proof_spec, nonce, Default::default())
+    };
+
+    prove_and_verify(&mut rng, g2_scalars.clone()).unwrap();
+
+    // Different scalars in the commitments fail to verify
+    let mut different_scalars = g2_scalars;
+    different_scalars[1] = Fr::rand(&mut rng);
+    assert!(prove_and_verify(&mut rng, different_scalars).is_err());
+
+    // Indices of scalars not in the commitments are rejected
+    assert!(matches!(
+        Statements::<Bls12_381>::new().add_cross_group_pedersen_commitments(
+            &mut MetaStatements::new(),
+            g1_key.clone(),
+            g1_commit(&g1_scalars),
+            g2_key.clone(),
+            G2Projective::rand(&mut rng).into_affine(),
+            vec![(0, 2)],
+        ),
+        Err(ProofSystemError::InvalidWitnessEquality)
+    ));
+}
+
+#[test]
+fn committed_value_is_sum_of_committed_values() {
+    // Prove that the value committed in one Pedersen commitment is the sum of the values committed in 2 others
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let comm_key = (0..2)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let commit = |value: Fr, randomness: Fr| {
+        G1Projective::msm_bigint(&comm_key, &[value.into_bigint(), randomness.into_bigint()])
+            .into_affine()
+    };
+
+    let a = Fr::rand(&mut rng);
+    let b = Fr::rand(&mut rng);
+    let randomness = (0..3).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+
+    let check = |rng: &mut StdRng, c: Fr| {
+        let values = [a, b, c];
+        let commitments = values
+            .iter()
+            .zip(randomness.iter())
+            .map(|(v, r)| commit(*v, *r))
+            .collect::<Vec<_>>();
+
+        let mut statements = Statements::<Bls12_381>::new();
+        for comm in &commitments {
+            statements.add(PedersenCommitmentStmt::new_statement_from_params(
+                comm_key.clone(),
+                *comm,
+            ));
+        }
+        let mut meta_statements = MetaStatements::new();
+        let sum_idx = statements
+            .add_sum_relation(&mut meta_statements, (2, 0), vec![(0, 0), (1, 0)])
+            .unwrap();
+        assert_eq!(sum_idx, 3);
+        assert_eq!(meta_statements.len(), 3);
+
+        let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+        proof_spec.validate().unwrap();
+
+        let mut witnesses = Witnesses::new();
+        for (v, r) in values.iter().zip(randomness.iter()) {
+            witnesses.add(Witness::PedersenCommitment(vec![*v, *r]));
+        }
+        witnesses.add(Witness::PedersenCommitment(values.to_vec()));
+
+        let proof = Proof::new::<StdRng, Blake2b512>(
+            rng,
+            proof_spec.clone(),
+            witnesses,
+            None,
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+        proof.verify::<StdRng, Blake2b512>(rng, proof_spec, None, Default::default())
+    };
+
+    // c = a + b
+    check(&mut rng, a + b).unwrap();
+
+    // Sum is wrong so the sum relation's proof, whose responses are shared with the commitments' proofs,
+    // doesn't verify
+    assert!(matches!(
+        check(&mut rng, a + b + Fr::from(1u64)),
+        Err(ProofSystemError::SchnorrProofContributionFailed(3, _))
+    ));
+
+    // At least one input is needed
+    assert!(matches!(
+        Statements::<Bls12_381>::new().add_sum_relation(&mut MetaStatements::new(), (0, 0), vec![]),
+        Err(ProofSystemError::InvalidWitnessEquality)
+    ));
+}
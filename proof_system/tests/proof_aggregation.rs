@@ -6,6 +6,7 @@ use ark_std::{
 use blake2::Blake2b512;
 use legogroth16::aggregation::srs;
 use proof_system::{
+    error::ProofSystemError,
     prelude::{
         generate_snark_srs_bound_check, EqualWitnesses, MetaStatements, ProofSpec, SnarkpackSRS,
         VerifierConfig, Witness, WitnessRef, Witnesses,
@@ -24,6 +25,7 @@ use proof_system::{
         saver::{SaverProver as SaverProverStmt, SaverVerifier as SaverVerifierStmt},
         Statements,
     },
+    statement_proof::StatementProof,
     witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
 };
 use saver::setup::{setup_for_groth16, ChunkedCommitmentGens, EncryptionGens};
@@ -205,6 +207,7 @@ fn pok_of_bbs_plus_sigs_and_verifiable_encryption_with_saver_aggregation() {
             None,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -214,6 +217,36 @@ fn pok_of_bbs_plus_sigs_and_verifiable_encryption_with_saver_aggregation() {
         start.elapsed()
     );
 
+    // Verifying the ciphertexts in chunks accepts the same proofs as verifying them at once and rejects
+    // the same proofs as well
+    let mut bad_proof = updated_proof.clone();
+    match &mut bad_proof.statement_proofs[3] {
+        StatementProof::SaverWithAggregation(p) => {
+            p.ciphertext.enc_chunks[0] = p.ciphertext.enc_chunks[1]
+        }
+        _ => panic!("unexpected statement proof"),
+    }
+    for saver_batch_chunk in [None, Some(1), Some(3), Some(8), Some(20)] {
+        let config = VerifierConfig {
+            use_lazy_randomized_pairing_checks: Some(false),
+            saver_batch_chunk,
+            ..Default::default()
+        };
+        updated_proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                verifier_proof_spec.clone(),
+                None,
+                config.clone(),
+            )
+            .unwrap();
+        assert!(bad_proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec.clone(), None, config)
+            .is_err());
+    }
+
     let start = Instant::now();
     updated_proof
         .verify::<StdRng, Blake2b512>(
@@ -222,6 +255,7 @@ fn pok_of_bbs_plus_sigs_and_verifiable_encryption_with_saver_aggregation() {
             None,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(true),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -402,6 +436,7 @@ fn pok_of_bbs_plus_sigs_and_bound_check_with_aggregation() {
             None,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -419,6 +454,7 @@ fn pok_of_bbs_plus_sigs_and_bound_check_with_aggregation() {
             None,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(true),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -428,3 +464,319 @@ fn pok_of_bbs_plus_sigs_and_bound_check_with_aggregation() {
         start.elapsed()
     );
 }
+
+#[test]
+fn incompatible_aggregation_spec_is_rejected() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 4;
+    let msgs = (1..=msg_count)
+        .map(|i| Fr::from(100u64 + i * 10_u64))
+        .collect::<Vec<_>>();
+    let (params, keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    let snark_pk = generate_snark_srs_bound_check::<Bls12_381, _>(&mut rng).unwrap();
+
+    let min = 100;
+    let max = 200;
+    let bounded_msg_indices = vec![0, 2];
+
+    let srs = srs::setup_fake_srs::<Bls12_381, _>(&mut rng, 100);
+    let (prover_srs, ver_srs) = srs.specialize(bounded_msg_indices.len() as u32);
+    let (_, bigger_ver_srs) = srs.specialize(4);
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        params.clone(),
+        BTreeMap::new(),
+    ));
+    let mut stmts_to_aggr = BTreeSet::new();
+    for _ in 0..bounded_msg_indices.len() {
+        let i = prover_statements
+            .add(BoundCheckProverStmt::new_statement_from_params_ref(min, max, 0).unwrap());
+        stmts_to_aggr.insert(i);
+    }
+
+    let mut meta_statements = MetaStatements::new();
+    for (i, m_idx) in bounded_msg_indices.iter().enumerate() {
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(0, *m_idx), (1 + i, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+    }
+
+    let prover_proof_spec = ProofSpec::new_with_aggregation(
+        prover_statements,
+        meta_statements.clone(),
+        vec![SetupParams::LegoSnarkProvingKey(snark_pk.clone())],
+        None,
+        None,
+        Some(vec![stmts_to_aggr.clone()]),
+        Some(SnarkpackSRS::ProverSrs(prover_srs.clone())),
+    );
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    for i in &bounded_msg_indices {
+        witnesses.add(Witness::BoundCheckLegoGroth16(msgs[*i]));
+    }
+
+    let (proof, _) = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap();
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        params,
+        keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    for _ in 0..bounded_msg_indices.len() {
+        verifier_statements
+            .add(BoundCheckVerifierStmt::new_statement_from_params_ref(min, max, 0).unwrap());
+    }
+
+    let verifier_proof_spec = |aggregate_groth16, aggregate_legogroth16, ver_srs| {
+        ProofSpec::new_with_aggregation(
+            verifier_statements.clone(),
+            meta_statements.clone(),
+            vec![SetupParams::LegoSnarkVerifyingKey(snark_pk.vk.clone())],
+            None,
+            aggregate_groth16,
+            aggregate_legogroth16,
+            Some(SnarkpackSRS::VerifierSrs(ver_srs)),
+        )
+    };
+
+    let updated_proof = proof.for_aggregate();
+
+    // Correct spec verifies
+    updated_proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec(None, Some(vec![stmts_to_aggr.clone()]), ver_srs.clone()),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+
+    // Signature statement included in LegoGroth16 aggregation
+    let res = updated_proof.clone().verify::<StdRng, Blake2b512>(
+        &mut rng,
+        verifier_proof_spec(None, Some(vec![BTreeSet::from([0, 1])]), ver_srs.clone()),
+        None,
+        Default::default(),
+    );
+    assert!(matches!(
+        res,
+        Err(ProofSystemError::AggregateStatementNotLegoGroth16(0))
+    ));
+
+    // Bound check statements included in Groth16 aggregation
+    let res = updated_proof.clone().verify::<StdRng, Blake2b512>(
+        &mut rng,
+        verifier_proof_spec(Some(vec![stmts_to_aggr.clone()]), None, ver_srs.clone()),
+        None,
+        Default::default(),
+    );
+    assert!(matches!(
+        res,
+        Err(ProofSystemError::AggregateStatementNotSaver(1))
+    ));
+
+    // Non-existent statement included in aggregation
+    let res = updated_proof.clone().verify::<StdRng, Blake2b512>(
+        &mut rng,
+        verifier_proof_spec(None, Some(vec![BTreeSet::from([1, 5])]), ver_srs.clone()),
+        None,
+        Default::default(),
+    );
+    assert!(matches!(
+        res,
+        Err(ProofSystemError::InvalidAggregateStatementIndex(5))
+    ));
+
+    // SRS for a different number of proofs
+    let res = updated_proof.clone().verify::<StdRng, Blake2b512>(
+        &mut rng,
+        verifier_proof_spec(None, Some(vec![stmts_to_aggr.clone()]), bigger_ver_srs),
+        None,
+        Default::default(),
+    );
+    assert!(matches!(
+        res,
+        Err(ProofSystemError::IncompatibleSnarkpackSrs(2, 4))
+    ));
+
+    // Aggregation requested without any SRS
+    let mut spec_without_srs =
+        verifier_proof_spec(None, Some(vec![stmts_to_aggr.clone()]), ver_srs.clone());
+    spec_without_srs.snark_aggregation_srs = None;
+    let res = updated_proof.clone().verify::<StdRng, Blake2b512>(
+        &mut rng,
+        spec_without_srs,
+        None,
+        Default::default(),
+    );
+    assert!(matches!(
+        res,
+        Err(ProofSystemError::SnarkpackSrsNotProvided)
+    ));
+
+    // Prover's SRS given to the verifier. This fails before the statement proofs, which aren't prepared
+    // for aggregation, are looked at
+    let mut spec_with_prover_srs =
+        verifier_proof_spec(None, Some(vec![stmts_to_aggr.clone()]), ver_srs.clone());
+    spec_with_prover_srs.snark_aggregation_srs = Some(SnarkpackSRS::ProverSrs(prover_srs));
+    let res = proof.clone().verify::<StdRng, Blake2b512>(
+        &mut rng,
+        spec_with_prover_srs,
+        None,
+        Default::default(),
+    );
+    assert!(matches!(
+        res,
+        Err(ProofSystemError::SnarkpackSrsNotProvided)
+    ));
+
+    // Statement proofs not prepared for aggregation
+    let res = proof.verify::<StdRng, Blake2b512>(
+        &mut rng,
+        verifier_proof_spec(None, Some(vec![stmts_to_aggr]), ver_srs),
+        None,
+        Default::default(),
+    );
+    assert!(matches!(
+        res,
+        Err(ProofSystemError::StatementProofNotForAggregation(1))
+    ));
+}
+
+#[test]
+fn aggregated_statement_indices_match_spec() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 4;
+    let msgs = (1..=msg_count)
+        .map(|i| Fr::from(100u64 + i * 10_u64))
+        .collect::<Vec<_>>();
+    let (params, keypair, sig) = bbs_plus_sig_setup_given_messages(&mut rng, &msgs);
+
+    let snark_pk = generate_snark_srs_bound_check::<Bls12_381, _>(&mut rng).unwrap();
+
+    let min = 100;
+    let max = 200;
+    let bounded_msg_indices = vec![1, 3];
+
+    let srs = srs::setup_fake_srs::<Bls12_381, _>(&mut rng, 100);
+    let (prover_srs, ver_srs) = srs.specialize(bounded_msg_indices.len() as u32);
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        params.clone(),
+        BTreeMap::new(),
+    ));
+    let mut stmts_to_aggr = BTreeSet::new();
+    for _ in 0..bounded_msg_indices.len() {
+        let i = prover_statements
+            .add(BoundCheckProverStmt::new_statement_from_params_ref(min, max, 0).unwrap());
+        stmts_to_aggr.insert(i);
+    }
+
+    let mut meta_statements = MetaStatements::new();
+    for (i, m_idx) in bounded_msg_indices.iter().enumerate() {
+        meta_statements.add_witness_equality(EqualWitnesses(
+            vec![(0, *m_idx), (1 + i, 0)]
+                .into_iter()
+                .collect::<BTreeSet<WitnessRef>>(),
+        ));
+    }
+
+    let prover_proof_spec = ProofSpec::new_with_aggregation(
+        prover_statements,
+        meta_statements.clone(),
+        vec![SetupParams::LegoSnarkProvingKey(snark_pk.clone())],
+        None,
+        None,
+        Some(vec![stmts_to_aggr.clone()]),
+        Some(SnarkpackSRS::ProverSrs(prover_srs)),
+    );
+    prover_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    for i in &bounded_msg_indices {
+        witnesses.add(Witness::BoundCheckLegoGroth16(msgs[*i]));
+    }
+
+    let (proof, _) = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap();
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        params,
+        keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    for _ in 0..bounded_msg_indices.len() {
+        verifier_statements
+            .add(BoundCheckVerifierStmt::new_statement_from_params_ref(min, max, 0).unwrap());
+    }
+    let verifier_proof_spec = ProofSpec::new_with_aggregation(
+        verifier_statements,
+        meta_statements,
+        vec![SetupParams::LegoSnarkVerifyingKey(snark_pk.vk.clone())],
+        None,
+        None,
+        Some(vec![stmts_to_aggr.clone()]),
+        Some(SnarkpackSRS::VerifierSrs(ver_srs)),
+    );
+    verifier_proof_spec.validate().unwrap();
+
+    let updated_proof = proof.for_aggregate();
+    updated_proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+
+    // The aggregated statements are the ones the spec asked to aggregate
+    let (groth16, legogroth16) = updated_proof.aggregated_statement_indices();
+    assert!(groth16.is_empty());
+    assert_eq!(legogroth16, vec![stmts_to_aggr]);
+    assert_eq!(
+        legogroth16,
+        verifier_proof_spec.aggregate_legogroth16.unwrap()
+    );
+
+    // A proof without aggregation has no aggregated statements
+    let proof = Proof::<Bls12_381> {
+        statement_proofs: vec![],
+        aggregated_groth16: None,
+        aggregated_legogroth16: None,
+    };
+    assert_eq!(proof.aggregated_statement_indices(), (vec![], vec![]));
+}
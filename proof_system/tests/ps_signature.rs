@@ -41,7 +41,7 @@ use proof_system::{
     },
     witness::{Membership as MembershipWit, NonMembership as NonMembershipWit, PoKPSSignature},
 };
-use test_utils::{accumulators::*, test_serialization};
+use test_utils::{accumulators::*, test_proof_size_estimate, test_serialization};
 
 #[test]
 fn pok_of_3_ps_sig_and_message_equality() {
@@ -213,6 +213,7 @@ fn pok_of_3_ps_sig_and_message_equality() {
             None,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .is_err());
@@ -235,6 +236,7 @@ fn pok_of_3_ps_sig_and_message_equality() {
             Some(b"random...".to_vec()),
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .is_err());
@@ -265,6 +267,7 @@ fn pok_of_3_ps_sig_and_message_equality() {
             nonce,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -392,6 +395,7 @@ fn pok_of_ps_sig_and_accumulator() {
             nonce.clone(),
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -436,6 +440,7 @@ fn pok_of_ps_sig_and_accumulator() {
             nonce.clone(),
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .is_err());
@@ -486,6 +491,7 @@ fn pok_of_ps_sig_and_accumulator() {
             nonce.clone(),
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .is_err());
@@ -591,6 +597,7 @@ fn pok_of_ps_sig_and_accumulator() {
             nonce.clone(),
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -688,6 +695,7 @@ fn pok_of_ps_sig_and_accumulator() {
             nonce.clone(),
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -812,6 +820,7 @@ fn pok_of_ps_sig_and_accumulator() {
             nonce,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -1598,6 +1607,7 @@ fn pok_of_ps_sig_with_reusing_setup_params() {
             None,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -1683,3 +1693,112 @@ fn proof_spec_validation() {
     let ps_3 = ProofSpec::new(statements_3, meta_statements_3, vec![], None);
     assert!(ps_3.validate().is_err());
 }
+
+#[test]
+fn hidden_message_count_of_ps_sig_proof() {
+    // The number of hidden messages can be read from a signature's proof without the proof spec
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 6;
+    let (secret_key, public_key, sig_params, msgs) =
+        test_setup::<Bls12_381, Blake2b512, _>(&mut rng, msg_count);
+    let sig = Signature::<Bls12_381>::new(&mut rng, &msgs, &secret_key, &sig_params).unwrap();
+
+    let revealed_indices = BTreeSet::from([1, 4, 5]);
+    let mut revealed_msgs = BTreeMap::new();
+    let mut unrevealed_msgs = BTreeMap::new();
+    for (i, m) in msgs.iter().enumerate() {
+        if revealed_indices.contains(&i) {
+            revealed_msgs.insert(i, *m);
+        } else {
+            unrevealed_msgs.insert(i, *m);
+        }
+    }
+
+    let mut statements = Statements::new();
+    statements.add(PoKPSSignatureStatement::new_statement_from_params(
+        sig_params,
+        public_key,
+        revealed_msgs,
+    ));
+    let proof_spec = ProofSpec::new(statements, MetaStatements::new(), vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKPSSignature::new_as_witness(sig, unrevealed_msgs));
+
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        proof_spec.clone(),
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    assert_eq!(proof.statement_proofs[0].hidden_message_count(), Some(3));
+
+    proof
+        .verify::<StdRng, Blake2b512>(&mut rng, proof_spec, None, Default::default())
+        .unwrap();
+}
+
+#[test]
+fn proof_size_estimate_for_ps_sig() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 6;
+    let (secret_key, public_key, sig_params, msgs) =
+        test_setup::<Bls12_381, Blake2b512, _>(&mut rng, msg_count);
+    let sig = Signature::<Bls12_381>::new(&mut rng, &msgs, &secret_key, &sig_params).unwrap();
+
+    let revealed_msgs = BTreeMap::from([(1, msgs[1]), (4, msgs[4])]);
+    let unrevealed_msgs = msgs
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !revealed_msgs.contains_key(i))
+        .map(|(i, m)| (i, *m))
+        .collect::<BTreeMap<_, _>>();
+
+    let bases = (0..3)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let scalars = vec![msgs[2], Fr::rand(&mut rng), Fr::rand(&mut rng)];
+    let commitment = G1Projective::msm_unchecked(&bases, &scalars).into_affine();
+
+    let mut statements = Statements::new();
+    statements.add(PoKPSSignatureStatement::new_statement_from_params(
+        sig_params,
+        public_key,
+        revealed_msgs,
+    ));
+    statements.add(PedersenCommitmentStmt::new_statement_from_params(
+        bases, commitment,
+    ));
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKPSSignature::new_as_witness(sig, unrevealed_msgs));
+    witnesses.add(Witness::PedersenCommitment(scalars));
+
+    // Without witness equalities, all responses are present
+    test_proof_size_estimate!(
+        &mut rng,
+        ProofSpec::new(statements.clone(), MetaStatements::new(), vec![], None),
+        witnesses.clone()
+    );
+
+    // The proof of the signature still has all the responses but the Pedersen commitment's proof skips the
+    // response for the message
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, 2), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+    test_proof_size_estimate!(
+        &mut rng,
+        ProofSpec::new(statements, meta_statements, vec![], None),
+        witnesses
+    );
+}
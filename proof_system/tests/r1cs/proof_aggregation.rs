@@ -191,6 +191,7 @@ fn pok_of_bbs_plus_sig_and_multiple_set_membership_proofs_aggregated() {
             None,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: None,
+                ..Default::default()
             },
         )
         .unwrap();
@@ -203,6 +204,7 @@ fn pok_of_bbs_plus_sig_and_multiple_set_membership_proofs_aggregated() {
             None,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(true),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -214,6 +216,7 @@ fn pok_of_bbs_plus_sig_and_multiple_set_membership_proofs_aggregated() {
             None,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
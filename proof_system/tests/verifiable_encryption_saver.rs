@@ -9,8 +9,8 @@ use blake2::Blake2b512;
 use dock_crypto_utils::randomized_pairing_check::RandomizedPairingChecker;
 use proof_system::{
     prelude::{
-        generate_snark_srs_bound_check, EqualWitnesses, MetaStatements, ProofSpec, ProverConfig,
-        VerifierConfig, Witness, WitnessRef, Witnesses,
+        generate_snark_srs_bound_check, EqualWitnesses, MetaStatements, ProofSpec,
+        ProofSystemError, ProverConfig, VerifierConfig, Witness, WitnessRef, Witnesses,
     },
     proof::Proof,
     prover::{OldLegoGroth16Proof, OldSaverProof},
@@ -31,6 +31,7 @@ use proof_system::{
         saver::{SaverProver as SaverProverStmt, SaverVerifier as SaverVerifierStmt},
         Statements,
     },
+    sub_protocols::saver::{re_randomize_saver_ciphertext, SaverCiphertextLinkProof},
     witness::{
         PoKBBSSignature23G1 as PoKSignatureBBS23G1Wit, PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
     },
@@ -252,6 +253,7 @@ macro_rules! gen_tests {
                     None,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -270,6 +272,7 @@ macro_rules! gen_tests {
                     None,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(true),
+                        ..Default::default()
                     },
                 )
                 .unwrap();
@@ -326,6 +329,7 @@ macro_rules! gen_tests {
             let config = ProverConfig::<Bls12_381> {
                 reuse_saver_proofs: Some(m),
                 reuse_legogroth16_proofs: None,
+                ..Default::default()
             };
             let proof = Proof::new::<StdRng, Blake2b512>(
                 &mut rng,
@@ -438,6 +442,7 @@ macro_rules! gen_tests {
                     None,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .is_err());
@@ -621,6 +626,7 @@ macro_rules! gen_tests {
                         None,
                         VerifierConfig {
                             use_lazy_randomized_pairing_checks: Some(false),
+                            ..Default::default()
                         },
                     )
                     .unwrap();
@@ -640,6 +646,7 @@ macro_rules! gen_tests {
                         None,
                         VerifierConfig {
                             use_lazy_randomized_pairing_checks: Some(true),
+                            ..Default::default()
                         },
                     )
                     .unwrap();
@@ -705,6 +712,7 @@ macro_rules! gen_tests {
                 let config = ProverConfig::<Bls12_381> {
                     reuse_saver_proofs: Some(m),
                     reuse_legogroth16_proofs: None,
+                    ..Default::default()
                 };
                 let proof = Proof::new::<StdRng, Blake2b512>(
                     &mut rng,
@@ -769,6 +777,265 @@ gen_tests!(
     PoKSignatureBBS23G1Wit
 );
 
+#[test]
+fn decryption_of_verifiably_encrypted_message_from_proof() {
+    // Decryptor decrypts the verifiably encrypted message and gives a proof of correct decryption to an auditor
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count);
+
+    let enc_gens = EncryptionGens::<Bls12_381>::new_using_rng(&mut rng);
+    let chunked_comm_gens = ChunkedCommitmentGens::<G1Affine>::new_using_rng(&mut rng);
+    let chunk_bit_size = 16;
+    let (snark_pk, sk, ek, dk) = setup_for_groth16(&mut rng, chunk_bit_size, &enc_gens).unwrap();
+
+    let enc_msg_idx = 2;
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(
+        SaverProverStmt::new_statement_from_params(
+            chunk_bit_size,
+            enc_gens.clone(),
+            chunked_comm_gens.clone(),
+            ek.clone(),
+            snark_pk.clone(),
+        )
+        .unwrap(),
+    );
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, enc_msg_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let prover_proof_spec =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+    prover_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::Saver(msgs[enc_msg_idx]));
+
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(
+        SaverVerifierStmt::new_statement_from_params(
+            chunk_bit_size,
+            enc_gens.clone(),
+            chunked_comm_gens,
+            ek,
+            snark_pk.pk.vk.clone(),
+        )
+        .unwrap(),
+    );
+    let verifier_proof_spec = ProofSpec::new(verifier_statements, meta_statements, vec![], None);
+    verifier_proof_spec.validate().unwrap();
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, None, Default::default())
+        .unwrap();
+
+    // Decryptor decrypts
+    let prepared_dk = PreparedDecryptionKey::from(dk);
+    let prepared_enc_gens = PreparedEncryptionGens::from(enc_gens);
+    let (decrypted, nu) = proof
+        .decrypt_saver_ciphertext(
+            1,
+            &sk,
+            prepared_dk.clone(),
+            &snark_pk.pk.vk,
+            chunk_bit_size,
+        )
+        .unwrap();
+    assert_eq!(decrypted, msgs[enc_msg_idx]);
+
+    // Auditor checks the decryption without the decryptor's secret key
+    proof
+        .verify_saver_ciphertext_decryption(
+            1,
+            &decrypted,
+            &nu,
+            chunk_bit_size,
+            prepared_dk.clone(),
+            &snark_pk.pk.vk,
+            prepared_enc_gens.clone(),
+        )
+        .unwrap();
+
+    // A different message doesn't verify with the decryption proof
+    assert!(proof
+        .verify_saver_ciphertext_decryption(
+            1,
+            &msgs[0],
+            &nu,
+            chunk_bit_size,
+            prepared_dk.clone(),
+            &snark_pk.pk.vk,
+            prepared_enc_gens,
+        )
+        .is_err());
+
+    // The statement at index 0 isn't a verifiable encryption
+    assert!(matches!(
+        proof.decrypt_saver_ciphertext(
+            0,
+            &sk,
+            prepared_dk,
+            &snark_pk.pk.vk,
+            chunk_bit_size
+        ),
+        Err(ProofSystemError::NotASaverStatementProof)
+    ));
+}
+
+#[test]
+fn re_randomize_verifiably_encrypted_message() {
+    // Holder re-randomizes the ciphertext from a proof so that the new ciphertext can't be linked to the
+    // proof but proves to someone knowing both ciphertexts that they encrypt the same message
+    let mut rng = StdRng::seed_from_u64(0u64);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count);
+
+    let enc_gens = EncryptionGens::<Bls12_381>::new_using_rng(&mut rng);
+    let chunked_comm_gens = ChunkedCommitmentGens::<G1Affine>::new_using_rng(&mut rng);
+    let chunk_bit_size = 16;
+    let (snark_pk, sk, ek, dk) = setup_for_groth16(&mut rng, chunk_bit_size, &enc_gens).unwrap();
+
+    let enc_msg_idx = 2;
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(
+        SaverProverStmt::new_statement_from_params(
+            chunk_bit_size,
+            enc_gens.clone(),
+            chunked_comm_gens.clone(),
+            ek.clone(),
+            snark_pk.clone(),
+        )
+        .unwrap(),
+    );
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, enc_msg_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let prover_proof_spec =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+    prover_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::Saver(msgs[enc_msg_idx]));
+
+    let proof = Proof::new::<StdRng, Blake2b512>(
+        &mut rng,
+        prover_proof_spec,
+        witnesses,
+        None,
+        Default::default(),
+    )
+    .unwrap()
+    .0;
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(
+        SaverVerifierStmt::new_statement_from_params(
+            chunk_bit_size,
+            enc_gens.clone(),
+            chunked_comm_gens,
+            ek.clone(),
+            snark_pk.pk.vk.clone(),
+        )
+        .unwrap(),
+    );
+    let verifier_proof_spec = ProofSpec::new(verifier_statements, meta_statements, vec![], None);
+    verifier_proof_spec.validate().unwrap();
+
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(&mut rng, verifier_proof_spec, None, Default::default())
+        .unwrap();
+
+    let ct = proof.get_saver_ciphertext_and_proof(1).unwrap().0;
+    let randomness = Fr::rand(&mut rng);
+    let (new_ct, link_proof) =
+        re_randomize_saver_ciphertext::<_, _, Blake2b512>(&mut rng, ct, &randomness, &ek).unwrap();
+    assert_ne!(&new_ct, ct);
+    link_proof.verify::<Blake2b512>(ct, &new_ct, &ek).unwrap();
+
+    test_serialization!(SaverCiphertextLinkProof<Bls12_381>, link_proof);
+
+    // The new ciphertext decrypts to the same message with the same key
+    let (decrypted, nu) = new_ct
+        .decrypt_given_groth16_vk(&sk, dk.clone(), &snark_pk.pk.vk, chunk_bit_size)
+        .unwrap();
+    assert_eq!(decrypted, msgs[enc_msg_idx]);
+    new_ct
+        .verify_decryption_given_groth16_vk(
+            &decrypted,
+            &nu,
+            chunk_bit_size,
+            dk,
+            &snark_pk.pk.vk,
+            enc_gens,
+        )
+        .unwrap();
+
+    // The link proof doesn't verify for a different ciphertext even if it encrypts the same message
+    let randomness = Fr::rand(&mut rng);
+    let (other_ct, _) =
+        re_randomize_saver_ciphertext::<_, _, Blake2b512>(&mut rng, ct, &randomness, &ek).unwrap();
+    assert!(matches!(
+        link_proof.verify::<Blake2b512>(ct, &other_ct, &ek),
+        Err(ProofSystemError::SaverCiphertextLinkProofFailed)
+    ));
+    assert!(matches!(
+        link_proof.verify::<Blake2b512>(&other_ct, &new_ct, &ek),
+        Err(ProofSystemError::SaverCiphertextLinkProofFailed)
+    ));
+}
+
 #[test]
 fn pok_of_bbs_plus_sig_and_verifiable_encryption_for_different_decryptors() {
     // Prove knowledge of BBS+ signature and a certain messages are verifiably encrypted for 2 different decryptors
@@ -1121,6 +1388,7 @@ fn pok_of_bbs_plus_sig_and_verifiable_encryption_for_different_decryptors() {
                 None,
                 VerifierConfig {
                     use_lazy_randomized_pairing_checks: Some(false),
+                    ..Default::default()
                 },
             )
             .unwrap();
@@ -1138,6 +1406,7 @@ fn pok_of_bbs_plus_sig_and_verifiable_encryption_for_different_decryptors() {
                 None,
                 VerifierConfig {
                     use_lazy_randomized_pairing_checks: Some(false),
+                    ..Default::default()
                 },
             )
             .unwrap();
@@ -1199,6 +1468,7 @@ fn pok_of_bbs_plus_sig_and_verifiable_encryption_for_different_decryptors() {
         let config = ProverConfig::<Bls12_381> {
             reuse_saver_proofs: Some(m),
             reuse_legogroth16_proofs: None,
+            ..Default::default()
         };
         let proof = Proof::new::<StdRng, Blake2b512>(
             &mut rng,
@@ -1444,6 +1714,7 @@ fn pok_of_bbs_plus_sig_and_bounded_message_and_verifiable_encryption() {
             None,
             VerifierConfig {
                 use_lazy_randomized_pairing_checks: Some(false),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -1452,6 +1723,83 @@ fn pok_of_bbs_plus_sig_and_bounded_message_and_verifiable_encryption() {
         start.elapsed()
     );
 
+    let start = Instant::now();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            None,
+            VerifierConfig {
+                use_lazy_randomized_pairing_checks: Some(true),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    println!(
+        "Time taken to verify proof of 2 bound checks and 1 verifiable encryption with lazy randomized pairing check: {:?}",
+        start.elapsed()
+    );
+
+    // Bound checks are lazy but the verifiable encryption is not
+    let start = Instant::now();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            None,
+            VerifierConfig {
+                use_lazy_randomized_pairing_checks: Some(true),
+                per_statement_laziness: Some(BTreeMap::from([(3, false)])),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    println!(
+        "Time taken to verify proof of 2 bound checks and 1 verifiable encryption with lazy randomized pairing check except for verifiable encryption: {:?}",
+        start.elapsed()
+    );
+
+    // Bound checks are not lazy but the verifiable encryption is
+    let start = Instant::now();
+    proof
+        .clone()
+        .verify::<StdRng, Blake2b512>(
+            &mut rng,
+            verifier_proof_spec.clone(),
+            None,
+            VerifierConfig {
+                use_lazy_randomized_pairing_checks: Some(false),
+                per_statement_laziness: Some(BTreeMap::from([(3, true)])),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    println!(
+        "Time taken to verify proof of 2 bound checks and 1 verifiable encryption with randomized pairing check lazy only for verifiable encryption: {:?}",
+        start.elapsed()
+    );
+
+    // The pairing checks deferred with either laziness still fail for a proof created with another nonce
+    for per_statement_laziness in [BTreeMap::from([(3, false)]), BTreeMap::from([(3, true)])] {
+        assert!(proof
+            .clone()
+            .verify::<StdRng, Blake2b512>(
+                &mut rng,
+                verifier_proof_spec.clone(),
+                Some(b"other nonce".to_vec()),
+                VerifierConfig {
+                    use_lazy_randomized_pairing_checks: Some(
+                        !per_statement_laziness.get(&3).unwrap()
+                    ),
+                    per_statement_laziness: Some(per_statement_laziness),
+                    ..Default::default()
+                },
+            )
+            .is_err());
+    }
+
     decrypt_and_verify(
         &proof,
         3,
@@ -1463,6 +1811,20 @@ fn pok_of_bbs_plus_sig_and_bounded_message_and_verifiable_encryption() {
         chunk_bit_size,
     );
 
+    // Ciphertexts and LegoGroth16 proofs can be found without knowing the statement indices
+    let cts = proof.saver_ciphertexts().collect::<Vec<_>>();
+    assert_eq!(cts.len(), 1);
+    assert_eq!(cts[0].0, 3);
+    assert_eq!(cts[0].1, proof.get_saver_ciphertext_and_proof(3).unwrap().0);
+    assert_eq!(
+        proof
+            .legogroth16_proofs()
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+    assert_eq!(proof.statement_proofs_iter().count(), 4);
+
     let mut l = BTreeMap::new();
     let p1 = proof.get_legogroth16_proof(1).unwrap();
     let p2 = proof.get_legogroth16_proof(2).unwrap();
@@ -1484,6 +1846,7 @@ fn pok_of_bbs_plus_sig_and_bounded_message_and_verifiable_encryption() {
     let config = ProverConfig::<Bls12_381> {
         reuse_saver_proofs: Some(g),
         reuse_legogroth16_proofs: Some(l),
+        ..Default::default()
     };
     let start = Instant::now();
     let proof = Proof::new::<StdRng, Blake2b512>(
@@ -7,8 +7,10 @@ use ark_std::{
 use blake2::Blake2b512;
 use dock_crypto_utils::elgamal::keygen;
 use proof_system::{
+    error::ProofSystemError,
     prelude::{
-        EqualWitnesses, MetaStatements, ProofSpec, VerifierConfig, Witness, WitnessRef, Witnesses,
+        EqualWitnesses, HashAlgorithm, MetaStatements, ProofSpec, Shake256Digest, VerifierConfig,
+        Witness, WitnessRef, Witnesses,
     },
     proof::Proof,
     setup_params::ElgamalEncryptionParams,
@@ -20,6 +22,8 @@ use proof_system::{
         verifiable_encryption_tz_21::VerifiableEncryptionTZ21,
         Statements,
     },
+    statement_proof::StatementProof,
+    sub_protocols::verifiable_encryption_tz_21::rdkgith_decls,
     witness::PoKBBSSignatureG1 as PoKSignatureBBSG1Wit,
 };
 use std::{
@@ -243,6 +247,7 @@ macro_rules! gen_tests {
                     None,
                     VerifierConfig {
                         use_lazy_randomized_pairing_checks: Some(false),
+                        ..Default::default()
                     },
                 )
                 .is_err());
@@ -404,3 +409,202 @@ gen_tests!(
     VeTZ21Robust,
     get_tz21_robust_ciphertext_and_commitment
 );
+
+#[test]
+fn verify_with_runtime_selected_hash_algorithm() {
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let enc_gen = G1Affine::rand(&mut rng);
+    let (_, enc_key) = keygen::<_, G1Affine>(&mut rng, &enc_gen);
+
+    let msg_count = 5;
+    let (msgs, sig_params, sig_keypair, sig) = bbs_plus_sig_setup(&mut rng, msg_count);
+
+    let enc_msg_idx = 1;
+    let comm_key_for_ve = (0..2).map(|_| G1Affine::rand(&mut rng)).collect::<Vec<_>>();
+    let enc_params = ElgamalEncryptionParams {
+        g: enc_gen,
+        public_key: enc_key.0,
+    };
+
+    let mut prover_statements = Statements::new();
+    prover_statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params.clone(),
+        BTreeMap::new(),
+    ));
+    prover_statements.add(VerifiableEncryptionTZ21::new_statement_from_params(
+        enc_params.clone(),
+        comm_key_for_ve.clone(),
+    ));
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, enc_msg_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let prover_proof_spec =
+        ProofSpec::new(prover_statements, meta_statements.clone(), vec![], None);
+    prover_proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::VeTZ21(vec![msgs[enc_msg_idx]]));
+
+    let mut verifier_statements = Statements::new();
+    verifier_statements.add(PoKSignatureBBSG1VerifierStmt::new_statement_from_params(
+        sig_params,
+        sig_keypair.public_key.clone(),
+        BTreeMap::new(),
+    ));
+    verifier_statements.add(VerifiableEncryptionTZ21::new_statement_from_params(
+        enc_params,
+        comm_key_for_ve,
+    ));
+    let verifier_proof_spec = ProofSpec::new(verifier_statements, meta_statements, vec![], None);
+    verifier_proof_spec.validate().unwrap();
+
+    // Blake2b and Blake3 are only offered with their features enabled
+    #[allow(unused_mut)]
+    let mut proofs = vec![(
+        HashAlgorithm::Shake256,
+        Proof::new::<StdRng, Shake256Digest>(
+            &mut rng,
+            prover_proof_spec.clone(),
+            witnesses.clone(),
+            None,
+            Default::default(),
+        )
+        .unwrap()
+        .0,
+    )];
+    #[cfg(feature = "blake2")]
+    proofs.push((
+        HashAlgorithm::Blake2b512,
+        Proof::new::<StdRng, Blake2b512>(
+            &mut rng,
+            prover_proof_spec.clone(),
+            witnesses.clone(),
+            None,
+            Default::default(),
+        )
+        .unwrap()
+        .0,
+    ));
+    #[cfg(feature = "blake3")]
+    proofs.push((
+        HashAlgorithm::Blake3,
+        Proof::new::<StdRng, blake3::Hasher>(
+            &mut rng,
+            prover_proof_spec.clone(),
+            witnesses.clone(),
+            None,
+            Default::default(),
+        )
+        .unwrap()
+        .0,
+    ));
+
+    let algorithms = proofs.iter().map(|(a, _)| *a).collect::<Vec<_>>();
+    for (prover_algorithm, proof) in proofs {
+        for algorithm in algorithms.iter().copied() {
+            let result = proof.clone().verify_with_algorithm(
+                &mut rng,
+                verifier_proof_spec.clone(),
+                None,
+                Default::default(),
+                algorithm,
+            );
+            // Verification only succeeds when the verifier uses the same hash function as the prover
+            assert_eq!(result.is_ok(), algorithm == prover_algorithm);
+        }
+        if prover_algorithm == HashAlgorithm::Shake256 {
+            assert!(proof
+                .verify::<StdRng, Blake2b512>(
+                    &mut rng,
+                    verifier_proof_spec.clone(),
+                    None,
+                    Default::default()
+                )
+                .is_err());
+        }
+    }
+}
+
+#[test]
+fn tz21_robust_subset_size_mismatch() {
+    // A robust TZ21 proof created with different parameters than the verifier's gives an error instead
+    // of an incorrect compressed ciphertext
+    let mut rng = StdRng::seed_from_u64(0u64);
+    let enc_gen = G1Affine::rand(&mut rng);
+    let (dec_key, enc_key) = keygen::<_, G1Affine>(&mut rng, &enc_gen);
+
+    let msg_count = 5;
+    let (msgs, sig_params, _, sig) = bbs_plus_sig_setup(&mut rng, msg_count);
+
+    let enc_msg_idx = 1;
+    let comm_key_for_ve = (0..2).map(|_| G1Affine::rand(&mut rng)).collect::<Vec<_>>();
+
+    let mut statements = Statements::new();
+    statements.add(PoKSignatureBBSG1ProverStmt::new_statement_from_params(
+        sig_params,
+        BTreeMap::new(),
+    ));
+    statements.add(
+        VerifiableEncryptionTZ21::new_statement_from_params_for_robust(
+            ElgamalEncryptionParams {
+                g: enc_gen,
+                public_key: enc_key.0,
+            },
+            comm_key_for_ve.clone(),
+        ),
+    );
+
+    let mut meta_statements = MetaStatements::new();
+    meta_statements.add_witness_equality(EqualWitnesses(
+        vec![(0, enc_msg_idx), (1, 0)]
+            .into_iter()
+            .collect::<BTreeSet<WitnessRef>>(),
+    ));
+
+    let proof_spec = ProofSpec::new(statements, meta_statements, vec![], None);
+    proof_spec.validate().unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(PoKSignatureBBSG1Wit::new_as_witness(
+        sig,
+        msgs.clone().into_iter().enumerate().collect(),
+    ));
+    witnesses.add(Witness::VeTZ21Robust(vec![msgs[enc_msg_idx]]));
+
+    let proof =
+        Proof::new::<StdRng, Blake2b512>(&mut rng, proof_spec, witnesses, None, Default::default())
+            .unwrap()
+            .0;
+
+    let (ct, comm) = proof
+        .get_tz21_robust_ciphertext_and_commitment::<Blake2b512>(1)
+        .unwrap();
+    assert_eq!(
+        ct.decrypt::<Blake2b512>(&dec_key.0, &comm, &comm_key_for_ve)
+            .unwrap()[0],
+        msgs[enc_msg_idx]
+    );
+
+    // Proof with fewer ciphertexts of hidden parties than the parameters expect
+    let expected = rdkgith_decls::NUM_PARTIES - rdkgith_decls::THRESHOLD;
+    let mut mismatched = proof.clone();
+    match &mut mismatched.statement_proofs[1] {
+        StatementProof::VeTZ21Robust(p) => {
+            p.ve_proof.ciphertexts.pop();
+        }
+        _ => panic!("expected a robust TZ21 proof"),
+    }
+    assert!(matches!(
+        mismatched.get_tz21_robust_ciphertext_and_commitment::<Blake2b512>(1),
+        Err(ProofSystemError::TZ21SubsetSizeMismatch(found, exp)) if found == expected - 1 && exp == expected
+    ));
+}
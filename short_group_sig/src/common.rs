@@ -119,6 +119,26 @@ impl<G: AffineRepr> ProvingKey<G> {
             Z: affine_group_element_from_byte_slices![label, b" : Z"],
         }
     }
+
+    /// Identifier of this key, the first 32 bytes of the hash of compressed `X`, `Y` and `Z`, zero padded
+    /// if the digest is shorter. Prover and verifier can compare identifiers to check that they use the
+    /// same key.
+    pub fn id<D: Digest>(&self) -> [u8; 32] {
+        let mut bytes = Vec::with_capacity(self.X.compressed_size() * 3);
+        self.X.serialize_compressed(&mut bytes).unwrap();
+        self.Y.serialize_compressed(&mut bytes).unwrap();
+        self.Z.serialize_compressed(&mut bytes).unwrap();
+        digest_to_id::<D>(&bytes)
+    }
+}
+
+/// First 32 bytes of the hash of `bytes`, zero padded if the digest is shorter
+pub fn digest_to_id<D: Digest>(bytes: &[u8]) -> [u8; 32] {
+    let hash = D::digest(bytes);
+    let mut id = [0; 32];
+    let len = hash.len().min(32);
+    id[..len].copy_from_slice(&hash[..len]);
+    id
 }
 
 impl<G: AffineRepr> AsRef<ProvingKey<G>> for ProvingKey<G> {
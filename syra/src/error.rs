@@ -5,6 +5,8 @@ use short_group_sig::error::ShortGroupSigError;
 #[derive(Debug)]
 pub enum SyraError {
     InvalidProof,
+    /// The proof isn't valid for any of the given issuer public keys
+    NoIssuerVerifiesProof,
     SchnorrError(SchnorrError),
     ShortGroupSigError(ShortGroupSigError),
     Serialization(SerializationError),
@@ -57,13 +57,28 @@ use ark_ec::{
 };
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{io::Write, mem, ops::Neg, rand::RngCore, vec::Vec, UniformRand};
-use dock_crypto_utils::elgamal::Ciphertext as ElgamalCiphertext;
+use dock_crypto_utils::{
+    elgamal::Ciphertext as ElgamalCiphertext, randomized_pairing_check::RandomizedPairingChecker,
+};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 /// Protocol to generate a pseudonym and its proof of correctness.
-#[derive(
-    Clone, PartialEq, Eq, Debug, Zeroize, ZeroizeOnDrop, CanonicalSerialize, CanonicalDeserialize,
-)]
+///
+/// This is deliberately not serializable as it contains the user's secrets (`s`, `alpha`, `beta`, etc.) and the
+/// blindings. Only the [`PseudonymProof`] created by [`PseudonymGenProtocol::gen_proof`] should be sent to the verifier.
+///
+/// ```compile_fail
+/// use ark_bls12_381::Bls12_381;
+/// use ark_serialize::CanonicalSerialize;
+/// use syra::pseudonym::PseudonymGenProtocol;
+///
+/// fn leak(protocol: &PseudonymGenProtocol<Bls12_381>) -> Vec<u8> {
+///     let mut bytes = vec![];
+///     protocol.serialize_compressed(&mut bytes).unwrap();
+///     bytes
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug, Zeroize, ZeroizeOnDrop)]
 pub struct PseudonymGenProtocol<E: Pairing> {
     alpha: E::ScalarField,
     beta: E::ScalarField,
@@ -384,6 +399,101 @@ impl<E: Pairing> PseudonymProof<E> {
         Ok(())
     }
 
+    /// Verify the proof against each of the given issuer public keys in order, as the proof doesn't reveal
+    /// the issuer, and return the index of the first key for which the proof is valid. Returns an error if
+    /// the proof isn't valid for any of them.
+    pub fn verify_against_issuers(
+        &self,
+        challenge: &E::ScalarField,
+        Z: E::G1Affine,
+        issuer_pks: &[PreparedIssuerPublicKey<E>],
+        params: impl Into<PreparedSetupParams<E>>,
+    ) -> Result<usize, SyraError> {
+        let params = params.into();
+        issuer_pks
+            .iter()
+            .position(|pk| {
+                self.verify(challenge, Z, pk.clone(), params.clone())
+                    .is_ok()
+            })
+            .ok_or(SyraError::NoIssuerVerifiesProof)
+    }
+
+    /// Same as [`Self::verify`] but the relations involving pairings are added to the given `RandomizedPairingChecker`
+    /// so that verification of several pseudonyms (or other protocols) needs only a single final exponentiation. The
+    /// remaining relations are checked here. `pairing_checker.verify()` must be called to complete the verification.
+    pub fn verify_with_pairing_checker(
+        &self,
+        challenge: &E::ScalarField,
+        Z: E::G1Affine,
+        issuer_pk: PreparedIssuerPublicKey<E>,
+        params: impl Into<PreparedSetupParams<E>>,
+        pairing_checker: &mut RandomizedPairingChecker<E>,
+    ) -> Result<(), SyraError> {
+        let params = params.into();
+        let F = issuer_pk.w_g_hat;
+        let G = issuer_pk.minus_g_w_hat;
+        let I = issuer_pk.w_vk;
+        let minus_challenge = challenge.neg();
+
+        // Relations not involving pairings are checked right away
+        if self.t_C1 != (params.g * self.resp_beta + self.C.eph_pk * minus_challenge).into() {
+            return Err(SyraError::InvalidProof);
+        }
+        if self.t_C1_hat
+            != (params.g_hat * self.resp_alpha + self.C_hat.eph_pk * minus_challenge).into()
+        {
+            return Err(SyraError::InvalidProof);
+        }
+        if self.t_K1 != F * self.resp_s + G * self.resp_r1 + self.K1 * minus_challenge {
+            return Err(SyraError::InvalidProof);
+        }
+        let F_bs = F * self.resp_beta_times_s;
+        let K2_c = self.K2 * minus_challenge;
+        if self.t_K2 != F_bs + G * self.resp_r2 + K2_c {
+            return Err(SyraError::InvalidProof);
+        }
+
+        let C2_hat_prepared = E::G2Prepared::from(self.C_hat.encrypted);
+        let C2 = self.C.encrypted.into_group();
+        let g = params.g.into_group();
+
+        // t_B = A * resp_alpha + B * -challenge where A = e(Z, w_hat) and B = e(Z, C2_hat) - T, so
+        // e(Z * resp_alpha, w_hat) * e(Z * -challenge, C2_hat) = t_B + T * -challenge
+        let Z_group = Z.into_group();
+        pairing_checker.add_multiple_sources_and_target(
+            &E::G1::normalize_batch(&[Z_group * self.resp_alpha, Z_group * minus_challenge]),
+            [issuer_pk.w_hat_prepared.clone(), C2_hat_prepared.clone()],
+            &(self.t_B + self.T * minus_challenge),
+        );
+
+        // t_E = F * resp_beta + G * resp_alpha + E * -challenge where E = e(C2, g_hat) - e(g, C2_hat), so
+        // e(C2 * -challenge, g_hat) * e(g * challenge, C2_hat) = t_E - F * resp_beta - G * resp_alpha
+        pairing_checker.add_multiple_sources_and_target(
+            &E::G1::normalize_batch(&[C2 * minus_challenge, g * challenge]),
+            [params.g_hat_prepared.clone(), C2_hat_prepared.clone()],
+            &(self.t_E - F * self.resp_beta - G * self.resp_alpha),
+        );
+
+        // t_H = I * resp_beta + F * resp_beta_times_s + J * resp_s + H * -challenge where J = e(-C2, g_hat) and
+        // H = e(C2, vk) - e(g, g_hat), so
+        // e(-C2 * resp_s, g_hat) * e(C2 * -challenge, vk) = t_H - I * resp_beta - F * resp_beta_times_s + e(g, g_hat) * -challenge
+        pairing_checker.add_multiple_sources_and_target(
+            &E::G1::normalize_batch(&[-C2 * self.resp_s, C2 * minus_challenge]),
+            [params.g_hat_prepared.clone(), issuer_pk.vk_prepared],
+            &(self.t_H - I * self.resp_beta - F_bs + params.pairing * minus_challenge),
+        );
+
+        // t_K2_product = E * resp_s + G * resp_r3 + K2 * -challenge, so
+        // e(C2 * resp_s, g_hat) * e(-g * resp_s, C2_hat) = t_K2_product - G * resp_r3 - K2 * -challenge
+        pairing_checker.add_multiple_sources_and_target(
+            &E::G1::normalize_batch(&[C2 * self.resp_s, -g * self.resp_s]),
+            [params.g_hat_prepared, C2_hat_prepared],
+            &(self.t_K2_product - G * self.resp_r3 - K2_c),
+        );
+        Ok(())
+    }
+
     pub fn challenge_contribution<W: Write>(
         &self,
         Z: &E::G1Affine,
@@ -490,4 +600,246 @@ mod tests {
             .unwrap();
         println!("Time to verify proof {:?}", start.elapsed());
     }
+
+    fn check_pseudonym_on_curve<E: Pairing>() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let params = SetupParams::<E>::new_for_curve::<Blake2b512>(b"test");
+        assert!(!params.g.is_zero());
+        assert!(!params.g_hat.is_zero());
+        let prepared_params = PreparedSetupParams::<E>::from(params.clone());
+
+        let isk = IssuerSecretKey::new(&mut rng);
+        let ipk = IssuerPublicKey::new(&mut rng, &isk, &params);
+        let prepared_ipk = PreparedIssuerPublicKey::new(ipk.clone(), params.clone());
+
+        let user_id =
+            compute_random_oracle_challenge::<E::ScalarField, Blake2b512>(b"low entropy user-id");
+        let usk = UserSecretKey::new(user_id, &isk, prepared_params.clone());
+        usk.verify(user_id, &ipk, prepared_params.clone()).unwrap();
+
+        let Z = affine_group_elem_from_try_and_incr::<E::G1Affine, Blake2b512>(b"test-context");
+        let protocol = PseudonymGenProtocol::init(
+            &mut rng,
+            Z,
+            user_id,
+            None,
+            &usk,
+            prepared_ipk.clone(),
+            prepared_params.clone(),
+        );
+        let mut chal_bytes = vec![];
+        protocol
+            .challenge_contribution(&Z, &mut chal_bytes)
+            .unwrap();
+        let challenge = compute_random_oracle_challenge::<E::ScalarField, Blake2b512>(&chal_bytes);
+        let proof = protocol.gen_proof(&challenge);
+        proof
+            .verify(&challenge, Z, prepared_ipk.clone(), prepared_params.clone())
+            .unwrap();
+
+        // Another user's secret key isn't valid for this user id
+        let other_user_id =
+            compute_random_oracle_challenge::<E::ScalarField, Blake2b512>(b"another user-id");
+        let other_usk = UserSecretKey::new(other_user_id, &isk, prepared_params.clone());
+        assert!(other_usk
+            .verify(user_id, &ipk, prepared_params.clone())
+            .is_err());
+
+        // Proof created with another user's secret key doesn't verify for this user id
+        let protocol = PseudonymGenProtocol::init(
+            &mut rng,
+            Z,
+            user_id,
+            None,
+            &other_usk,
+            prepared_ipk.clone(),
+            prepared_params.clone(),
+        );
+        let mut chal_bytes = vec![];
+        protocol
+            .challenge_contribution(&Z, &mut chal_bytes)
+            .unwrap();
+        let other_challenge =
+            compute_random_oracle_challenge::<E::ScalarField, Blake2b512>(&chal_bytes);
+        let other_proof = protocol.gen_proof(&other_challenge);
+        assert!(other_proof
+            .verify(
+                &other_challenge,
+                Z,
+                prepared_ipk.clone(),
+                prepared_params.clone()
+            )
+            .is_err());
+
+        let wrong_challenge = challenge + E::ScalarField::from(1u64);
+        assert!(proof
+            .verify(&wrong_challenge, Z, prepared_ipk, prepared_params)
+            .is_err());
+    }
+
+    #[test]
+    fn pseudonym_on_different_curves() {
+        // Parameters created for BLS12-381 don't need any rejection so are same as those from `new`
+        assert_eq!(
+            SetupParams::<Bls12_381>::new_for_curve::<Blake2b512>(b"test"),
+            SetupParams::<Bls12_381>::new::<Blake2b512>(b"test")
+        );
+        check_pseudonym_on_curve::<Bls12_381>();
+        check_pseudonym_on_curve::<ark_bls12_377::Bls12_377>();
+        check_pseudonym_on_curve::<ark_bn254::Bn254>();
+    }
+
+    #[test]
+    fn verify_pseudonym_against_issuers() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let params = SetupParams::<Bls12_381>::new::<Blake2b512>(b"test");
+        let prepared_params = PreparedSetupParams::<Bls12_381>::from(params.clone());
+
+        let isks = (0..3)
+            .map(|_| IssuerSecretKey::new(&mut rng))
+            .collect::<Vec<_>>();
+        let prepared_ipks = isks
+            .iter()
+            .map(|isk| {
+                let ipk = IssuerPublicKey::new(&mut rng, isk, &params);
+                PreparedIssuerPublicKey::new(ipk, params.clone())
+            })
+            .collect::<Vec<_>>();
+
+        // User's secret key is issued by the 2nd issuer
+        let user_id = compute_random_oracle_challenge::<Fr, Blake2b512>(b"low entropy user-id");
+        let usk = UserSecretKey::new(user_id, &isks[1], prepared_params.clone());
+
+        let Z = affine_group_elem_from_try_and_incr::<G1Affine, Blake2b512>(b"test-context");
+        let protocol = PseudonymGenProtocol::init(
+            &mut rng,
+            Z,
+            user_id,
+            None,
+            &usk,
+            prepared_ipks[1].clone(),
+            prepared_params.clone(),
+        );
+        let mut chal_bytes = vec![];
+        protocol
+            .challenge_contribution(&Z, &mut chal_bytes)
+            .unwrap();
+        let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+        let proof = protocol.gen_proof(&challenge);
+
+        assert_eq!(
+            proof
+                .verify_against_issuers(&challenge, Z, &prepared_ipks, prepared_params.clone())
+                .unwrap(),
+            1
+        );
+
+        // None of the issuers issued the user's secret key
+        let other_ipks = [prepared_ipks[0].clone(), prepared_ipks[2].clone()];
+        assert!(matches!(
+            proof.verify_against_issuers(&challenge, Z, &other_ipks, prepared_params.clone()),
+            Err(SyraError::NoIssuerVerifiesProof)
+        ));
+        assert!(matches!(
+            proof.verify_against_issuers(&challenge, Z, &[], prepared_params),
+            Err(SyraError::NoIssuerVerifiesProof)
+        ));
+    }
+
+    #[test]
+    fn verify_pseudonyms_with_pairing_checker() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let params = SetupParams::<Bls12_381>::new::<Blake2b512>(b"test");
+        let prepared_params = PreparedSetupParams::<Bls12_381>::from(params.clone());
+
+        let isk = IssuerSecretKey::new(&mut rng);
+        let ipk = IssuerPublicKey::new(&mut rng, &isk, &params);
+        let prepared_ipk = PreparedIssuerPublicKey::new(ipk.clone(), params.clone());
+
+        let count = 10;
+        let mut pseudonyms = vec![];
+        for i in 0..count {
+            let user_id = compute_random_oracle_challenge::<Fr, Blake2b512>(
+                format!("user-id-{}", i).as_bytes(),
+            );
+            let usk = UserSecretKey::new(user_id, &isk, prepared_params.clone());
+            let Z = affine_group_elem_from_try_and_incr::<G1Affine, Blake2b512>(
+                format!("context-{}", i).as_bytes(),
+            );
+            let protocol = PseudonymGenProtocol::init(
+                &mut rng,
+                Z,
+                user_id,
+                None,
+                &usk,
+                prepared_ipk.clone(),
+                prepared_params.clone(),
+            );
+            let mut chal_bytes = vec![];
+            protocol
+                .challenge_contribution(&Z, &mut chal_bytes)
+                .unwrap();
+            let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+            pseudonyms.push((Z, challenge, protocol.gen_proof(&challenge)));
+        }
+
+        let start = Instant::now();
+        for (Z, challenge, proof) in &pseudonyms {
+            proof
+                .verify(challenge, *Z, prepared_ipk.clone(), prepared_params.clone())
+                .unwrap();
+        }
+        println!(
+            "Time to verify {} pseudonyms individually {:?}",
+            count,
+            start.elapsed()
+        );
+
+        for lazy in [true, false] {
+            let start = Instant::now();
+            let mut checker = RandomizedPairingChecker::new_using_rng(&mut rng, lazy);
+            for (Z, challenge, proof) in &pseudonyms {
+                proof
+                    .verify_with_pairing_checker(
+                        challenge,
+                        *Z,
+                        prepared_ipk.clone(),
+                        prepared_params.clone(),
+                        &mut checker,
+                    )
+                    .unwrap();
+            }
+            assert!(checker.verify());
+            println!(
+                "Time to verify {} pseudonyms using {}randomized pairing checker {:?}",
+                count,
+                if lazy { "lazy " } else { "" },
+                start.elapsed()
+            );
+        }
+
+        // A proof failing individual verification fails the pairing checker as well
+        let (Z, challenge, proof) = &pseudonyms[0];
+        let mut tampered = proof.clone();
+        tampered.t_B += tampered.T;
+        assert!(tampered
+            .verify(challenge, *Z, prepared_ipk.clone(), prepared_params.clone(),)
+            .is_err());
+        let mut checker = RandomizedPairingChecker::new_using_rng(&mut rng, false);
+        for (i, (Z, challenge, proof)) in pseudonyms.iter().enumerate() {
+            let p = if i == 0 { &tampered } else { proof };
+            p.verify_with_pairing_checker(
+                challenge,
+                *Z,
+                prepared_ipk.clone(),
+                prepared_params.clone(),
+                &mut checker,
+            )
+            .unwrap();
+        }
+        assert!(!checker.verify());
+    }
 }
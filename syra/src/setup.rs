@@ -8,7 +8,7 @@ use ark_ec::{
 };
 use ark_ff::PrimeField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{ops::Neg, rand::RngCore, vec::Vec, UniformRand};
+use ark_std::{ops::Neg, rand::RngCore, vec::Vec, UniformRand, Zero};
 use digest::Digest;
 use dock_crypto_utils::affine_group_element_from_byte_slices;
 #[cfg(feature = "serde")]
@@ -116,6 +116,40 @@ impl<E: Pairing> SetupParams<E> {
         let g_hat = affine_group_element_from_byte_slices!(label, b" : g_hat");
         Self { g, g_hat }
     }
+
+    /// Same as `Self::new` but doesn't rely on the cofactors of the curve being small. Clearing a large
+    /// cofactor, like that of G2 in BN254 or BLS12-377, can result in the identity element so such
+    /// generators are rejected and the label is re-hashed with an attempt counter until both generators
+    /// are non-identity elements of the prime order subgroups.
+    pub fn new_for_curve<D: Digest>(label: &[u8]) -> Self {
+        let mut g = affine_group_element_from_byte_slices!(label, b" : g");
+        let mut j = 1u64;
+        while !Self::is_valid_generator(&g) {
+            g = affine_group_element_from_byte_slices!(
+                label,
+                b" : g",
+                b"-attempt-",
+                &j.to_le_bytes()
+            );
+            j += 1;
+        }
+        let mut g_hat = affine_group_element_from_byte_slices!(label, b" : g_hat");
+        let mut j = 1u64;
+        while !Self::is_valid_generator(&g_hat) {
+            g_hat = affine_group_element_from_byte_slices!(
+                label,
+                b" : g_hat",
+                b"-attempt-",
+                &j.to_le_bytes()
+            );
+            j += 1;
+        }
+        Self { g, g_hat }
+    }
+
+    fn is_valid_generator<G: AffineRepr>(g: &G) -> bool {
+        !g.is_zero() && g.mul_bigint(G::ScalarField::MODULUS).is_zero()
+    }
 }
 
 impl<E: Pairing> From<SetupParams<E>> for PreparedSetupParams<E> {
@@ -4,6 +4,7 @@ macro_rules! test_serialization {
         let mut serz = vec![];
         CanonicalSerialize::serialize_compressed(&$obj, &mut serz).unwrap();
         println!("Serialized byte size: {}", serz.len());
+        assert_eq!(serz.len(), CanonicalSerialize::compressed_size(&$obj));
         let start = $Instant::now();
         let deserz: $obj_type = CanonicalDeserialize::deserialize_compressed(&serz[..]).unwrap();
         println!("Deserialized time: {:?}", start.elapsed());
@@ -11,6 +12,7 @@ macro_rules! test_serialization {
 
         let mut serz = vec![];
         $obj.serialize_uncompressed(&mut serz).unwrap();
+        assert_eq!(serz.len(), CanonicalSerialize::uncompressed_size(&$obj));
         let deserz: $obj_type = CanonicalDeserialize::deserialize_uncompressed(&serz[..]).unwrap();
         assert_eq!(deserz, $obj);
 
@@ -28,11 +30,13 @@ macro_rules! test_serialization {
         let mut serz = vec![];
         CanonicalSerialize::serialize_compressed(&$obj, &mut serz).unwrap();
         println!("Serialized byte size: {}", serz.len());
+        assert_eq!(serz.len(), CanonicalSerialize::compressed_size(&$obj));
         let deserz: $obj_type = CanonicalDeserialize::deserialize_compressed(&serz[..]).unwrap();
         assert_eq!(deserz, $obj);
 
         let mut serz = vec![];
         $obj.serialize_uncompressed(&mut serz).unwrap();
+        assert_eq!(serz.len(), CanonicalSerialize::uncompressed_size(&$obj));
         let deserz: $obj_type = CanonicalDeserialize::deserialize_uncompressed(&serz[..]).unwrap();
         assert_eq!(deserz, $obj);
 
@@ -47,3 +51,38 @@ macro_rules! test_serialization {
         assert_eq!($obj, deser);
     };
 }
+
+/// Checks that `serialized_size` of each statement proof in `$proof` is the length of its serialization
+#[macro_export]
+macro_rules! test_statement_proof_sizes {
+    ($proof: expr) => {
+        for sp in &$proof.statement_proofs {
+            let mut serz = vec![];
+            CanonicalSerialize::serialize_compressed(sp, &mut serz).unwrap();
+            assert_eq!(serz.len(), CanonicalSerialize::compressed_size(sp));
+
+            let mut serz = vec![];
+            CanonicalSerialize::serialize_uncompressed(sp, &mut serz).unwrap();
+            assert_eq!(serz.len(), CanonicalSerialize::uncompressed_size(sp));
+        }
+    };
+}
+
+/// Checks that the estimated proof size of `$proof_spec` is the size of the proof created for it
+#[macro_export]
+macro_rules! test_proof_size_estimate {
+    ($rng: expr, $proof_spec: expr, $witnesses: expr) => {
+        let proof_spec = $proof_spec;
+        let estimate = proof_spec.estimate_proof_size().unwrap();
+        let proof = Proof::new::<StdRng, Blake2b512>(
+            $rng,
+            proof_spec,
+            $witnesses,
+            None,
+            Default::default(),
+        )
+        .unwrap()
+        .0;
+        assert_eq!(estimate, CanonicalSerialize::compressed_size(&proof));
+    };
+}
@@ -3,7 +3,11 @@ use ark_ec::{
     AffineRepr, Group,
 };
 use ark_ff::{One, PrimeField, Zero};
+use ark_serialize::CanonicalSerialize;
 use ark_std::{cfg_iter, ops::MulAssign, rand::Rng, vec, vec::Vec, UniformRand};
+use sha2::Sha512;
+
+use crate::{hashing_utils::field_elem_from_try_and_incr, transcript::Transcript};
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
@@ -33,9 +37,17 @@ pub struct RandomizedPairingChecker<E: Pairing> {
     lazy: bool,
     /// Keeps the pairs of G1, G2 elements that need to be used in miller loops when running lazily
     pending: (Vec<E::G1Prepared>, Vec<E::G2Prepared>),
+    /// If set, the miller loop over the pending pairs is computed as soon as their number exceeds this,
+    /// bounding the memory used when running lazily.
+    max_pending: Option<usize>,
+    /// Number of pairs added so far, whether pending or already used in a miller loop
+    total_added: usize,
     random: E::ScalarField,
     /// For each pairing equation, its multiplied by `self.random`
     current_random: E::ScalarField,
+    /// Multiplier derived from `self.random` and the domain of the equations being added with
+    /// `Self::add_with_domain`
+    domain_random: Option<E::ScalarField>,
 }
 
 impl<E: Pairing> RandomizedPairingChecker<E> {
@@ -47,8 +59,11 @@ impl<E: Pairing> RandomizedPairingChecker<E> {
             right: PairingOutput::zero(),
             lazy,
             pending: (vec![], vec![]),
+            max_pending: None,
+            total_added: 0,
             random,
             current_random: E::ScalarField::one(),
+            domain_random: None,
         }
     }
 
@@ -57,6 +72,75 @@ impl<E: Pairing> RandomizedPairingChecker<E> {
         Self::new(E::ScalarField::rand(rng), lazy)
     }
 
+    /// Same as `Self::new` except that the random value is a challenge from `transcript`. For the prover to
+    /// not be able to predict it, the transcript should already have everything the prover sent, like the
+    /// proof being verified. The verification is then reproducible without needing an RNG.
+    pub fn new_from_transcript(transcript: &mut impl Transcript, lazy: bool) -> Self {
+        Self::new(
+            transcript.challenge_scalar(b"randomized-pairing-checker"),
+            lazy,
+        )
+    }
+
+    /// Whether miller loops of the added pairing equations are delayed by default
+    pub fn is_lazy(&self) -> bool {
+        self.lazy
+    }
+
+    /// Change whether miller loops of the pairing equations added from now on are delayed by default.
+    /// Equations added already are not affected.
+    pub fn set_lazy(&mut self, lazy: bool) {
+        self.lazy = lazy;
+    }
+
+    /// Set the maximum number of pairs kept pending when running lazily. Once more pairs than this are
+    /// pending, their miller loop is computed right away rather than in `Self::verify`. `None` means
+    /// no limit.
+    pub fn set_max_pending(&mut self, max_pending: Option<usize>) {
+        self.max_pending = max_pending;
+    }
+
+    /// Number of pairs whose miller loop is pending, i.e. will be computed in `Self::verify`
+    pub fn pending_pairing_count(&self) -> usize {
+        self.pending.0.len()
+    }
+
+    /// Number of pairs added so far. This includes the pairs whose miller loop is pending and the ones
+    /// whose miller loop has already been computed.
+    pub fn total_added(&self) -> usize {
+        self.total_added
+    }
+
+    /// Add the pairing equations added by `add` such that their random multipliers are also bound to
+    /// `domain`, like the index of the statement whose proof the equations verify.
+    ///
+    /// Without a domain, the multiplier of an equation only depends on the number of equations added
+    /// before it so a prover who can influence the order in which statements add their equations, like
+    /// by reordering statements, knows which equations will share which power of the random value. With
+    /// a domain, each equation's multiplier is also multiplied by a value derived by hashing the random
+    /// value and `domain`, so the multipliers of equations added for different domains are unrelated
+    /// even if the order of the domains changes. The random value remains unknown to the prover so this
+    /// doesn't weaken the check, and using distinct domains, like statement indices, is recommended.
+    pub fn add_with_domain(&mut self, domain: usize, add: impl FnOnce(&mut Self)) {
+        let previous = self.domain_random;
+        self.set_domain(Some(domain));
+        add(self);
+        self.domain_random = previous;
+    }
+
+    /// Bind the multipliers of the pairing equations added from now on to `domain` as described in
+    /// `Self::add_with_domain`, or stop binding them to any domain if `domain` is `None`. Useful when the
+    /// equations of a domain can't be added in a single closure.
+    pub fn set_domain(&mut self, domain: Option<usize>) {
+        self.domain_random = domain.map(|domain| {
+            let mut bytes = vec![];
+            self.random.serialize_compressed(&mut bytes).unwrap();
+            bytes.extend_from_slice(b"-domain-");
+            bytes.extend_from_slice(&(domain as u64).to_le_bytes());
+            field_elem_from_try_and_incr::<E::ScalarField, Sha512>(&bytes)
+        });
+    }
+
     /// Add single elements from source and target groups
     pub fn add_sources_and_target(
         &mut self,
@@ -64,11 +148,13 @@ impl<E: Pairing> RandomizedPairingChecker<E> {
         b: impl Into<E::G2Prepared>,
         out: &PairingOutput<E>,
     ) {
-        let m = self.current_random.into_bigint();
+        let m = self.multiplier().into_bigint();
         let a_m = E::G1Prepared::from(a.mul_bigint(m));
+        self.total_added += 1;
         if self.lazy {
             self.pending.0.push(a_m);
             self.pending.1.push(b.into());
+            self.flush_pending_if_over_limit();
         } else {
             self.left.0.mul_assign(E::miller_loop(a_m, b.into()).0);
         }
@@ -120,16 +206,18 @@ impl<E: Pairing> RandomizedPairingChecker<E> {
         out: &PairingOutput<E>,
         lazy: bool,
     ) {
-        let m = self.current_random.into_bigint();
+        let m = self.multiplier().into_bigint();
         // {a_m}_i = a_i * m
         let mut a_m = cfg_iter!(a)
             .map(|a| E::G1Prepared::from(a.mul_bigint(m)))
             .collect::<Vec<_>>();
+        self.total_added += a_m.len();
         if lazy {
             self.pending.0.append(&mut a_m);
             self.pending
                 .1
                 .append(&mut b.into_iter().map(|b| b.into()).collect());
+            self.flush_pending_if_over_limit();
         } else {
             self.left.0.mul_assign(E::multi_miller_loop(a_m, b).0);
         }
@@ -147,7 +235,7 @@ impl<E: Pairing> RandomizedPairingChecker<E> {
         d: impl IntoIterator<Item = impl Into<E::G2Prepared>>,
         lazy: bool,
     ) {
-        let m = self.current_random.into_bigint();
+        let m = self.multiplier().into_bigint();
         // {a_m}_i = a_i * m
         let mut a_m = cfg_iter!(a)
             .map(|a| E::G1Prepared::from(a.mul_bigint(m)))
@@ -156,6 +244,7 @@ impl<E: Pairing> RandomizedPairingChecker<E> {
         let mut c_m = cfg_iter!(c)
             .map(|c| E::G1Prepared::from(-c.mul_bigint(m)))
             .collect::<Vec<_>>();
+        self.total_added += a_m.len() + c_m.len();
         if lazy {
             self.pending.0.append(&mut a_m);
             self.pending
@@ -165,6 +254,7 @@ impl<E: Pairing> RandomizedPairingChecker<E> {
             self.pending
                 .1
                 .append(&mut d.into_iter().map(|d| d.into()).collect());
+            self.flush_pending_if_over_limit();
         } else {
             self.left.0.mul_assign(E::multi_miller_loop(a_m, b).0);
             self.left.0.mul_assign(E::multi_miller_loop(c_m, d).0);
@@ -182,16 +272,18 @@ impl<E: Pairing> RandomizedPairingChecker<E> {
         d: impl Into<E::G2Prepared>,
         lazy: bool,
     ) {
-        let m = self.current_random.into_bigint();
+        let m = self.multiplier().into_bigint();
         let am = E::G1Prepared::from(a.mul_bigint(m));
         let cm = E::G1Prepared::from(-c.mul_bigint(m));
         let b = b.into();
         let d = d.into();
+        self.total_added += 2;
         if lazy {
             self.pending.0.push(am);
             self.pending.0.push(cm);
             self.pending.1.push(b);
             self.pending.1.push(d);
+            self.flush_pending_if_over_limit();
         } else {
             self.left
                 .0
@@ -200,6 +292,25 @@ impl<E: Pairing> RandomizedPairingChecker<E> {
         self.current_random *= self.random;
     }
 
+    /// Random multiplier for the next pairing equation
+    fn multiplier(&self) -> E::ScalarField {
+        match self.domain_random {
+            Some(d) => self.current_random * d,
+            None => self.current_random,
+        }
+    }
+
+    /// Compute the miller loop over the pending pairs and fold it into `self.left` if there are more
+    /// pending pairs than `self.max_pending`
+    fn flush_pending_if_over_limit(&mut self) {
+        if let Some(max_pending) = self.max_pending {
+            if self.pending.0.len() > max_pending {
+                let (a, b) = core::mem::take(&mut self.pending);
+                self.left.0.mul_assign(E::multi_miller_loop(a, b).0);
+            }
+        }
+    }
+
     /// Verify that all added pairing equations are satisfied.
     pub fn verify(&self) -> bool {
         debug_assert_eq!(self.pending.0.len(), self.pending.1.len());
@@ -217,6 +328,7 @@ impl<E: Pairing> RandomizedPairingChecker<E> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::transcript::new_merlin_transcript;
     use ark_bls12_381::{Bls12_381, G1Projective, G2Projective};
     use ark_ec::{bls12::G2Prepared, CurveGroup};
     use ark_std::{
@@ -418,4 +530,192 @@ mod test {
             assert!(!checker.verify());
         }
     }
+
+    #[test]
+    fn random_from_transcript() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let a = G1Projective::rand(&mut rng).into_affine();
+        let b = G2Projective::rand(&mut rng).into_affine();
+
+        let transcript = |data: &[u8]| {
+            let mut t = new_merlin_transcript(b"test");
+            t.append_message(b"data", data);
+            t
+        };
+
+        // Same transcript gives the same random value and different transcripts give different ones
+        let checker_1 = RandomizedPairingChecker::<Bls12_381>::new_from_transcript(
+            &mut transcript(b"proof"),
+            false,
+        );
+        let checker_2 = RandomizedPairingChecker::<Bls12_381>::new_from_transcript(
+            &mut transcript(b"proof"),
+            true,
+        );
+        let checker_3 = RandomizedPairingChecker::<Bls12_381>::new_from_transcript(
+            &mut transcript(b"another proof"),
+            false,
+        );
+        assert_eq!(checker_1.random, checker_2.random);
+        assert_ne!(checker_1.random, checker_3.random);
+
+        for mut checker in [checker_1, checker_2, checker_3] {
+            checker.add_sources(&a, b, &a, b);
+            checker.add_sources_and_target(&a, b, &Bls12_381::pairing(a, b));
+            assert!(checker.verify());
+        }
+    }
+
+    #[test]
+    fn lazy_with_max_pending() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let n = 6;
+
+        let a = (0..n)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let b = (0..n)
+            .map(|_| G2Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let a_rev = rev_vec(&a);
+        let b_rev = rev_vec(&b);
+        let out = Bls12_381::multi_pairing(a.clone(), b.clone());
+        let wrong_out = Bls12_381::pairing(a[0], b[1]);
+
+        let random = <Bls12_381 as Pairing>::ScalarField::rand(&mut rng);
+        let add_equations = |checker: &mut RandomizedPairingChecker<Bls12_381>,
+                             out: &PairingOutput<Bls12_381>| {
+            checker.add_multiple_sources_and_target(&a, &b, out);
+            checker.add_multiple_sources(&a, &b, &a_rev, &b_rev);
+            checker.add_sources(&a[0], b[0], &a[0], b[0]);
+            checker.add_sources_and_target(&a[1], b[1], &Bls12_381::pairing(a[1], b[1]));
+        };
+
+        for (out, valid) in [(out, true), (wrong_out, false)] {
+            let mut fully_lazy = RandomizedPairingChecker::<Bls12_381>::new(random, true);
+            add_equations(&mut fully_lazy, &out);
+            assert_eq!(fully_lazy.pending.0.len(), 3 * n + 3);
+            assert_eq!(fully_lazy.verify(), valid);
+
+            for max_pending in [0, 1, 4, 3 * n + 3] {
+                let mut checker = RandomizedPairingChecker::<Bls12_381>::new(random, true);
+                checker.set_max_pending(Some(max_pending));
+                add_equations(&mut checker, &out);
+                // Pending pairs are flushed after an addition takes them over the limit
+                assert!(checker.pending.0.len() <= max_pending);
+                assert_eq!(checker.verify(), valid);
+            }
+        }
+    }
+
+    #[test]
+    fn pairing_counts() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let n = 5;
+
+        let a = (0..n)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let b = (0..n)
+            .map(|_| G2Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let a_rev = rev_vec(&a);
+        let b_rev = rev_vec(&b);
+        let out = Bls12_381::multi_pairing(a.clone(), b.clone());
+
+        for (lazy, max_pending) in [(false, None), (true, None), (true, Some(4))] {
+            let mut checker = RandomizedPairingChecker::<Bls12_381>::new_using_rng(&mut rng, lazy);
+            checker.set_max_pending(max_pending);
+            assert_eq!(checker.total_added(), 0);
+            assert_eq!(checker.pending_pairing_count(), 0);
+
+            // Each call adds as many pairs as there are elements of the source groups
+            let mut expected = 0;
+            checker.add_multiple_sources_and_target(&a, &b, &out);
+            expected += n;
+            assert_eq!(checker.total_added(), expected);
+            checker.add_multiple_sources(&a, &b, &a_rev, &b_rev);
+            expected += 2 * n;
+            assert_eq!(checker.total_added(), expected);
+            checker.add_sources(&a[0], b[0], &a[0], b[0]);
+            expected += 2;
+            assert_eq!(checker.total_added(), expected);
+            checker.add_sources_and_target(&a[1], b[1], &Bls12_381::pairing(a[1], b[1]));
+            expected += 1;
+            assert_eq!(checker.total_added(), expected);
+            checker.add_multiple_sources_with_laziness_choice(&a, &b, &a_rev, &b_rev, !lazy);
+            expected += 2 * n;
+            assert_eq!(checker.total_added(), expected);
+
+            // Only the pairs added lazily are pending
+            match (lazy, max_pending) {
+                (false, _) => assert_eq!(checker.pending_pairing_count(), 2 * n),
+                (true, None) => assert_eq!(checker.pending_pairing_count(), expected - 2 * n),
+                (true, Some(m)) => assert!(checker.pending_pairing_count() <= m),
+            }
+            assert!(checker.verify());
+        }
+    }
+
+    #[test]
+    fn equations_with_domains() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let n = 5;
+
+        let a = (0..n)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let b = (0..n)
+            .map(|_| G2Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let a_rev = rev_vec(&a);
+        let b_rev = rev_vec(&b);
+        let out = Bls12_381::multi_pairing(a.clone(), b.clone());
+        let wrong_out = Bls12_381::pairing(a[0], b[1]);
+
+        let random = <Bls12_381 as Pairing>::ScalarField::rand(&mut rng);
+
+        for lazy in [true, false] {
+            // Equations of 2 "statements" added in either order
+            for domains in [[0, 1], [1, 0]] {
+                for (out, valid) in [(out, true), (wrong_out, false)] {
+                    let mut checker = RandomizedPairingChecker::<Bls12_381>::new(random, lazy);
+                    checker.add_with_domain(domains[0], |c| {
+                        c.add_multiple_sources_and_target(&a, &b, &out);
+                        c.add_sources(&a[0], b[0], &a[0], b[0]);
+                    });
+                    checker.add_with_domain(domains[1], |c| {
+                        c.add_multiple_sources(&a, &b, &a_rev, &b_rev);
+                        c.add_sources_and_target(&a[1], b[1], &Bls12_381::pairing(a[1], b[1]));
+                    });
+                    // Equations without a domain can be mixed with the ones with domains
+                    checker.add_sources(&a[2], b[2], &a[2], b[2]);
+                    assert_eq!(checker.domain_random, None);
+                    assert_eq!(checker.verify(), valid);
+                }
+            }
+        }
+
+        // Multipliers for the same position differ across domains and from the one without a domain
+        let checker = RandomizedPairingChecker::<Bls12_381>::new(random, true);
+        let mut multipliers = vec![checker.multiplier()];
+        let mut checker = checker.clone();
+        for domain in 0..3 {
+            checker.add_with_domain(domain, |c| multipliers.push(c.multiplier()));
+        }
+        for i in 0..multipliers.len() {
+            for j in i + 1..multipliers.len() {
+                assert_ne!(multipliers[i], multipliers[j]);
+            }
+        }
+
+        // Setting the domain gives the same multipliers as adding with that domain
+        let mut checker = RandomizedPairingChecker::<Bls12_381>::new(random, true);
+        for domain in 0..3 {
+            checker.set_domain(Some(domain));
+            assert_eq!(checker.multiplier(), multipliers[domain + 1]);
+        }
+        checker.set_domain(None);
+        assert_eq!(checker.multiplier(), multipliers[0]);
+    }
 }
@@ -50,6 +50,16 @@ pub enum VBAccumulatorError {
     SSError(SSError),
     OTError(OTError),
     MissingSchnorrResponseForElement,
+    /// The (non)membership proof's pairing check failed for the current accumulator value but passed for one
+    /// of the previous accumulator values given to `verify_with_previous_accumulator_values`, so the witness
+    /// used by the prover wasn't updated after the accumulator changed. The proof is still invalid.
+    StaleWitnessLikely,
+    /// The proving key has an element that is the identity or equal to another element of the key
+    DegenerateProvingKey,
+    /// The given serialization version isn't supported
+    UnsupportedSerializationVersion(u8),
+    /// The identifier of the verifier's proving key differs from the one the prover used
+    ProvingKeyMismatch,
 }
 
 impl From<SchnorrError> for VBAccumulatorError {
@@ -11,6 +11,7 @@
 //! - a dynamic universal accumulator [`UniversalAccumulator`], that supports membership and non-membership proofs.
 //! - a zero knowledge proof of membership and non-membership in the accumulators with [`ProofProtocol`] as described in the paper.
 //!   These are essentially proofs of knowledge of a weak-BB signature
+//!   The membership proof can also be created when the accumulator and witness are in group G2 and the public key in group G1.
 //! - an alternate and more efficient protocol of zero knowledge proof of membership and non-membership based on a more
 //!   efficient protocol for proving knowledge of a weak-BB signature. This isn't described in the paper.
 //! - keyed verification proofs of membership and non-membership where the verifier knows the secret key. Such accumulator don't need pairings
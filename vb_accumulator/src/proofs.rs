@@ -90,11 +90,28 @@
 //!
 //! // Non-membership proof has a similar API, see tests for example.
 //! ```
+//!
+//! # Side channels
+//!
+//! The responses are computed with field additions and multiplications only, like
+//! `s_y = r_y + challenge * element` and `s_w = r_w - (challenge * d) * pi` in the non-membership proof,
+//! which don't branch on their inputs in the arkworks backends. But the arkworks field and group arithmetic
+//! is not designed to be constant time, eg. the scalar multiplications by the secret `d` and the blindings
+//! are done with window tables indexed by the scalar's bits, so these protocols should not be relied
+//! upon to not leak secrets through timing or cache access patterns.
+//!
+//! The inversion of `d` during non-membership proof creation uses the binary extended Euclidean algorithm
+//! whose running time depends on its input. [`NonMembershipProofProtocol::init_with_blinded_inversion`]
+//! instead inverts `d * r` for a random `r` and multiplies the result by `r`. As `d * r` is independent of
+//! `d`, this removes the dependence of the inversion's running time on `d`, but not the leakage from the
+//! scalar multiplications mentioned above. It consumes more randomness than
+//! [`NonMembershipProofProtocol::init`] so the two don't create the same proof from the same seeded RNG.
 
 use crate::{
     error::VBAccumulatorError,
     setup::{
-        NonMembershipProvingKey, PreparedPublicKey, PreparedSetupParams, PublicKey, SetupParams,
+        NonMembershipProvingKey, PreparedPublicKey, PreparedSetupParams, PublicKey, PublicKeyG1,
+        SetupParams,
     },
     witness::{MembershipWitness, NonMembershipWitness},
 };
@@ -103,12 +120,22 @@ use ark_ec::{
     scalar_mul::wnaf::WnafContext,
     AffineRepr, CurveGroup, Group,
 };
-use ark_ff::{Field, PrimeField};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{fmt::Debug, io::Write, rand::RngCore, vec::Vec, UniformRand};
+use ark_ff::{Field, PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError, Valid};
+use ark_std::{
+    fmt::Debug,
+    io::{Read, Write},
+    rand::RngCore,
+    vec::Vec,
+    UniformRand,
+};
+use digest::{Digest, Output};
 #[cfg(feature = "serde")]
 use dock_crypto_utils::serde_utils::*;
-use dock_crypto_utils::{msm::WindowTable, randomized_pairing_check::RandomizedPairingChecker};
+use dock_crypto_utils::{
+    aliases::FullDigest, concat_slices, hashing_utils::hash_to_field, msm::WindowTable,
+    randomized_pairing_check::RandomizedPairingChecker,
+};
 use schnorr_pok::{error::SchnorrError, SchnorrChallengeContributor};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -165,17 +192,89 @@ pub struct Blindings<F: PrimeField> {
 #[derive(Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(bound = ""))]
-pub struct SchnorrCommit<E: Pairing> {
+pub struct SchnorrCommit<E: Pairing, G: AffineRepr = <E as Pairing>::G1Affine> {
     #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))]
     pub R_E: PairingOutput<E>,
     #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))]
-    pub R_sigma: E::G1Affine,
+    pub R_sigma: G,
     #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))]
-    pub R_rho: E::G1Affine,
+    pub R_rho: G,
     #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))]
-    pub R_delta_sigma: E::G1Affine,
+    pub R_delta_sigma: G,
     #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))]
-    pub R_delta_rho: E::G1Affine,
+    pub R_delta_rho: G,
+}
+
+/// Version of the serialized form of `SchnorrCommit` given by its `CanonicalSerialize` implementation,
+/// where `R_E` is serialized in whatever form arkworks serializes elements of the target field
+pub const SCHNORR_COMMIT_LEGACY_SERIALIZATION_VERSION: u8 = 0;
+
+/// Version of the serialized form of `SchnorrCommit` where `R_E` is serialized as its coefficients over
+/// the base prime field, each in compressed form, followed by the remaining elements in compressed form.
+/// Unlike the legacy form, this doesn't depend on how arkworks serializes target field elements.
+pub const SCHNORR_COMMIT_SERIALIZATION_VERSION: u8 = 1;
+
+impl<E: Pairing, G: AffineRepr> SchnorrCommit<E, G> {
+    /// Serialize using the given version of the serialized form, either `SCHNORR_COMMIT_LEGACY_SERIALIZATION_VERSION`
+    /// or `SCHNORR_COMMIT_SERIALIZATION_VERSION`. The version isn't written so the caller should store it
+    /// along with the bytes.
+    pub fn serialize_with_version<W: Write>(
+        &self,
+        version: u8,
+        mut writer: W,
+    ) -> Result<(), VBAccumulatorError> {
+        match version {
+            SCHNORR_COMMIT_LEGACY_SERIALIZATION_VERSION => self.serialize_compressed(writer)?,
+            SCHNORR_COMMIT_SERIALIZATION_VERSION => {
+                for c in self.R_E.0.to_base_prime_field_elements() {
+                    c.serialize_compressed(&mut writer)?;
+                }
+                self.R_sigma.serialize_compressed(&mut writer)?;
+                self.R_rho.serialize_compressed(&mut writer)?;
+                self.R_delta_sigma.serialize_compressed(&mut writer)?;
+                self.R_delta_rho.serialize_compressed(&mut writer)?;
+            }
+            _ => return Err(VBAccumulatorError::UnsupportedSerializationVersion(version)),
+        }
+        Ok(())
+    }
+
+    /// Deserialize bytes created by `Self::serialize_with_version` with the given version. Bytes created by
+    /// `CanonicalSerialize::serialize_compressed`, like the ones persisted before versioning was added, are
+    /// of version `SCHNORR_COMMIT_LEGACY_SERIALIZATION_VERSION`.
+    pub fn deserialize_with_version<R: Read>(
+        version: u8,
+        mut reader: R,
+    ) -> Result<Self, VBAccumulatorError> {
+        match version {
+            SCHNORR_COMMIT_LEGACY_SERIALIZATION_VERSION => {
+                Ok(Self::deserialize_compressed(reader)?)
+            }
+            SCHNORR_COMMIT_SERIALIZATION_VERSION => {
+                let coeffs = (0..E::TargetField::extension_degree())
+                    .map(|_| {
+                        <E::TargetField as Field>::BasePrimeField::deserialize_compressed(
+                            &mut reader,
+                        )
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let R_E = PairingOutput(
+                    E::TargetField::from_base_prime_field_elems(&coeffs)
+                        .ok_or(SerializationError::InvalidData)?,
+                );
+                // Same check as done by `deserialize_compressed` for the legacy version
+                R_E.check().map_err(|_| SerializationError::InvalidData)?;
+                Ok(Self {
+                    R_E,
+                    R_sigma: G::deserialize_compressed(&mut reader)?,
+                    R_rho: G::deserialize_compressed(&mut reader)?,
+                    R_delta_sigma: G::deserialize_compressed(&mut reader)?,
+                    R_delta_rho: G::deserialize_compressed(&mut reader)?,
+                })
+            }
+            _ => Err(VBAccumulatorError::UnsupportedSerializationVersion(version)),
+        }
+    }
 }
 
 /// Common elements of the response (Schnorr protocol, step 3) between membership and non-membership witness
@@ -220,7 +319,9 @@ pub struct MembershipBlindings<F: PrimeField>(pub Blindings<F>);
 #[derive(Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(bound = ""))]
-pub struct MembershipSchnorrCommit<E: Pairing>(pub SchnorrCommit<E>);
+pub struct MembershipSchnorrCommit<E: Pairing, G: AffineRepr = <E as Pairing>::G1Affine>(
+    pub SchnorrCommit<E, G>,
+);
 
 /// Responses from various Schnorr protocols used during membership proof protocol
 #[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
@@ -258,6 +359,37 @@ pub struct MembershipProofProtocol<E: Pairing> {
     pub schnorr_blindings: MembershipBlindings<E::ScalarField>,
 }
 
+/// Proof of knowledge of the member and the membership witness when the accumulator and the witness are
+/// in group G2
+#[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct MembershipProofG2<E: Pairing> {
+    pub randomized_witness: MembershipRandomizedWitness<E::G2Affine>,
+    pub schnorr_commit: MembershipSchnorrCommit<E, E::G2Affine>,
+    pub schnorr_response: MembershipSchnorrResponse<E::ScalarField>,
+}
+
+/// Protocol for proving knowledge of the member and the membership witness when the accumulator and the
+/// witness are in group G2
+#[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
+#[derive(
+    Clone, PartialEq, Eq, Debug, Zeroize, ZeroizeOnDrop, CanonicalSerialize, CanonicalDeserialize,
+)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MembershipProofProtocolG2<E: Pairing> {
+    #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))]
+    pub element: E::ScalarField,
+    #[cfg_attr(feature = "serde", serde(bound = ""))]
+    pub randomized_witness: MembershipRandomizedWitness<E::G2Affine>,
+    #[zeroize(skip)]
+    #[cfg_attr(feature = "serde", serde(bound = ""))]
+    pub schnorr_commit: MembershipSchnorrCommit<E, E::G2Affine>,
+    #[cfg_attr(feature = "serde", serde(bound = ""))]
+    pub schnorr_blindings: MembershipBlindings<E::ScalarField>,
+}
+
 /// Randomized non-membership witness
 #[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
 #[derive(
@@ -414,7 +546,7 @@ where
     }
 }
 
-impl<E: Pairing> SchnorrChallengeContributor for SchnorrCommit<E> {
+impl<E: Pairing, G: AffineRepr> SchnorrChallengeContributor for SchnorrCommit<E, G> {
     fn challenge_contribution<W: Write>(&self, mut writer: W) -> Result<(), SchnorrError> {
         self.R_E.serialize_compressed(&mut writer)?;
         self.R_sigma.serialize_compressed(&mut writer)?;
@@ -435,7 +567,7 @@ where
     }
 }
 
-impl<E: Pairing> SchnorrChallengeContributor for MembershipSchnorrCommit<E> {
+impl<E: Pairing, G: AffineRepr> SchnorrChallengeContributor for MembershipSchnorrCommit<E, G> {
     fn challenge_contribution<W: Write>(&self, writer: W) -> Result<(), SchnorrError> {
         self.0.challenge_contribution(writer)
     }
@@ -470,6 +602,130 @@ impl<F: PrimeField> SchnorrResponse<F> {
     }
 }
 
+/// The group containing the accumulator, the witness and the proving key used in the proof protocols. The public
+/// key and the setup parameter it is paired with are in the other group.
+pub trait AccumulatorGroup<E: Pairing> {
+    type Affine: AffineRepr<ScalarField = E::ScalarField, Group = Self::Group>;
+    type Group: CurveGroup<ScalarField = E::ScalarField, Affine = Self::Affine>;
+    type PublicKey: CanonicalSerialize;
+    type PreparedSetupParams: Clone;
+    type PreparedPublicKey: Clone;
+
+    /// Returns `e(p, P_tilde) * e(q, Q_tilde)` when the accumulator is in G1 and `e(P, p) * e(Q, q)` when its in G2
+    fn pairing(
+        p: Self::Affine,
+        q: Self::Affine,
+        params: &SetupParams<E>,
+        pk: &Self::PublicKey,
+    ) -> PairingOutput<E>;
+
+    /// Same as `Self::pairing` but with prepared setup params and public key
+    fn prepared_pairing(
+        p: Self::Affine,
+        q: Self::Affine,
+        params: Self::PreparedSetupParams,
+        pk: Self::PreparedPublicKey,
+    ) -> PairingOutput<E>;
+
+    /// Add the check that `Self::prepared_pairing` equals `target` to the randomized pairing checker
+    fn add_to_pairing_checker(
+        pairing_checker: &mut RandomizedPairingChecker<E>,
+        p: Self::Affine,
+        q: Self::Affine,
+        params: Self::PreparedSetupParams,
+        pk: Self::PreparedPublicKey,
+        target: &PairingOutput<E>,
+    );
+}
+
+/// Accumulator, witness and proving key are in group G1 and public key in group G2
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AccumulatorInG1;
+
+/// Accumulator, witness and proving key are in group G2 and public key in group G1
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AccumulatorInG2;
+
+impl<E: Pairing> AccumulatorGroup<E> for AccumulatorInG1 {
+    type Affine = E::G1Affine;
+    type Group = E::G1;
+    type PublicKey = PublicKey<E>;
+    type PreparedSetupParams = PreparedSetupParams<E>;
+    type PreparedPublicKey = PreparedPublicKey<E>;
+
+    fn pairing(
+        p: Self::Affine,
+        q: Self::Affine,
+        params: &SetupParams<E>,
+        pk: &Self::PublicKey,
+    ) -> PairingOutput<E> {
+        E::multi_pairing(
+            [p, q],
+            [
+                E::G2Prepared::from(params.P_tilde),
+                E::G2Prepared::from(pk.0),
+            ],
+        )
+    }
+
+    fn prepared_pairing(
+        p: Self::Affine,
+        q: Self::Affine,
+        params: Self::PreparedSetupParams,
+        pk: Self::PreparedPublicKey,
+    ) -> PairingOutput<E> {
+        E::multi_pairing([p, q], [params.P_tilde, pk.0])
+    }
+
+    fn add_to_pairing_checker(
+        pairing_checker: &mut RandomizedPairingChecker<E>,
+        p: Self::Affine,
+        q: Self::Affine,
+        params: Self::PreparedSetupParams,
+        pk: Self::PreparedPublicKey,
+        target: &PairingOutput<E>,
+    ) {
+        pairing_checker.add_multiple_sources_and_target(&[p, q], [params.P_tilde, pk.0], target);
+    }
+}
+
+impl<E: Pairing> AccumulatorGroup<E> for AccumulatorInG2 {
+    type Affine = E::G2Affine;
+    type Group = E::G2;
+    type PublicKey = PublicKeyG1<E>;
+    type PreparedSetupParams = SetupParams<E>;
+    type PreparedPublicKey = PublicKeyG1<E>;
+
+    fn pairing(
+        p: Self::Affine,
+        q: Self::Affine,
+        params: &SetupParams<E>,
+        pk: &Self::PublicKey,
+    ) -> PairingOutput<E> {
+        E::multi_pairing([params.P, pk.0], [p, q])
+    }
+
+    fn prepared_pairing(
+        p: Self::Affine,
+        q: Self::Affine,
+        params: Self::PreparedSetupParams,
+        pk: Self::PreparedPublicKey,
+    ) -> PairingOutput<E> {
+        Self::pairing(p, q, &params, &pk)
+    }
+
+    fn add_to_pairing_checker(
+        pairing_checker: &mut RandomizedPairingChecker<E>,
+        p: Self::Affine,
+        q: Self::Affine,
+        params: Self::PreparedSetupParams,
+        pk: Self::PreparedPublicKey,
+        target: &PairingOutput<E>,
+    ) {
+        pairing_checker.add_multiple_sources_and_target(&[params.P, pk.0], [p, q], target);
+    }
+}
+
 /// Protocol to prove knowledge of (non)member and corresponding witness in zero knowledge. It commits
 /// to the (non)member and the witness, does Schnorr proofs of knowledge of these committed values and
 /// satisfies the witness verification (pairing) equation with the committed values.
@@ -511,7 +767,7 @@ impl<F: PrimeField> SchnorrResponse<F> {
 ///  be satisfied is
 ///   `e(E_c, P_tilde)^y * e(Z, P_tilde)^{-delta_sigma - delta_rho} * e(Z, Q_tilde)^{-sigma - rho} = e(V, P_tilde) / (e(E_c, Q_tilde)`
 ///   Note that there is no `E_d` or `E_{d^-1}` and thus relations proving knowledge of them are omitted
-pub(crate) trait ProofProtocol<E: Pairing> {
+pub(crate) trait ProofProtocol<E: Pairing, G: AccumulatorGroup<E> = AccumulatorInG1> {
     /// Randomize the witness and compute commitments for step 1 of the Schnorr protocol.
     /// `element` is the accumulator (non)member about which the proof is being created.
     /// `element_blinding` is the randomness used for `element` in the Schnorr protocol and is useful
@@ -522,26 +778,122 @@ pub(crate) trait ProofProtocol<E: Pairing> {
         rng: &mut R,
         element: &E::ScalarField,
         element_blinding: Option<E::ScalarField>,
-        witness: &E::G1Affine,
-        pairing_extra: Option<E::G1>,
-        pk: &PublicKey<E>,
+        witness: &G::Affine,
+        pairing_extra: Option<G::Group>,
+        pk: &G::PublicKey,
         params: &SetupParams<E>,
-        prk: &ProvingKey<E::G1Affine>,
+        prk: &ProvingKey<G::Affine>,
     ) -> (
-        RandomizedWitness<E::G1Affine>,
-        SchnorrCommit<E>,
+        RandomizedWitness<G::Affine>,
+        SchnorrCommit<E, G::Affine>,
+        Blindings<E::ScalarField>,
+    ) {
+        Self::randomize_witness_and_compute_commitments_using_blinding_fn(
+            || E::ScalarField::rand(rng),
+            element,
+            element_blinding,
+            witness,
+            pairing_extra,
+            pk,
+            params,
+            prk,
+        )
+    }
+
+    /// Same as [`randomize_witness_and_compute_commitments`] but the randomness (the witness randomizers and
+    /// the blindings) is taken from `new_blinding` rather than sampled from an RNG.
+    ///
+    /// [`randomize_witness_and_compute_commitments`]: ProofProtocol::randomize_witness_and_compute_commitments
+    fn randomize_witness_and_compute_commitments_using_blinding_fn(
+        new_blinding: impl FnMut() -> E::ScalarField,
+        element: &E::ScalarField,
+        element_blinding: Option<E::ScalarField>,
+        witness: &G::Affine,
+        pairing_extra: Option<G::Group>,
+        pk: &G::PublicKey,
+        params: &SetupParams<E>,
+        prk: &ProvingKey<G::Affine>,
+    ) -> (
+        RandomizedWitness<G::Affine>,
+        SchnorrCommit<E, G::Affine>,
         Blindings<E::ScalarField>,
     ) {
-        // TODO: Since proving key is fixed, these tables can be created just once and stored.
         // There are multiple multiplications with X, Y and Z so create tables for them. 20 multiplications
         // is the upper bound
         let X_table = WindowTable::new(20, prk.X.into_group());
         let Y_table = WindowTable::new(20, prk.Y.into_group());
         let Z_table = WindowTable::new(20, prk.Z.into_group());
+        Self::randomize_witness_and_compute_commitments_using_tables_and_blinding_fn(
+            new_blinding,
+            element,
+            element_blinding,
+            witness,
+            pairing_extra,
+            pk,
+            params,
+            &X_table,
+            &Y_table,
+            &Z_table,
+        )
+    }
+
+    /// Same as [`randomize_witness_and_compute_commitments`] but uses the given window tables for
+    /// `X`, `Y` and `Z` of the proving key rather than creating them.
+    ///
+    /// [`randomize_witness_and_compute_commitments`]: ProofProtocol::randomize_witness_and_compute_commitments
+    fn randomize_witness_and_compute_commitments_using_tables<R: RngCore>(
+        rng: &mut R,
+        element: &E::ScalarField,
+        element_blinding: Option<E::ScalarField>,
+        witness: &G::Affine,
+        pairing_extra: Option<G::Group>,
+        pk: &G::PublicKey,
+        params: &SetupParams<E>,
+        X_table: &WindowTable<G::Group>,
+        Y_table: &WindowTable<G::Group>,
+        Z_table: &WindowTable<G::Group>,
+    ) -> (
+        RandomizedWitness<G::Affine>,
+        SchnorrCommit<E, G::Affine>,
+        Blindings<E::ScalarField>,
+    ) {
+        Self::randomize_witness_and_compute_commitments_using_tables_and_blinding_fn(
+            || E::ScalarField::rand(rng),
+            element,
+            element_blinding,
+            witness,
+            pairing_extra,
+            pk,
+            params,
+            X_table,
+            Y_table,
+            Z_table,
+        )
+    }
 
+    /// Same as [`randomize_witness_and_compute_commitments_using_tables`] but the randomness is taken from
+    /// `new_blinding` rather than sampled from an RNG.
+    ///
+    /// [`randomize_witness_and_compute_commitments_using_tables`]: ProofProtocol::randomize_witness_and_compute_commitments_using_tables
+    fn randomize_witness_and_compute_commitments_using_tables_and_blinding_fn(
+        mut new_blinding: impl FnMut() -> E::ScalarField,
+        element: &E::ScalarField,
+        element_blinding: Option<E::ScalarField>,
+        witness: &G::Affine,
+        pairing_extra: Option<G::Group>,
+        pk: &G::PublicKey,
+        params: &SetupParams<E>,
+        X_table: &WindowTable<G::Group>,
+        Y_table: &WindowTable<G::Group>,
+        Z_table: &WindowTable<G::Group>,
+    ) -> (
+        RandomizedWitness<G::Affine>,
+        SchnorrCommit<E, G::Affine>,
+        Blindings<E::ScalarField>,
+    ) {
         // To prove e(witness, element*P_tilde + Q_tilde) == e(accumulated, P_tilde)
-        let sigma = E::ScalarField::rand(rng);
-        let rho = E::ScalarField::rand(rng);
+        let sigma = new_blinding();
+        let rho = new_blinding();
         // Commitment to witness
         // E_C = witness + (sigma + rho) * prk.Z
         let mut E_C = Z_table.multiply(&(sigma + rho));
@@ -556,11 +908,11 @@ pub(crate) trait ProofProtocol<E: Pairing> {
 
         // Commit phase of Schnorr
         // Create blindings for pairing equation
-        let r_y = element_blinding.unwrap_or_else(|| E::ScalarField::rand(rng)); // blinding for proving knowledge of element
-        let r_sigma = E::ScalarField::rand(rng);
-        let r_delta_sigma = E::ScalarField::rand(rng);
-        let r_rho = E::ScalarField::rand(rng);
-        let r_delta_rho = E::ScalarField::rand(rng);
+        let r_y = element_blinding.unwrap_or_else(&mut new_blinding); // blinding for proving knowledge of element
+        let r_sigma = new_blinding();
+        let r_delta_sigma = new_blinding();
+        let r_rho = new_blinding();
+        let r_delta_rho = new_blinding();
 
         // Compute R_E using a multi-pairing
         // R_E = e(E_C, params.P_tilde)^r_y * e(prk.Z, params.P_tilde)^(-r_delta_sigma - r_delta_rho) * e(prk.Z, Q_tilde)^(-r_sigma - r_rho) * pairing_extra
@@ -578,15 +930,13 @@ pub(crate) trait ProofProtocol<E: Pairing> {
             p += pairing_extra.unwrap();
         }
 
-        let P_tilde_prepared = E::G2Prepared::from(params.P_tilde);
-        let R_E = E::multi_pairing(
-            [
-                // e(r_y * E_C + (-r_delta_sigma - r_delta_rho) * prk.Z + -r_v * K, params.P_tilde)
-                p.into_affine(),
-                // e((-r_sigma - r_rho) * prk.Z, Q_tilde)
-                Z_table.multiply(&(-r_sigma - r_rho)).into_affine(),
-            ],
-            [P_tilde_prepared, E::G2Prepared::from(pk.0)],
+        let R_E = G::pairing(
+            // e(r_y * E_C + (-r_delta_sigma - r_delta_rho) * prk.Z + -r_v * K, params.P_tilde)
+            p.into_affine(),
+            // e((-r_sigma - r_rho) * prk.Z, Q_tilde)
+            Z_table.multiply(&(-r_sigma - r_rho)).into_affine(),
+            params,
+            pk,
         );
 
         // R_sigma = r_sigma * prk.X
@@ -634,8 +984,8 @@ pub(crate) trait ProofProtocol<E: Pairing> {
     fn compute_challenge_contribution<W: Write>(
         randomized_witness: &impl SchnorrChallengeContributor,
         schnorr_commit: &impl SchnorrChallengeContributor,
-        accumulator_value: &E::G1Affine,
-        pk: &PublicKey<E>,
+        accumulator_value: &G::Affine,
+        pk: &G::PublicKey,
         params: &SetupParams<E>,
         prk: &impl SchnorrChallengeContributor,
         mut writer: W,
@@ -695,15 +1045,15 @@ pub(crate) trait ProofProtocol<E: Pairing> {
     /// `pairing_extra` is used when verifying non-membership proofs and is included in this function
     /// only because its efficient to do a multi-pairing.
     fn verify_proof(
-        randomized_witness: &RandomizedWitness<E::G1Affine>,
-        schnorr_commit: &SchnorrCommit<E>,
+        randomized_witness: &RandomizedWitness<G::Affine>,
+        schnorr_commit: &SchnorrCommit<E, G::Affine>,
         schnorr_response: &SchnorrResponse<E::ScalarField>,
-        pairing_extra: Option<E::G1>,
-        accumulator_value: &E::G1Affine,
+        pairing_extra: Option<G::Group>,
+        accumulator_value: &G::Affine,
         challenge: &E::ScalarField,
-        pk: impl Into<PreparedPublicKey<E>>,
-        params: impl Into<PreparedSetupParams<E>>,
-        prk: &ProvingKey<E::G1Affine>,
+        pk: impl Into<G::PreparedPublicKey>,
+        params: impl Into<G::PreparedSetupParams>,
+        prk: &ProvingKey<G::Affine>,
     ) -> Result<(), VBAccumulatorError> {
         let (p, q) = Self::verify_proof_except_pairings(
             None,
@@ -715,7 +1065,7 @@ pub(crate) trait ProofProtocol<E: Pairing> {
             challenge,
             prk,
         )?;
-        let R_E = E::multi_pairing([p, q], [params.into().P_tilde, pk.into().0]);
+        let R_E = G::prepared_pairing(p, q, params.into(), pk.into());
         if R_E != schnorr_commit.R_E {
             return Err(VBAccumulatorError::PairingResponseInvalid);
         }
@@ -725,15 +1075,15 @@ pub(crate) trait ProofProtocol<E: Pairing> {
 
     fn verify_partial_proof(
         resp_for_element: &E::ScalarField,
-        randomized_witness: &RandomizedWitness<E::G1Affine>,
-        schnorr_commit: &SchnorrCommit<E>,
+        randomized_witness: &RandomizedWitness<G::Affine>,
+        schnorr_commit: &SchnorrCommit<E, G::Affine>,
         schnorr_response: &SchnorrResponse<E::ScalarField>,
-        pairing_extra: Option<E::G1>,
-        accumulator_value: &E::G1Affine,
+        pairing_extra: Option<G::Group>,
+        accumulator_value: &G::Affine,
         challenge: &E::ScalarField,
-        pk: impl Into<PreparedPublicKey<E>>,
-        params: impl Into<PreparedSetupParams<E>>,
-        prk: &ProvingKey<E::G1Affine>,
+        pk: impl Into<G::PreparedPublicKey>,
+        params: impl Into<G::PreparedSetupParams>,
+        prk: &ProvingKey<G::Affine>,
     ) -> Result<(), VBAccumulatorError> {
         let (p, q) = Self::verify_proof_except_pairings(
             Some(resp_for_element),
@@ -745,7 +1095,7 @@ pub(crate) trait ProofProtocol<E: Pairing> {
             challenge,
             prk,
         )?;
-        let R_E = E::multi_pairing([p, q], [params.into().P_tilde, pk.into().0]);
+        let R_E = G::prepared_pairing(p, q, params.into(), pk.into());
         if R_E != schnorr_commit.R_E {
             return Err(VBAccumulatorError::PairingResponseInvalid);
         }
@@ -753,16 +1103,64 @@ pub(crate) trait ProofProtocol<E: Pairing> {
         Ok(())
     }
 
+    /// Same as `Self::verify_proof` but when the pairing equation isn't satisfied for `accumulator_value`,
+    /// checks it for each of `previous_accumulator_values`, like the values before the most recent updates.
+    /// If it's satisfied for one of them, the prover used a witness for that older value, i.e. one that
+    /// wasn't updated, and `VBAccumulatorError::StaleWitnessLikely` is returned. Otherwise, like when the
+    /// proof was tampered with or is for another public key, `VBAccumulatorError::PairingResponseInvalid` is
+    /// returned. As `E_C` perfectly hides the witness, a stale witness can't be detected without knowing the
+    /// older value. Each previous value costs a multi-pairing but only when the proof is invalid.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_proof_with_previous_accumulator_values(
+        randomized_witness: &RandomizedWitness<G::Affine>,
+        schnorr_commit: &SchnorrCommit<E, G::Affine>,
+        schnorr_response: &SchnorrResponse<E::ScalarField>,
+        pairing_extra: Option<G::Group>,
+        accumulator_value: &G::Affine,
+        previous_accumulator_values: &[G::Affine],
+        challenge: &E::ScalarField,
+        pk: impl Into<G::PreparedPublicKey>,
+        params: impl Into<G::PreparedSetupParams>,
+        prk: &ProvingKey<G::Affine>,
+    ) -> Result<(), VBAccumulatorError> {
+        let (p, q) = Self::verify_proof_except_pairings(
+            None,
+            randomized_witness,
+            schnorr_commit,
+            schnorr_response,
+            pairing_extra,
+            accumulator_value,
+            challenge,
+            prk,
+        )?;
+        let params = params.into();
+        let pk = pk.into();
+        if G::prepared_pairing(p, q, params.clone(), pk.clone()) == schnorr_commit.R_E {
+            return Ok(());
+        }
+        // `p` contains `-challenge * accumulator_value` so replace it with `-challenge * previous_value`
+        for previous_value in previous_accumulator_values {
+            let p_prev = p.into_group()
+                + (accumulator_value.into_group() - previous_value.into_group()) * challenge;
+            if G::prepared_pairing(p_prev.into_affine(), q, params.clone(), pk.clone())
+                == schnorr_commit.R_E
+            {
+                return Err(VBAccumulatorError::StaleWitnessLikely);
+            }
+        }
+        Err(VBAccumulatorError::PairingResponseInvalid)
+    }
+
     fn verify_proof_with_randomized_pairing_checker(
-        randomized_witness: &RandomizedWitness<E::G1Affine>,
-        schnorr_commit: &SchnorrCommit<E>,
+        randomized_witness: &RandomizedWitness<G::Affine>,
+        schnorr_commit: &SchnorrCommit<E, G::Affine>,
         schnorr_response: &SchnorrResponse<E::ScalarField>,
-        pairing_extra: Option<E::G1>,
-        accumulator_value: &E::G1Affine,
+        pairing_extra: Option<G::Group>,
+        accumulator_value: &G::Affine,
         challenge: &E::ScalarField,
-        pk: impl Into<PreparedPublicKey<E>>,
-        params: impl Into<PreparedSetupParams<E>>,
-        prk: &ProvingKey<E::G1Affine>,
+        pk: impl Into<G::PreparedPublicKey>,
+        params: impl Into<G::PreparedSetupParams>,
+        prk: &ProvingKey<G::Affine>,
         pairing_checker: &mut RandomizedPairingChecker<E>,
     ) -> Result<(), VBAccumulatorError> {
         let (p, q) = Self::verify_proof_except_pairings(
@@ -775,9 +1173,12 @@ pub(crate) trait ProofProtocol<E: Pairing> {
             challenge,
             prk,
         )?;
-        pairing_checker.add_multiple_sources_and_target(
-            &[p, q],
-            [params.into().P_tilde, pk.into().0],
+        G::add_to_pairing_checker(
+            pairing_checker,
+            p,
+            q,
+            params.into(),
+            pk.into(),
             &schnorr_commit.R_E,
         );
         Ok(())
@@ -785,15 +1186,15 @@ pub(crate) trait ProofProtocol<E: Pairing> {
 
     fn verify_partial_proof_with_randomized_pairing_checker(
         resp_for_element: &E::ScalarField,
-        randomized_witness: &RandomizedWitness<E::G1Affine>,
-        schnorr_commit: &SchnorrCommit<E>,
+        randomized_witness: &RandomizedWitness<G::Affine>,
+        schnorr_commit: &SchnorrCommit<E, G::Affine>,
         schnorr_response: &SchnorrResponse<E::ScalarField>,
-        pairing_extra: Option<E::G1>,
-        accumulator_value: &E::G1Affine,
+        pairing_extra: Option<G::Group>,
+        accumulator_value: &G::Affine,
         challenge: &E::ScalarField,
-        pk: impl Into<PreparedPublicKey<E>>,
-        params: impl Into<PreparedSetupParams<E>>,
-        prk: &ProvingKey<E::G1Affine>,
+        pk: impl Into<G::PreparedPublicKey>,
+        params: impl Into<G::PreparedSetupParams>,
+        prk: &ProvingKey<G::Affine>,
         pairing_checker: &mut RandomizedPairingChecker<E>,
     ) -> Result<(), VBAccumulatorError> {
         let (p, q) = Self::verify_proof_except_pairings(
@@ -806,9 +1207,12 @@ pub(crate) trait ProofProtocol<E: Pairing> {
             challenge,
             prk,
         )?;
-        pairing_checker.add_multiple_sources_and_target(
-            &[p, q],
-            [params.into().P_tilde, pk.into().0],
+        G::add_to_pairing_checker(
+            pairing_checker,
+            p,
+            q,
+            params.into(),
+            pk.into(),
             &schnorr_commit.R_E,
         );
         Ok(())
@@ -818,14 +1222,14 @@ pub(crate) trait ProofProtocol<E: Pairing> {
     /// protocol or others) and the pairing equations are combined in a randomized pairing check.
     fn verify_proof_except_pairings(
         resp_for_element: Option<&E::ScalarField>,
-        randomized_witness: &RandomizedWitness<E::G1Affine>,
-        schnorr_commit: &SchnorrCommit<E>,
+        randomized_witness: &RandomizedWitness<G::Affine>,
+        schnorr_commit: &SchnorrCommit<E, G::Affine>,
         schnorr_response: &SchnorrResponse<E::ScalarField>,
-        pairing_extra: Option<E::G1>,
-        accumulator_value: &E::G1Affine,
+        pairing_extra: Option<G::Group>,
+        accumulator_value: &G::Affine,
         challenge: &E::ScalarField,
-        prk: &ProvingKey<E::G1Affine>,
-    ) -> Result<(E::G1Affine, E::G1Affine), VBAccumulatorError> {
+        prk: &ProvingKey<G::Affine>,
+    ) -> Result<(G::Affine, G::Affine), VBAccumulatorError> {
         let (context, X_table, Y_table, Z_table, T_sigma_table, T_rho_table, E_C_table) =
             Self::get_tables(prk, randomized_witness);
         Self::verify_schnorr_proofs(
@@ -854,16 +1258,16 @@ pub(crate) trait ProofProtocol<E: Pairing> {
 
     /// There are multiple multiplications with X, Y and Z which can be done in variable time so use wNAF.
     fn get_tables(
-        prk: &ProvingKey<E::G1Affine>,
-        randomized_witness: &RandomizedWitness<E::G1Affine>,
+        prk: &ProvingKey<G::Affine>,
+        randomized_witness: &RandomizedWitness<G::Affine>,
     ) -> (
         WnafContext,
-        Vec<E::G1>,
-        Vec<E::G1>,
-        Vec<E::G1>,
-        Vec<E::G1>,
-        Vec<E::G1>,
-        Vec<E::G1>,
+        Vec<G::Group>,
+        Vec<G::Group>,
+        Vec<G::Group>,
+        Vec<G::Group>,
+        Vec<G::Group>,
+        Vec<G::Group>,
     ) {
         let context = WnafContext::new(4);
         // TODO: Since proving key is fixed, these tables can be created just once and stored.
@@ -889,14 +1293,14 @@ pub(crate) trait ProofProtocol<E: Pairing> {
     /// and compares them with the `R_`s from the proof for equality
     fn verify_schnorr_proofs(
         resp_for_element: Option<&E::ScalarField>,
-        schnorr_commit: &SchnorrCommit<E>,
+        schnorr_commit: &SchnorrCommit<E, G::Affine>,
         schnorr_response: &SchnorrResponse<E::ScalarField>,
         challenge: &E::ScalarField,
         context: &WnafContext,
-        X_table: &[E::G1],
-        Y_table: &[E::G1],
-        T_sigma_table: &[E::G1],
-        T_rho_table: &[E::G1],
+        X_table: &[G::Group],
+        Y_table: &[G::Group],
+        T_sigma_table: &[G::Group],
+        T_rho_table: &[G::Group],
     ) -> Result<(), VBAccumulatorError> {
         let s_y = if let Some(r) = resp_for_element {
             r
@@ -946,13 +1350,13 @@ pub(crate) trait ProofProtocol<E: Pairing> {
     fn get_g1_for_pairing_checks(
         resp_for_element: Option<&E::ScalarField>,
         schnorr_response: &SchnorrResponse<E::ScalarField>,
-        pairing_extra: Option<E::G1>,
-        accumulator_value: &E::G1Affine,
+        pairing_extra: Option<G::Group>,
+        accumulator_value: &G::Affine,
         challenge: &E::ScalarField,
         context: &WnafContext,
-        E_C_table: &[E::G1],
-        Z_table: &[E::G1],
-    ) -> Result<(E::G1Affine, E::G1Affine), VBAccumulatorError> {
+        E_C_table: &[G::Group],
+        Z_table: &[G::Group],
+    ) -> Result<(G::Affine, G::Affine), VBAccumulatorError> {
         let s_y = if let Some(r) = resp_for_element {
             r
         } else if let Some(r) = schnorr_response.s_y.as_ref() {
@@ -998,7 +1402,34 @@ pub(crate) trait ProofProtocol<E: Pairing> {
 
 impl<E: Pairing> ProofProtocol<E> for MembershipProofProtocol<E> {}
 
+/// Window tables for the membership proving key used when creating a membership proof. As these don't
+/// depend on the member or its witness, they can be created once and used with
+/// [`MembershipProofProtocol::init_using_precomputed`] to create many proofs. The tables are only read
+/// while creating proofs so an application can keep them in its own cache and share them between threads.
+#[derive(Clone, Debug)]
+pub struct MembershipProofParams<E: Pairing> {
+    pub X_table: WindowTable<E::G1>,
+    pub Y_table: WindowTable<E::G1>,
+    pub Z_table: WindowTable<E::G1>,
+}
+
+impl<E: Pairing> MembershipProofParams<E> {
+    pub fn precompute(prk: impl AsRef<ProvingKey<E::G1Affine>>) -> Self {
+        let prk = prk.as_ref();
+        Self {
+            X_table: WindowTable::new(20, prk.X.into_group()),
+            Y_table: WindowTable::new(20, prk.Y.into_group()),
+            Z_table: WindowTable::new(20, prk.Z.into_group()),
+        }
+    }
+}
+
 impl<E: Pairing> MembershipProofProtocol<E> {
+    /// Domain separation tag used when deriving the randomness from a seed in [`init_from_seed`]
+    ///
+    /// [`init_from_seed`]: MembershipProofProtocol::init_from_seed
+    pub const SEEDED_BLINDING_DST: &'static [u8] = b"VB-ACCUM-MEM-PROOF-BLINDING";
+
     /// Initialize a membership proof protocol. Delegates to [`randomize_witness_and_compute_commitments`]
     ///
     /// [`randomize_witness_and_compute_commitments`]: ProofProtocol::randomize_witness_and_compute_commitments
@@ -1029,6 +1460,94 @@ impl<E: Pairing> MembershipProofProtocol<E> {
         }
     }
 
+    /// Same as [`init`] but rather than sampling the randomness (the witness randomizers and the blindings)
+    /// from an RNG, derives it deterministically from `seed`. The `i`-th random value is
+    /// `hash_to_field(SEEDED_BLINDING_DST, seed || i as 4 byte little endian)` and the values are used in the
+    /// order `sigma`, `rho`, `r_y` (only when `element_blinding` is `None`), `r_sigma`, `r_delta_sigma`,
+    /// `r_rho`, `r_delta_rho`. Thus the same inputs always result in the same proof which makes this useful
+    /// for generating test vectors to check other implementations against.
+    ///
+    /// This must only be used for testing as reusing a seed with different challenges reveals the witness.
+    ///
+    /// [`init`]: MembershipProofProtocol::init
+    pub fn init_from_seed<D: FullDigest>(
+        seed: [u8; 32],
+        element: E::ScalarField,
+        element_blinding: Option<E::ScalarField>,
+        witness: &MembershipWitness<E::G1Affine>,
+        pk: &PublicKey<E>,
+        params: &SetupParams<E>,
+        prk: impl AsRef<ProvingKey<E::G1Affine>>,
+    ) -> Self {
+        let mut counter = 0u32;
+        let new_blinding = || {
+            let b = hash_to_field::<E::ScalarField, D>(
+                Self::SEEDED_BLINDING_DST,
+                &concat_slices!(seed, counter.to_le_bytes()),
+            );
+            counter += 1;
+            b
+        };
+        let (rw, sc, bl) = Self::randomize_witness_and_compute_commitments_using_blinding_fn(
+            new_blinding,
+            &element,
+            element_blinding,
+            &witness.0,
+            None,
+            pk,
+            params,
+            prk.as_ref(),
+        );
+        Self {
+            element,
+            randomized_witness: MembershipRandomizedWitness(rw),
+            schnorr_commit: MembershipSchnorrCommit(sc),
+            schnorr_blindings: MembershipBlindings(bl),
+        }
+    }
+
+    /// Same as [`init`] but uses the window tables in `precomputed` rather than creating them on each
+    /// call. `precomputed` must have been created from the same `prk`. Useful for a prover that keeps
+    /// re-proving membership of the same element as the accumulator changes, as after updating its witness
+    /// (see [`MembershipWitness::update_after_addition`], etc) it can create a new proof while reusing the
+    /// tables.
+    ///
+    /// Only the tables are reused and not the randomness of a previous proof. The randomized witness
+    /// and the blindings are sampled afresh from `rng` on each call as reusing them is insecure: two
+    /// proofs with the same blindings but different challenges (which is always the case when the
+    /// accumulator changes) allow solving for the witness, and a reused randomizer makes the proofs
+    /// linkable. So this only saves the table creation and not the rest of `init` and `gen_proof`.
+    ///
+    /// [`init`]: MembershipProofProtocol::init
+    pub fn init_using_precomputed<R: RngCore>(
+        rng: &mut R,
+        element: E::ScalarField,
+        element_blinding: Option<E::ScalarField>,
+        witness: &MembershipWitness<E::G1Affine>,
+        pk: &PublicKey<E>,
+        params: &SetupParams<E>,
+        precomputed: &MembershipProofParams<E>,
+    ) -> Self {
+        let (rw, sc, bl) = Self::randomize_witness_and_compute_commitments_using_tables(
+            rng,
+            &element,
+            element_blinding,
+            &witness.0,
+            None,
+            pk,
+            params,
+            &precomputed.X_table,
+            &precomputed.Y_table,
+            &precomputed.Z_table,
+        );
+        Self {
+            element,
+            randomized_witness: MembershipRandomizedWitness(rw),
+            schnorr_commit: MembershipSchnorrCommit(sc),
+            schnorr_blindings: MembershipBlindings(bl),
+        }
+    }
+
     /// Contribution of this protocol to the overall challenge (when using this protocol as a sub-protocol).
     /// Delegates to [`compute_challenge_contribution`]
     ///
@@ -1085,6 +1604,30 @@ impl<E: Pairing> MembershipProofProtocol<E> {
 
 impl<E: Pairing> ProofProtocol<E> for NonMembershipProofProtocol<E> {}
 
+/// Window tables for the setup params and the non-membership proving key used when creating a
+/// non-membership proof. As these don't depend on the non-member or its witness, they can be created
+/// once and used with [`NonMembershipProofProtocol::init_using_precomputed`] to create many proofs. The
+/// tables for `X`, `Y` and `Z` are the ones for the derived membership proving key so `XYZ` can be passed
+/// to [`MembershipProofProtocol::init_using_precomputed`] as well.
+#[derive(Clone, Debug)]
+pub struct NonMembershipProofParams<E: Pairing> {
+    pub XYZ: MembershipProofParams<E>,
+    pub P_table: WindowTable<E::G1>,
+    pub K_table: WindowTable<E::G1>,
+}
+
+impl<E: Pairing> NonMembershipProofParams<E> {
+    pub fn precompute(params: &SetupParams<E>, prk: &NonMembershipProvingKey<E::G1Affine>) -> Self {
+        // There are multiple multiplications with each of these so create tables for them. 20 multiplications
+        // is the upper bound
+        Self {
+            XYZ: MembershipProofParams::precompute(&prk.XYZ),
+            P_table: WindowTable::new(20, params.P.into_group()),
+            K_table: WindowTable::new(20, prk.K.into_group()),
+        }
+    }
+}
+
 impl<E: Pairing> NonMembershipProofProtocol<E> {
     /// Initialize a non-membership proof protocol. Create blindings for proving `witness.d != 0` and
     /// then delegates to [`randomize_witness_and_compute_commitments`]
@@ -1099,25 +1642,105 @@ impl<E: Pairing> NonMembershipProofProtocol<E> {
         params: &SetupParams<E>,
         prk: &NonMembershipProvingKey<E::G1Affine>,
     ) -> Self {
-        // TODO: Since proving key is fixed, these tables can be created just once and stored.
-        // There are multiple multiplications with P and K so create tables for them. 20 multiplications
-        // is the upper bound
-        let P_table = WindowTable::new(20, params.P.into_group());
-        let K_table = WindowTable::new(20, prk.K.into_group());
-
-        // To prove non-zero d of witness
-        let tau = E::ScalarField::rand(rng); // blinding in commitment to d
-        let pi = E::ScalarField::rand(rng);
-
-        // Commitment to d
-        // E_d = witness.d * pk.P + tau * prk.K
-        let mut E_d = P_table.multiply(&witness.d);
-        E_d += K_table.multiply(&tau);
-
-        // Commitment to d^-1
-        // E_d_inv = 1/witness.d * pk.P + pi * prk.K;
-        let mut E_d_inv = P_table.multiply(&witness.d.inverse().unwrap());
-        E_d_inv += K_table.multiply(&pi);
+        Self::init_using_precomputed(
+            rng,
+            element,
+            element_blinding,
+            witness,
+            pk,
+            params,
+            &NonMembershipProofParams::precompute(params, prk),
+        )
+    }
+
+    /// Same as [`NonMembershipProofProtocol::init`] but uses the window tables in `precomputed` rather
+    /// than creating them on each call. `precomputed` must have been created from the same `params`
+    /// and proving key.
+    pub fn init_using_precomputed<R: RngCore>(
+        rng: &mut R,
+        element: E::ScalarField,
+        element_blinding: Option<E::ScalarField>,
+        witness: &NonMembershipWitness<E::G1Affine>,
+        pk: &PublicKey<E>,
+        params: &SetupParams<E>,
+        precomputed: &NonMembershipProofParams<E>,
+    ) -> Self {
+        Self::init_using_given_tables(
+            rng,
+            element,
+            element_blinding,
+            witness,
+            pk,
+            params,
+            &precomputed.P_table,
+            &precomputed.K_table,
+            &precomputed.XYZ.X_table,
+            &precomputed.XYZ.Y_table,
+            &precomputed.XYZ.Z_table,
+            false,
+        )
+    }
+
+    /// Same as [`NonMembershipProofProtocol::init_using_precomputed`] but inverts `witness.d` after blinding
+    /// it with a random value so that the time taken by the inversion doesn't depend on `witness.d`. See the
+    /// module docs on side channels for what this does and doesn't protect.
+    pub fn init_with_blinded_inversion<R: RngCore>(
+        rng: &mut R,
+        element: E::ScalarField,
+        element_blinding: Option<E::ScalarField>,
+        witness: &NonMembershipWitness<E::G1Affine>,
+        pk: &PublicKey<E>,
+        params: &SetupParams<E>,
+        precomputed: &NonMembershipProofParams<E>,
+    ) -> Self {
+        Self::init_using_given_tables(
+            rng,
+            element,
+            element_blinding,
+            witness,
+            pk,
+            params,
+            &precomputed.P_table,
+            &precomputed.K_table,
+            &precomputed.XYZ.X_table,
+            &precomputed.XYZ.Y_table,
+            &precomputed.XYZ.Z_table,
+            true,
+        )
+    }
+
+    fn init_using_given_tables<R: RngCore>(
+        rng: &mut R,
+        element: E::ScalarField,
+        element_blinding: Option<E::ScalarField>,
+        witness: &NonMembershipWitness<E::G1Affine>,
+        pk: &PublicKey<E>,
+        params: &SetupParams<E>,
+        P_table: &WindowTable<E::G1>,
+        K_table: &WindowTable<E::G1>,
+        X_table: &WindowTable<E::G1>,
+        Y_table: &WindowTable<E::G1>,
+        Z_table: &WindowTable<E::G1>,
+        blind_inversion: bool,
+    ) -> Self {
+        // To prove non-zero d of witness
+        let tau = E::ScalarField::rand(rng); // blinding in commitment to d
+        let pi = E::ScalarField::rand(rng);
+
+        // Commitment to d
+        // E_d = witness.d * pk.P + tau * prk.K
+        let mut E_d = P_table.multiply(&witness.d);
+        E_d += K_table.multiply(&tau);
+
+        // Commitment to d^-1
+        // E_d_inv = 1/witness.d * pk.P + pi * prk.K;
+        let d_inv = if blind_inversion {
+            blinded_inverse(rng, &witness.d)
+        } else {
+            witness.d.inverse()
+        };
+        let mut E_d_inv = P_table.multiply(&d_inv.unwrap());
+        E_d_inv += K_table.multiply(&pi);
 
         // Create blindings for d != 0
         let r_u = E::ScalarField::rand(rng); // blinding for proving knowledge of d
@@ -1136,7 +1759,7 @@ impl<E: Pairing> NonMembershipProofProtocol<E> {
         // new R_E = e(E_C, params.P_tilde)^r_y * e(prk.Z, params.P_tilde)^(-r_delta_sigma - r_delta_rho) * e(prk.Z, Q_tilde)^(-r_sigma - r_rho) * e(prk.K, params.P_tilde)^-r_v
         // sc.R_E = e(E_C, params.P_tilde)^r_y * e(prk.Z, params.P_tilde)^(-r_delta_sigma - r_delta_rho) * e(prk.Z, Q_tilde)^(-r_sigma - r_rho)
         // => new R_E = e(prk.K, params.P_tilde)^-r_v * sc.R_E = e(-r_v * prk.K, params.P_tilde) * sc.R_E
-        let (rw, sc, bl) = Self::randomize_witness_and_compute_commitments(
+        let (rw, sc, bl) = Self::randomize_witness_and_compute_commitments_using_tables(
             rng,
             &element,
             element_blinding,
@@ -1144,7 +1767,9 @@ impl<E: Pairing> NonMembershipProofProtocol<E> {
             Some(K_table.multiply(&-r_v)),
             pk,
             params,
-            &prk.XYZ,
+            X_table,
+            Y_table,
+            Z_table,
         );
 
         Self {
@@ -1243,6 +1868,28 @@ impl<E: Pairing> NonMembershipProofProtocol<E> {
 }
 
 impl<E: Pairing> MembershipProof<E> {
+    /// Deserialize a compressed proof from untrusted bytes. The group elements are not checked while reading
+    /// but all together once the proof is read, and an error is returned if any of them is not in the prime
+    /// order subgroup.
+    pub fn deserialize_and_validate<R: Read>(reader: R) -> Result<Self, VBAccumulatorError> {
+        let proof = Self::deserialize_compressed_unchecked(reader)?;
+        let w = &proof.randomized_witness.0;
+        let c = &proof.schnorr_commit.0;
+        check_group_elements(
+            &[
+                w.E_C,
+                w.T_sigma,
+                w.T_rho,
+                c.R_sigma,
+                c.R_rho,
+                c.R_delta_sigma,
+                c.R_delta_rho,
+            ],
+            &c.R_E,
+        )?;
+        Ok(proof)
+    }
+
     /// Challenge contribution for this proof
     pub fn challenge_contribution<W: Write>(
         &self,
@@ -1287,6 +1934,33 @@ impl<E: Pairing> MembershipProof<E> {
         )
     }
 
+    /// Same as `Self::verify` but tells apart a proof created with a stale witness. Delegates to
+    /// [`verify_proof_with_previous_accumulator_values`]
+    ///
+    /// [`verify_proof_with_previous_accumulator_values`]: ProofProtocol::verify_proof_with_previous_accumulator_values
+    pub fn verify_with_previous_accumulator_values(
+        &self,
+        accumulator_value: &E::G1Affine,
+        previous_accumulator_values: &[E::G1Affine],
+        challenge: &E::ScalarField,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+        prk: impl AsRef<ProvingKey<E::G1Affine>>,
+    ) -> Result<(), VBAccumulatorError> {
+        <MembershipProofProtocol<E> as ProofProtocol<E>>::verify_proof_with_previous_accumulator_values(
+            &self.randomized_witness.0,
+            &self.schnorr_commit.0,
+            &self.schnorr_response.0,
+            None,
+            accumulator_value,
+            previous_accumulator_values,
+            challenge,
+            pk,
+            params,
+            prk.as_ref(),
+        )
+    }
+
     pub fn verify_partial(
         &self,
         resp_for_element: &E::ScalarField,
@@ -1363,149 +2037,424 @@ impl<E: Pairing> MembershipProof<E> {
     pub fn get_schnorr_response_for_element(&self) -> Option<&E::ScalarField> {
         self.schnorr_response.0.get_response_for_element()
     }
+
+    /// Hash of the canonically serialized proof using the digest `D`. A verifier can keep the hashes of recently
+    /// seen proofs to reject replays. Since each proof is randomized, this only catches the exact same proof
+    /// being presented again and not a fresh proof for the same member.
+    pub fn proof_hash<D: Digest>(&self) -> Result<Output<D>, VBAccumulatorError> {
+        let mut bytes = Vec::with_capacity(self.compressed_size());
+        self.serialize_compressed(&mut bytes)?;
+        Ok(D::digest(&bytes))
+    }
 }
 
-impl<E: Pairing> NonMembershipProof<E> {
+impl<E: Pairing> ProofProtocol<E, AccumulatorInG2> for MembershipProofProtocolG2<E> {}
+
+impl<E: Pairing> MembershipProofProtocolG2<E> {
+    /// Initialize a membership proof protocol. Delegates to [`randomize_witness_and_compute_commitments`]
+    ///
+    /// [`randomize_witness_and_compute_commitments`]: ProofProtocol::randomize_witness_and_compute_commitments
+    pub fn init<R: RngCore>(
+        rng: &mut R,
+        element: E::ScalarField,
+        element_blinding: Option<E::ScalarField>,
+        witness: &MembershipWitness<E::G2Affine>,
+        pk: &PublicKeyG1<E>,
+        params: &SetupParams<E>,
+        prk: impl AsRef<ProvingKey<E::G2Affine>>,
+    ) -> Self {
+        let (rw, sc, bl) = Self::randomize_witness_and_compute_commitments(
+            rng,
+            &element,
+            element_blinding,
+            &witness.0,
+            None,
+            pk,
+            params,
+            prk.as_ref(),
+        );
+        Self {
+            element,
+            randomized_witness: MembershipRandomizedWitness(rw),
+            schnorr_commit: MembershipSchnorrCommit(sc),
+            schnorr_blindings: MembershipBlindings(bl),
+        }
+    }
+
+    /// Contribution of this protocol to the overall challenge (when using this protocol as a sub-protocol).
+    /// Delegates to [`compute_challenge_contribution`]
+    ///
+    /// [`compute_challenge_contribution`]: ProofProtocol::compute_challenge_contribution
+    pub fn challenge_contribution<W: Write>(
+        &self,
+        accumulator_value: &E::G2Affine,
+        pk: &PublicKeyG1<E>,
+        params: &SetupParams<E>,
+        prk: impl AsRef<ProvingKey<E::G2Affine>>,
+        writer: W,
+    ) -> Result<(), VBAccumulatorError> {
+        Self::compute_challenge_contribution(
+            &self.randomized_witness,
+            &self.schnorr_commit,
+            accumulator_value,
+            pk,
+            params,
+            prk.as_ref(),
+            writer,
+        )
+    }
+
+    /// Create membership proof once the overall challenge is ready. Delegates to [`compute_responses`]
+    ///
+    /// [`compute_responses`]: ProofProtocol::compute_responses
+    pub fn gen_proof(
+        self,
+        challenge: &E::ScalarField,
+    ) -> Result<MembershipProofG2<E>, VBAccumulatorError> {
+        let resp = Self::compute_responses(&self.element, &self.schnorr_blindings.0, challenge);
+        Ok(MembershipProofG2 {
+            randomized_witness: self.randomized_witness.clone(),
+            schnorr_commit: self.schnorr_commit.clone(),
+            schnorr_response: MembershipSchnorrResponse(resp),
+        })
+    }
+
+    /// Create membership proof once the overall challenge is ready. Delegates to [`compute_partial_responses`]
+    ///
+    /// [`compute_partial_responses`]: ProofProtocol::compute_partial_responses
+    pub fn gen_partial_proof(
+        self,
+        challenge: &E::ScalarField,
+    ) -> Result<MembershipProofG2<E>, VBAccumulatorError> {
+        let resp = Self::compute_partial_responses(&self.schnorr_blindings.0, challenge);
+        Ok(MembershipProofG2 {
+            randomized_witness: self.randomized_witness.clone(),
+            schnorr_commit: self.schnorr_commit.clone(),
+            schnorr_response: MembershipSchnorrResponse(resp),
+        })
+    }
+}
+
+impl<E: Pairing> MembershipProofG2<E> {
     /// Challenge contribution for this proof
     pub fn challenge_contribution<W: Write>(
         &self,
-        accumulator_value: &E::G1Affine,
-        pk: &PublicKey<E>,
+        accumulator_value: &E::G2Affine,
+        pk: &PublicKeyG1<E>,
         params: &SetupParams<E>,
-        prk: &NonMembershipProvingKey<E::G1Affine>,
-        mut writer: W,
+        prk: impl AsRef<ProvingKey<E::G2Affine>>,
+        writer: W,
     ) -> Result<(), VBAccumulatorError> {
-        NonMembershipProofProtocol::compute_challenge_contribution(
+        MembershipProofProtocolG2::compute_challenge_contribution(
             &self.randomized_witness,
             &self.schnorr_commit,
             accumulator_value,
             pk,
             params,
-            &prk.XYZ,
-            &mut writer,
+            prk.as_ref(),
+            writer,
         )
     }
 
-    /// Verify this proof. Verify the responses for the relation `witness.d != 0` and then delegates
-    /// to [`verify_proof`]
+    /// Verify this proof. Delegates to [`verify_proof`]
     ///
     /// [`verify_proof`]: ProofProtocol::verify_proof
     pub fn verify(
         &self,
-        accumulator_value: &E::G1Affine,
+        accumulator_value: &E::G2Affine,
         challenge: &E::ScalarField,
-        pk: impl Into<PreparedPublicKey<E>>,
-        params: impl Into<PreparedSetupParams<E>>,
-        prk: &NonMembershipProvingKey<E::G1Affine>,
+        pk: PublicKeyG1<E>,
+        params: SetupParams<E>,
+        prk: impl AsRef<ProvingKey<E::G2Affine>>,
     ) -> Result<(), VBAccumulatorError> {
-        let params = params.into();
-        let pairing_extra = self.verify_except_pairings(challenge, &params.P, prk)?;
-
-        <NonMembershipProofProtocol<E> as ProofProtocol<E>>::verify_proof(
-            &self.randomized_witness.C,
-            &self.schnorr_commit.C,
-            &self.schnorr_response.C,
-            Some(pairing_extra),
+        MembershipProofProtocolG2::verify_proof(
+            &self.randomized_witness.0,
+            &self.schnorr_commit.0,
+            &self.schnorr_response.0,
+            None,
             accumulator_value,
             challenge,
             pk,
             params,
-            &prk.XYZ,
+            prk.as_ref(),
         )
     }
 
     pub fn verify_partial(
         &self,
         resp_for_element: &E::ScalarField,
-        accumulator_value: &E::G1Affine,
+        accumulator_value: &E::G2Affine,
         challenge: &E::ScalarField,
-        pk: impl Into<PreparedPublicKey<E>>,
-        params: impl Into<PreparedSetupParams<E>>,
-        prk: &NonMembershipProvingKey<E::G1Affine>,
+        pk: PublicKeyG1<E>,
+        params: SetupParams<E>,
+        prk: impl AsRef<ProvingKey<E::G2Affine>>,
     ) -> Result<(), VBAccumulatorError> {
-        let params = params.into();
-        let pairing_extra = self.verify_except_pairings(challenge, &params.P, prk)?;
-
-        <NonMembershipProofProtocol<E> as ProofProtocol<E>>::verify_partial_proof(
+        MembershipProofProtocolG2::verify_partial_proof(
             resp_for_element,
-            &self.randomized_witness.C,
-            &self.schnorr_commit.C,
-            &self.schnorr_response.C,
-            Some(pairing_extra),
+            &self.randomized_witness.0,
+            &self.schnorr_commit.0,
+            &self.schnorr_response.0,
+            None,
             accumulator_value,
             challenge,
             pk,
             params,
-            &prk.XYZ,
+            prk.as_ref(),
         )
     }
 
     pub fn verify_with_randomized_pairing_checker(
         &self,
-        accumulator_value: &E::G1Affine,
+        accumulator_value: &E::G2Affine,
         challenge: &E::ScalarField,
-        pk: impl Into<PreparedPublicKey<E>>,
-        params: impl Into<PreparedSetupParams<E>>,
-        prk: &NonMembershipProvingKey<E::G1Affine>,
+        pk: PublicKeyG1<E>,
+        params: SetupParams<E>,
+        prk: impl AsRef<ProvingKey<E::G2Affine>>,
         pairing_checker: &mut RandomizedPairingChecker<E>,
     ) -> Result<(), VBAccumulatorError> {
-        let params = params.into();
-        let pairing_extra = self.verify_except_pairings(challenge, &params.P, prk)?;
-
-        <NonMembershipProofProtocol<E> as ProofProtocol<E>>::verify_proof_with_randomized_pairing_checker(
-            &self.randomized_witness.C,
-            &self.schnorr_commit.C,
-            &self.schnorr_response.C,
-            Some(pairing_extra),
+        MembershipProofProtocolG2::verify_proof_with_randomized_pairing_checker(
+            &self.randomized_witness.0,
+            &self.schnorr_commit.0,
+            &self.schnorr_response.0,
+            None,
             accumulator_value,
             challenge,
             pk,
             params,
-            &prk.XYZ,
-            pairing_checker
+            prk.as_ref(),
+            pairing_checker,
         )
     }
 
     pub fn verify_partial_with_randomized_pairing_checker(
         &self,
         resp_for_element: &E::ScalarField,
-        accumulator_value: &E::G1Affine,
+        accumulator_value: &E::G2Affine,
         challenge: &E::ScalarField,
-        pk: impl Into<PreparedPublicKey<E>>,
-        params: impl Into<PreparedSetupParams<E>>,
-        prk: &NonMembershipProvingKey<E::G1Affine>,
+        pk: PublicKeyG1<E>,
+        params: SetupParams<E>,
+        prk: impl AsRef<ProvingKey<E::G2Affine>>,
         pairing_checker: &mut RandomizedPairingChecker<E>,
     ) -> Result<(), VBAccumulatorError> {
-        let params = params.into();
-        let pairing_extra = self.verify_except_pairings(challenge, &params.P, prk)?;
-
-        <NonMembershipProofProtocol<E> as ProofProtocol<E>>::verify_partial_proof_with_randomized_pairing_checker(
+        MembershipProofProtocolG2::verify_partial_proof_with_randomized_pairing_checker(
             resp_for_element,
-            &self.randomized_witness.C,
-            &self.schnorr_commit.C,
-            &self.schnorr_response.C,
-            Some(pairing_extra),
+            &self.randomized_witness.0,
+            &self.schnorr_commit.0,
+            &self.schnorr_response.0,
+            None,
             accumulator_value,
             challenge,
             pk,
             params,
-            &prk.XYZ,
-            pairing_checker
+            prk.as_ref(),
+            pairing_checker,
         )
     }
 
-    /// Get response for Schnorr protocol for the non-member. This is useful when the non-member is also used
+    /// Get response for Schnorr protocol for the member. This is useful when the member is also used
     /// in another relation that is proven along this protocol.
     pub fn get_schnorr_response_for_element(&self) -> Option<&E::ScalarField> {
-        self.schnorr_response.C.get_response_for_element()
+        self.schnorr_response.0.get_response_for_element()
     }
+}
 
-    /// There are multiple multiplications with K, P and E_d which can be done in variable time so use wNAF.
-    pub fn get_tables(
-        prk: &NonMembershipProvingKey<E::G1Affine>,
-        P: &E::G1Affine,
-        E_d: &E::G1Affine,
-    ) -> (WnafContext, Vec<E::G1>, Vec<E::G1>, Vec<E::G1>) {
+impl<E: Pairing> NonMembershipProof<E> {
+    /// Deserialize a compressed proof from untrusted bytes. The group elements are not checked while reading
+    /// but all together once the proof is read, and an error is returned if any of them is not in the prime
+    /// order subgroup.
+    pub fn deserialize_and_validate<R: Read>(reader: R) -> Result<Self, VBAccumulatorError> {
+        let proof = Self::deserialize_compressed_unchecked(reader)?;
+        let w = &proof.randomized_witness;
+        let c = &proof.schnorr_commit;
+        check_group_elements(
+            &[
+                w.C.E_C,
+                w.C.T_sigma,
+                w.C.T_rho,
+                w.E_d,
+                w.E_d_inv,
+                c.C.R_sigma,
+                c.C.R_rho,
+                c.C.R_delta_sigma,
+                c.C.R_delta_rho,
+                c.R_A,
+                c.R_B,
+            ],
+            &c.C.R_E,
+        )?;
+        Ok(proof)
+    }
+
+    /// Challenge contribution for this proof
+    pub fn challenge_contribution<W: Write>(
+        &self,
+        accumulator_value: &E::G1Affine,
+        pk: &PublicKey<E>,
+        params: &SetupParams<E>,
+        prk: &NonMembershipProvingKey<E::G1Affine>,
+        mut writer: W,
+    ) -> Result<(), VBAccumulatorError> {
+        NonMembershipProofProtocol::compute_challenge_contribution(
+            &self.randomized_witness,
+            &self.schnorr_commit,
+            accumulator_value,
+            pk,
+            params,
+            &prk.XYZ,
+            &mut writer,
+        )
+    }
+
+    /// Verify this proof. Verify the responses for the relation `witness.d != 0` and then delegates
+    /// to [`verify_proof`]
+    ///
+    /// [`verify_proof`]: ProofProtocol::verify_proof
+    pub fn verify(
+        &self,
+        accumulator_value: &E::G1Affine,
+        challenge: &E::ScalarField,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+        prk: &NonMembershipProvingKey<E::G1Affine>,
+    ) -> Result<(), VBAccumulatorError> {
+        let params = params.into();
+        let pairing_extra = self.verify_except_pairings(challenge, &params.P, prk)?;
+
+        <NonMembershipProofProtocol<E> as ProofProtocol<E>>::verify_proof(
+            &self.randomized_witness.C,
+            &self.schnorr_commit.C,
+            &self.schnorr_response.C,
+            Some(pairing_extra),
+            accumulator_value,
+            challenge,
+            pk,
+            params,
+            &prk.XYZ,
+        )
+    }
+
+    /// Same as `Self::verify` but tells apart a proof created with a stale witness. Delegates to
+    /// [`verify_proof_with_previous_accumulator_values`]
+    ///
+    /// [`verify_proof_with_previous_accumulator_values`]: ProofProtocol::verify_proof_with_previous_accumulator_values
+    pub fn verify_with_previous_accumulator_values(
+        &self,
+        accumulator_value: &E::G1Affine,
+        previous_accumulator_values: &[E::G1Affine],
+        challenge: &E::ScalarField,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+        prk: &NonMembershipProvingKey<E::G1Affine>,
+    ) -> Result<(), VBAccumulatorError> {
+        let params = params.into();
+        let pairing_extra = self.verify_except_pairings(challenge, &params.P, prk)?;
+
+        <NonMembershipProofProtocol<E> as ProofProtocol<E>>::verify_proof_with_previous_accumulator_values(
+            &self.randomized_witness.C,
+            &self.schnorr_commit.C,
+            &self.schnorr_response.C,
+            Some(pairing_extra),
+            accumulator_value,
+            previous_accumulator_values,
+            challenge,
+            pk,
+            params,
+            &prk.XYZ,
+        )
+    }
+
+    pub fn verify_partial(
+        &self,
+        resp_for_element: &E::ScalarField,
+        accumulator_value: &E::G1Affine,
+        challenge: &E::ScalarField,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+        prk: &NonMembershipProvingKey<E::G1Affine>,
+    ) -> Result<(), VBAccumulatorError> {
+        let params = params.into();
+        let pairing_extra = self.verify_except_pairings(challenge, &params.P, prk)?;
+
+        <NonMembershipProofProtocol<E> as ProofProtocol<E>>::verify_partial_proof(
+            resp_for_element,
+            &self.randomized_witness.C,
+            &self.schnorr_commit.C,
+            &self.schnorr_response.C,
+            Some(pairing_extra),
+            accumulator_value,
+            challenge,
+            pk,
+            params,
+            &prk.XYZ,
+        )
+    }
+
+    pub fn verify_with_randomized_pairing_checker(
+        &self,
+        accumulator_value: &E::G1Affine,
+        challenge: &E::ScalarField,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+        prk: &NonMembershipProvingKey<E::G1Affine>,
+        pairing_checker: &mut RandomizedPairingChecker<E>,
+    ) -> Result<(), VBAccumulatorError> {
+        let params = params.into();
+        let pairing_extra = self.verify_except_pairings(challenge, &params.P, prk)?;
+
+        <NonMembershipProofProtocol<E> as ProofProtocol<E>>::verify_proof_with_randomized_pairing_checker(
+            &self.randomized_witness.C,
+            &self.schnorr_commit.C,
+            &self.schnorr_response.C,
+            Some(pairing_extra),
+            accumulator_value,
+            challenge,
+            pk,
+            params,
+            &prk.XYZ,
+            pairing_checker
+        )
+    }
+
+    pub fn verify_partial_with_randomized_pairing_checker(
+        &self,
+        resp_for_element: &E::ScalarField,
+        accumulator_value: &E::G1Affine,
+        challenge: &E::ScalarField,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+        prk: &NonMembershipProvingKey<E::G1Affine>,
+        pairing_checker: &mut RandomizedPairingChecker<E>,
+    ) -> Result<(), VBAccumulatorError> {
+        let params = params.into();
+        let pairing_extra = self.verify_except_pairings(challenge, &params.P, prk)?;
+
+        <NonMembershipProofProtocol<E> as ProofProtocol<E>>::verify_partial_proof_with_randomized_pairing_checker(
+            resp_for_element,
+            &self.randomized_witness.C,
+            &self.schnorr_commit.C,
+            &self.schnorr_response.C,
+            Some(pairing_extra),
+            accumulator_value,
+            challenge,
+            pk,
+            params,
+            &prk.XYZ,
+            pairing_checker
+        )
+    }
+
+    /// Get response for Schnorr protocol for the non-member. This is useful when the non-member is also used
+    /// in another relation that is proven along this protocol.
+    pub fn get_schnorr_response_for_element(&self) -> Option<&E::ScalarField> {
+        self.schnorr_response.C.get_response_for_element()
+    }
+
+    /// There are multiple multiplications with K, P and E_d which can be done in variable time so use wNAF.
+    pub fn get_tables(
+        prk: &NonMembershipProvingKey<E::G1Affine>,
+        P: &E::G1Affine,
+        E_d: &E::G1Affine,
+    ) -> (WnafContext, Vec<E::G1>, Vec<E::G1>, Vec<E::G1>) {
         let context = WnafContext::new(4);
         let K_table = context.table(prk.K.into_group());
         let P_table = context.table(P.into_group());
@@ -1580,6 +2529,224 @@ impl<E: Pairing> NonMembershipProof<E> {
     }
 }
 
+/// Protocol for proving that each of several elements is a non-member of the same accumulator. Each
+/// element has its own non-membership protocol but all of them share the challenge and the verifier
+/// combines their pairing checks into a single multi-pairing.
+#[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
+#[derive(
+    Clone, PartialEq, Eq, Debug, Zeroize, ZeroizeOnDrop, CanonicalSerialize, CanonicalDeserialize,
+)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BatchNonMembershipProofProtocol<E: Pairing>(
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            bound = "NonMembershipProofProtocol<E>: Serialize, for<'a> NonMembershipProofProtocol<E>: Deserialize<'a>"
+        )
+    )]
+    pub Vec<NonMembershipProofProtocol<E>>,
+);
+
+/// Proof that each of several elements is a non-member of the same accumulator
+#[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BatchNonMembershipProof<E: Pairing>(
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            bound = "NonMembershipProof<E>: Serialize, for<'a> NonMembershipProof<E>: Deserialize<'a>"
+        )
+    )]
+    pub Vec<NonMembershipProof<E>>,
+);
+
+impl<E: Pairing> BatchNonMembershipProofProtocol<E> {
+    /// Initialize a non-membership proof protocol for each element and its witness. The window tables
+    /// for the setup params and proving key are created once and shared by all elements.
+    pub fn init<R: RngCore>(
+        rng: &mut R,
+        elements_and_witnesses: &[(E::ScalarField, NonMembershipWitness<E::G1Affine>)],
+        pk: &PublicKey<E>,
+        params: &SetupParams<E>,
+        prk: &NonMembershipProvingKey<E::G1Affine>,
+    ) -> Self {
+        let precomputed = NonMembershipProofParams::precompute(params, prk);
+        Self(
+            elements_and_witnesses
+                .iter()
+                .map(|(element, witness)| {
+                    NonMembershipProofProtocol::init_using_precomputed(
+                        rng,
+                        *element,
+                        None,
+                        witness,
+                        pk,
+                        params,
+                        &precomputed,
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// Contribution of this protocol to the overall challenge. The accumulator value, public key, params
+    /// and proving key are contributed once, followed by the commitments of each element's protocol.
+    pub fn challenge_contribution<W: Write>(
+        &self,
+        accumulator_value: &E::G1Affine,
+        pk: &PublicKey<E>,
+        params: &SetupParams<E>,
+        prk: &NonMembershipProvingKey<E::G1Affine>,
+        writer: W,
+    ) -> Result<(), VBAccumulatorError> {
+        batch_non_membership_challenge_contribution(
+            self.0
+                .iter()
+                .map(|p| (&p.randomized_witness, &p.schnorr_commit)),
+            accumulator_value,
+            pk,
+            params,
+            prk,
+            writer,
+        )
+    }
+
+    /// Create the proof once the overall challenge is ready. The same challenge is used for each element.
+    pub fn gen_proof(
+        mut self,
+        challenge: &E::ScalarField,
+    ) -> Result<BatchNonMembershipProof<E>, VBAccumulatorError> {
+        let protocols = core::mem::take(&mut self.0);
+        Ok(BatchNonMembershipProof(
+            protocols
+                .into_iter()
+                .map(|p| p.gen_proof(challenge))
+                .collect::<Result<Vec<_>, _>>()?,
+        ))
+    }
+}
+
+impl<E: Pairing> BatchNonMembershipProof<E> {
+    /// Challenge contribution for this proof
+    pub fn challenge_contribution<W: Write>(
+        &self,
+        accumulator_value: &E::G1Affine,
+        pk: &PublicKey<E>,
+        params: &SetupParams<E>,
+        prk: &NonMembershipProvingKey<E::G1Affine>,
+        writer: W,
+    ) -> Result<(), VBAccumulatorError> {
+        batch_non_membership_challenge_contribution(
+            self.0
+                .iter()
+                .map(|p| (&p.randomized_witness, &p.schnorr_commit)),
+            accumulator_value,
+            pk,
+            params,
+            prk,
+            writer,
+        )
+    }
+
+    /// Verify the Schnorr responses of each element's proof and then check all pairing equations using a
+    /// single multi-pairing. The pairing equation of each element is raised to a random power chosen by
+    /// the verifier so that the multi-pairing fails if any of the equations isn't satisfied.
+    pub fn verify<R: RngCore>(
+        &self,
+        rng: &mut R,
+        accumulator_value: &E::G1Affine,
+        challenge: &E::ScalarField,
+        pk: impl Into<PreparedPublicKey<E>>,
+        params: impl Into<PreparedSetupParams<E>>,
+        prk: &NonMembershipProvingKey<E::G1Affine>,
+    ) -> Result<(), VBAccumulatorError> {
+        let params = params.into();
+        let pk = pk.into();
+        let mut p_combined = E::G1::zero();
+        let mut q_combined = E::G1::zero();
+        let mut R_E_combined = PairingOutput::<E>::zero();
+        for (i, proof) in self.0.iter().enumerate() {
+            let pairing_extra = proof.verify_except_pairings(challenge, &params.P, prk)?;
+            let (p, q) =
+                <NonMembershipProofProtocol<E> as ProofProtocol<E>>::verify_proof_except_pairings(
+                    None,
+                    &proof.randomized_witness.C,
+                    &proof.schnorr_commit.C,
+                    &proof.schnorr_response.C,
+                    Some(pairing_extra),
+                    accumulator_value,
+                    challenge,
+                    &prk.XYZ,
+                )?;
+            // The first equation doesn't need to be randomized
+            if i == 0 {
+                p_combined += p;
+                q_combined += q;
+                R_E_combined += proof.schnorr_commit.C.R_E;
+            } else {
+                let r = E::ScalarField::rand(rng);
+                p_combined += p * r;
+                q_combined += q * r;
+                R_E_combined += proof.schnorr_commit.C.R_E * r;
+            }
+        }
+        let R_E = E::multi_pairing(
+            [p_combined.into_affine(), q_combined.into_affine()],
+            [params.P_tilde, pk.0],
+        );
+        if R_E != R_E_combined {
+            return Err(VBAccumulatorError::PairingResponseInvalid);
+        }
+        Ok(())
+    }
+}
+
+fn batch_non_membership_challenge_contribution<'a, E: Pairing, W: Write>(
+    commitments: impl Iterator<
+        Item = (
+            &'a NonMembershipRandomizedWitness<E::G1Affine>,
+            &'a NonMembershipSchnorrCommit<E>,
+        ),
+    >,
+    accumulator_value: &E::G1Affine,
+    pk: &PublicKey<E>,
+    params: &SetupParams<E>,
+    prk: &NonMembershipProvingKey<E::G1Affine>,
+    mut writer: W,
+) -> Result<(), VBAccumulatorError> {
+    accumulator_value.serialize_compressed(&mut writer)?;
+    pk.serialize_compressed(&mut writer)?;
+    params.serialize_compressed(&mut writer)?;
+    prk.challenge_contribution(&mut writer)?;
+    for (randomized_witness, schnorr_commit) in commitments {
+        randomized_witness.challenge_contribution(&mut writer)?;
+        schnorr_commit.challenge_contribution(&mut writer)?;
+    }
+    Ok(())
+}
+
+/// Inverse of `f` computed as `(f * r)^-1 * r` for a random non-zero `r` so that the input to the
+/// inversion is independent of `f`. Returns `None` if `f` is 0.
+fn blinded_inverse<F: Field, R: RngCore>(rng: &mut R, f: &F) -> Option<F> {
+    let r = loop {
+        let r = F::rand(rng);
+        if !r.is_zero() {
+            break r;
+        }
+    };
+    (*f * r).inverse().map(|i| i * r)
+}
+
+fn check_group_elements<E: Pairing>(
+    g1_elems: &[E::G1Affine],
+    gt_elem: &PairingOutput<E>,
+) -> Result<(), VBAccumulatorError> {
+    E::G1Affine::batch_check(g1_elems.iter())?;
+    gt_elem.check()?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1589,7 +2756,7 @@ mod tests {
         universal::tests::setup_universal_accum,
     };
 
-    use crate::setup::MembershipProvingKey;
+    use crate::setup::{MembershipProvingKey, SecretKey};
     use ark_bls12_381::Bls12_381;
     use ark_std::{
         rand::{rngs::StdRng, SeedableRng},
@@ -1597,10 +2764,119 @@ mod tests {
     };
     use blake2::Blake2b512;
     use schnorr_pok::compute_random_oracle_challenge;
-    use std::time::{Duration, Instant};
+    use std::{
+        hint::black_box,
+        time::{Duration, Instant},
+    };
 
     type Fr = <Bls12_381 as Pairing>::ScalarField;
 
+    #[test]
+    fn schnorr_commit_versioned_serialization() {
+        let g1 = <Bls12_381 as Pairing>::G1Affine::generator();
+        let g2 = <Bls12_381 as Pairing>::G2Affine::generator();
+        let commit = SchnorrCommit::<Bls12_381> {
+            R_E: Bls12_381::pairing(g1, g2),
+            R_sigma: (g1 * Fr::from(2u64)).into_affine(),
+            R_rho: (g1 * Fr::from(3u64)).into_affine(),
+            R_delta_sigma: (g1 * Fr::from(4u64)).into_affine(),
+            R_delta_rho: (g1 * Fr::from(5u64)).into_affine(),
+        };
+
+        // Bytes of the above commitment serialized with `CanonicalSerialize::serialize_compressed` before
+        // versioning was added
+        let legacy_hex = concat!(
+            "b68917caaa0543a808c53908f694d1b6e7b38de90ce9d83d505ca1ef1b442d2727d7d06831d8b2a7920afc71",
+            "d8eb50120f17a0ea982a88591d9f43503e94a8f1abaf2e4589f65aafb7923c484540a868883432a5c60e7586",
+            "0b11e5465b1c9a08873ec29e844c1c888cb396933057ffdd541b03a5220eda16b2b3a6728ea678034ce39c68",
+            "39f20397202d7c5c44bb68134f93193cec215031b17399577a1de5ff1f5b0666bdd8907c61a7651e4e79e037",
+            "2951505a07fa73c25788db6eb8023519a5aa97b51f1cad1d43d8aabbff4dc319c79a58cafc035218747c2f75",
+            "daf8f2fb7c00c44da85b129113173d4722f5b201b6b4454062e9ea8ba78c5ca3cadaf7238b47bace5ce56180",
+            "4ae16b8f4b63da4645b8457a93793cbd64a7254f150781019de87ee42682940f3e70a88683d512bb2c3fb7b2",
+            "434da5dedbb2d0b3fb8487c84da0d5c315bdd69c46fb05d23763f2191aabd5d5c2e12a10b8f002ff681bfd1b",
+            "2ee0bf619d80d2a795eb22f2aa7b85d5ffb671a70c94809f0dafc5b73ea2fb0657bae23373b4931bc9fa321e",
+            "8848ef78894e987bff150d7d671aee30b3931ac8c50e0b3b0868effc38bf48cd24b4b811a2995ac2a09122be",
+            "d9fd9fa0c510a87b10290836ad06c8203397b56a78e9a0c61c77e56ccb4f1bc3d3fcaea7550f3503efe30f2d",
+            "24f00891cb45620605fcfaa4292687b3a7db7c1c0554a93579e889a121fd8f72649b2402996a084d2381c504",
+            "3166673b3849e4fd1e7ee4af24aa8ed443f56dfd6b68ffde4435a92cd7a4ac3bc77e1ad0cb728606cf08bf63",
+            "86e5410fa572cbea904d67468808c8eb50a9450c9721db309128012543902d0ac358a62ae28f75bb8f1c7c42",
+            "c39a8c5529bf0f4e89ece308f9d1f0131765212deca99697b112d61f9be9a5f1f3780a51335b3ff981747a0b",
+            "2ca2179b96d2c0c9024e5224ac9b60d5afcbd5663a8a44b7c5a02f19e9a77ab0a35bd65809bb5c67ec582c89",
+            "7feb04decc694b13e08587f3ff9b5b60b0e7791fb972fe014159aa33a98622da3cdc98ff707965e536d8636b",
+            "5fcc5ac7a91a8c46e59a00dca575af0f18fb13dc",
+        );
+        let legacy_bytes = (0..legacy_hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&legacy_hex[i..i + 2], 16).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            SchnorrCommit::<Bls12_381>::deserialize_with_version(
+                SCHNORR_COMMIT_LEGACY_SERIALIZATION_VERSION,
+                &legacy_bytes[..]
+            )
+            .unwrap(),
+            commit
+        );
+        let mut bytes = vec![];
+        commit
+            .serialize_with_version(SCHNORR_COMMIT_LEGACY_SERIALIZATION_VERSION, &mut bytes)
+            .unwrap();
+        assert_eq!(bytes, legacy_bytes);
+
+        let mut bytes = vec![];
+        commit
+            .serialize_with_version(SCHNORR_COMMIT_SERIALIZATION_VERSION, &mut bytes)
+            .unwrap();
+        assert_eq!(bytes.len(), 12 * 48 + 4 * 48);
+        assert_eq!(
+            SchnorrCommit::<Bls12_381>::deserialize_with_version(
+                SCHNORR_COMMIT_SERIALIZATION_VERSION,
+                &bytes[..]
+            )
+            .unwrap(),
+            commit
+        );
+        // Truncated bytes
+        assert!(SchnorrCommit::<Bls12_381>::deserialize_with_version(
+            SCHNORR_COMMIT_SERIALIZATION_VERSION,
+            &bytes[..bytes.len() - 1]
+        )
+        .is_err());
+
+        // Target field element that isn't in the target group
+        let mut bytes = vec![];
+        let not_in_gt = <Bls12_381 as Pairing>::TargetField::from(2u64);
+        assert!(PairingOutput::<Bls12_381>(not_in_gt).check().is_err());
+        for c in not_in_gt.to_base_prime_field_elements() {
+            c.serialize_compressed(&mut bytes).unwrap();
+        }
+        commit.R_sigma.serialize_compressed(&mut bytes).unwrap();
+        commit.R_rho.serialize_compressed(&mut bytes).unwrap();
+        commit
+            .R_delta_sigma
+            .serialize_compressed(&mut bytes)
+            .unwrap();
+        commit.R_delta_rho.serialize_compressed(&mut bytes).unwrap();
+        assert!(matches!(
+            SchnorrCommit::<Bls12_381>::deserialize_with_version(
+                SCHNORR_COMMIT_SERIALIZATION_VERSION,
+                &bytes[..]
+            ),
+            Err(VBAccumulatorError::Serialization(
+                SerializationError::InvalidData
+            ))
+        ));
+
+        assert!(matches!(
+            commit.serialize_with_version(2, &mut vec![]),
+            Err(VBAccumulatorError::UnsupportedSerializationVersion(2))
+        ));
+        assert!(matches!(
+            SchnorrCommit::<Bls12_381>::deserialize_with_version(2, &bytes[..]),
+            Err(VBAccumulatorError::UnsupportedSerializationVersion(2))
+        ));
+    }
+
     #[test]
     fn membership_proof_positive_accumulator() {
         // Proof of knowledge of membership witness
@@ -1767,30 +3043,713 @@ mod tests {
                 .unwrap();
         }
 
-        let start = Instant::now();
+        let start = Instant::now();
+        assert!(pairing_checker.verify());
+        proof_verif_with_rand_pair_check_duration += start.elapsed();
+
+        println!(
+            "Time to create {} membership proofs is {:?}",
+            count, proof_create_duration
+        );
+        println!(
+            "Time to verify {} membership proofs is {:?}",
+            count, proof_verif_duration
+        );
+        println!(
+            "Time to verify {} membership proofs using prepared params is {:?}",
+            count, proof_verif_with_prepared_duration
+        );
+        println!(
+            "Time to verify {} membership proofs using randomized pairing checker is {:?}",
+            count, proof_verif_with_rand_pair_check_duration
+        );
+        println!(
+            "Time to verify {} membership proofs using prepared params and randomized pairing checker is {:?}",
+            count, proof_verif_with_prepared_and_rand_pair_check_duration
+        );
+    }
+
+    #[test]
+    fn membership_proof_with_stale_witness() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (params, keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+        let prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+        let elem = Fr::rand(&mut rng);
+        accumulator = accumulator
+            .add(elem, &keypair.secret_key, &mut state)
+            .unwrap();
+        let witness = accumulator
+            .get_membership_witness(&elem, &keypair.secret_key, &state)
+            .unwrap();
+
+        // Accumulator changes but the witness isn't updated
+        let old_value = *accumulator.value();
+        accumulator = accumulator
+            .add(Fr::rand(&mut rng), &keypair.secret_key, &mut state)
+            .unwrap();
+        assert!(!accumulator.verify_membership(&elem, &witness, &keypair.public_key, &params));
+
+        let protocol = MembershipProofProtocol::init(
+            &mut rng,
+            elem,
+            None,
+            &witness,
+            &keypair.public_key,
+            &params,
+            &prk,
+        );
+        let mut chal_bytes = vec![];
+        protocol
+            .challenge_contribution(
+                accumulator.value(),
+                &keypair.public_key,
+                &params,
+                &prk,
+                &mut chal_bytes,
+            )
+            .unwrap();
+        let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+        let proof = protocol.gen_proof(&challenge).unwrap();
+
+        assert!(matches!(
+            proof.verify(
+                accumulator.value(),
+                &challenge,
+                keypair.public_key.clone(),
+                params.clone(),
+                &prk,
+            ),
+            Err(VBAccumulatorError::PairingResponseInvalid)
+        ));
+        // Knowing the older accumulator value, the verifier can tell that the witness is stale
+        let other_value = <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine();
+        assert!(matches!(
+            proof.verify_with_previous_accumulator_values(
+                accumulator.value(),
+                &[other_value, old_value],
+                &challenge,
+                keypair.public_key.clone(),
+                params.clone(),
+                &prk,
+            ),
+            Err(VBAccumulatorError::StaleWitnessLikely)
+        ));
+        assert!(matches!(
+            proof.verify_with_previous_accumulator_values(
+                accumulator.value(),
+                &[other_value],
+                &challenge,
+                keypair.public_key.clone(),
+                params.clone(),
+                &prk,
+            ),
+            Err(VBAccumulatorError::PairingResponseInvalid)
+        ));
+
+        // A tampered pairing output or a different public key isn't reported as a stale witness
+        let mut tampered = proof.clone();
+        tampered.schnorr_commit.0.R_E =
+            tampered.schnorr_commit.0.R_E + tampered.schnorr_commit.0.R_E;
+        assert!(matches!(
+            tampered.verify_with_previous_accumulator_values(
+                accumulator.value(),
+                &[old_value],
+                &challenge,
+                keypair.public_key.clone(),
+                params.clone(),
+                &prk,
+            ),
+            Err(VBAccumulatorError::PairingResponseInvalid)
+        ));
+        let other_keypair = crate::setup::Keypair::generate_using_rng(&mut rng, &params);
+        assert!(matches!(
+            proof.verify_with_previous_accumulator_values(
+                accumulator.value(),
+                &[old_value],
+                &challenge,
+                other_keypair.public_key.clone(),
+                params.clone(),
+                &prk,
+            ),
+            Err(VBAccumulatorError::PairingResponseInvalid)
+        ));
+
+        // An up to date witness verifies
+        let witness = accumulator
+            .get_membership_witness(&elem, &keypair.secret_key, &state)
+            .unwrap();
+        let protocol = MembershipProofProtocol::init(
+            &mut rng,
+            elem,
+            None,
+            &witness,
+            &keypair.public_key,
+            &params,
+            &prk,
+        );
+        let proof = protocol.gen_proof(&challenge).unwrap();
+        proof
+            .verify_with_previous_accumulator_values(
+                accumulator.value(),
+                &[old_value],
+                &challenge,
+                keypair.public_key.clone(),
+                params.clone(),
+                &prk,
+            )
+            .unwrap();
+
+        // A tampered Schnorr response isn't reported as a stale witness
+        let mut tampered = proof.clone();
+        tampered.schnorr_response.0.s_sigma += Fr::from(1u64);
+        assert!(matches!(
+            tampered.verify(
+                accumulator.value(),
+                &challenge,
+                keypair.public_key.clone(),
+                params.clone(),
+                &prk,
+            ),
+            Err(VBAccumulatorError::SigmaResponseInvalid)
+        ));
+    }
+
+    #[test]
+    fn membership_proof_deserialize_and_validate() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (params, keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+        let prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+        let elem = Fr::rand(&mut rng);
+        accumulator = accumulator
+            .add(elem, &keypair.secret_key, &mut state)
+            .unwrap();
+        let witness = accumulator
+            .get_membership_witness(&elem, &keypair.secret_key, &state)
+            .unwrap();
+
+        let protocol = MembershipProofProtocol::init(
+            &mut rng,
+            elem,
+            None,
+            &witness,
+            &keypair.public_key,
+            &params,
+            &prk,
+        );
+        let proof = protocol.gen_proof(&Fr::rand(&mut rng)).unwrap();
+
+        let mut bytes = vec![];
+        proof.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(
+            MembershipProof::<Bls12_381>::deserialize_and_validate(&bytes[..]).unwrap(),
+            proof
+        );
+
+        // A point on the curve but not in the prime order subgroup
+        let mut x = ark_bls12_381::Fq::from(1u64);
+        let off_subgroup = loop {
+            if let Some(p) = ark_bls12_381::G1Affine::get_point_from_x_unchecked(x, true) {
+                if !p.is_in_correct_subgroup_assuming_on_curve() {
+                    break p;
+                }
+            }
+            x += ark_bls12_381::Fq::from(1u64);
+        };
+
+        let mut bad_proof = proof.clone();
+        bad_proof.randomized_witness.0.E_C = off_subgroup;
+        let mut bytes = vec![];
+        bad_proof.serialize_compressed(&mut bytes).unwrap();
+        // Reading without checks accepts it
+        assert_eq!(
+            MembershipProof::<Bls12_381>::deserialize_compressed_unchecked(&bytes[..]).unwrap(),
+            bad_proof
+        );
+        assert!(matches!(
+            MembershipProof::<Bls12_381>::deserialize_and_validate(&bytes[..]),
+            Err(VBAccumulatorError::Serialization(
+                ark_serialize::SerializationError::InvalidData
+            ))
+        ));
+    }
+
+    #[test]
+    fn membership_proof_from_seed() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (params, keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+        let prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+        let elem = Fr::rand(&mut rng);
+        accumulator = accumulator
+            .add(elem, &keypair.secret_key, &mut state)
+            .unwrap();
+        let witness = accumulator
+            .get_membership_witness(&elem, &keypair.secret_key, &state)
+            .unwrap();
+
+        let init = |seed: [u8; 32]| {
+            MembershipProofProtocol::init_from_seed::<Blake2b512>(
+                seed,
+                elem,
+                None,
+                &witness,
+                &keypair.public_key,
+                &params,
+                &prk,
+            )
+        };
+
+        // Same seed results in the same randomized witness and commitments
+        let protocol_1 = init([1; 32]);
+        let protocol_2 = init([1; 32]);
+        assert_eq!(protocol_1.randomized_witness, protocol_2.randomized_witness);
+        assert_eq!(protocol_1.schnorr_commit, protocol_2.schnorr_commit);
+
+        let protocol_3 = init([2; 32]);
+        assert_ne!(protocol_1.randomized_witness, protocol_3.randomized_witness);
+        assert_ne!(protocol_1.schnorr_commit, protocol_3.schnorr_commit);
+
+        let mut chal_bytes = vec![];
+        protocol_1
+            .challenge_contribution(
+                accumulator.value(),
+                &keypair.public_key,
+                &params,
+                &prk,
+                &mut chal_bytes,
+            )
+            .unwrap();
+        let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+        let proof_1 = protocol_1.gen_proof(&challenge).unwrap();
+        let proof_2 = protocol_2.gen_proof(&challenge).unwrap();
+        assert_eq!(proof_1, proof_2);
+
+        proof_1
+            .verify(
+                accumulator.value(),
+                &challenge,
+                keypair.public_key.clone(),
+                params.clone(),
+                &prk,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn membership_proof_with_updated_witness() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (params, keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+        let prk = MembershipProvingKey::generate_using_rng(&mut rng);
+        let precomputed = MembershipProofParams::precompute(&prk);
+
+        let elem = Fr::rand(&mut rng);
+        accumulator = accumulator
+            .add(elem, &keypair.secret_key, &mut state)
+            .unwrap();
+        let mut witness = accumulator
+            .get_membership_witness(&elem, &keypair.secret_key, &state)
+            .unwrap();
+
+        for _ in 0..3 {
+            let protocol = MembershipProofProtocol::init_using_precomputed(
+                &mut rng,
+                elem,
+                None,
+                &witness,
+                &keypair.public_key,
+                &params,
+                &precomputed,
+            );
+            let mut chal_bytes = vec![];
+            protocol
+                .challenge_contribution(
+                    accumulator.value(),
+                    &keypair.public_key,
+                    &params,
+                    &prk,
+                    &mut chal_bytes,
+                )
+                .unwrap();
+            let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+            let proof = protocol.gen_proof(&challenge).unwrap();
+            proof
+                .verify(
+                    accumulator.value(),
+                    &challenge,
+                    keypair.public_key.clone(),
+                    params.clone(),
+                    &prk,
+                )
+                .unwrap();
+
+            // Add another element so that the witness needs to be updated before proving again
+            let old_accum = *accumulator.value();
+            let new_elem = Fr::rand(&mut rng);
+            accumulator = accumulator
+                .add(new_elem, &keypair.secret_key, &mut state)
+                .unwrap();
+            let stale_witness = witness.clone();
+            witness = witness.update_after_addition(&elem, &new_elem, &old_accum);
+            assert!(accumulator.verify_membership(&elem, &witness, &keypair.public_key, &params));
+            assert!(!accumulator.verify_membership(
+                &elem,
+                &stale_witness,
+                &keypair.public_key,
+                &params
+            ));
+        }
+    }
+
+    #[test]
+    fn membership_proof_with_epoch_proving_keys() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (params, keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+        let base_label = b"test-accumulator";
+        let prk_1 = MembershipProvingKey::<<Bls12_381 as Pairing>::G1Affine>::new_for_epoch::<
+            Blake2b512,
+        >(base_label, 1);
+        let prk_2 = MembershipProvingKey::<<Bls12_381 as Pairing>::G1Affine>::new_for_epoch::<
+            Blake2b512,
+        >(base_label, 2);
+        prk_1.validate().unwrap();
+        prk_2.validate().unwrap();
+        assert_eq!(
+            prk_1,
+            MembershipProvingKey::new_for_epoch::<Blake2b512>(base_label, 1)
+        );
+        assert_ne!(prk_1, prk_2);
+        assert_ne!(prk_1, MembershipProvingKey::new::<Blake2b512>(base_label));
+
+        let non_mem_prk_1 =
+            NonMembershipProvingKey::<<Bls12_381 as Pairing>::G1Affine>::new_for_epoch::<Blake2b512>(
+                base_label, 1,
+            );
+        let non_mem_prk_2 =
+            NonMembershipProvingKey::<<Bls12_381 as Pairing>::G1Affine>::new_for_epoch::<Blake2b512>(
+                base_label, 2,
+            );
+        non_mem_prk_1.validate().unwrap();
+        assert_ne!(non_mem_prk_1, non_mem_prk_2);
+
+        let elem = Fr::rand(&mut rng);
+        accumulator = accumulator
+            .add(elem, &keypair.secret_key, &mut state)
+            .unwrap();
+        let witness = accumulator
+            .get_membership_witness(&elem, &keypair.secret_key, &state)
+            .unwrap();
+
+        let protocol = MembershipProofProtocol::init(
+            &mut rng,
+            elem,
+            None,
+            &witness,
+            &keypair.public_key,
+            &params,
+            &prk_1,
+        );
+        let mut chal_bytes = vec![];
+        protocol
+            .challenge_contribution(
+                accumulator.value(),
+                &keypair.public_key,
+                &params,
+                &prk_1,
+                &mut chal_bytes,
+            )
+            .unwrap();
+        let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+        let proof = protocol.gen_proof(&challenge).unwrap();
+        proof
+            .verify(
+                accumulator.value(),
+                &challenge,
+                keypair.public_key.clone(),
+                params.clone(),
+                &prk_1,
+            )
+            .unwrap();
+
+        // The proof doesn't verify with the key of another epoch, even with the challenge computed using it
+        assert!(proof
+            .verify(
+                accumulator.value(),
+                &challenge,
+                keypair.public_key.clone(),
+                params.clone(),
+                &prk_2,
+            )
+            .is_err());
+        let mut chal_bytes = vec![];
+        proof
+            .challenge_contribution(
+                accumulator.value(),
+                &keypair.public_key,
+                &params,
+                &prk_2,
+                &mut chal_bytes,
+            )
+            .unwrap();
+        let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+        assert!(proof
+            .verify(
+                accumulator.value(),
+                &challenge,
+                keypair.public_key.clone(),
+                params.clone(),
+                &prk_2,
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn membership_proof_with_mismatched_proving_key() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (params, keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+        let prover_prk =
+            MembershipProvingKey::<<Bls12_381 as Pairing>::G1Affine>::new::<Blake2b512>(b"prover");
+        let verifier_prk = MembershipProvingKey::<<Bls12_381 as Pairing>::G1Affine>::new::<
+            Blake2b512,
+        >(b"verifier");
+        assert_eq!(
+            prover_prk.id::<Blake2b512>(),
+            MembershipProvingKey::<<Bls12_381 as Pairing>::G1Affine>::new::<Blake2b512>(b"prover")
+                .id::<Blake2b512>()
+        );
+        assert_ne!(
+            prover_prk.id::<Blake2b512>(),
+            verifier_prk.id::<Blake2b512>()
+        );
+
+        let elem = Fr::rand(&mut rng);
+        accumulator = accumulator
+            .add(elem, &keypair.secret_key, &mut state)
+            .unwrap();
+        let witness = accumulator
+            .get_membership_witness(&elem, &keypair.secret_key, &state)
+            .unwrap();
+
+        let protocol = MembershipProofProtocol::init(
+            &mut rng,
+            elem,
+            None,
+            &witness,
+            &keypair.public_key,
+            &params,
+            &prover_prk,
+        );
+        let mut chal_bytes = vec![];
+        protocol
+            .challenge_contribution(
+                accumulator.value(),
+                &keypair.public_key,
+                &params,
+                &prover_prk,
+                &mut chal_bytes,
+            )
+            .unwrap();
+        let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+        let proof = protocol.gen_proof(&challenge).unwrap();
+        // Sent by the prover along with the proof
+        let prover_prk_id = prover_prk.id::<Blake2b512>();
+
+        prover_prk.check_id::<Blake2b512>(&prover_prk_id).unwrap();
+        proof
+            .verify(
+                accumulator.value(),
+                &challenge,
+                keypair.public_key.clone(),
+                params.clone(),
+                &prover_prk,
+            )
+            .unwrap();
+
+        // Verifying with another key fails with an error that doesn't point at the key
+        let mut chal_bytes = vec![];
+        proof
+            .challenge_contribution(
+                accumulator.value(),
+                &keypair.public_key,
+                &params,
+                &verifier_prk,
+                &mut chal_bytes,
+            )
+            .unwrap();
+        let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+        assert!(matches!(
+            proof.verify(
+                accumulator.value(),
+                &challenge,
+                keypair.public_key.clone(),
+                params.clone(),
+                &verifier_prk,
+            ),
+            Err(VBAccumulatorError::SigmaResponseInvalid)
+        ));
+        // but checking the key identifier first does
+        assert!(matches!(
+            verifier_prk.check_id::<Blake2b512>(&prover_prk_id),
+            Err(VBAccumulatorError::ProvingKeyMismatch)
+        ));
+
+        let non_mem_prk = NonMembershipProvingKey::<<Bls12_381 as Pairing>::G1Affine>::new::<
+            Blake2b512,
+        >(b"prover");
+        let mut other_non_mem_prk = non_mem_prk.clone();
+        other_non_mem_prk.K = verifier_prk.0.X;
+        non_mem_prk
+            .check_id::<Blake2b512>(&non_mem_prk.id::<Blake2b512>())
+            .unwrap();
+        assert!(matches!(
+            other_non_mem_prk.check_id::<Blake2b512>(&non_mem_prk.id::<Blake2b512>()),
+            Err(VBAccumulatorError::ProvingKeyMismatch)
+        ));
+    }
+
+    #[test]
+    fn membership_proof_hash() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (params, keypair, mut accumulator, mut state) = setup_positive_accum(&mut rng);
+        let prk = MembershipProvingKey::generate_using_rng(&mut rng);
+
+        let elem = Fr::rand(&mut rng);
+        accumulator = accumulator
+            .add(elem, &keypair.secret_key, &mut state)
+            .unwrap();
+        let witness = accumulator
+            .get_membership_witness(&elem, &keypair.secret_key, &state)
+            .unwrap();
+
+        let mut create_proof = || {
+            let protocol = MembershipProofProtocol::init(
+                &mut rng,
+                elem,
+                None,
+                &witness,
+                &keypair.public_key,
+                &params,
+                &prk,
+            );
+            let mut chal_bytes = vec![];
+            protocol
+                .challenge_contribution(
+                    accumulator.value(),
+                    &keypair.public_key,
+                    &params,
+                    &prk,
+                    &mut chal_bytes,
+                )
+                .unwrap();
+            let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+            protocol.gen_proof(&challenge).unwrap()
+        };
+
+        let proof_1 = create_proof();
+        let proof_2 = create_proof();
+
+        // The same proof always hashes the same
+        let hash_1 = proof_1.proof_hash::<Blake2b512>().unwrap();
+        assert_eq!(hash_1, proof_1.clone().proof_hash::<Blake2b512>().unwrap());
+        assert_eq!(hash_1.len(), 64);
+
+        // Independently generated proofs for the same member hash differently
+        assert_ne!(hash_1, proof_2.proof_hash::<Blake2b512>().unwrap());
+    }
+
+    #[test]
+    fn membership_proof_accumulator_in_g2() {
+        // Proof of knowledge of membership witness when the accumulator and witness are in G2
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        type G2 = <Bls12_381 as Pairing>::G2;
+        type G2Affine = <Bls12_381 as Pairing>::G2Affine;
+
+        let params = SetupParams::<Bls12_381>::generate_using_rng(&mut rng);
+        let secret_key = SecretKey::new(&mut rng);
+        let pk = PublicKeyG1::new_from_secret_key(&secret_key, &params);
+        let prk = MembershipProvingKey::<G2Affine>::generate_using_rng(&mut rng);
+
+        // Accumulator `V` in G2 and witness of `elem` is `C = 1/(elem + sk) * V`
+        let elem = Fr::rand(&mut rng);
+        let accumulator_value = G2::rand(&mut rng).into_affine();
+        let witness = MembershipWitness(
+            (accumulator_value * (elem + secret_key.0).inverse().unwrap()).into_affine(),
+        );
+        // e(elem * P + Q, C) == e(P, V)
+        assert_eq!(
+            Bls12_381::pairing(params.P * elem + pk.0, witness.0),
+            Bls12_381::pairing(params.P, accumulator_value)
+        );
+
+        let protocol =
+            MembershipProofProtocolG2::init(&mut rng, elem, None, &witness, &pk, &params, &prk);
+        let mut chal_bytes_prover = vec![];
+        protocol
+            .challenge_contribution(
+                &accumulator_value,
+                &pk,
+                &params,
+                &prk,
+                &mut chal_bytes_prover,
+            )
+            .unwrap();
+        let challenge_prover =
+            compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes_prover);
+        let proof = protocol.gen_proof(&challenge_prover).unwrap();
+
+        let mut chal_bytes_verifier = vec![];
+        proof
+            .challenge_contribution(
+                &accumulator_value,
+                &pk,
+                &params,
+                &prk,
+                &mut chal_bytes_verifier,
+            )
+            .unwrap();
+        let challenge_verifier =
+            compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes_verifier);
+        assert_eq!(challenge_prover, challenge_verifier);
+
+        proof
+            .verify(
+                &accumulator_value,
+                &challenge_verifier,
+                pk.clone(),
+                params.clone(),
+                &prk,
+            )
+            .unwrap();
+
+        let mut pairing_checker = RandomizedPairingChecker::new_using_rng(&mut rng, true);
+        proof
+            .verify_with_randomized_pairing_checker(
+                &accumulator_value,
+                &challenge_verifier,
+                pk.clone(),
+                params.clone(),
+                &prk,
+                &mut pairing_checker,
+            )
+            .unwrap();
         assert!(pairing_checker.verify());
-        proof_verif_with_rand_pair_check_duration += start.elapsed();
 
-        println!(
-            "Time to create {} membership proofs is {:?}",
-            count, proof_create_duration
-        );
-        println!(
-            "Time to verify {} membership proofs is {:?}",
-            count, proof_verif_duration
-        );
-        println!(
-            "Time to verify {} membership proofs using prepared params is {:?}",
-            count, proof_verif_with_prepared_duration
-        );
-        println!(
-            "Time to verify {} membership proofs using randomized pairing checker is {:?}",
-            count, proof_verif_with_rand_pair_check_duration
-        );
-        println!(
-            "Time to verify {} membership proofs using prepared params and randomized pairing checker is {:?}",
-            count, proof_verif_with_prepared_and_rand_pair_check_duration
-        );
+        test_serialization!(MembershipProofG2<Bls12_381>, proof);
+
+        // Proof doesn't verify for a different accumulator
+        let other_value = G2::rand(&mut rng).into_affine();
+        assert!(proof
+            .verify(&other_value, &challenge_verifier, pk, params, &prk)
+            .is_err());
     }
 
     #[test]
@@ -1829,7 +3788,7 @@ mod tests {
         for _ in 0..count {
             let elem = Fr::rand(&mut rng);
             let w = accumulator
-                .get_non_membership_witness(&elem, &keypair.secret_key, &mut state, &params)
+                .get_non_membership_witness(&elem, &keypair.secret_key, &state, &params)
                 .unwrap();
             assert!(accumulator.verify_non_membership(&elem, &w, &keypair.public_key, &params));
             elems.push(elem);
@@ -1997,4 +3956,568 @@ mod tests {
             count, proof_verif_with_prepared_and_rand_pair_check_duration
         );
     }
+
+    #[test]
+    fn non_membership_proof_using_precomputed_params() {
+        // Non-membership proofs created using precomputed window tables are the same as the ones
+        // created without them and creating them is faster
+        let max = 100;
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (params, keypair, mut accumulator, initial_elems, mut state) =
+            setup_universal_accum(&mut rng, max);
+        let prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+
+        for _ in 0..50 {
+            accumulator = accumulator
+                .add(
+                    Fr::rand(&mut rng),
+                    &keypair.secret_key,
+                    &initial_elems,
+                    &mut state,
+                )
+                .unwrap();
+        }
+
+        let count = 10;
+        let mut elems = vec![];
+        let mut witnesses = vec![];
+        for _ in 0..count {
+            let elem = Fr::rand(&mut rng);
+            let w = accumulator
+                .get_non_membership_witness(&elem, &keypair.secret_key, &state, &params)
+                .unwrap();
+            elems.push(elem);
+            witnesses.push(w);
+        }
+
+        let start = Instant::now();
+        let precomputed = NonMembershipProofParams::precompute(&params, &prk);
+        let precompute_duration = start.elapsed();
+
+        let mut init_duration = Duration::default();
+        let mut init_using_precomputed_duration = Duration::default();
+
+        for i in 0..count {
+            let seed = i as u64;
+
+            let mut rng_1 = StdRng::seed_from_u64(seed);
+            let start = Instant::now();
+            let protocol_1 = NonMembershipProofProtocol::init(
+                &mut rng_1,
+                elems[i],
+                None,
+                &witnesses[i],
+                &keypair.public_key,
+                &params,
+                &prk,
+            );
+            init_duration += start.elapsed();
+
+            let mut rng_2 = StdRng::seed_from_u64(seed);
+            let start = Instant::now();
+            let protocol_2 = NonMembershipProofProtocol::init_using_precomputed(
+                &mut rng_2,
+                elems[i],
+                None,
+                &witnesses[i],
+                &keypair.public_key,
+                &params,
+                &precomputed,
+            );
+            init_using_precomputed_duration += start.elapsed();
+
+            assert_eq!(protocol_1, protocol_2);
+
+            let mut chal_bytes_prover = vec![];
+            protocol_2
+                .challenge_contribution(
+                    accumulator.value(),
+                    &keypair.public_key,
+                    &params,
+                    &prk,
+                    &mut chal_bytes_prover,
+                )
+                .unwrap();
+            let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes_prover);
+            let proof = protocol_2.gen_proof(&challenge).unwrap();
+            proof
+                .verify(
+                    accumulator.value(),
+                    &challenge,
+                    keypair.public_key.clone(),
+                    params.clone(),
+                    &prk,
+                )
+                .unwrap();
+        }
+
+        println!(
+            "Time to initialize {} non-membership proof protocols is {:?}",
+            count, init_duration
+        );
+        println!(
+            "Time to initialize {} non-membership proof protocols using precomputed params is {:?} and time to precompute is {:?}",
+            count, init_using_precomputed_duration, precompute_duration
+        );
+    }
+
+    #[test]
+    fn proofs_using_shared_proving_key_tables() {
+        // Tables created once are shared by threads creating membership and non-membership proofs
+        // and give the same proofs as the ones created without them
+        let max = 100;
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (params, keypair, mut accumulator, initial_elems, mut state) =
+            setup_universal_accum(&mut rng, max);
+        let non_mem_prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+        let mem_prk = non_mem_prk.derive_membership_proving_key();
+
+        let members = (0..4).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        accumulator = accumulator
+            .add_batch(
+                members.clone(),
+                &keypair.secret_key,
+                &initial_elems,
+                &mut state,
+            )
+            .unwrap();
+        let mem_witnesses = members
+            .iter()
+            .map(|m| {
+                accumulator
+                    .get_membership_witness(m, &keypair.secret_key, &state)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let non_members = (0..4).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let non_mem_witnesses = non_members
+            .iter()
+            .map(|n| {
+                accumulator
+                    .get_non_membership_witness(n, &keypair.secret_key, &state, &params)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let precomputed = NonMembershipProofParams::precompute(&params, &non_mem_prk);
+
+        std::thread::scope(|scope| {
+            for i in 0..members.len() {
+                let (params, keypair, accumulator) = (&params, &keypair, &accumulator);
+                let (mem_prk, non_mem_prk, precomputed) = (&mem_prk, &non_mem_prk, &precomputed);
+                let (member, mem_witness) = (members[i], &mem_witnesses[i]);
+                let (non_member, non_mem_witness) = (non_members[i], &non_mem_witnesses[i]);
+                scope.spawn(move || {
+                    let seed = i as u64;
+
+                    let protocol = MembershipProofProtocol::init_using_precomputed(
+                        &mut StdRng::seed_from_u64(seed),
+                        member,
+                        None,
+                        mem_witness,
+                        &keypair.public_key,
+                        params,
+                        &precomputed.XYZ,
+                    );
+                    assert_eq!(
+                        protocol,
+                        MembershipProofProtocol::init(
+                            &mut StdRng::seed_from_u64(seed),
+                            member,
+                            None,
+                            mem_witness,
+                            &keypair.public_key,
+                            params,
+                            mem_prk,
+                        )
+                    );
+                    let mut chal_bytes = vec![];
+                    protocol
+                        .challenge_contribution(
+                            accumulator.value(),
+                            &keypair.public_key,
+                            params,
+                            mem_prk,
+                            &mut chal_bytes,
+                        )
+                        .unwrap();
+                    let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+                    protocol
+                        .gen_proof(&challenge)
+                        .unwrap()
+                        .verify(
+                            accumulator.value(),
+                            &challenge,
+                            keypair.public_key.clone(),
+                            params.clone(),
+                            mem_prk,
+                        )
+                        .unwrap();
+
+                    let protocol = NonMembershipProofProtocol::init_using_precomputed(
+                        &mut StdRng::seed_from_u64(seed),
+                        non_member,
+                        None,
+                        non_mem_witness,
+                        &keypair.public_key,
+                        params,
+                        precomputed,
+                    );
+                    assert_eq!(
+                        protocol,
+                        NonMembershipProofProtocol::init(
+                            &mut StdRng::seed_from_u64(seed),
+                            non_member,
+                            None,
+                            non_mem_witness,
+                            &keypair.public_key,
+                            params,
+                            non_mem_prk,
+                        )
+                    );
+                    let mut chal_bytes = vec![];
+                    protocol
+                        .challenge_contribution(
+                            accumulator.value(),
+                            &keypair.public_key,
+                            params,
+                            non_mem_prk,
+                            &mut chal_bytes,
+                        )
+                        .unwrap();
+                    let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes);
+                    protocol
+                        .gen_proof(&challenge)
+                        .unwrap()
+                        .verify(
+                            accumulator.value(),
+                            &challenge,
+                            keypair.public_key.clone(),
+                            params.clone(),
+                            non_mem_prk,
+                        )
+                        .unwrap();
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn inverse_with_blinding() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        for _ in 0..10 {
+            let f = Fr::rand(&mut rng);
+            assert_eq!(blinded_inverse(&mut rng, &f), f.inverse());
+        }
+        assert_eq!(blinded_inverse(&mut rng, &Fr::zero()), None);
+    }
+
+    #[test]
+    #[ignore]
+    fn blinded_inverse_timing() {
+        // Timing based so ignored by default, run with `cargo test --release -- --ignored`. The plain inversion
+        // is much faster for small inputs like 1 than for random ones, the blinded one takes about the same time
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let count = 10000;
+        let inputs = [Fr::from(1u64), Fr::from(2u64), Fr::rand(&mut rng)];
+        let times = inputs
+            .iter()
+            .map(|f| {
+                let start = Instant::now();
+                for _ in 0..count {
+                    black_box(blinded_inverse(&mut rng, black_box(f)));
+                }
+                start.elapsed().as_nanos() as f64
+            })
+            .collect::<Vec<_>>();
+        let min = times.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = times.iter().cloned().fold(0.0, f64::max);
+        println!("Times for blinded inversion of {count} inputs: {times:?}");
+        assert!(max / min < 1.2);
+    }
+
+    #[test]
+    fn non_membership_proof_with_blinded_inversion() {
+        let max = 100;
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (params, keypair, accumulator, _, state) = setup_universal_accum(&mut rng, max);
+        let prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+        let precomputed = NonMembershipProofParams::precompute(&params, &prk);
+
+        let elem = Fr::rand(&mut rng);
+        let witness = accumulator
+            .get_non_membership_witness(&elem, &keypair.secret_key, &state, &params)
+            .unwrap();
+
+        let protocol = NonMembershipProofProtocol::init_with_blinded_inversion(
+            &mut rng,
+            elem,
+            None,
+            &witness,
+            &keypair.public_key,
+            &params,
+            &precomputed,
+        );
+        let mut chal_bytes_prover = vec![];
+        protocol
+            .challenge_contribution(
+                accumulator.value(),
+                &keypair.public_key,
+                &params,
+                &prk,
+                &mut chal_bytes_prover,
+            )
+            .unwrap();
+        let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes_prover);
+        let proof = protocol.gen_proof(&challenge).unwrap();
+        proof
+            .verify(
+                accumulator.value(),
+                &challenge,
+                keypair.public_key.clone(),
+                params.clone(),
+                &prk,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn non_membership_proof_challenge_binds_d_commitments() {
+        // The commitments `R_A` and `R_B` of the proof of `d != 0` contribute to the challenge so
+        // a proof with either of them changed fails to verify
+        let max = 100;
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (params, keypair, mut accumulator, initial_elems, mut state) =
+            setup_universal_accum(&mut rng, max);
+        let prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+
+        accumulator = accumulator
+            .add(
+                Fr::rand(&mut rng),
+                &keypair.secret_key,
+                &initial_elems,
+                &mut state,
+            )
+            .unwrap();
+        let elem = Fr::rand(&mut rng);
+        let witness = accumulator
+            .get_non_membership_witness(&elem, &keypair.secret_key, &state, &params)
+            .unwrap();
+
+        let protocol = NonMembershipProofProtocol::init(
+            &mut rng,
+            elem,
+            None,
+            &witness,
+            &keypair.public_key,
+            &params,
+            &prk,
+        );
+        let mut chal_bytes_prover = vec![];
+        protocol
+            .challenge_contribution(
+                accumulator.value(),
+                &keypair.public_key,
+                &params,
+                &prk,
+                &mut chal_bytes_prover,
+            )
+            .unwrap();
+        let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes_prover);
+        let proof = protocol.gen_proof(&challenge).unwrap();
+
+        let verify = |proof: &NonMembershipProof<Bls12_381>| {
+            let mut chal_bytes_verifier = vec![];
+            proof
+                .challenge_contribution(
+                    accumulator.value(),
+                    &keypair.public_key,
+                    &params,
+                    &prk,
+                    &mut chal_bytes_verifier,
+                )
+                .unwrap();
+            let challenge = compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes_verifier);
+            (
+                chal_bytes_verifier,
+                proof.verify(
+                    accumulator.value(),
+                    &challenge,
+                    keypair.public_key.clone(),
+                    params.clone(),
+                    &prk,
+                ),
+            )
+        };
+
+        let (chal_bytes_verifier, result) = verify(&proof);
+        assert_eq!(chal_bytes_prover, chal_bytes_verifier);
+        result.unwrap();
+
+        let mut tampered = proof.clone();
+        tampered.schnorr_commit.R_A = (tampered.schnorr_commit.R_A + params.P).into_affine();
+        let (chal_bytes_verifier, result) = verify(&tampered);
+        assert_ne!(chal_bytes_prover, chal_bytes_verifier);
+        assert!(result.is_err());
+
+        let mut tampered = proof;
+        tampered.schnorr_commit.R_B = (tampered.schnorr_commit.R_B + params.P).into_affine();
+        let (chal_bytes_verifier, result) = verify(&tampered);
+        assert_ne!(chal_bytes_prover, chal_bytes_verifier);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn batch_non_membership_proof() {
+        // Proof of knowledge of non-membership witnesses of several elements in the same accumulator
+        let max = 100;
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (params, keypair, mut accumulator, initial_elems, mut state) =
+            setup_universal_accum(&mut rng, max);
+        let prk = NonMembershipProvingKey::generate_using_rng(&mut rng);
+        let prepared_params = PreparedSetupParams::from(params.clone());
+        let prepared_pk = PreparedPublicKey::from(keypair.public_key.clone());
+
+        for _ in 0..50 {
+            accumulator = accumulator
+                .add(
+                    Fr::rand(&mut rng),
+                    &keypair.secret_key,
+                    &initial_elems,
+                    &mut state,
+                )
+                .unwrap();
+        }
+
+        let count = 10;
+        let mut elems_and_witnesses = vec![];
+        for _ in 0..count {
+            let elem = Fr::rand(&mut rng);
+            let w = accumulator
+                .get_non_membership_witness(&elem, &keypair.secret_key, &state, &params)
+                .unwrap();
+            elems_and_witnesses.push((elem, w));
+        }
+
+        let start = Instant::now();
+        let protocol = BatchNonMembershipProofProtocol::init(
+            &mut rng,
+            &elems_and_witnesses,
+            &keypair.public_key,
+            &params,
+            &prk,
+        );
+        let mut chal_bytes_prover = vec![];
+        protocol
+            .challenge_contribution(
+                accumulator.value(),
+                &keypair.public_key,
+                &params,
+                &prk,
+                &mut chal_bytes_prover,
+            )
+            .unwrap();
+        let challenge_prover =
+            compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes_prover);
+        let proof = protocol.gen_proof(&challenge_prover).unwrap();
+        println!(
+            "Time to create batch non-membership proof for {} elements is {:?}",
+            count,
+            start.elapsed()
+        );
+        assert_eq!(proof.0.len(), count);
+
+        test_serialization!(BatchNonMembershipProof<Bls12_381>, proof);
+
+        let mut chal_bytes_verifier = vec![];
+        proof
+            .challenge_contribution(
+                accumulator.value(),
+                &keypair.public_key,
+                &params,
+                &prk,
+                &mut chal_bytes_verifier,
+            )
+            .unwrap();
+        let challenge_verifier =
+            compute_random_oracle_challenge::<Fr, Blake2b512>(&chal_bytes_verifier);
+        assert_eq!(challenge_prover, challenge_verifier);
+
+        let start = Instant::now();
+        proof
+            .verify(
+                &mut rng,
+                accumulator.value(),
+                &challenge_verifier,
+                prepared_pk.clone(),
+                prepared_params.clone(),
+                &prk,
+            )
+            .unwrap();
+        println!(
+            "Time to verify batch non-membership proof for {} elements using a single multi-pairing is {:?}",
+            count,
+            start.elapsed()
+        );
+
+        // Each proof in the batch is a valid non-membership proof for the shared challenge
+        let start = Instant::now();
+        for p in &proof.0 {
+            p.verify(
+                accumulator.value(),
+                &challenge_verifier,
+                prepared_pk.clone(),
+                prepared_params.clone(),
+                &prk,
+            )
+            .unwrap();
+        }
+        println!(
+            "Time to verify {} non-membership proofs separately is {:?}",
+            count,
+            start.elapsed()
+        );
+
+        // Fails for a different accumulator
+        let other_accum = accumulator
+            .add(
+                Fr::rand(&mut rng),
+                &keypair.secret_key,
+                &initial_elems,
+                &mut state,
+            )
+            .unwrap();
+        assert!(proof
+            .verify(
+                &mut rng,
+                other_accum.value(),
+                &challenge_verifier,
+                prepared_pk.clone(),
+                prepared_params.clone(),
+                &prk,
+            )
+            .is_err());
+
+        // Fails when pairing outputs of two elements' proofs are swapped as each equation is checked
+        let mut tampered = proof.clone();
+        let R_E_0 = tampered.0[0].schnorr_commit.C.R_E;
+        tampered.0[0].schnorr_commit.C.R_E = tampered.0[1].schnorr_commit.C.R_E;
+        tampered.0[1].schnorr_commit.C.R_E = R_E_0;
+        assert!(matches!(
+            tampered.verify(
+                &mut rng,
+                accumulator.value(),
+                &challenge_verifier,
+                prepared_pk,
+                prepared_params,
+                &prk,
+            ),
+            Err(VBAccumulatorError::PairingResponseInvalid)
+        ));
+    }
 }
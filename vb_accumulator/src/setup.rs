@@ -28,6 +28,7 @@
 //!
 //! ```
 
+use crate::error::VBAccumulatorError;
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
 use ark_ff::PrimeField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
@@ -41,7 +42,7 @@ use dock_crypto_utils::{
     join,
 };
 use schnorr_pok::{error::SchnorrError, SchnorrChallengeContributor};
-use short_group_sig::common::ProvingKey;
+use short_group_sig::common::{digest_to_id, ProvingKey};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 #[cfg(feature = "serde")]
@@ -67,6 +68,14 @@ pub struct PublicKey<E: Pairing>(
     #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))] pub E::G2Affine,
 );
 
+/// Public key for accumulator manager in group G1. Used when the accumulator and witnesses are in group G2
+#[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
+#[derive(Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PublicKeyG1<E: Pairing>(
+    #[cfg_attr(feature = "serde", serde_as(as = "ArkObjectBytes"))] pub E::G1Affine,
+);
+
 #[derive(
     Clone, PartialEq, Eq, Debug, Zeroize, ZeroizeOnDrop, CanonicalSerialize, CanonicalDeserialize,
 )]
@@ -202,6 +211,26 @@ impl<E: Pairing> PublicKey<E> {
     }
 }
 
+impl<E: Pairing> PublicKeyG1<E> {
+    /// Generate public key from given secret key and signature parameters
+    pub fn new_from_secret_key(
+        secret_key: &SecretKey<E::ScalarField>,
+        setup_params: &SetupParams<E>,
+    ) -> Self {
+        Self(
+            setup_params
+                .P
+                .mul_bigint(secret_key.0.into_bigint())
+                .into_affine(),
+        )
+    }
+
+    /// Public key shouldn't be 0
+    pub fn is_valid(&self) -> bool {
+        !self.0.is_zero()
+    }
+}
+
 #[cfg_attr(feature = "serde", cfg_eval::cfg_eval, serde_with::serde_as)]
 #[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -275,6 +304,30 @@ where
     pub fn new<D: Digest>(label: &[u8]) -> Self {
         Self(ProvingKey::generate_using_hash::<D>(label))
     }
+
+    /// Generate by hashing known strings for the given epoch. Keys for different epochs are distinct and
+    /// anyone with the same `base_label` and `epoch` gets the same key.
+    pub fn new_for_epoch<D: Digest>(base_label: &[u8], epoch: u64) -> Self {
+        Self::new::<D>(&epoch_label(base_label, epoch))
+    }
+
+    /// Check that `X`, `Y` and `Z` are distinct and none of them is the identity. Keys created with
+    /// `generate_using_rng` or `new` satisfy this, but a key received from elsewhere might not.
+    pub fn validate(&self) -> Result<(), VBAccumulatorError> {
+        check_non_degenerate(&[&self.0.X, &self.0.Y, &self.0.Z])
+    }
+
+    /// Identifier of this key. See [`ProvingKey::id`]
+    pub fn id<D: Digest>(&self) -> [u8; 32] {
+        self.0.id::<D>()
+    }
+
+    /// Check that this key's identifier is `expected_id`, the identifier of the prover's key. Proofs created
+    /// with another key fail verification with errors like `VBAccumulatorError::SigmaResponseInvalid` which
+    /// don't say why so verifiers can call this first to get `VBAccumulatorError::ProvingKeyMismatch`.
+    pub fn check_id<D: Digest>(&self, expected_id: &[u8; 32]) -> Result<(), VBAccumulatorError> {
+        check_id(self.id::<D>(), expected_id)
+    }
 }
 
 impl<G> NonMembershipProvingKey<G>
@@ -300,10 +353,56 @@ where
         }
     }
 
+    /// Generate by hashing known strings for the given epoch. Keys for different epochs are distinct and
+    /// anyone with the same `base_label` and `epoch` gets the same key.
+    pub fn new_for_epoch<D: Digest>(base_label: &[u8], epoch: u64) -> Self {
+        Self::new::<D>(&epoch_label(base_label, epoch))
+    }
+
     /// Derive the membership proving key when doing a membership proof with a universal accumulator.
     pub fn derive_membership_proving_key(&self) -> MembershipProvingKey<G> {
         MembershipProvingKey(self.XYZ.clone())
     }
+
+    /// Check that `X`, `Y`, `Z` and `K` are distinct and none of them is the identity. Keys created with
+    /// `generate_using_rng` or `new` satisfy this, but a key received from elsewhere might not.
+    pub fn validate(&self) -> Result<(), VBAccumulatorError> {
+        check_non_degenerate(&[&self.XYZ.X, &self.XYZ.Y, &self.XYZ.Z, &self.K])
+    }
+
+    /// Identifier of this key, computed like [`ProvingKey::id`] but over `K` as well
+    pub fn id<D: Digest>(&self) -> [u8; 32] {
+        let mut bytes = self.XYZ.id::<D>().to_vec();
+        self.K.serialize_compressed(&mut bytes).unwrap();
+        digest_to_id::<D>(&bytes)
+    }
+
+    /// Check that this key's identifier is `expected_id`, the identifier of the prover's key. See
+    /// [`MembershipProvingKey::check_id`]
+    pub fn check_id<D: Digest>(&self, expected_id: &[u8; 32]) -> Result<(), VBAccumulatorError> {
+        check_id(self.id::<D>(), expected_id)
+    }
+}
+
+/// The epoch is appended as fixed size bytes so that different epochs give different labels
+fn epoch_label(base_label: &[u8], epoch: u64) -> Vec<u8> {
+    concat_slices![base_label, b" : epoch : ", epoch.to_le_bytes()]
+}
+
+fn check_id(id: [u8; 32], expected_id: &[u8; 32]) -> Result<(), VBAccumulatorError> {
+    if &id != expected_id {
+        return Err(VBAccumulatorError::ProvingKeyMismatch);
+    }
+    Ok(())
+}
+
+fn check_non_degenerate<G: AffineRepr>(elems: &[&G]) -> Result<(), VBAccumulatorError> {
+    for (i, e) in elems.iter().enumerate() {
+        if e.is_zero() || elems[..i].contains(e) {
+            return Err(VBAccumulatorError::DegenerateProvingKey);
+        }
+    }
+    Ok(())
 }
 
 impl<G: AffineRepr> AsRef<ProvingKey<G>> for MembershipProvingKey<G> {
@@ -407,6 +506,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn degenerate_proving_keys() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        type G1 = <Bls12_381 as Pairing>::G1Affine;
+
+        let mem_prk = MembershipProvingKey::<G1>::generate_using_rng(&mut rng);
+        mem_prk.validate().unwrap();
+        MembershipProvingKey::<G1>::new::<Blake2b512>("test".as_bytes())
+            .validate()
+            .unwrap();
+        let non_mem_prk = NonMembershipProvingKey::<G1>::generate_using_rng(&mut rng);
+        non_mem_prk.validate().unwrap();
+        NonMembershipProvingKey::<G1>::new::<Blake2b512>("test".as_bytes())
+            .validate()
+            .unwrap();
+
+        let mut k = mem_prk.clone();
+        k.0.Y = G1::zero();
+        assert!(matches!(
+            k.validate(),
+            Err(VBAccumulatorError::DegenerateProvingKey)
+        ));
+        let mut k = mem_prk.clone();
+        k.0.Z = k.0.X;
+        assert!(matches!(
+            k.validate(),
+            Err(VBAccumulatorError::DegenerateProvingKey)
+        ));
+
+        let mut k = non_mem_prk.clone();
+        k.K = G1::zero();
+        assert!(matches!(
+            k.validate(),
+            Err(VBAccumulatorError::DegenerateProvingKey)
+        ));
+        let mut k = non_mem_prk.clone();
+        k.K = k.XYZ.Y;
+        assert!(matches!(
+            k.validate(),
+            Err(VBAccumulatorError::DegenerateProvingKey)
+        ));
+        // The derived membership key is still fine as it doesn't contain `K`
+        k.derive_membership_proving_key().validate().unwrap();
+    }
+
     #[test]
     fn proof_of_knowledge_of_public_key() {
         let mut rng = StdRng::seed_from_u64(0u64);
@@ -98,8 +98,8 @@
 //!
 //! ```
 
-use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
-use ark_ff::{batch_inversion, fields::Field, One, PrimeField};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{batch_inversion, fields::Field, One, PrimeField, Zero};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{cfg_into_iter, cfg_iter, fmt::Debug, vec::Vec};
 #[cfg(feature = "serde")]
@@ -114,7 +114,7 @@ use serde_with::serde_as;
 use crate::{
     batch_utils::{Omega, Poly_d, Poly_v_A, Poly_v_AD, Poly_v_D},
     error::VBAccumulatorError,
-    setup::SecretKey,
+    setup::{SecretKey, SetupParams},
 };
 
 use dock_crypto_utils::msm::WindowTable;
@@ -633,6 +633,25 @@ impl<G> NonMembershipWitness<G>
 where
     G: AffineRepr,
 {
+    /// Check whether this is a valid witness of `non_member` for the accumulator value `accumulator_value`
+    /// using the secret key, i.e. `d != 0` and `C * (non_member + sk) + P * d == V`. Gives the same result
+    /// as `UniversalAccumulator::verify_non_membership` but doesn't need pairings. Intended to be used by
+    /// the manager, like to check witnesses before giving them to users.
+    pub fn is_valid_with_secret_key<E: Pairing<G1Affine = G, ScalarField = G::ScalarField>>(
+        &self,
+        non_member: &G::ScalarField,
+        accumulator_value: &G,
+        sk: &SecretKey<G::ScalarField>,
+        params: &SetupParams<E>,
+    ) -> bool {
+        if self.d.is_zero() {
+            return false;
+        }
+        // C * (non_member + sk) + P * d
+        let V = G::Group::msm_unchecked(&[self.C, params.P], &[*non_member + sk.0, self.d]);
+        V == accumulator_value.into_group()
+    }
+
     /// Update a non-membership witness after an element is added to the accumulator. Needs the
     /// accumulator before the addition was done.
     pub fn update_after_addition(
@@ -1069,6 +1088,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn non_membership_witness_validity_with_secret_key() {
+        let max = 100;
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let (params, keypair, mut accumulator, initial_elements, mut state) =
+            setup_universal_accum(&mut rng, max);
+
+        let check = |non_member: &Fr, wit: &NonMembershipWitness<G1Affine>, V: &G1Affine| {
+            let valid = UniversalAccumulator::from_accumulated(*V).verify_non_membership(
+                non_member,
+                wit,
+                &keypair.public_key,
+                &params,
+            );
+            assert_eq!(
+                wit.is_valid_with_secret_key(non_member, V, &keypair.secret_key, &params),
+                valid
+            );
+            valid
+        };
+
+        let non_members = (0..10).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let witnesses = non_members
+            .iter()
+            .map(|e| {
+                accumulator
+                    .get_non_membership_witness(e, &keypair.secret_key, &state, &params)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let old_accumulator = *accumulator.value();
+        for (e, w) in non_members.iter().zip(witnesses.iter()) {
+            assert!(check(e, w, &old_accumulator));
+        }
+
+        // Witness of a different non-member
+        assert!(!check(&non_members[0], &witnesses[1], &old_accumulator));
+
+        // Tampered witnesses
+        let mut wit = witnesses[0].clone();
+        wit.d += Fr::from(1u64);
+        assert!(!check(&non_members[0], &wit, &old_accumulator));
+        let mut wit = witnesses[0].clone();
+        wit.C = (wit.C + params.P).into_affine();
+        assert!(!check(&non_members[0], &wit, &old_accumulator));
+        let mut wit = witnesses[0].clone();
+        wit.d = Fr::zero();
+        assert!(!check(&non_members[0], &wit, &old_accumulator));
+
+        // Witnesses aren't valid for the accumulator after an addition unless updated
+        let addition = Fr::rand(&mut rng);
+        accumulator = accumulator
+            .add(addition, &keypair.secret_key, &initial_elements, &mut state)
+            .unwrap();
+        for (e, w) in non_members.iter().zip(witnesses.iter()) {
+            assert!(!check(e, w, accumulator.value()));
+            let new_wit = w.update_after_addition(e, &addition, &old_accumulator);
+            assert!(check(e, &new_wit, accumulator.value()));
+        }
+    }
+
     #[test]
     fn batch_updates_witnesses_positive_accumulator() {
         // Accumulator manager who knows the secret key batch updates witnesses